@@ -1,101 +1,979 @@
 //! Configuration module for PumpGuard
 
+use serde::{Serialize, Serializer};
 use std::env;
 
+use crate::modules::custom_watch::CustomWatchRule;
+use crate::modules::rug_detector::SellWindow;
+use crate::utils::alerts::ALERT_TYPES;
+use crate::utils::solana::{SubscriptionMode, TxEncoding};
+use crate::utils::tx_kind::LaunchInstruction;
+
+/// Serialize a secret as `***` (or null when unset) so it never leaks via the API
+fn redact_secret<S: Serializer>(value: &Option<String>, serializer: S) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(_) => serializer.serialize_str("***"),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Hide URL query strings, where RPC providers usually put API keys
+pub(crate) fn redact_url(url: &str) -> String {
+    match url.split_once('?') {
+        Some((base, _)) => format!("{}?***", base),
+        None => url.to_string(),
+    }
+}
+
+fn redact_url_field<S: Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&redact_url(value))
+}
+
+fn redact_url_list<S: Serializer>(value: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(value.iter().map(|url| redact_url(url)))
+}
+
+/// Solana cluster presets for RPC/WS endpoints and explorer links
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Cluster {
+    Mainnet,
+    Devnet,
+    Localnet,
+    /// No presets; endpoints are expected to be set explicitly
+    Custom,
+}
+
+impl Cluster {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "mainnet" | "mainnet-beta" => Some(Self::Mainnet),
+            "devnet" => Some(Self::Devnet),
+            "localnet" | "localhost" => Some(Self::Localnet),
+            "custom" => Some(Self::Custom),
+            _ => None,
+        }
+    }
+
+    fn default_rpc_url(self) -> &'static str {
+        match self {
+            Self::Mainnet => "https://api.mainnet-beta.solana.com",
+            Self::Devnet => "https://api.devnet.solana.com",
+            Self::Localnet | Self::Custom => "http://127.0.0.1:8899",
+        }
+    }
+
+    fn default_ws_url(self) -> &'static str {
+        match self {
+            Self::Mainnet => "wss://api.mainnet-beta.solana.com",
+            Self::Devnet => "wss://api.devnet.solana.com",
+            Self::Localnet | Self::Custom => "ws://127.0.0.1:8900",
+        }
+    }
+
+    /// Explorer link template; `{path}` is replaced with e.g. `address/<mint>`
+    fn default_explorer_url(self) -> &'static str {
+        match self {
+            Self::Mainnet => "https://explorer.solana.com/{path}",
+            Self::Devnet => "https://explorer.solana.com/{path}?cluster=devnet",
+            Self::Localnet | Self::Custom => {
+                "https://explorer.solana.com/{path}?cluster=custom&customUrl=http%3A%2F%2F127.0.0.1%3A8899"
+            }
+        }
+    }
+}
+
 /// Application configuration loaded from environment variables
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Config {
     // Solana RPC (read-only, no wallet needed)
+    pub cluster: Cluster,                 // Picks default RPC/WS/explorer URLs
+    #[serde(serialize_with = "redact_url_field")]
     pub rpc_url: String,
+    #[serde(serialize_with = "redact_url_list")]
+    pub rpc_urls: Vec<String>,            // All RPC endpoints, fastest healthy one is used
+    pub rpc_probe_interval_secs: u64,     // How often endpoint latency is measured
+    pub rpc_max_failures: u32,            // Consecutive probe failures before demotion
+    pub rpc_tx_encoding: TxEncoding,      // Encoding requested from getTransaction
+    pub rpc_tx_fallback_encoding: Option<TxEncoding>, // Retried once when the node rejects the preferred one
+    #[serde(serialize_with = "redact_url_field")]
     pub ws_url: String,
+    #[serde(serialize_with = "redact_url_list")]
+    pub ws_urls: Vec<String>,             // Log subscription endpoints, assigned to connections round-robin
+    pub ws_redundancy: usize,             // Concurrent log subscription connections (1 = no redundancy)
+    pub subscription_mode: SubscriptionMode, // ws = logsSubscribe, poll = getSignaturesForAddress on an interval
+    pub poll_interval_ms: u64,            // Delay between signature polls in poll mode
+    pub poll_signature_limit: usize,      // Signatures requested per program per poll
+    pub ws_poll_fallback_failures: u32,   // Consecutive WebSocket connect failures before polling instead (0 = never)
+    pub explorer_url: String,             // Explorer link template with a `{path}` placeholder
+
+    // WebSocket reconnect backoff
+    pub ws_reconnect_initial_secs: f64,   // First reconnect delay
+    pub ws_reconnect_multiplier: f64,     // Delay growth factor per failed attempt
+    pub ws_reconnect_max_secs: f64,       // Upper bound on reconnect delay
+    pub ws_reconnect_stable_secs: u64,    // Connection uptime required before backoff resets
+    pub ws_max_message_bytes: usize,      // Largest RPC WebSocket message accepted
+
+    // Transaction analysis worker pool (per module)
+    pub analysis_workers: usize,          // Worker tasks fetching/analyzing matched transactions
+    pub analysis_queue_size: usize,       // Matched signatures buffered before new ones are dropped
 
     // Pump.fun
     pub pump_program_id: String,
+    pub custom_watch_rules: Vec<CustomWatchRule>, // Generic log alerts for other programs
 
     // Telegram Alerts
+    #[serde(serialize_with = "redact_secret")]
     pub telegram_bot_token: Option<String>,
     pub telegram_chat_id: Option<String>,
+    pub telegram_connect_timeout_secs: u64, // Telegram API connect timeout
+    pub telegram_timeout_secs: u64,       // Telegram API total request timeout
+    pub telegram_buttons: bool,           // Attach Solscan/pump.fun link buttons to token alerts
+    pub telegram_send_workers: usize,     // Concurrent Telegram sends
+    pub telegram_queue_size: usize,       // Telegram sends buffered before new ones are dropped
+
+    // File Alerts
+    pub alert_file_path: Option<String>,  // Append alerts as NDJSON to this file
+    pub alert_file_max_bytes: u64,        // Rotate the alert file past this size (0 = never)
+
+    // Alert Formatting
+    pub alert_sol_digits: u32,            // Significant digits shown for sub-1 SOL amounts
+    pub alert_dedup_window_secs: u64,     // Skip alerts already sent before a restart within this window (0 = off)
+    pub muted_alert_types: Vec<String>,   // Types set to ALERT_<TYPE>=false: recorded, never delivered
+
+    // Rug Webhook
+    #[serde(serialize_with = "redact_secret")]
+    pub rug_webhook_url: Option<String>,  // POSTed a minimal JSON payload on every confirmed rug
+    #[serde(serialize_with = "redact_secret")]
+    pub rug_webhook_secret: Option<String>, // Signs the rug webhook body with HMAC-SHA256 when set
+    pub rug_webhook_timeout_ms: u64,       // Connect/request timeout for the rug webhook
+
+    // Daily Digest
+    pub digest_time: Option<String>,      // UTC "HH:MM" to send the 24h digest (unset = off)
+    pub digest_sections: Vec<String>,     // Sections to include: tokens, rugs, whales, movers
 
     // Token Monitor - Alert Filtering
     pub min_liquidity_sol: f64,           // Minimum liquidity to trigger alerts
     pub max_alerts_per_minute: u32,       // Rate limit for alerts (0 = unlimited)
+    pub launch_burst_threshold: u32,      // Tokens/min that triggers a launch_burst alert (0 = disabled)
+    pub launch_surge_zscore: f64,         // Std devs above the rolling tokens/min baseline for a launch_surge alert (0 = disabled)
+    pub launch_surge_baseline_minutes: usize, // Minutes of launch history in the surge baseline
+    pub creator_launch_limit: u32,        // Launches per window above which a creator's alerts are suppressed (0 = off)
+    pub creator_launch_window_secs: u64,  // Sliding window for the per-creator launch count
+    pub creator_suppress_secs: u64,       // How long a spam creator stays suppressed
+    pub creator_auto_blacklist: bool,     // Also blacklist spam creators (permanent until restart)
+    pub auto_blacklist_ruggers: bool,     // Blacklist (and save) the creator of a token raising a rug alert
+    pub sampling_threshold: u32,          // Tokens/min above which create fetches are sampled (0 = off)
+    pub sampling_rate: f64,               // Fraction of low-priority creates still fetched while sampling
+    pub new_token_confirm_delay_ms: u64,  // Re-read bonding curve liquidity after this delay (0 = off)
+    pub launch_instruction: LaunchInstruction, // Instruction that marks a launch; the other only enriches
+    pub copycat_max_distance: usize,      // Max name/symbol edit distance to a rugged token to flag a copycat
+    pub launch_holder_alert_percent: f64, // Alert when one wallet holds this % of supply at launch (0 = off)
+    pub detection_lag_warn_ms: i64,       // Warn in the new-token alert when detected this long after creation (0 = off)
+    pub require_complete_metadata: bool,  // Hold new-token alerts until name/symbol and liquidity resolve
+    pub alert_incomplete_metadata: bool,  // Still send a degraded alert when resolution fails (else skip)
+    pub reject_unparsed_tokens: bool,     // Drop detections with 0 liquidity and Unknown/UNK name and symbol
     pub alert_new_tokens: bool,           // Enable/disable new token alerts
+    #[serde(serialize_with = "redact_secret")]
+    pub new_token_webhook_url: Option<String>, // Receives every parsed DetectedToken, ignoring filters
+    pub new_token_webhook_timeout_ms: u64, // Connect/request timeout for the new-token webhook
 
     // Whale Watcher
     pub whale_threshold_sol: f64,
     pub alert_on_accumulation: bool,
     pub alert_on_dump: bool,
+    pub whale_alert_cooldown_secs: u64,   // Min seconds between alerts for the same wallet
+    pub min_pattern_net_flow_sol: f64,    // Net window flow a dump pattern needs
+    pub accumulation_windows_secs: Vec<u64>, // Windows averaged into a token's accumulation score
+    pub accumulation_buy_weight: f64,     // Score per whale buy in a window
+    pub accumulation_flow_weight: f64,    // Score per whale threshold of net inflow in a window
+    pub accumulation_buyer_weight: f64,   // Score per unique buyer in a window
+    pub accumulation_alert_score: f64,    // accumulation alert when a token's score crosses this (0 = off)
+    pub alert_on_creator_activity: bool,  // Elevated alert when a token's creator makes a whale trade in it
+    pub alert_on_rotation: bool,          // whale_rotation alert when a wallet sells one token then buys another
+    pub rotation_window_secs: u64,        // Max seconds between the sell and the buy of a rotation
+    pub rotation_min_sell_sol: f64,       // Sell size a rotation needs
+    pub rotation_min_buy_sol: f64,        // Buy size a rotation needs
+    pub alert_on_sandwich: bool,          // possible_sandwich alert when one wallet brackets another's trade
+    pub sandwich_window_slots: u64,       // Most slots between the front-run and the back-run
+    pub sandwich_min_victim_sol: f64,     // Sandwiched trade size worth an alert
+    pub coordinated_buy_min_wallets: usize, // Distinct wallets buying within the slot window that count as coordinated (0 = off)
+    pub coordinated_buy_window_slots: u64, // Most slots between the buys of a coordinated buy
+    pub coordinated_buy_min_sol: f64,     // Combined SOL a coordinated buy needs
+    pub whale_alert_history: bool,        // Attach wallet volume, tokens traded and net flow to whale alerts
+    pub whale_history_cache_secs: u64,    // How long a wallet's history summary is reused
+    pub auto_watch_top_movers: bool,      // Hand high-volume movers to the rug detector
+    pub auto_watch_min_volume_sol: f64,   // Mover volume required for auto-watch
+    pub auto_watch_interval_secs: u64,    // How often top movers are polled
+    pub movers_min_volume_sol: f64,       // Default minimum volume for /api/whales/movers
+    pub max_tracked_tokens: usize,        // Cap on mints tracked for movers (0 = unlimited)
 
     // Rug Detection
     pub lp_removal_threshold_percent: f64,
     pub suspicious_sell_percent: f64,
+    pub graduated_lp_removal_percent: f64,   // LP removal threshold once a token trades on its AMM pool
+    pub graduated_suspicious_sell_percent: f64, // Large sell threshold once a token trades on its AMM pool
     pub dev_wallet_sell_alert: bool,
+    pub dev_wallet_from_curve: bool,      // Take dev_wallet from the bonding curve creator on watch
+    pub liquidity_added_alert_percent: f64, // Curve balance rise between checks that alerts (0 = off)
+    pub oscillation_min_flips: u32,       // Liquidity direction flips in the window that alert (0 = off)
+    pub oscillation_window_secs: u64,     // Window the flips are counted over
+    pub oscillation_min_change_percent: f64, // Smaller liquidity moves are noise, not flips
+    pub bundle_launch_window_secs: u64,   // A coordinated buy this soon after launch is a launch bundle
+    pub bundle_dump_window_secs: u64,     // Dev/bundle wallet sell this soon after the bundle is a bundle_rug (0 = off)
+    pub bundle_dump_min_sol: f64,         // Sell size that counts as dumping the bundle
+    pub min_dev_hold_secs: u64,           // First dev sell sooner than this after launch is an instant_dev_sell
+    pub rug_prefilter_logs: bool,         // Skip tx fetches whose logs don't reference a watched mint
+    pub store_raw_logs: bool,             // Keep program logs of transactions that raised rug alerts
+    pub lp_ignore_programs: Vec<String>,  // Program IDs/log patterns whose withdraw/migrate logs are benign
+    pub trusted_creators: Vec<String>,    // Creators whose tokens skip suspicion scoring (LP pulls still alert)
+    pub sell_windows: Vec<SellWindow>,    // Sell-pressure windows evaluated on every sell
+    pub rug_confirm_recheck: bool,        // Re-read liquidity before firing a liquidity-based rug alert
+    pub verify_curve_owner: bool,         // Only trust curve balances from accounts owned by the pump program
+    pub rug_confirm_delay_ms: u64,        // Delay before the confirmation re-read
+    pub health_check_secs: u64,           // Base liquidity health check interval per watched token
+    pub health_check_min_secs: u64,       // Interval for suspicious tokens; also the scheduler tick
+    pub health_check_max_secs: u64,       // Interval for tokens that have gone quiet
+    pub rug_abandon_after_secs: u64,      // Flag watched tokens with no trades for this long (0 = off)
+    pub rug_abandon_grace_secs: u64,      // Stop health-checking abandoned tokens after this long
+    pub auto_unwatch_after_secs: u64,     // Unwatch graduated/inactive tokens after this long (0 = never)
+    pub auto_unwatch_flagged: bool,       // Let auto-unwatch drop rugged/suspicious tokens too
+    pub auto_unwatch_suspicious_after_secs: u64, // Longer expiry for suspicious tokens (0 = never)
+    pub restore_watched_hours: u64,       // Re-watch live tokens detected this recently on startup (0 = off)
+    pub safety_recheck_secs: u64,         // Safety re-evaluation interval (0 = off)
+    pub safety_caution_score: i32,        // Risk score where a token stops being classified safe
+    pub safety_hysteresis: i32,           // Risk points below a boundary needed to upgrade again
+    pub holder_snapshot_interval_secs: u64, // Holder count snapshot interval (0 = off)
+    pub holder_snapshot_retention_hours: u64, // Delete holder snapshots older than this
+
+    // Database write retries
+    pub db_write_retries: u32,            // Background retries for a failed write before it is dead-lettered
+    pub db_write_backoff_ms: u64,         // Delay before the first retry, doubled for each one after
+    pub db_dead_letter_drain_secs: u64,   // Replay dead-lettered writes this often (0 = never)
+    pub db_tx_batch_size: usize,          // Buffer transaction inserts and flush this many at once (0 = direct)
+    pub db_tx_flush_ms: u64,              // Flush buffered transactions at least this often
+    pub max_db_size_mb: u64,              // Evict the oldest data once the database grows past this (0 = no cap)
+    pub archive_gzip_level: u32,          // Compression level for /api/export/archive bundles (0-9)
+    pub metrics_persist_secs: u64,        // Save cumulative counters to the database this often (0 = off)
+    pub holder_milestones: Vec<u64>,      // Holder counts that trigger a growth alert
+    pub mcap_milestones_usd: Vec<f64>,    // Implied market caps (USD) that trigger an alert
+    pub sol_usd_price: f64,               // SOL price used for USD market caps
+    pub cohort_buckets_secs: Vec<u64>,    // Token age boundaries for /api/stats/cohorts
 
     // Dashboard
     pub dashboard_port: u16,
+    pub health_max_log_age_secs: u64,     // Report "degraded" when no log arrived for this long
+    pub ws_ping_interval_secs: u64,       // Server ping interval for dashboard WebSocket clients
+    pub ws_client_timeout_secs: u64,      // Drop dashboard clients silent for this long
+    pub ws_alert_batch_ms: u64,           // Alert batching window for clients that opt in (0 = off)
+    #[serde(serialize_with = "redact_secret")]
+    pub dashboard_api_key: Option<String>, // Required for admin endpoints when set
+    pub cors_allowed_origins: Vec<String>, // Browser origins allowed to call the API (empty = any)
+    pub dashboard_allowed_ips: Vec<String>, // CIDR ranges allowed to reach the dashboard (empty = any)
+    pub trust_proxy: bool,                // Take the client address from X-Forwarded-For
+    pub api_rate_limit: u32,              // Requests per client per window on /api routes (0 = off)
+    pub api_rate_limit_window_secs: u64,  // Window the API rate limit refills over
+
+    // Shutdown
+    pub shutdown_drain_timeout_secs: u64, // Wait this long for queued analysis to finish before flushing
+    pub shutdown_dashboard_timeout_secs: u64, // Wait this long for dashboard requests and WebSockets to close
+
+    // Coordination
+    pub instance_id: Option<String>,      // Enables leader election over the shared DB when set
+    pub instance_name: Option<String>,    // Labels alerts and webhooks; defaults to the hostname
+    pub lease_ttl_secs: u64,              // Leader lease lapses after this long without a heartbeat
 }
 
+/// Looks a setting up by name, like `std::env::var`
+type VarLookup<'a> = &'a dyn Fn(&str) -> Result<String, env::VarError>;
+
 impl Config {
     /// Load configuration from environment variables
     pub fn from_env() -> Self {
         dotenvy::dotenv().ok();
+        Self::from_vars(&|key| env::var(key))
+    }
 
+    /// Every setting at its default, ignoring `.env` and the process environment
+    #[cfg(test)]
+    pub fn for_tests() -> Self {
         Self {
-            rpc_url: env::var("SOLANA_RPC_URL")
-                .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string()),
-            ws_url: env::var("SOLANA_WS_URL")
-                .unwrap_or_else(|_| "wss://api.mainnet-beta.solana.com".to_string()),
+            instance_name: None,
+            ..Self::from_vars(&|_| Err(env::VarError::NotPresent))
+        }
+    }
 
-            pump_program_id: env::var("PUMP_PROGRAM_ID")
+    /// Build the configuration from `var`, which looks settings up by name
+    fn from_vars(var: VarLookup) -> Self {
+        let cluster = var("CLUSTER")
+            .ok()
+            .and_then(|v| Cluster::parse(&v))
+            .unwrap_or(Cluster::Mainnet);
+        let rpc_url = var("SOLANA_RPC_URL")
+            .unwrap_or_else(|_| cluster.default_rpc_url().to_string());
+        let ws_url = var("SOLANA_WS_URL")
+            .unwrap_or_else(|_| cluster.default_ws_url().to_string());
+
+        Self {
+            cluster,
+            rpc_urls: Self::parse_list(var, "SOLANA_RPC_URLS")
+                .filter(|urls| !urls.is_empty())
+                .unwrap_or_else(|| vec![rpc_url.clone()]),
+            rpc_url,
+            rpc_probe_interval_secs: var("RPC_PROBE_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            rpc_max_failures: var("RPC_MAX_FAILURES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3),
+            rpc_tx_encoding: var("RPC_TX_ENCODING")
+                .ok()
+                .and_then(|v| TxEncoding::parse(&v))
+                .unwrap_or(TxEncoding::JsonParsed),
+            rpc_tx_fallback_encoding: match var("RPC_TX_FALLBACK_ENCODING") {
+                Ok(v) if v.trim().eq_ignore_ascii_case("off") => None,
+                Ok(v) => TxEncoding::parse(&v).or(Some(TxEncoding::Base64)),
+                Err(_) => Some(TxEncoding::Base64),
+            },
+            ws_urls: Self::parse_list(var, "SOLANA_WS_URLS")
+                .filter(|urls| !urls.is_empty())
+                .unwrap_or_else(|| vec![ws_url.clone()]),
+            ws_url,
+            ws_redundancy: var("WS_REDUNDANCY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1),
+            subscription_mode: var("SUBSCRIPTION_MODE")
+                .ok()
+                .and_then(|v| SubscriptionMode::parse(&v))
+                .unwrap_or(SubscriptionMode::Ws),
+            poll_interval_ms: var("POLL_INTERVAL_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(2000),
+            poll_signature_limit: var("POLL_SIGNATURE_LIMIT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(100),
+            ws_poll_fallback_failures: var("WS_POLL_FALLBACK_FAILURES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            explorer_url: var("EXPLORER_URL")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| cluster.default_explorer_url().to_string()),
+
+            ws_reconnect_initial_secs: var("WS_RECONNECT_INITIAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5.0),
+            ws_reconnect_multiplier: var("WS_RECONNECT_MULTIPLIER")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(2.0),
+            ws_reconnect_max_secs: var("WS_RECONNECT_MAX_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60.0),
+            ws_reconnect_stable_secs: var("WS_RECONNECT_STABLE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            ws_max_message_bytes: var("WS_MAX_MESSAGE_BYTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(4 * 1024 * 1024),
+
+            analysis_workers: var("ANALYSIS_WORKERS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(4),
+            analysis_queue_size: var("ANALYSIS_QUEUE_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(256),
+
+            pump_program_id: var("PUMP_PROGRAM_ID")
                 .unwrap_or_else(|_| "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P".to_string()),
+            // `;`-separated, since patterns and titles may contain commas
+            custom_watch_rules: var("CUSTOM_WATCH_RULES")
+                .map(|v| v.split(';').filter_map(CustomWatchRule::parse).collect())
+                .unwrap_or_default(),
+
+            telegram_bot_token: var("TELEGRAM_BOT_TOKEN").ok(),
+            telegram_chat_id: var("TELEGRAM_CHAT_ID").ok(),
+            telegram_connect_timeout_secs: var("TELEGRAM_CONNECT_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
+            telegram_timeout_secs: var("TELEGRAM_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+            telegram_buttons: var("TELEGRAM_BUTTONS")
+                .map(|v| v != "false")
+                .unwrap_or(true),
+            telegram_send_workers: var("TELEGRAM_SEND_WORKERS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(4),
+            telegram_queue_size: var("TELEGRAM_QUEUE_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(500),
 
-            telegram_bot_token: env::var("TELEGRAM_BOT_TOKEN").ok(),
-            telegram_chat_id: env::var("TELEGRAM_CHAT_ID").ok(),
+            alert_file_path: var("ALERT_FILE_PATH").ok(),
+            alert_file_max_bytes: var("ALERT_FILE_MAX_BYTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10 * 1024 * 1024),
+
+            alert_dedup_window_secs: var("ALERT_DEDUP_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3600),
+            alert_sol_digits: var("ALERT_SOL_DIGITS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3),
+            muted_alert_types: ALERT_TYPES
+                .iter()
+                .filter(|alert_type| {
+                    var(&format!("ALERT_{}", alert_type.to_uppercase()))
+                        .map(|v| v == "false")
+                        .unwrap_or(false)
+                })
+                .map(|alert_type| alert_type.to_string())
+                .collect(),
+
+            rug_webhook_url: var("RUG_WEBHOOK_URL").ok().filter(|v| !v.is_empty()),
+            rug_webhook_secret: var("RUG_WEBHOOK_SECRET").ok().filter(|v| !v.is_empty()),
+            rug_webhook_timeout_ms: var("RUG_WEBHOOK_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(2000),
+
+            digest_time: var("DIGEST_TIME").ok().filter(|v| !v.trim().is_empty()),
+            digest_sections: Self::parse_list(var, "DIGEST_SECTIONS").unwrap_or_else(|| {
+                vec!["tokens".into(), "rugs".into(), "whales".into(), "movers".into()]
+            }),
 
             // Token monitor filtering - reduce alert noise
-            min_liquidity_sol: env::var("MIN_LIQUIDITY_SOL")
+            min_liquidity_sol: var("MIN_LIQUIDITY_SOL")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(1.0), // Default: only alert for tokens with >= 1 SOL liquidity
-            max_alerts_per_minute: env::var("MAX_ALERTS_PER_MINUTE")
+            max_alerts_per_minute: var("MAX_ALERTS_PER_MINUTE")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(10), // Default: max 10 alerts per minute (0 = unlimited)
-            alert_new_tokens: env::var("ALERT_NEW_TOKENS")
+            creator_launch_limit: var("CREATOR_LAUNCH_LIMIT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            creator_launch_window_secs: var("CREATOR_LAUNCH_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+            creator_suppress_secs: var("CREATOR_SUPPRESS_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3600),
+            creator_auto_blacklist: var("CREATOR_AUTO_BLACKLIST")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            auto_blacklist_ruggers: var("AUTO_BLACKLIST_RUGGERS")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            launch_burst_threshold: var("LAUNCH_BURST_THRESHOLD")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(100),
+            launch_surge_zscore: var("LAUNCH_SURGE_ZSCORE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3.0),
+            launch_surge_baseline_minutes: var("LAUNCH_SURGE_BASELINE_MINUTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+            sampling_threshold: var("TOKEN_SAMPLING_THRESHOLD")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            sampling_rate: var("TOKEN_SAMPLING_RATE")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .map(|v| v.clamp(0.0, 1.0))
+                .unwrap_or(0.25),
+            launch_instruction: var("LAUNCH_INSTRUCTION")
+                .ok()
+                .and_then(|v| LaunchInstruction::parse(&v))
+                .unwrap_or(LaunchInstruction::Create),
+            new_token_confirm_delay_ms: var("NEW_TOKEN_CONFIRM_DELAY_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            copycat_max_distance: var("COPYCAT_MAX_DISTANCE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1),
+            launch_holder_alert_percent: var("LAUNCH_HOLDER_ALERT_PERCENT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(20.0),
+            detection_lag_warn_ms: var("DETECTION_LAG_WARN_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30_000),
+            require_complete_metadata: var("REQUIRE_COMPLETE_METADATA")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            alert_incomplete_metadata: var("ALERT_INCOMPLETE_METADATA")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            reject_unparsed_tokens: var("REJECT_UNPARSED_TOKENS")
                 .map(|v| v != "false")
                 .unwrap_or(true),
+            alert_new_tokens: var("ALERT_NEW_TOKENS")
+                .map(|v| v != "false")
+                .unwrap_or(true),
+            new_token_webhook_url: var("NEW_TOKEN_WEBHOOK_URL")
+                .ok()
+                .filter(|v| !v.is_empty()),
+            new_token_webhook_timeout_ms: var("NEW_TOKEN_WEBHOOK_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(2000),
 
-            whale_threshold_sol: env::var("WHALE_THRESHOLD_SOL")
+            whale_threshold_sol: var("WHALE_THRESHOLD_SOL")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(50.0),
-            alert_on_accumulation: env::var("ALERT_ON_ACCUMULATION")
+            alert_on_accumulation: var("ALERT_ON_ACCUMULATION")
+                .map(|v| v != "false")
+                .unwrap_or(true),
+            alert_on_dump: var("ALERT_ON_DUMP")
+                .map(|v| v != "false")
+                .unwrap_or(true),
+            whale_alert_cooldown_secs: var("WHALE_ALERT_COOLDOWN_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+            min_pattern_net_flow_sol: var("MIN_PATTERN_NET_FLOW_SOL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(100.0),
+            alert_on_creator_activity: var("ALERT_ON_CREATOR_ACTIVITY")
+                .map(|v| v != "false")
+                .unwrap_or(true),
+            alert_on_rotation: var("ALERT_ON_ROTATION")
+                .map(|v| v != "false")
+                .unwrap_or(true),
+            rotation_window_secs: var("ROTATION_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(600),
+            rotation_min_sell_sol: var("ROTATION_MIN_SELL_SOL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(25.0),
+            rotation_min_buy_sol: var("ROTATION_MIN_BUY_SOL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(25.0),
+            alert_on_sandwich: var("ALERT_ON_SANDWICH")
                 .map(|v| v != "false")
                 .unwrap_or(true),
-            alert_on_dump: env::var("ALERT_ON_DUMP")
+            sandwich_window_slots: var("SANDWICH_WINDOW_SLOTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(2),
+            sandwich_min_victim_sol: var("SANDWICH_MIN_VICTIM_SOL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1.0),
+            coordinated_buy_min_wallets: var("COORDINATED_BUY_MIN_WALLETS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(4),
+            coordinated_buy_window_slots: var("COORDINATED_BUY_WINDOW_SLOTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1),
+            coordinated_buy_min_sol: var("COORDINATED_BUY_MIN_SOL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(2.0),
+            accumulation_windows_secs: Self::parse_list(var, "ACCUMULATION_WINDOWS_SECS")
+                .map(|list| list.iter().filter_map(|v| v.parse().ok()).filter(|&w| w > 0).collect())
+                .unwrap_or_else(|| vec![300, 900, 3600]),
+            accumulation_buy_weight: var("ACCUMULATION_BUY_WEIGHT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1.0),
+            accumulation_flow_weight: var("ACCUMULATION_FLOW_WEIGHT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1.0),
+            accumulation_buyer_weight: var("ACCUMULATION_BUYER_WEIGHT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0.5),
+            accumulation_alert_score: var("ACCUMULATION_ALERT_SCORE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(8.0),
+            whale_alert_history: var("WHALE_ALERT_HISTORY")
                 .map(|v| v != "false")
                 .unwrap_or(true),
+            whale_history_cache_secs: var("WHALE_HISTORY_CACHE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(300),
+            auto_watch_top_movers: var("AUTO_WATCH_TOP_MOVERS")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            auto_watch_min_volume_sol: var("AUTO_WATCH_MIN_VOLUME_SOL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(100.0),
+            movers_min_volume_sol: var("MOVERS_MIN_VOLUME_SOL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5.0),
+            max_tracked_tokens: var("MAX_TRACKED_TOKENS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10000),
+            auto_watch_interval_secs: var("AUTO_WATCH_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
 
-            lp_removal_threshold_percent: env::var("LP_REMOVAL_THRESHOLD_PERCENT")
+            lp_removal_threshold_percent: var("LP_REMOVAL_THRESHOLD_PERCENT")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(50.0),
-            suspicious_sell_percent: env::var("SUSPICIOUS_SELL_PERCENT")
+            suspicious_sell_percent: var("SUSPICIOUS_SELL_PERCENT")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(10.0),
-            dev_wallet_sell_alert: env::var("DEV_WALLET_SELL_ALERT")
+            graduated_lp_removal_percent: var("GRADUATED_LP_REMOVAL_PERCENT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(50.0),
+            graduated_suspicious_sell_percent: var("GRADUATED_SUSPICIOUS_SELL_PERCENT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5.0),
+            dev_wallet_sell_alert: var("DEV_WALLET_SELL_ALERT")
+                .map(|v| v != "false")
+                .unwrap_or(true),
+            dev_wallet_from_curve: var("DEV_WALLET_FROM_CURVE")
                 .map(|v| v != "false")
                 .unwrap_or(true),
+            liquidity_added_alert_percent: var("LIQUIDITY_ADDED_ALERT_PERCENT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(100.0),
+            oscillation_min_flips: var("LIQUIDITY_OSCILLATION_FLIPS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(4),
+            oscillation_window_secs: var("LIQUIDITY_OSCILLATION_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(600),
+            oscillation_min_change_percent: var("LIQUIDITY_OSCILLATION_MIN_CHANGE_PERCENT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5.0),
+            bundle_launch_window_secs: var("BUNDLE_LAUNCH_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+            bundle_dump_window_secs: var("BUNDLE_DUMP_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1800),
+            bundle_dump_min_sol: var("BUNDLE_DUMP_MIN_SOL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0.5),
+            min_dev_hold_secs: var("MIN_DEV_HOLD_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(300),
+            rug_prefilter_logs: var("RUG_PREFILTER_LOGS")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            store_raw_logs: var("STORE_RAW_LOGS")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            lp_ignore_programs: Self::parse_list(var, "LP_IGNORE_PROGRAMS").unwrap_or_default(),
+            trusted_creators: Self::parse_list(var, "TRUSTED_CREATORS").unwrap_or_default(),
+            sell_windows: Self::parse_list(var, "SELL_WINDOWS")
+                .map(|specs| specs.iter().filter_map(|s| SellWindow::parse(s)).collect())
+                .unwrap_or_else(|| SellWindow::parse("60:3:30").into_iter().collect()),
+            rug_confirm_recheck: var("RUG_CONFIRM_RECHECK")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            verify_curve_owner: var("VERIFY_CURVE_OWNER")
+                .map(|v| v != "false")
+                .unwrap_or(true),
+            rug_confirm_delay_ms: var("RUG_CONFIRM_DELAY_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(2000),
+            health_check_secs: var("HEALTH_CHECK_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            health_check_min_secs: var("HEALTH_CHECK_MIN_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+            health_check_max_secs: var("HEALTH_CHECK_MAX_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(120),
+            rug_abandon_after_secs: var("RUG_ABANDON_AFTER_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3600),
+            rug_abandon_grace_secs: var("RUG_ABANDON_GRACE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3600),
+            auto_unwatch_after_secs: var("AUTO_UNWATCH_AFTER_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(86400),
+            auto_unwatch_flagged: var("AUTO_UNWATCH_FLAGGED")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            auto_unwatch_suspicious_after_secs: var("AUTO_UNWATCH_SUSPICIOUS_AFTER_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(604800),
+            restore_watched_hours: var("RESTORE_WATCHED_HOURS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(24),
+            safety_recheck_secs: var("SAFETY_RECHECK_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(300),
+            safety_caution_score: var("SAFETY_CAUTION_SCORE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            safety_hysteresis: var("SAFETY_HYSTERESIS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+            holder_snapshot_interval_secs: var("HOLDER_SNAPSHOT_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            holder_snapshot_retention_hours: var("HOLDER_SNAPSHOT_RETENTION_HOURS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(168),
+            db_write_retries: var("DB_WRITE_RETRIES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3),
+            db_write_backoff_ms: var("DB_WRITE_BACKOFF_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(100),
+            archive_gzip_level: var("ARCHIVE_GZIP_LEVEL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(6),
+            db_tx_batch_size: var("DB_TX_BATCH_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(100),
+            db_tx_flush_ms: var("DB_TX_FLUSH_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(500),
+            db_dead_letter_drain_secs: var("DB_DEAD_LETTER_DRAIN_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+            max_db_size_mb: var("MAX_DB_SIZE_MB")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            metrics_persist_secs: var("METRICS_PERSIST_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+            holder_milestones: Self::parse_list(var, "HOLDER_MILESTONES")
+                .map(|list| list.iter().filter_map(|v| v.parse().ok()).collect())
+                .unwrap_or_else(|| vec![100, 500, 1000]),
+            mcap_milestones_usd: Self::parse_list(var, "MCAP_MILESTONES_USD")
+                .map(|list| list.iter().filter_map(|v| v.parse().ok()).collect())
+                .unwrap_or_else(|| vec![100_000.0, 500_000.0, 1_000_000.0]),
+            sol_usd_price: var("SOL_USD_PRICE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(150.0),
+            cohort_buckets_secs: Self::parse_list(var, "COHORT_BUCKETS_SECS")
+                .map(|list| list.iter().filter_map(|v| v.parse().ok()).collect())
+                .unwrap_or_else(|| vec![60, 600, 3600]),
 
-            dashboard_port: env::var("DASHBOARD_PORT")
+            dashboard_port: var("DASHBOARD_PORT")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(3000),
+            health_max_log_age_secs: var("HEALTH_MAX_LOG_AGE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(120),
+            ws_ping_interval_secs: var("DASHBOARD_WS_PING_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            ws_client_timeout_secs: var("DASHBOARD_WS_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(90),
+            ws_alert_batch_ms: var("DASHBOARD_WS_BATCH_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(250),
+            dashboard_api_key: var("DASHBOARD_API_KEY")
+                .ok()
+                .filter(|v| !v.is_empty()),
+            cors_allowed_origins: Self::parse_list(var, "CORS_ALLOWED_ORIGINS").unwrap_or_default(),
+            dashboard_allowed_ips: Self::parse_list(var, "DASHBOARD_ALLOWED_IPS")
+                .unwrap_or_default(),
+            trust_proxy: var("TRUST_PROXY")
+                .map(|v| v == "true")
+                .unwrap_or(false),
+            api_rate_limit: var("API_RATE_LIMIT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            api_rate_limit_window_secs: var("API_RATE_LIMIT_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+
+            shutdown_drain_timeout_secs: var("SHUTDOWN_DRAIN_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+            shutdown_dashboard_timeout_secs: var("SHUTDOWN_DASHBOARD_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
+
+            instance_id: var("INSTANCE_ID")
+                .ok()
+                .filter(|v| !v.is_empty()),
+            instance_name: match var("INSTANCE_NAME") {
+                Ok(v) if v.trim().eq_ignore_ascii_case("off") => None,
+                Ok(v) if !v.trim().is_empty() => Some(v.trim().to_string()),
+                _ => Self::hostname(var),
+            },
+            lease_ttl_secs: var("LEASE_TTL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
         }
     }
 }
 
+impl Config {
+    /// Explorer link for a path such as `address/<mint>` or `tx/<signature>`
+    pub fn explorer_link(&self, path: &str) -> String {
+        self.explorer_url.replace("{path}", path)
+    }
+
+    /// Problems with the RPC/WS endpoints for the chosen cluster, for logging at startup
+    pub fn endpoint_warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let rpc = reqwest::Url::parse(&self.rpc_url).ok();
+        let ws = reqwest::Url::parse(&self.ws_url).ok();
+
+        match &rpc {
+            Some(url) if matches!(url.scheme(), "http" | "https") => {}
+            _ => warnings.push(format!("SOLANA_RPC_URL {:?} is not an http(s) URL", self.rpc_url)),
+        }
+        match &ws {
+            Some(url) if matches!(url.scheme(), "ws" | "wss") => {}
+            _ => warnings.push(format!("SOLANA_WS_URL {:?} is not a ws(s) URL", self.ws_url)),
+        }
+        if let (Some(rpc), Some(ws)) = (&rpc, &ws) {
+            if rpc.host_str() != ws.host_str() {
+                warnings.push(format!(
+                    "SOLANA_RPC_URL and SOLANA_WS_URL point at different hosts ({} vs {})",
+                    rpc.host_str().unwrap_or("?"),
+                    ws.host_str().unwrap_or("?")
+                ));
+            }
+        }
+
+        // Public endpoints name their cluster, so a mismatch is easy to spot
+        let is_local = |url: &Option<reqwest::Url>| {
+            url.as_ref()
+                .and_then(|u| u.host_str())
+                .is_some_and(|host| matches!(host, "localhost" | "127.0.0.1" | "0.0.0.0"))
+        };
+        let endpoints = [
+            ("SOLANA_RPC_URL", &rpc, &self.rpc_url),
+            ("SOLANA_WS_URL", &ws, &self.ws_url),
+        ];
+        for (name, url, raw) in endpoints {
+            let mismatch = match self.cluster {
+                Cluster::Mainnet => raw.contains("devnet") || raw.contains("testnet") || is_local(url),
+                Cluster::Devnet => raw.contains("mainnet") || raw.contains("testnet") || is_local(url),
+                Cluster::Localnet => !is_local(url),
+                Cluster::Custom => false,
+            };
+            if mismatch {
+                warnings.push(format!("{} {} doesn't look like a {:?} endpoint", name, raw, self.cluster));
+            }
+        }
+
+        if self.cluster == Cluster::Custom
+            && (env::var("SOLANA_RPC_URL").is_err() || env::var("SOLANA_WS_URL").is_err())
+        {
+            warnings.push("CLUSTER=custom should set SOLANA_RPC_URL and SOLANA_WS_URL explicitly".to_string());
+        }
+
+        warnings
+    }
+
+    /// Machine name from `HOSTNAME` or `/etc/hostname`
+    fn hostname(var: VarLookup) -> Option<String> {
+        var("HOSTNAME")
+            .ok()
+            .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Parse a comma-separated env var into a list, skipping empty entries
+    fn parse_list(var: VarLookup, key: &str) -> Option<Vec<String>> {
+        var(key).ok().map(|v| {
+            v.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self::from_env()
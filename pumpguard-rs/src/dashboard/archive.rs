@@ -0,0 +1,97 @@
+//! Archive Export - Streaming gzip NDJSON bundles of historical data for cold storage
+
+use axum::body::Body;
+use chrono::{DateTime, NaiveDate, Utc};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::{self, Write};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::utils::error::{PumpError, PumpResult};
+use crate::utils::DatabaseService;
+
+/// Compressed chunks buffered between the encoder and a slow client
+const ARCHIVE_STREAM_CHUNKS: usize = 16;
+
+/// Normalize `before` (`YYYY-MM-DD` or RFC 3339) to the RFC 3339 UTC form rows are stored in
+pub fn parse_cutoff(value: &str) -> PumpResult<String> {
+    let value = value.trim();
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Ok(ts.with_timezone(&Utc).to_rfc3339());
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|midnight| midnight.and_utc().to_rfc3339())
+        .ok_or_else(|| {
+            PumpError::InvalidInput(format!("before must be YYYY-MM-DD or RFC 3339, got {}", value))
+        })
+}
+
+/// Hands each compressed chunk to the response body; fails once the client has gone
+struct ChannelWriter(mpsc::Sender<io::Result<Vec<u8>>>);
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .blocking_send(Ok(buf.to_vec()))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "archive client disconnected"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Response body streaming everything older than `before`. The export runs on a blocking
+/// thread and only ever holds one page of rows and the encoder's buffer in memory. With
+/// `delete`, the archived rows are removed once the whole bundle has been written; an
+/// export that fails or is cut off deletes nothing.
+pub fn stream(database: Arc<DatabaseService>, before: String, delete: bool, level: u32) -> Body {
+    let (sender, receiver) = mpsc::channel(ARCHIVE_STREAM_CHUNKS);
+
+    tokio::task::spawn_blocking(move || {
+        let mut encoder =
+            GzEncoder::new(ChannelWriter(sender.clone()), Compression::new(level.min(9)));
+        let exported = database
+            .write_archive(&before, &mut encoder)
+            .and_then(|manifest| Ok(encoder.finish().map(|_| manifest)?));
+
+        let manifest = match exported {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                warn!(target: "DASHBOARD", "Archive export before {} failed: {}", before, e);
+                let _ = sender.blocking_send(Err(io::Error::other(e.to_string())));
+                return;
+            }
+        };
+        info!(
+            target: "DASHBOARD",
+            "Archived {} tokens, {} transactions, {} alerts before {}",
+            manifest.tokens,
+            manifest.transactions,
+            manifest.alerts,
+            before
+        );
+
+        if delete {
+            match database.delete_archived(&before) {
+                Ok(deleted) => info!(
+                    target: "DASHBOARD",
+                    "Deleted archived rows: {} tokens, {} transactions, {} alerts",
+                    deleted.tokens,
+                    deleted.transactions,
+                    deleted.alerts
+                ),
+                Err(e) => warn!(target: "DASHBOARD", "Failed to delete archived rows: {}", e),
+            }
+        }
+    });
+
+    Body::from_stream(futures_util::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|chunk| (chunk, receiver))
+    }))
+}
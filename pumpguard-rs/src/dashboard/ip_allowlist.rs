@@ -0,0 +1,118 @@
+//! IP Allowlist - Network-level restriction of dashboard clients by CIDR range
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// One allowed range, e.g. `10.0.0.0/8`; a bare address is a single-host range
+#[derive(Debug, Clone, Copy)]
+struct IpRange {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl IpRange {
+    fn parse(spec: &str) -> Option<Self> {
+        let (addr, prefix) = match spec.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix.parse::<u8>().ok()?)),
+            None => (spec, None),
+        };
+        let network: IpAddr = addr.trim().parse().ok()?;
+        let max_prefix = if network.is_ipv4() { 32 } else { 128 };
+        let prefix = prefix.unwrap_or(max_prefix);
+        (prefix <= max_prefix).then_some(Self { network, prefix })
+    }
+
+    fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix as u32).unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Parsed `DASHBOARD_ALLOWED_IPS`, checked on every request
+#[derive(Debug)]
+pub struct IpAllowlist {
+    ranges: Vec<IpRange>,
+    trust_proxy: bool,
+}
+
+impl IpAllowlist {
+    /// `None` when no ranges are configured, leaving the dashboard unrestricted.
+    /// Invalid entries are skipped, so a list of only typos denies everyone.
+    pub fn from_config(entries: &[String], trust_proxy: bool) -> Option<Self> {
+        if entries.is_empty() {
+            return None;
+        }
+        let ranges = entries
+            .iter()
+            .filter_map(|entry| match IpRange::parse(entry) {
+                Some(range) => Some(range),
+                None => {
+                    warn!(target: "DASHBOARD", "Ignoring invalid allowed IP range: {}", entry);
+                    None
+                }
+            })
+            .collect();
+        Some(Self { ranges, trust_proxy })
+    }
+
+    fn allows(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.ranges.iter().any(|range| range.contains(ip))
+    }
+}
+
+/// The peer address, or behind a trusted proxy the last `X-Forwarded-For` hop,
+/// which is the address the proxy itself saw (earlier hops are client-supplied)
+pub(crate) fn client_ip(request: &Request, trust_proxy: bool) -> Option<IpAddr> {
+    if trust_proxy {
+        let forwarded = request
+            .headers()
+            .get("x-forwarded-for")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.rsplit(',').next())
+            .and_then(|hop| hop.trim().parse().ok());
+        if forwarded.is_some() {
+            return forwarded;
+        }
+    }
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+}
+
+/// Reject requests from outside the allowlist with 403
+pub async fn restrict_ips(
+    State(allowlist): State<Arc<IpAllowlist>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match client_ip(&request, allowlist.trust_proxy) {
+        Some(ip) if allowlist.allows(ip) => next.run(request).await,
+        client => {
+            debug!(target: "DASHBOARD", "Rejected request from {:?}: not in allowed IPs", client);
+            (
+                StatusCode::FORBIDDEN,
+                Json(serde_json::json!({"error": "Forbidden"})),
+            )
+                .into_response()
+        }
+    }
+}
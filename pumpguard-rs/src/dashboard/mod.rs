@@ -1,7 +1,11 @@
 //! Dashboard server module
 
+pub mod archive;
+pub mod ip_allowlist;
+pub mod rate_limit;
 pub mod server;
 
-pub use server::DashboardServer;
-
+#[cfg(test)]
+mod tests;
 
+pub use server::DashboardServer;
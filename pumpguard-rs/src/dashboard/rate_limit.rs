@@ -0,0 +1,125 @@
+//! Rate Limit - Per-client token buckets guarding the `/api` routes against polling loops
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use dashmap::DashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use crate::dashboard::ip_allowlist::client_ip;
+
+/// Clients tracked before buckets that have refilled completely are dropped
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    refilled: Instant,
+}
+
+/// `API_RATE_LIMIT` requests per `API_RATE_LIMIT_WINDOW_SECS`, refilled continuously,
+/// so a client can burst up to the limit and then sustain `limit / window` per second
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    trust_proxy: bool,
+    buckets: DashMap<IpAddr, Bucket>,
+}
+
+impl RateLimiter {
+    /// `None` when the limit or window is 0, leaving the API unthrottled
+    pub fn from_config(limit: u32, window_secs: u64, trust_proxy: bool) -> Option<Self> {
+        if limit == 0 || window_secs == 0 {
+            return None;
+        }
+        Some(Self {
+            limit,
+            window: Duration::from_secs(window_secs),
+            trust_proxy,
+            buckets: DashMap::new(),
+        })
+    }
+
+    /// Tokens regained per second
+    fn refill_rate(&self) -> f64 {
+        self.limit as f64 / self.window.as_secs_f64()
+    }
+
+    /// Spend one token for `ip`: the tokens left, or how long until one is available
+    fn take(&self, ip: IpAddr) -> Result<u32, Duration> {
+        let now = Instant::now();
+        let rate = self.refill_rate();
+        let result = {
+            let mut bucket = self.buckets.entry(ip).or_insert(Bucket {
+                tokens: self.limit as f64,
+                refilled: now,
+            });
+            let elapsed = now.duration_since(bucket.refilled).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * rate).min(self.limit as f64);
+            bucket.refilled = now;
+
+            if bucket.tokens >= 1.0 {
+                bucket.tokens -= 1.0;
+                Ok(bucket.tokens.floor() as u32)
+            } else {
+                Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+            }
+        };
+
+        // A bucket untouched for a whole window is full again, so forgetting it changes nothing
+        if self.buckets.len() > MAX_TRACKED_CLIENTS {
+            self.buckets.retain(|_, bucket| now.duration_since(bucket.refilled) < self.window);
+        }
+        result
+    }
+
+    fn set_headers(&self, headers: &mut HeaderMap, remaining: u32) {
+        headers.insert("x-ratelimit-limit", HeaderValue::from(self.limit));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from(remaining));
+    }
+}
+
+/// Throttle `/api/*` per client with 429 and `Retry-After`; other routes, including
+/// `/health` and `/metrics`, pass straight through
+pub async fn limit_requests(
+    State(limiter): State<Arc<RateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !request.uri().path().starts_with("/api/") {
+        return next.run(request).await;
+    }
+    let Some(ip) = client_ip(&request, limiter.trust_proxy) else {
+        return next.run(request).await;
+    };
+
+    match limiter.take(ip.to_canonical()) {
+        Ok(remaining) => {
+            let mut response = next.run(request).await;
+            limiter.set_headers(response.headers_mut(), remaining);
+            response
+        }
+        Err(retry_after) => {
+            let retry_secs = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+            debug!(target: "DASHBOARD", "Rate limited {} for {}s", ip, retry_secs);
+            let mut response = (
+                StatusCode::TOO_MANY_REQUESTS,
+                Json(serde_json::json!({"error": "Too many requests", "retryAfter": retry_secs})),
+            )
+                .into_response();
+            limiter.set_headers(response.headers_mut(), 0);
+            response
+                .headers_mut()
+                .insert("retry-after", HeaderValue::from(retry_secs));
+            response
+        }
+    }
+}
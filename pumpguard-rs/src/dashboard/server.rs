@@ -2,10 +2,11 @@
 
 use axum::{
     extract::{
-        ws::{Message, WebSocket, WebSocketUpgrade},
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
         Path, Query, State,
     },
-    http::StatusCode,
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
+    middleware,
     response::{Html, IntoResponse, Response},
     routing::{get, post},
     Json, Router,
@@ -13,23 +14,76 @@ use axum::{
 use futures_util::{SinkExt, StreamExt};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
 use std::sync::Arc;
-use tokio::sync::broadcast;
-use tower_http::cors::{Any, CorsLayer};
+use tokio::sync::watch;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use tower_http::services::ServeDir;
-use tracing::{error, info};
+use tracing::{info, warn};
 
 use crate::config::Config;
-use crate::modules::{RugDetector, TokenMonitor, WhaleWatcher};
-use crate::utils::{AlertService, DatabaseService, MetricsService};
+use crate::dashboard::archive;
+use crate::dashboard::ip_allowlist::{restrict_ips, IpAllowlist};
+use crate::dashboard::rate_limit::{limit_requests, RateLimiter};
+use crate::modules::custom_watch::CustomWatchRule;
+use crate::modules::whale_watcher::MoverSort;
+use crate::modules::{CustomWatcher, RugDetector, TokenMonitor, WhaleWatcher};
+use crate::utils::{AlertService, DatabaseService, LeaderElection, MetricsService, PumpError, SolanaService};
 use crate::utils::alerts::Alert;
+use crate::utils::database::TokenQuery;
+use crate::utils::metrics::{advance_counter, CounterTotals};
+use crate::utils::leader::InstanceRole;
+use crate::utils::logger::LogFilter;
+use crate::utils::preflight::PreflightReport;
+use crate::utils::solana::SubscriptionMode;
 
 /// Query params for list endpoints
 #[derive(Debug, Deserialize)]
 pub struct ListParams {
     limit: Option<usize>,
+    offset: Option<usize>,
+}
+
+/// Top movers query
+#[derive(Debug, Deserialize)]
+pub struct MoversParams {
+    limit: Option<usize>,
+    min_volume: Option<f64>,
+    sort: Option<MoverSort>,
+}
+
+/// Token listing query with optional status filters
+#[derive(Debug, Deserialize)]
+pub struct TokenListParams {
+    limit: Option<usize>,
+    rugged: Option<bool>,
+    graduated: Option<bool>,
+    min_liquidity: Option<f64>,
+}
+
+impl TokenListParams {
+    fn query(&self) -> TokenQuery {
+        TokenQuery {
+            rugged: self.rugged,
+            graduated: self.graduated,
+            min_liquidity: self.min_liquidity,
+        }
+    }
+}
+
+/// Token search query
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    q: String,
+    limit: Option<usize>,
+}
+
+/// Cohort bucket override, e.g. `?buckets=60,600,3600`
+#[derive(Debug, Deserialize)]
+pub struct CohortParams {
+    buckets: Option<String>,
 }
 
 /// Filter request body
@@ -39,6 +93,56 @@ pub struct FilterRequest {
     value: f64,
 }
 
+/// Monitored program list request body
+#[derive(Debug, Deserialize)]
+pub struct ProgramsRequest {
+    programs: Vec<String>,
+}
+
+/// Custom watch rule list request body
+#[derive(Debug, Deserialize)]
+pub struct CustomWatchRulesRequest {
+    rules: Vec<CustomWatchRule>,
+}
+
+/// Manual rug flag request body
+#[derive(Debug, Deserialize)]
+pub struct FlagTokenRequest {
+    rugged: bool,
+    reason: Option<String>,
+}
+
+/// Alert type toggles request body; types left out keep their setting
+#[derive(Debug, Deserialize)]
+pub struct AlertSettingsRequest {
+    types: HashMap<String, bool>,
+}
+
+/// Runtime log level request body
+#[derive(Debug, Deserialize)]
+pub struct LogLevelRequest {
+    target: String,
+    level: String,
+}
+
+/// LP-removal allowlist request body
+#[derive(Debug, Deserialize)]
+pub struct LpAllowlistRequest {
+    entries: Vec<String>,
+}
+
+/// Sell-pressure window list request body
+#[derive(Debug, Deserialize)]
+pub struct SellWindowsRequest {
+    entries: Vec<String>,
+}
+
+/// Trusted creator list request body
+#[derive(Debug, Deserialize)]
+pub struct TrustedCreatorsRequest {
+    entries: Vec<String>,
+}
+
 /// Blacklist/Whitelist request body
 #[derive(Debug, Deserialize)]
 pub struct AddressRequest {
@@ -62,6 +166,42 @@ pub struct WatchTokenRequest {
     initial_liquidity: f64,
 }
 
+/// Snapshot of in-memory detection state for backup/migration
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    #[serde(default)]
+    exported_at: String,
+    #[serde(default)]
+    watched_tokens: Vec<crate::modules::rug_detector::WatchedToken>,
+    #[serde(default)]
+    watched_wallets: Vec<crate::modules::whale_watcher::WatchedWallet>,
+    /// Informational only - movements are rebuilt from live trades and not imported
+    #[serde(default)]
+    token_movements: Vec<crate::modules::whale_watcher::TopMover>,
+    filters: Option<crate::modules::token_monitor::TokenFilters>,
+}
+
+/// Watched token plus the threshold profile currently applied to it
+#[derive(Debug, Serialize)]
+pub struct TokenDetailsResponse {
+    #[serde(flatten)]
+    token: crate::modules::rug_detector::WatchedToken,
+    profile: crate::modules::rug_detector::ThresholdProfile,
+}
+
+/// Query for an archive export; deleting needs both flags
+#[derive(Debug, Deserialize)]
+pub struct ArchiveParams {
+    before: String,
+    #[serde(default)]
+    delete: bool,
+    #[serde(default)]
+    confirm: bool,
+}
+
+/// Max sells/transactions kept per token/wallet in a state export
+const EXPORT_MAX_TRANSACTIONS: usize = 20;
+
 /// API success response
 #[derive(Debug, Serialize)]
 pub struct ApiResponse {
@@ -74,7 +214,19 @@ pub struct ApiResponse {
 pub struct HealthResponse {
     status: String,
     uptime: f64,
+    last_log_age_secs: Option<f64>,
+    subscription_mode: SubscriptionMode,
+    instance_name: Option<String>,
     modules: ModuleStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instance: Option<InstanceStatus>,
+}
+
+/// Leader election status, present when `INSTANCE_ID` is set
+#[derive(Debug, Serialize)]
+pub struct InstanceStatus {
+    instance_id: String,
+    role: InstanceRole,
 }
 
 #[derive(Debug, Serialize)]
@@ -99,28 +251,79 @@ pub struct StatsResponse {
 pub enum WsMessage {
     #[serde(rename = "init")]
     Init {
-        stats: StatsResponse,
+        stats: Box<StatsResponse>,
         recent_alerts: Vec<Alert>,
     },
     #[serde(rename = "alert")]
     Alert(Alert),
-    #[serde(rename = "stats")]
-    Stats(StatsResponse),
+    /// Alerts buffered over the batching window, for clients that opted in
+    #[serde(rename = "alert_batch")]
+    AlertBatch(Vec<Alert>),
+    /// The client fell behind the alert channel and missed this many alerts; an `init`
+    /// resync follows
+    #[serde(rename = "gap")]
+    Gap { missed: u64 },
 }
 
+impl WsMessage {
+    /// Current stats and recent alerts, sent on connect and after a gap
+    fn init(state: &AppState) -> Self {
+        WsMessage::Init {
+            stats: Box::new(StatsResponse {
+                token_monitor: state.token_monitor.get_stats(),
+                rug_detector: state.rug_detector.get_stats(),
+                whale_watcher: state.whale_watcher.get_stats(),
+            }),
+            recent_alerts: state.alerts.get_recent_alerts(20),
+        }
+    }
+}
+
+/// Messages a WebSocket client can send
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+enum WsClientMessage {
+    #[serde(rename = "subscribe")]
+    Subscribe {
+        /// Receive alerts as `alert_batch` frames instead of one frame each
+        #[serde(default, rename = "batchAlerts")]
+        batch_alerts: bool,
+    },
+}
+
+/// Flush an alert batch early once it holds this many alerts
+const MAX_ALERT_BATCH: usize = 100;
+
 /// Application state shared across handlers
 #[derive(Clone)]
 pub struct AppState {
     pub config: Config,
+    pub solana: Arc<SolanaService>,
     pub token_monitor: TokenMonitor,
     pub rug_detector: RugDetector,
     pub whale_watcher: WhaleWatcher,
     pub alerts: Arc<AlertService>,
     pub database: Arc<DatabaseService>,
     pub metrics: Arc<MetricsService>,
+    pub election: Option<LeaderElection>,
+    pub preflight: Arc<RwLock<Option<PreflightReport>>>,
+    pub custom_watcher: Option<CustomWatcher>,
+    pub shutdown: Option<watch::Receiver<bool>>,
+    pub log_filter: Option<Arc<LogFilter>>,
     pub start_time: std::time::Instant,
 }
 
+/// Resolves once `true` is sent on `shutdown`; never without a channel or once its
+/// sender is gone
+async fn shutdown_requested(shutdown: Option<watch::Receiver<bool>>) {
+    if let Some(mut shutdown) = shutdown {
+        if shutdown.wait_for(|requested| *requested).await.is_ok() {
+            return;
+        }
+    }
+    std::future::pending::<()>().await
+}
+
 /// Dashboard server
 pub struct DashboardServer {
     config: Config,
@@ -129,8 +332,10 @@ pub struct DashboardServer {
 
 impl DashboardServer {
     /// Create a new dashboard server
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         config: Config,
+        solana: Arc<SolanaService>,
         token_monitor: TokenMonitor,
         rug_detector: RugDetector,
         whale_watcher: WhaleWatcher,
@@ -140,52 +345,180 @@ impl DashboardServer {
     ) -> Self {
         let state = AppState {
             config: config.clone(),
+            solana,
             token_monitor,
             rug_detector,
             whale_watcher,
             alerts,
             database,
             metrics,
+            election: None,
+            preflight: Arc::new(RwLock::new(None)),
+            custom_watcher: None,
+            shutdown: None,
+            log_filter: None,
             start_time: std::time::Instant::now(),
         };
 
         Self { config, state }
     }
 
+    /// Report this instance's leader election status in `/health`
+    pub fn with_election(mut self, election: LeaderElection) -> Self {
+        self.state.election = Some(election);
+        self
+    }
+
+    /// Serve and edit the custom watch rules at `/api/custom-watch/rules`
+    pub fn with_custom_watcher(mut self, custom_watcher: CustomWatcher) -> Self {
+        self.state.custom_watcher = Some(custom_watcher);
+        self
+    }
+
+    /// Stop accepting connections and close WebSocket clients once `true` is sent
+    pub fn with_shutdown(mut self, shutdown: watch::Receiver<bool>) -> Self {
+        self.state.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Let `/api/log/level` change log levels through `log_filter`
+    pub fn with_log_filter(mut self, log_filter: Arc<LogFilter>) -> Self {
+        self.state.log_filter = Some(log_filter);
+        self
+    }
+
+    /// Serve the startup preflight report at `/api/preflight`
+    pub fn with_preflight(mut self, preflight: Arc<RwLock<Option<PreflightReport>>>) -> Self {
+        self.state.preflight = preflight;
+        self
+    }
+
     /// Start the dashboard server
     pub async fn start(&self) -> anyhow::Result<()> {
-        let cors = CorsLayer::new()
-            .allow_origin(Any)
-            .allow_methods(Any)
-            .allow_headers(Any);
+        if self.config.dashboard_api_key.is_some() && self.allowed_origins().is_none() {
+            warn!(
+                target: "DASHBOARD",
+                "DASHBOARD_API_KEY is set but CORS allows any origin; set CORS_ALLOWED_ORIGINS to restrict browser access"
+            );
+        }
+
+        let app = self.router();
+
+        let addr = SocketAddr::from(([0, 0, 0, 0], self.config.dashboard_port));
+        info!(target: "DASHBOARD", "✅ Dashboard running at http://localhost:{}", self.config.dashboard_port);
+
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        // Peer addresses are needed by the IP allowlist
+        axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+            .with_graceful_shutdown(shutdown_requested(self.state.shutdown.clone()))
+            .await?;
+
+        info!(target: "DASHBOARD", "Dashboard stopped");
+        Ok(())
+    }
 
-        let app = Router::new()
+    /// Explicit CORS origins, or `None` when any origin is allowed (no list, or a `*` entry)
+    fn allowed_origins(&self) -> Option<Vec<HeaderValue>> {
+        let origins = &self.config.cors_allowed_origins;
+        if origins.is_empty() || origins.iter().any(|o| o == "*") {
+            return None;
+        }
+        let parsed = origins
+            .iter()
+            .filter_map(|origin| match HeaderValue::from_str(origin.trim_end_matches('/')) {
+                Ok(value) => Some(value),
+                Err(_) => {
+                    warn!(target: "DASHBOARD", "Ignoring invalid CORS origin: {}", origin);
+                    None
+                }
+            })
+            .collect();
+        Some(parsed)
+    }
+
+    /// Build the API/WebSocket router with its shared state
+    pub fn router(&self) -> Router {
+        // A wildcard origin never allows credentials; only listed origins get them reflected
+        let cors = match self.allowed_origins() {
+            Some(origins) => CorsLayer::new()
+                .allow_origin(AllowOrigin::list(origins))
+                .allow_methods([Method::GET, Method::POST])
+                .allow_headers([
+                    header::CONTENT_TYPE,
+                    header::AUTHORIZATION,
+                    HeaderName::from_static("x-api-key"),
+                ])
+                .allow_credentials(true),
+            None => CorsLayer::new()
+                .allow_origin(Any)
+                .allow_methods(Any)
+                .allow_headers(Any),
+        };
+
+        let router = Router::new()
             // API Routes
             .route("/api/stats", get(get_stats))
+            .route("/api/stats/cohorts", get(get_token_cohorts))
+            .route("/api/config", get(get_config))
+            .route("/api/preflight", get(get_preflight))
             // Token Monitor
             .route("/api/tokens/recent", get(get_recent_tokens))
             .route("/api/tokens/start", post(start_token_monitor))
             .route("/api/tokens/stop", post(stop_token_monitor))
             .route("/api/tokens/filter", post(set_token_filter))
             .route("/api/tokens/blacklist", post(blacklist_creator))
+            .route("/api/tokens/blacklist/remove", post(unblacklist_creator))
+            .route("/api/tokens/spam-creators", get(get_spam_creators))
             // Rug Detector
             .route("/api/rug/watched", get(get_watched_tokens))
             .route("/api/rug/token/:mint", get(get_token_details))
+            .route("/api/rug/token/:mint/dev", get(get_token_dev).post(verify_token_dev))
+            .route("/api/rug/token/:mint/holders", get(get_token_holders))
+            .route("/api/rug/token/:mint/mute", post(mute_token))
+            .route("/api/rug/token/:mint/unmute", post(unmute_token))
+            .route("/api/rug/token/:mint/flag", post(flag_token))
             .route("/api/rug/watch", post(watch_token))
+            .route("/api/rug/thresholds", get(get_rug_thresholds).post(set_rug_threshold))
+            .route("/api/rug/lp-allowlist", get(get_lp_allowlist).post(set_lp_allowlist))
+            .route("/api/rug/sell-windows", get(get_sell_windows).post(set_sell_windows))
+            .route(
+                "/api/rug/trusted-creators",
+                get(get_trusted_creators).post(set_trusted_creators),
+            )
             .route("/api/rug/start", post(start_rug_detector))
             .route("/api/rug/stop", post(stop_rug_detector))
             // Whale Watcher
             .route("/api/whales", get(get_whales))
             .route("/api/whales/movers", get(get_top_movers))
+            .route("/api/whales/accumulating", get(get_accumulating))
             .route("/api/whales/wallet/:address", get(get_wallet_activity))
+            .route("/api/whales/wallet/:address/transactions", get(get_wallet_transactions))
             .route("/api/whales/watch", post(watch_wallet))
+            .route("/api/whales/import", post(import_wallets_csv))
+            .route("/api/whales/export", get(export_wallets_csv))
+            .route("/api/whales/thresholds", get(get_whale_thresholds).post(set_whale_threshold))
             .route("/api/whales/start", post(start_whale_watcher))
             .route("/api/whales/stop", post(stop_whale_watcher))
+            // Solana
+            .route("/api/solana/programs", get(get_programs).post(set_programs))
+            // Custom watch
+            .route("/api/custom-watch/rules", get(get_custom_watch_rules).post(set_custom_watch_rules))
+            .route("/api/custom-watch/stats", get(get_custom_watch_stats))
             // Alerts
             .route("/api/alerts", get(get_alerts))
+            .route("/api/alerts/settings", get(get_alert_settings).post(set_alert_settings))
+            .route("/api/log/level", get(get_log_level).post(set_log_level))
             // Tokens from database
             .route("/api/tokens", get(get_db_tokens))
+            .route("/api/tokens/search", get(search_db_tokens))
             .route("/api/tokens/:mint", get(get_db_token))
+            .route("/api/tokens/:mint/transactions/:signature/logs", get(get_tx_logs))
+            .route("/api/db/dead-letters", get(get_dead_letters))
+            .route("/api/db/dead-letters/replay", post(replay_dead_letters))
+            // State snapshot
+            .route("/api/export/state", get(export_state))
+            .route("/api/export/archive", get(export_archive))
+            .route("/api/import/state", post(import_state))
             // Prometheus metrics
             .route("/metrics", get(get_metrics))
             // Health checks
@@ -194,17 +527,28 @@ impl DashboardServer {
             // WebSocket
             .route("/ws", get(ws_handler))
             // Static files (dashboard)
-            .nest_service("/", ServeDir::new("public").fallback(get(serve_index)))
-            .layer(cors)
-            .with_state(self.state.clone());
+            .nest_service("/", ServeDir::new("public").fallback(get(serve_index)));
 
-        let addr = SocketAddr::from(([0, 0, 0, 0], self.config.dashboard_port));
-        info!(target: "DASHBOARD", "✅ Dashboard running at http://localhost:{}", self.config.dashboard_port);
-
-        let listener = tokio::net::TcpListener::bind(addr).await?;
-        axum::serve(listener, app).await?;
+        // Inside CORS, so browser clients can read the 429 and its headers
+        let router = match RateLimiter::from_config(
+            self.config.api_rate_limit,
+            self.config.api_rate_limit_window_secs,
+            self.config.trust_proxy,
+        ) {
+            Some(limiter) => {
+                router.layer(middleware::from_fn_with_state(Arc::new(limiter), limit_requests))
+            }
+            None => router,
+        };
+        let router = router.layer(cors).with_state(self.state.clone());
 
-        Ok(())
+        // Outermost, so rejected clients never reach CORS or the handlers
+        match IpAllowlist::from_config(&self.config.dashboard_allowed_ips, self.config.trust_proxy) {
+            Some(allowlist) => {
+                router.layer(middleware::from_fn_with_state(Arc::new(allowlist), restrict_ips))
+            }
+            None => router,
+        }
     }
 }
 
@@ -216,6 +560,115 @@ async fn serve_index() -> Html<&'static str> {
     Html(include_str!("../../public/index.html"))
 }
 
+/// Check the `X-API-Key` (or `Authorization: Bearer`) header when an API key is configured
+fn is_authorized(state: &AppState, headers: &HeaderMap) -> bool {
+    let expected = match &state.config.dashboard_api_key {
+        Some(key) => key,
+        None => return true,
+    };
+
+    let provided = headers
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| {
+            headers
+                .get(axum::http::header::AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+        });
+
+    provided == Some(expected.as_str())
+}
+
+impl IntoResponse for PumpError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            PumpError::NotFound(_) => StatusCode::NOT_FOUND,
+            PumpError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+            PumpError::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            PumpError::RpcUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            _ if self.is_storage_busy() => StatusCode::SERVICE_UNAVAILABLE,
+            PumpError::Storage(_) | PumpError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (status, Json(serde_json::json!({"error": self.to_string()}))).into_response()
+    }
+}
+
+fn unauthorized() -> Response {
+    (StatusCode::UNAUTHORIZED, Json(serde_json::json!({"error": "Unauthorized"}))).into_response()
+}
+
+// Effective configuration with secrets redacted
+async fn get_config(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+    Json(state.config.clone()).into_response()
+}
+
+// Startup preflight report
+async fn get_preflight(State(state): State<AppState>) -> Response {
+    match state.preflight.read().clone() {
+        Some(report) => Json(report).into_response(),
+        None => PumpError::NotFound("Preflight report".to_string()).into_response(),
+    }
+}
+
+// Solana handlers
+async fn get_programs(State(state): State<AppState>) -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "programs": state.solana.monitored_programs() }))
+}
+
+async fn set_programs(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<ProgramsRequest>,
+) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+    match state.solana.set_monitored_programs(req.programs) {
+        Ok(programs) => Json(serde_json::json!({ "programs": programs })).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// Custom watch handlers
+fn custom_watcher(state: &AppState) -> Result<&CustomWatcher, PumpError> {
+    state
+        .custom_watcher
+        .as_ref()
+        .ok_or_else(|| PumpError::NotFound("Custom watcher".to_string()))
+}
+
+async fn get_custom_watch_rules(State(state): State<AppState>) -> Response {
+    match custom_watcher(&state) {
+        Ok(watcher) => Json(serde_json::json!({ "rules": watcher.get_rules() })).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn set_custom_watch_rules(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<CustomWatchRulesRequest>,
+) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+    match custom_watcher(&state).and_then(|watcher| watcher.set_rules(req.rules)) {
+        Ok(rules) => Json(serde_json::json!({ "rules": rules })).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn get_custom_watch_stats(State(state): State<AppState>) -> Response {
+    match custom_watcher(&state) {
+        Ok(watcher) => Json(watcher.get_stats()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
 async fn get_stats(State(state): State<AppState>) -> Json<StatsResponse> {
     Json(StatsResponse {
         token_monitor: state.token_monitor.get_stats(),
@@ -227,10 +680,24 @@ async fn get_stats(State(state): State<AppState>) -> Json<StatsResponse> {
 // Token Monitor handlers
 async fn get_recent_tokens(
     State(state): State<AppState>,
-    Query(params): Query<ListParams>,
+    Query(params): Query<TokenListParams>,
 ) -> Json<Vec<crate::modules::token_monitor::DetectedToken>> {
     let limit = params.limit.unwrap_or(50);
-    Json(state.token_monitor.get_recent_tokens(limit))
+    let query = params.query();
+    let mut tokens = state.token_monitor.get_recent_tokens(usize::MAX);
+
+    // Status lives on the rug detector's watched entry; unwatched tokens count as neither
+    tokens.retain(|token| {
+        let watched = state.rug_detector.get_token_details(&token.mint);
+        let rugged = watched.as_ref().is_some_and(|w| w.is_rugged);
+        let graduated = watched.as_ref().is_some_and(|w| w.graduated);
+        let liquidity = watched.as_ref().map_or(token.initial_liquidity, |w| w.current_liquidity);
+        query.rugged.is_none_or(|r| r == rugged)
+            && query.graduated.is_none_or(|g| g == graduated)
+            && query.min_liquidity.is_none_or(|min| liquidity >= min)
+    });
+    tokens.truncate(limit);
+    Json(tokens)
 }
 
 async fn start_token_monitor(State(state): State<AppState>) -> Json<ApiResponse> {
@@ -264,13 +731,34 @@ async fn blacklist_creator(
     State(state): State<AppState>,
     Json(req): Json<AddressRequest>,
 ) -> Json<ApiResponse> {
-    state.token_monitor.blacklist_creator(&req.address);
+    state.token_monitor.blacklist_creator(&req.address, "manual");
     Json(ApiResponse {
         success: true,
         message: format!("Creator {} blacklisted", req.address),
     })
 }
 
+async fn unblacklist_creator(
+    State(state): State<AppState>,
+    Json(req): Json<AddressRequest>,
+) -> Response {
+    match state.token_monitor.unblacklist_creator(&req.address) {
+        Ok(true) => Json(ApiResponse {
+            success: true,
+            message: format!("Creator {} removed from blacklist", req.address),
+        })
+        .into_response(),
+        Ok(false) => PumpError::NotFound("Blacklisted creator".to_string()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn get_spam_creators(
+    State(state): State<AppState>,
+) -> Json<Vec<crate::modules::token_monitor::SuppressedCreator>> {
+    Json(state.token_monitor.get_suppressed_creators())
+}
+
 // Rug Detector handlers
 async fn get_watched_tokens(
     State(state): State<AppState>,
@@ -283,11 +771,95 @@ async fn get_token_details(
     Path(mint): Path<String>,
 ) -> Response {
     match state.rug_detector.get_token_details(&mint) {
-        Some(token) => Json(token).into_response(),
+        Some(token) => {
+            let profile = state.rug_detector.get_thresholds().profile(token.graduated);
+            Json(TokenDetailsResponse { token, profile }).into_response()
+        }
         None => (StatusCode::NOT_FOUND, Json(serde_json::json!({"error": "Token not found"}))).into_response(),
     }
 }
 
+async fn get_token_dev(State(state): State<AppState>, Path(mint): Path<String>) -> Response {
+    match state.rug_detector.dev_wallet_info(&mint) {
+        Ok(info) => Json(info).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn verify_token_dev(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(mint): Path<String>,
+) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+    match state.rug_detector.verify_dev_wallet(&mint).await {
+        Ok(info) => Json(info).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn get_token_holders(
+    State(state): State<AppState>,
+    Path(mint): Path<String>,
+    Query(params): Query<ListParams>,
+) -> Response {
+    let limit = params.limit.unwrap_or(100).min(1000) as i64;
+    match state.database.get_holder_snapshots(&mint, limit) {
+        Ok(snapshots) => Json(snapshots).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn mute_token(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(mint): Path<String>,
+) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+    set_token_muted(&state, &mint, true).await
+}
+
+async fn unmute_token(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(mint): Path<String>,
+) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+    set_token_muted(&state, &mint, false).await
+}
+
+async fn set_token_muted(state: &AppState, mint: &str, muted: bool) -> Response {
+    match state.rug_detector.set_muted(mint, muted).await {
+        Some(token) => Json(token).into_response(),
+        None => (StatusCode::NOT_FOUND, Json(serde_json::json!({"error": "Token not watched"}))).into_response(),
+    }
+}
+
+async fn flag_token(
+    State(state): State<AppState>,
+    Path(mint): Path<String>,
+    headers: HeaderMap,
+    Json(req): Json<FlagTokenRequest>,
+) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+    let reason = req
+        .reason
+        .filter(|r| !r.trim().is_empty())
+        .unwrap_or_else(|| "manual".to_string());
+    match state.rug_detector.flag_token(&mint, req.rugged, &reason).await {
+        Ok(token) => Json(token).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
 async fn watch_token(
     State(state): State<AppState>,
     Json(req): Json<WatchTokenRequest>,
@@ -305,6 +877,58 @@ async fn watch_token(
     })
 }
 
+async fn get_lp_allowlist(State(state): State<AppState>) -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "entries": state.rug_detector.get_lp_ignore_programs() }))
+}
+
+async fn set_lp_allowlist(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<LpAllowlistRequest>,
+) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+    let entries = state.rug_detector.set_lp_ignore_programs(req.entries);
+    Json(serde_json::json!({ "entries": entries })).into_response()
+}
+
+async fn get_sell_windows(State(state): State<AppState>) -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "entries": state.rug_detector.get_sell_windows() }))
+}
+
+async fn set_sell_windows(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<SellWindowsRequest>,
+) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+    match state.rug_detector.set_sell_windows(&req.entries) {
+        Ok(entries) => Json(serde_json::json!({ "entries": entries })).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn get_trusted_creators(State(state): State<AppState>) -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "entries": state.rug_detector.get_trusted_creators() }))
+}
+
+async fn set_trusted_creators(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<TrustedCreatorsRequest>,
+) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+    match state.rug_detector.set_trusted_creators(req.entries) {
+        Ok(entries) => Json(serde_json::json!({ "entries": entries })).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
 async fn start_rug_detector(State(state): State<AppState>) -> Json<ApiResponse> {
     let _ = state.rug_detector.start().await;
     Json(ApiResponse {
@@ -330,8 +954,31 @@ async fn get_whales(
 
 async fn get_top_movers(
     State(state): State<AppState>,
-) -> Json<Vec<crate::modules::whale_watcher::TopMover>> {
-    Json(state.whale_watcher.get_top_movers(10))
+    Query(params): Query<MoversParams>,
+) -> Json<serde_json::Value> {
+    let limit = params.limit.unwrap_or(10);
+    let min_volume = params.min_volume.unwrap_or(state.config.movers_min_volume_sol);
+    let sort = params.sort.unwrap_or_default();
+    let movers = state.whale_watcher.get_top_movers(limit, min_volume, sort);
+    Json(serde_json::json!({
+        "movers": movers,
+        "filter": {
+            "limit": limit,
+            "minVolume": min_volume,
+            "sort": sort,
+        },
+    }))
+}
+
+async fn get_accumulating(
+    State(state): State<AppState>,
+    Query(params): Query<ListParams>,
+) -> Json<serde_json::Value> {
+    let limit = params.limit.unwrap_or(20);
+    Json(serde_json::json!({
+        "tokens": state.whale_watcher.get_accumulating(limit),
+        "alertScore": state.whale_watcher.get_thresholds().accumulation_alert_score,
+    }))
 }
 
 async fn get_wallet_activity(
@@ -344,6 +991,19 @@ async fn get_wallet_activity(
     }
 }
 
+async fn get_wallet_transactions(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+    Query(params): Query<ListParams>,
+) -> Response {
+    let limit = params.limit.unwrap_or(100).min(1000) as i64;
+    let offset = params.offset.unwrap_or(0) as i64;
+    match state.database.get_transactions_for_wallet(&address, limit, offset) {
+        Ok(txs) => Json(txs).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
 async fn watch_wallet(
     State(state): State<AppState>,
     Json(req): Json<WatchWalletRequest>,
@@ -355,6 +1015,63 @@ async fn watch_wallet(
     })
 }
 
+async fn import_wallets_csv(
+    State(state): State<AppState>,
+    body: String,
+) -> Json<crate::modules::whale_watcher::WalletCsvImport> {
+    Json(state.whale_watcher.import_wallets_csv(&body))
+}
+
+async fn export_wallets_csv(State(state): State<AppState>) -> Response {
+    (
+        [(axum::http::header::CONTENT_TYPE, "text/csv; charset=utf-8")],
+        state.whale_watcher.export_wallets_csv(),
+    )
+        .into_response()
+}
+
+async fn get_rug_thresholds(
+    State(state): State<AppState>,
+) -> Json<crate::modules::rug_detector::RugThresholds> {
+    Json(state.rug_detector.get_thresholds())
+}
+
+async fn set_rug_threshold(
+    State(state): State<AppState>,
+    Json(req): Json<FilterRequest>,
+) -> Response {
+    if state.rug_detector.set_threshold(&req.key, req.value) {
+        Json(ApiResponse {
+            success: true,
+            message: format!("Threshold {} set to {}", req.key, req.value),
+        })
+        .into_response()
+    } else {
+        (StatusCode::BAD_REQUEST, Json(serde_json::json!({"error": "Unknown threshold"}))).into_response()
+    }
+}
+
+async fn get_whale_thresholds(
+    State(state): State<AppState>,
+) -> Json<crate::modules::whale_watcher::WhaleThresholds> {
+    Json(state.whale_watcher.get_thresholds())
+}
+
+async fn set_whale_threshold(
+    State(state): State<AppState>,
+    Json(req): Json<FilterRequest>,
+) -> Response {
+    if state.whale_watcher.set_threshold(&req.key, req.value) {
+        Json(ApiResponse {
+            success: true,
+            message: format!("Threshold {} set to {}", req.key, req.value),
+        })
+        .into_response()
+    } else {
+        (StatusCode::BAD_REQUEST, Json(serde_json::json!({"error": "Unknown threshold"}))).into_response()
+    }
+}
+
 async fn start_whale_watcher(State(state): State<AppState>) -> Json<ApiResponse> {
     let _ = state.whale_watcher.start().await;
     Json(ApiResponse {
@@ -380,15 +1097,124 @@ async fn get_alerts(
     Json(state.alerts.get_recent_alerts(limit))
 }
 
+async fn get_alert_settings(State(state): State<AppState>) -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "types": state.alerts.alert_settings() }))
+}
+
+async fn set_alert_settings(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<AlertSettingsRequest>,
+) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+    match state.alerts.set_alert_settings(&req.types) {
+        Ok(types) => Json(serde_json::json!({ "types": types })).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+fn log_filter_response(log_filter: &LogFilter, filter: String) -> Response {
+    Json(serde_json::json!({ "filter": filter, "overrides": log_filter.overrides() }))
+        .into_response()
+}
+
+async fn get_log_level(State(state): State<AppState>) -> Response {
+    match &state.log_filter {
+        Some(log_filter) => log_filter_response(log_filter, log_filter.current()),
+        None => PumpError::NotFound("Runtime log filter".to_string()).into_response(),
+    }
+}
+
+async fn set_log_level(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<LogLevelRequest>,
+) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+    let Some(log_filter) = &state.log_filter else {
+        return PumpError::NotFound("Runtime log filter".to_string()).into_response();
+    };
+    match log_filter.set_level(&req.target, &req.level) {
+        Ok(filter) => log_filter_response(log_filter, filter),
+        Err(e) => e.into_response(),
+    }
+}
+
 // Database handlers
+async fn get_dead_letters(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+    Json(serde_json::json!({
+        "writeFailures": state.database.write_failures(),
+        "deadLetters": state.database.dead_letters(),
+    }))
+    .into_response()
+}
+
+async fn replay_dead_letters(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+    let written = state.database.drain_dead_letters();
+    Json(serde_json::json!({
+        "written": written,
+        "remaining": state.database.dead_letter_count(),
+    }))
+    .into_response()
+}
+
 async fn get_db_tokens(
     State(state): State<AppState>,
-    Query(params): Query<ListParams>,
-) -> Json<Vec<crate::utils::database::TokenRecord>> {
+    Query(params): Query<TokenListParams>,
+) -> Response {
     let limit = params.limit.unwrap_or(50) as i64;
-    match state.database.get_recent_tokens(limit) {
-        Ok(tokens) => Json(tokens),
-        Err(_) => Json(vec![]),
+    match state.database.get_recent_tokens(limit, &params.query()) {
+        Ok(tokens) => Json(tokens).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn get_token_cohorts(
+    State(state): State<AppState>,
+    Query(params): Query<CohortParams>,
+) -> Response {
+    let boundaries = match params.buckets.as_deref() {
+        Some(buckets) => match buckets
+            .split(',')
+            .map(|b| b.trim().parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(boundaries) => boundaries,
+            Err(_) => {
+                return PumpError::InvalidInput(format!("buckets must be seconds, got {}", buckets))
+                    .into_response()
+            }
+        },
+        None => state.config.cohort_buckets_secs.clone(),
+    };
+    match state.database.get_token_cohorts(&boundaries) {
+        Ok(cohorts) => Json(serde_json::json!({ "cohorts": cohorts })).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn search_db_tokens(
+    State(state): State<AppState>,
+    Query(params): Query<SearchParams>,
+) -> Response {
+    let query = params.q.trim();
+    if query.is_empty() {
+        return PumpError::InvalidInput("q must not be empty".to_string()).into_response();
+    }
+    let limit = params.limit.unwrap_or(20).min(100) as i64;
+    match state.database.search_tokens(query, limit) {
+        Ok(tokens) => Json(tokens).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
@@ -398,18 +1224,190 @@ async fn get_db_token(
 ) -> Response {
     match state.database.get_token(&mint) {
         Ok(Some(token)) => Json(token).into_response(),
-        _ => (StatusCode::NOT_FOUND, Json(serde_json::json!({"error": "Token not found"}))).into_response(),
+        Ok(None) => PumpError::NotFound("Token".to_string()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn get_tx_logs(
+    State(state): State<AppState>,
+    Path((mint, signature)): Path<(String, String)>,
+) -> Response {
+    match state.database.get_tx_logs(&mint, &signature) {
+        Ok(Some(record)) => Json(record).into_response(),
+        Ok(None) => PumpError::NotFound("Transaction logs".to_string()).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+// State snapshot handlers
+async fn export_state(State(state): State<AppState>) -> Json<StateSnapshot> {
+    Json(StateSnapshot {
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        watched_tokens: state.rug_detector.export_watched_tokens(EXPORT_MAX_TRANSACTIONS),
+        watched_wallets: state.whale_watcher.export_wallets(EXPORT_MAX_TRANSACTIONS),
+        token_movements: state.whale_watcher.get_top_movers(usize::MAX, 0.0, MoverSort::NetFlow),
+        filters: Some(state.token_monitor.get_filters()),
+    })
+}
+
+async fn export_archive(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<ArchiveParams>,
+) -> Response {
+    let before = match archive::parse_cutoff(&params.before) {
+        Ok(before) => before,
+        Err(e) => return e.into_response(),
+    };
+    if params.delete {
+        if !is_authorized(&state, &headers) {
+            return unauthorized();
+        }
+        if !params.confirm {
+            return PumpError::InvalidInput("delete=true also needs confirm=true".to_string())
+                .into_response();
+        }
+    }
+
+    let filename = format!(
+        "attachment; filename=\"pumpguard-archive-{}.ndjson.gz\"",
+        &before[..10]
+    );
+    let body = archive::stream(
+        Arc::clone(&state.database),
+        before,
+        params.delete,
+        state.config.archive_gzip_level,
+    );
+    (
+        [
+            (header::CONTENT_TYPE, "application/gzip".to_string()),
+            (header::CONTENT_DISPOSITION, filename),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+async fn import_state(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(snapshot): Json<StateSnapshot>,
+) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
     }
+    let tokens_added = state.rug_detector.import_watched_tokens(snapshot.watched_tokens);
+    let wallets_added = state.whale_watcher.import_wallets(snapshot.watched_wallets);
+    let filters_merged = match snapshot.filters {
+        Some(filters) => {
+            state.token_monitor.merge_filters(filters);
+            true
+        }
+        None => false,
+    };
+
+    Json(serde_json::json!({
+        "success": true,
+        "tokens_added": tokens_added,
+        "wallets_added": wallets_added,
+        "filters_merged": filters_merged,
+    }))
+    .into_response()
 }
 
 // Metrics handler
 async fn get_metrics(State(state): State<AppState>) -> Response {
     // Update current state metrics
     state.metrics.pending_tokens.set(state.token_monitor.detected_tokens().len() as f64);
+    state.metrics.tokens_per_minute.set(state.token_monitor.get_stats().tokens_per_minute as f64);
     state.metrics.tokens_watched.set(state.rug_detector.watched_tokens.len() as f64);
+    let rug_stats = state.rug_detector.get_stats();
+    state.metrics.rpc_fetches_avoided.set(rug_stats.fetches_avoided as f64);
+    state.metrics.tokens_auto_unwatched.set(rug_stats.auto_unwatched as f64);
+    advance_counter(&state.metrics.safety_downgrades, rug_stats.safety_downgrades);
+    state.metrics.health_check_interval.set(rug_stats.avg_check_interval_secs);
+    advance_counter(&state.metrics.ws_parse_errors, state.solana.ws_parse_errors());
+    state
+        .metrics
+        .curve_verification_failures
+        .set(state.solana.curve_verification_failures() as f64);
+    state.metrics.rpc_encoding_fallbacks.set(state.solana.encoding_fallbacks() as f64);
+    let cache = state.database.token_cache_stats();
+    advance_counter(&state.metrics.token_cache_hits, cache.hits);
+    advance_counter(&state.metrics.token_cache_misses, cache.misses);
+    state.metrics.db_write_failures.set(state.database.write_failures() as f64);
+    state.metrics.db_dead_letters.set(state.database.dead_letter_count() as f64);
+    if let Ok(size) = state.database.size_bytes() {
+        state.metrics.db_size_bytes.set(size as f64);
+    }
+    let batching = state.database.tx_batch_stats();
+    state.metrics.db_tx_buffer_depth.set(batching.buffered as f64);
+    state.metrics.db_tx_flush_seconds.set(batching.last_flush_ms / 1000.0);
+    if let Some(lag) = state.token_monitor.avg_detection_lag_ms() {
+        state.metrics.avg_detection_lag.set(lag);
+    }
+    let (channel_len, receiver_count) = state.solana.log_channel_stats();
+    state.metrics.broadcast_channel_len.set(channel_len as f64);
+    state.metrics.broadcast_receiver_count.set(receiver_count as f64);
+    for endpoint in state.solana.endpoint_stats() {
+        if let Some(latency) = endpoint.latency_ms {
+            state.metrics.rpc_endpoint_latency.with_label_values(&[&endpoint.endpoint]).set(latency);
+        }
+        state.metrics.rpc_endpoint_healthy
+            .with_label_values(&[&endpoint.endpoint])
+            .set(if endpoint.healthy { 1.0 } else { 0.0 });
+    }
+    for connection in state.solana.ws_connection_stats() {
+        let index = connection.connection.to_string();
+        let labels = [index.as_str(), connection.endpoint.as_str()];
+        state.metrics.ws_connection_up
+            .with_label_values(&labels)
+            .set(if connection.connected { 1.0 } else { 0.0 });
+        advance_counter(
+            &state.metrics.ws_connection_messages.with_label_values(&labels),
+            connection.messages,
+        );
+        advance_counter(
+            &state.metrics.ws_connection_reconnects.with_label_values(&labels),
+            connection.reconnects,
+        );
+    }
+    state.metrics.ws_duplicate_logs.set(state.solana.duplicate_logs() as f64);
     state.metrics.set_module_status("tokenMonitor", state.token_monitor.is_running());
     state.metrics.set_module_status("rugDetector", state.rug_detector.is_running());
     state.metrics.set_module_status("whaleWatcher", state.whale_watcher.is_running());
+    let whale_stats = state.whale_watcher.get_stats();
+    let token_stats = state.token_monitor.get_stats();
+    state.metrics.token_movements_evicted.set(whale_stats.movements_evicted as f64);
+    for (module, depth) in [
+        ("tokenMonitor", token_stats.queue_depth),
+        ("rugDetector", rug_stats.queue_depth),
+        ("whaleWatcher", whale_stats.queue_depth),
+    ] {
+        state.metrics.analysis_queue_depth.with_label_values(&[module]).set(depth as f64);
+    }
+    for (module, fetch_none, parse_none) in [
+        ("tokenMonitor", token_stats.tx_fetch_none, token_stats.tx_parse_none),
+        ("rugDetector", rug_stats.tx_fetch_none, rug_stats.tx_parse_none),
+        ("whaleWatcher", whale_stats.tx_fetch_none, whale_stats.tx_parse_none),
+    ] {
+        for (reason, lost) in [("fetch_none", fetch_none), ("parse_none", parse_none)] {
+            advance_counter(&state.metrics.tx_lost.with_label_values(&[module, reason]), lost);
+        }
+    }
+    advance_counter(
+        &state.metrics.tx_lost.with_label_values(&["tokenMonitor", "parse_rejected"]),
+        token_stats.parse_rejected,
+    );
+    state.metrics.sync_totals(&CounterTotals {
+        tokens_detected: token_stats.tokens_detected,
+        token_alerts: token_stats.alerts_sent,
+        rugs_detected: rug_stats.rugs_detected,
+        accumulation_alerts: whale_stats.accumulation_alerts,
+        dump_alerts: whale_stats.dump_alerts,
+    });
 
     let metrics = state.metrics.get_metrics();
     (
@@ -421,14 +1419,28 @@ async fn get_metrics(State(state): State<AppState>) -> Response {
 
 // Health check handlers
 async fn health_check(State(state): State<AppState>) -> Json<HealthResponse> {
+    let uptime = state.start_time.elapsed().as_secs_f64();
+    let last_log_age_secs = state.solana.last_log_age_secs();
+
+    // A quiet feed means the subscription may be dead even though modules are "running"
+    let max_age = state.config.health_max_log_age_secs as f64;
+    let feed_stale = last_log_age_secs.unwrap_or(uptime) > max_age;
+
     Json(HealthResponse {
-        status: "healthy".to_string(),
-        uptime: state.start_time.elapsed().as_secs_f64(),
+        status: if feed_stale { "degraded" } else { "healthy" }.to_string(),
+        uptime,
+        last_log_age_secs,
+        subscription_mode: state.solana.subscription_mode(),
+        instance_name: state.config.instance_name.clone(),
         modules: ModuleStatus {
             token_monitor: state.token_monitor.is_running(),
             rug_detector: state.rug_detector.is_running(),
             whale_watcher: state.whale_watcher.is_running(),
         },
+        instance: state.election.as_ref().map(|election| InstanceStatus {
+            instance_id: election.instance_id().to_string(),
+            role: election.role(),
+        }),
     })
 }
 
@@ -456,40 +1468,144 @@ async fn handle_websocket(socket: WebSocket, state: AppState) {
     let (mut sender, mut receiver) = socket.split();
 
     info!(target: "DASHBOARD", "WebSocket client connected");
+    state.metrics.dashboard_ws_clients.inc();
 
     // Send initial state
-    let init_msg = WsMessage::Init {
-        stats: StatsResponse {
-            token_monitor: state.token_monitor.get_stats(),
-            rug_detector: state.rug_detector.get_stats(),
-            whale_watcher: state.whale_watcher.get_stats(),
-        },
-        recent_alerts: state.alerts.get_recent_alerts(20),
-    };
-
-    if let Ok(json) = serde_json::to_string(&init_msg) {
+    if let Ok(json) = serde_json::to_string(&WsMessage::init(&state)) {
         let _ = sender.send(Message::Text(json)).await;
     }
 
     // Subscribe to alerts
     let mut alert_rx = state.alerts.subscribe();
 
+    // Half-open connections never close on their own, so the server pings and
+    // drops clients that stop answering
+    let last_seen = Arc::new(AtomicI64::new(chrono::Utc::now().timestamp_millis()));
+    let ping_interval = std::time::Duration::from_secs(state.config.ws_ping_interval_secs.max(1));
+    let timeout_ms = (state.config.ws_client_timeout_secs * 1000) as i64;
+
+    // Per-alert frames until the client subscribes with batchAlerts
+    let batch_alerts = Arc::new(AtomicBool::new(false));
+    let batch_window = std::time::Duration::from_millis(state.config.ws_alert_batch_ms);
+
     // Forward alerts to websocket
-    let send_task = tokio::spawn(async move {
-        while let Ok(alert) = alert_rx.recv().await {
-            let msg = WsMessage::Alert(alert);
-            if let Ok(json) = serde_json::to_string(&msg) {
-                if sender.send(Message::Text(json)).await.is_err() {
-                    break;
+    let mut send_task = tokio::spawn({
+        let last_seen = Arc::clone(&last_seen);
+        let batch_alerts = Arc::clone(&batch_alerts);
+        let state = state.clone();
+        async move {
+            let mut ping_timer = tokio::time::interval(ping_interval);
+            let shutdown = shutdown_requested(state.shutdown.clone());
+            tokio::pin!(shutdown);
+            let mut batch: Vec<Alert> = Vec::new();
+            let mut flush_at: Option<tokio::time::Instant> = None;
+            loop {
+                let flush_timer = async {
+                    match flush_at {
+                        Some(at) => tokio::time::sleep_until(at).await,
+                        None => std::future::pending().await,
+                    }
+                };
+                tokio::select! {
+                    result = alert_rx.recv() => match result {
+                        Ok(alert) => {
+                            if batch_alerts.load(Ordering::SeqCst) {
+                                batch.push(alert);
+                                let now = tokio::time::Instant::now();
+                                if batch.len() >= MAX_ALERT_BATCH {
+                                    flush_at = Some(now);
+                                } else {
+                                    flush_at.get_or_insert(now + batch_window);
+                                }
+                                continue;
+                            }
+                            let msg = WsMessage::Alert(alert);
+                            if let Ok(json) = serde_json::to_string(&msg) {
+                                if sender.send(Message::Text(json)).await.is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(missed)) => {
+                            // The channel already dropped these for us; tell the client and
+                            // resync it instead of carrying on with a silent hole
+                            warn!(target: "DASHBOARD", "WebSocket client lagged, missed {} alerts", missed);
+                            state.metrics.dashboard_ws_missed_alerts.add(missed as f64);
+                            flush_at = None;
+                            let mut messages = Vec::new();
+                            if !batch.is_empty() {
+                                messages.push(WsMessage::AlertBatch(std::mem::take(&mut batch)));
+                            }
+                            messages.push(WsMessage::Gap { missed });
+                            messages.push(WsMessage::init(&state));
+                            let mut closed = false;
+                            for msg in messages {
+                                if let Ok(json) = serde_json::to_string(&msg) {
+                                    if sender.send(Message::Text(json)).await.is_err() {
+                                        closed = true;
+                                        break;
+                                    }
+                                }
+                            }
+                            if closed {
+                                break;
+                            }
+                        }
+                        Err(_) => break,
+                    },
+                    _ = flush_timer => {
+                        flush_at = None;
+                        let msg = WsMessage::AlertBatch(std::mem::take(&mut batch));
+                        if let Ok(json) = serde_json::to_string(&msg) {
+                            if sender.send(Message::Text(json)).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    _ = &mut shutdown => {
+                        // Alerts still batched go out first so the client misses nothing
+                        if !batch.is_empty() {
+                            let msg = WsMessage::AlertBatch(std::mem::take(&mut batch));
+                            if let Ok(json) = serde_json::to_string(&msg) {
+                                let _ = sender.send(Message::Text(json)).await;
+                            }
+                        }
+                        let _ = sender
+                            .send(Message::Close(Some(CloseFrame {
+                                code: close_code::AWAY,
+                                reason: "Server shutting down".into(),
+                            })))
+                            .await;
+                        break;
+                    }
+                    _ = ping_timer.tick() => {
+                        let silent_ms = chrono::Utc::now().timestamp_millis() - last_seen.load(Ordering::SeqCst);
+                        if silent_ms > timeout_ms {
+                            info!(target: "DASHBOARD", "WebSocket client timed out after {}s", silent_ms / 1000);
+                            let _ = sender.send(Message::Close(None)).await;
+                            break;
+                        }
+                        if sender.send(Message::Ping(Vec::new())).await.is_err() {
+                            break;
+                        }
+                    }
                 }
             }
         }
     });
 
-    // Handle incoming messages (mainly for keeping connection alive)
-    let recv_task = tokio::spawn(async move {
+    // Handle incoming messages; any frame, including pongs, counts as liveness
+    let batching_enabled = !batch_window.is_zero();
+    let mut recv_task = tokio::spawn(async move {
         while let Some(msg) = receiver.next().await {
+            last_seen.store(chrono::Utc::now().timestamp_millis(), Ordering::SeqCst);
             match msg {
+                Ok(Message::Text(text)) => {
+                    if let Ok(WsClientMessage::Subscribe { batch_alerts: batch }) = serde_json::from_str(&text) {
+                        // With batching disabled server-side, clients keep per-alert frames
+                        batch_alerts.store(batch && batching_enabled, Ordering::SeqCst);
+                    }
+                }
                 Ok(Message::Close(_)) => break,
                 Ok(Message::Ping(_)) => {
                     // Pong is handled automatically by axum
@@ -500,12 +1616,13 @@ async fn handle_websocket(socket: WebSocket, state: AppState) {
         }
     });
 
-    // Wait for either task to finish
+    // Wait for either task to finish, then cancel the other
     tokio::select! {
-        _ = send_task => {},
-        _ = recv_task => {},
+        _ = &mut send_task => recv_task.abort(),
+        _ = &mut recv_task => send_task.abort(),
     }
 
+    state.metrics.dashboard_ws_clients.dec();
     info!(target: "DASHBOARD", "WebSocket client disconnected");
 }
 
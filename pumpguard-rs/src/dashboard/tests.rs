@@ -0,0 +1,541 @@
+//! End-to-end tests for the dashboard HTTP and WebSocket routes
+//!
+//! The real services are used with an in-memory database and an unreachable RPC
+//! endpoint, so nothing here touches the network beyond the loopback listener.
+
+use futures_util::StreamExt;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::watch;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use super::DashboardServer;
+use crate::config::Config;
+use crate::modules::token_monitor::AlertSkipReason;
+use crate::modules::{RugDetector, TokenMonitor, WhaleWatcher};
+use crate::utils::database::TokenRecord;
+use crate::utils::logger::LogFilter;
+use crate::utils::{AlertService, DatabaseService, MetricsService, SolanaService};
+
+/// Serve a dashboard on an ephemeral loopback port and return its address
+async fn spawn_dashboard() -> SocketAddr {
+    spawn_dashboard_with(|_| {}).await
+}
+
+/// [`spawn_dashboard`] with test-specific config changes
+async fn spawn_dashboard_with(configure: impl FnOnce(&mut Config)) -> SocketAddr {
+    serve(build_dashboard(configure)).await
+}
+
+/// Dashboard over fresh services, not yet serving
+fn build_dashboard(configure: impl FnOnce(&mut Config)) -> DashboardServer {
+    build_dashboard_with_services(configure).dashboard
+}
+
+/// A dashboard plus handles on the services behind it, for seeding state and wiring links
+struct TestDashboard {
+    dashboard: DashboardServer,
+    token_monitor: TokenMonitor,
+    rug_detector: RugDetector,
+    database: Arc<DatabaseService>,
+}
+
+/// [`build_dashboard`] that also hands back the services it serves
+fn build_dashboard_with_services(configure: impl FnOnce(&mut Config)) -> TestDashboard {
+    let mut config = Config::for_tests();
+    config.rpc_url = "http://127.0.0.1:1".to_string();
+    config.rpc_urls = vec![config.rpc_url.clone()];
+    config.telegram_bot_token = None;
+    config.alert_file_path = None;
+    config.dashboard_api_key = None;
+    config.dashboard_allowed_ips = Vec::new();
+    configure(&mut config);
+
+    let solana = Arc::new(SolanaService::new(config.clone()));
+    let database = Arc::new(DatabaseService::new(":memory:").expect("in-memory database"));
+    let alerts = Arc::new(AlertService::new(config.clone()));
+    let metrics = Arc::new(MetricsService::new());
+
+    let token_monitor = TokenMonitor::new(
+        config.clone(),
+        Arc::clone(&solana),
+        Arc::clone(&alerts),
+        Arc::clone(&database),
+    );
+    let rug_detector = RugDetector::new(
+        config.clone(),
+        Arc::clone(&solana),
+        Arc::clone(&alerts),
+        Arc::clone(&database),
+    );
+    let whale_watcher = WhaleWatcher::new(
+        config.clone(),
+        Arc::clone(&solana),
+        Arc::clone(&alerts),
+        Arc::clone(&database),
+    );
+
+    let dashboard = DashboardServer::new(
+        config,
+        solana,
+        token_monitor.clone(),
+        rug_detector.clone(),
+        whale_watcher,
+        alerts,
+        Arc::clone(&database),
+        metrics,
+    );
+    TestDashboard { dashboard, token_monitor, rug_detector, database }
+}
+
+/// Serve `dashboard` on an ephemeral loopback port and return its address
+async fn serve(dashboard: DashboardServer) -> SocketAddr {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = dashboard.router();
+    tokio::spawn(async move {
+        axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+            .await
+            .unwrap();
+    });
+    addr
+}
+
+async fn get_json(addr: SocketAddr, path: &str) -> (u16, Value) {
+    let response = reqwest::get(format!("http://{}{}", addr, path)).await.unwrap();
+    let status = response.status().as_u16();
+    (status, response.json().await.unwrap())
+}
+
+#[tokio::test]
+async fn stats_reports_every_module() {
+    let addr = spawn_dashboard().await;
+    let (status, body) = get_json(addr, "/api/stats").await;
+
+    assert_eq!(status, 200);
+    assert!(body["tokenMonitor"].is_object());
+    assert!(body["rugDetector"].is_object());
+    assert!(body["whaleWatcher"].is_object());
+}
+
+#[tokio::test]
+async fn health_reports_status_and_modules() {
+    let addr = spawn_dashboard().await;
+    let (status, body) = get_json(addr, "/health").await;
+
+    assert_eq!(status, 200);
+    assert!(body["status"].is_string());
+    assert!(body["uptime"].is_number());
+    assert_eq!(body["subscription_mode"], json!("ws"));
+    assert_eq!(body["modules"]["token_monitor"], json!(false));
+}
+
+#[tokio::test]
+async fn health_reports_instance_name() {
+    let addr = spawn_dashboard_with(|config| {
+        config.instance_name = Some("devnet-a".to_string());
+    })
+    .await;
+    let (_, body) = get_json(addr, "/health").await;
+    assert_eq!(body["instance_name"], json!("devnet-a"));
+}
+
+#[tokio::test]
+async fn ip_allowlist_rejects_other_addresses() {
+    let blocked = spawn_dashboard_with(|config| {
+        config.dashboard_allowed_ips = vec!["10.0.0.0/8".to_string()];
+    })
+    .await;
+    let (status, body) = get_json(blocked, "/health").await;
+    assert_eq!(status, 403);
+    assert_eq!(body["error"], json!("Forbidden"));
+
+    let allowed = spawn_dashboard_with(|config| {
+        config.dashboard_allowed_ips = vec!["127.0.0.0/8".to_string()];
+    })
+    .await;
+    let (status, _) = get_json(allowed, "/health").await;
+    assert_eq!(status, 200);
+}
+
+#[tokio::test]
+async fn state_import_requires_api_key() {
+    let addr = spawn_dashboard_with(|config| {
+        config.dashboard_api_key = Some("secret".to_string());
+    })
+    .await;
+    let import = |key: Option<&str>| {
+        let request = reqwest::Client::new()
+            .post(format!("http://{}/api/import/state", addr))
+            .json(&json!({}));
+        match key {
+            Some(key) => request.header("x-api-key", key),
+            None => request,
+        }
+        .send()
+    };
+
+    assert_eq!(import(None).await.unwrap().status().as_u16(), 401);
+    assert_eq!(import(Some("wrong")).await.unwrap().status().as_u16(), 401);
+    assert_eq!(import(Some("secret")).await.unwrap().status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn token_mute_requires_api_key() {
+    let addr = spawn_dashboard_with(|config| {
+        config.dashboard_api_key = Some("secret".to_string());
+    })
+    .await;
+    let mute = |key: &str| {
+        reqwest::Client::new()
+            .post(format!("http://{}/api/rug/token/SomeMint/mute", addr))
+            .header("x-api-key", key)
+            .send()
+    };
+
+    assert_eq!(mute("wrong").await.unwrap().status().as_u16(), 401);
+    assert_eq!(mute("secret").await.unwrap().status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn dev_wallet_refresh_requires_api_key() {
+    let addr = spawn_dashboard_with(|config| {
+        config.dashboard_api_key = Some("secret".to_string());
+    })
+    .await;
+    let url = format!("http://{}/api/rug/token/SomeMint/dev", addr);
+    let verify = |key: &str| reqwest::Client::new().post(&url).header("x-api-key", key).send();
+
+    assert_eq!(reqwest::get(&url).await.unwrap().status().as_u16(), 404);
+    assert_eq!(verify("wrong").await.unwrap().status().as_u16(), 401);
+    assert_eq!(verify("secret").await.unwrap().status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn listed_cors_origin_may_send_bearer_tokens() {
+    let addr = spawn_dashboard_with(|config| {
+        config.cors_allowed_origins = vec!["https://ops.example".to_string()];
+    })
+    .await;
+    let preflight = reqwest::Client::new()
+        .request(reqwest::Method::OPTIONS, format!("http://{}/api/import/state", addr))
+        .header("origin", "https://ops.example")
+        .header("access-control-request-method", "POST")
+        .header("access-control-request-headers", "authorization")
+        .send()
+        .await
+        .unwrap();
+
+    let allowed = preflight.headers()["access-control-allow-headers"].to_str().unwrap();
+    assert!(allowed.split(',').any(|h| h.trim() == "authorization"));
+}
+
+#[tokio::test]
+async fn sell_windows_are_live_editable() {
+    let addr = spawn_dashboard().await;
+    let client = reqwest::Client::new();
+    let post = |path: &str, body: Value| {
+        client.post(format!("http://{}{}", addr, path)).json(&body).send()
+    };
+
+    let updated: Value = post(
+        "/api/rug/sell-windows",
+        json!({ "entries": ["900:10:70", "60:3:30"] }),
+    )
+    .await
+    .unwrap()
+    .json()
+    .await
+    .unwrap();
+    assert_eq!(updated["entries"], json!(["60:3:30", "900:10:70"]));
+    let invalid = post("/api/rug/sell-windows", json!({ "entries": ["60:3"] }));
+    assert_eq!(invalid.await.unwrap().status().as_u16(), 400);
+
+    // The pre-windows key still works and retargets the shortest window
+    let legacy = json!({ "key": "min_time_between_sells", "value": 120000 });
+    assert_eq!(post("/api/rug/thresholds", legacy).await.unwrap().status().as_u16(), 200);
+    let (_, windows) = get_json(addr, "/api/rug/sell-windows").await;
+    assert_eq!(windows["entries"], json!(["120:3:30", "900:10:70"]));
+}
+
+#[tokio::test]
+async fn api_rate_limit_returns_429_with_headers() {
+    let addr = spawn_dashboard_with(|config| {
+        config.api_rate_limit = 2;
+        config.api_rate_limit_window_secs = 60;
+    })
+    .await;
+    let get = |path: &str| reqwest::get(format!("http://{}{}", addr, path));
+
+    let first = get("/api/tokens/recent").await.unwrap();
+    assert_eq!(first.status().as_u16(), 200);
+    assert_eq!(first.headers()["x-ratelimit-limit"], "2");
+    assert_eq!(first.headers()["x-ratelimit-remaining"], "1");
+    assert_eq!(get("/api/tokens/recent").await.unwrap().status().as_u16(), 200);
+
+    let limited = get("/api/tokens/recent").await.unwrap();
+    assert_eq!(limited.status().as_u16(), 429);
+    assert_eq!(limited.headers()["x-ratelimit-remaining"], "0");
+    let retry_after: u64 = limited.headers()["retry-after"].to_str().unwrap().parse().unwrap();
+    assert!((1..=30).contains(&retry_after));
+
+    assert_eq!(get("/health").await.unwrap().status().as_u16(), 200);
+    assert_eq!(get("/metrics").await.unwrap().status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn cohort_stats_list_every_bucket() {
+    let TestDashboard { dashboard, database, .. } = build_dashboard_with_services(|_| {});
+    // (age, rugged) per token: two under a minute, one at 5m, none at 10m-1h, four past 1h
+    let seeded = [(30, true), (45, false), (300, false), (7200, true), (7200, true)];
+    let seeded = seeded.into_iter().chain([(9000, true), (9000, false)]);
+    for (i, (age_secs, rugged)) in seeded.enumerate() {
+        let mint = format!("cohort{}", i);
+        let created_at = chrono::Utc::now() - chrono::Duration::seconds(age_secs);
+        database
+            .save_token(&TokenRecord {
+                mint: mint.clone(),
+                name: "Cohort".to_string(),
+                symbol: "COH".to_string(),
+                creator: "creator".to_string(),
+                created_at: created_at.to_rfc3339(),
+                initial_liquidity: 1.0,
+                current_liquidity: 1.0,
+                holder_count: 0,
+                is_rugged: false,
+                rug_reason: None,
+                last_updated: chrono::Utc::now().to_rfc3339(),
+                is_muted: false,
+                is_graduated: false,
+            })
+            .unwrap();
+        if rugged {
+            database.mark_as_rugged(&mint, "test").unwrap();
+        }
+    }
+    let addr = serve(dashboard).await;
+
+    let (status, body) = get_json(addr, "/api/stats/cohorts").await;
+    assert_eq!(status, 200);
+    let cohorts = body["cohorts"].as_array().unwrap();
+    let column = |key: &str| cohorts.iter().map(|c| c[key].clone()).collect::<Vec<_>>();
+    assert_eq!(column("label"), [json!("<1m"), json!("1m-10m"), json!("10m-1h"), json!(">1h")]);
+    assert_eq!(column("tokens"), [json!(2), json!(1), json!(0), json!(4)]);
+    assert_eq!(column("rugged"), [json!(1), json!(0), json!(0), json!(3)]);
+    assert_eq!(column("rug_rate"), [json!(0.5), json!(0.0), json!(0.0), json!(0.75)]);
+
+    let (status, body) = get_json(addr, "/api/stats/cohorts?buckets=300").await;
+    assert_eq!(status, 200);
+    assert_eq!(
+        body["cohorts"][1],
+        json!({
+            "label": ">5m",
+            "min_age_secs": 300,
+            "max_age_secs": null,
+            "tokens": 5,
+            "rugged": 3,
+            "rug_rate": 0.6,
+        })
+    );
+
+    let (status, _) = get_json(addr, "/api/stats/cohorts?buckets=soon").await;
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn recent_tokens_starts_empty() {
+    let addr = spawn_dashboard().await;
+    let (status, body) = get_json(addr, "/api/tokens/recent").await;
+
+    assert_eq!(status, 200);
+    assert_eq!(body, json!([]));
+}
+
+#[tokio::test]
+async fn watched_token_is_listed() {
+    let addr = spawn_dashboard().await;
+    let mint = "So11111111111111111111111111111111111111112";
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/api/rug/watch", addr))
+        .json(&json!({
+            "mint": mint,
+            "name": "Test Token",
+            "symbol": "TEST",
+            "creator": "11111111111111111111111111111111",
+            "initial_liquidity": 10.0,
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["success"], json!(true));
+
+    let (status, watched) = get_json(addr, "/api/rug/watched").await;
+    assert_eq!(status, 200);
+    let watched = watched.as_array().unwrap();
+    assert_eq!(watched.len(), 1);
+    assert_eq!(watched[0]["mint"], json!(mint));
+    assert_eq!(watched[0]["symbol"], json!("TEST"));
+}
+
+#[tokio::test]
+async fn blacklisted_creator_can_be_removed() {
+    let TestDashboard { dashboard, token_monitor, rug_detector, .. } =
+        build_dashboard_with_services(|_| {});
+    let addr = serve(dashboard).await;
+    let client = reqwest::Client::new();
+    let rugger = "11111111111111111111111111111111";
+    let creator = json!({ "address": rugger });
+
+    // Confirming a rug blacklists its creator, which keeps their next launch from alerting
+    let mut rugs = rug_detector.subscribe_rugs();
+    rug_detector.watch_token("RuggedMint", "Rugged", "RUG", rugger, 10.0);
+    let response = client
+        .post(format!("http://{}/api/rug/token/RuggedMint/flag", addr))
+        .json(&json!({ "rugged": true, "reason": "LP pulled" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert!(token_monitor.blacklist_rugger(&rugs.recv().await.unwrap()));
+    assert_eq!(
+        token_monitor.creator_skip_reason(rugger),
+        Some(AlertSkipReason::BlacklistedCreator)
+    );
+
+    let response = client
+        .post(format!("http://{}/api/tokens/blacklist", addr))
+        .json(&creator)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+
+    let remove = || {
+        client
+            .post(format!("http://{}/api/tokens/blacklist/remove", addr))
+            .json(&creator)
+            .send()
+    };
+    assert_eq!(remove().await.unwrap().status().as_u16(), 200);
+    assert_eq!(remove().await.unwrap().status().as_u16(), 404);
+    assert_eq!(token_monitor.creator_skip_reason(rugger), None);
+}
+
+#[tokio::test]
+async fn alert_types_can_be_muted_live() {
+    let addr = spawn_dashboard().await;
+    let settings = |types: Value| {
+        reqwest::Client::new()
+            .post(format!("http://{}/api/alerts/settings", addr))
+            .json(&json!({ "types": types }))
+            .send()
+    };
+
+    let (status, body) = get_json(addr, "/api/alerts/settings").await;
+    assert_eq!(status, 200);
+    assert_eq!(body["types"]["whale_buy"], true);
+
+    assert_eq!(settings(json!({ "whale_buy": false })).await.unwrap().status().as_u16(), 200);
+    let (_, body) = get_json(addr, "/api/alerts/settings").await;
+    assert_eq!(body["types"]["whale_buy"], false);
+    assert_eq!(body["types"]["rug"], true);
+
+    assert_eq!(settings(json!({ "whale_dance": false })).await.unwrap().status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn log_level_can_be_raised_per_target() {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    // The handle only reloads while its layer is alive, so keep a subscriber around
+    // without installing it globally
+    let (layer, log_filter) = LogFilter::reloadable();
+    let _subscriber = tracing_subscriber::registry().with(layer);
+    let addr = serve(build_dashboard(|_| {}).with_log_filter(Arc::new(log_filter))).await;
+    let set_level = |target: &str, level: &str| {
+        reqwest::Client::new()
+            .post(format!("http://{}/api/log/level", addr))
+            .json(&json!({ "target": target, "level": level }))
+            .send()
+    };
+
+    let response = set_level("RUG_DETECTOR", "debug").await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert!(body["filter"].as_str().unwrap().contains("RUG_DETECTOR=debug"));
+    assert_eq!(body["overrides"]["RUG_DETECTOR"], "debug");
+
+    assert_eq!(set_level("RUG_DETECTOR", "loud").await.unwrap().status().as_u16(), 400);
+    assert_eq!(set_level("RUG DETECTOR", "debug").await.unwrap().status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn archive_export_starts_with_manifest() {
+    use std::io::Read;
+
+    let addr = spawn_dashboard().await;
+    let response = reqwest::get(format!("http://{}/api/export/archive?before=2000-01-01", addr))
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+
+    let compressed = response.bytes().await.unwrap();
+    let mut ndjson = String::new();
+    flate2::read::GzDecoder::new(&compressed[..])
+        .read_to_string(&mut ndjson)
+        .unwrap();
+    let lines: Vec<Value> = ndjson.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0]["type"], json!("manifest"));
+    assert_eq!(lines[0]["data"]["before"], json!("2000-01-01T00:00:00+00:00"));
+    assert_eq!(lines[0]["data"]["tokens"], json!(0));
+
+    let (status, _) = get_json(addr, "/api/export/archive?before=2000-01-01&delete=true").await;
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn websocket_sends_init_on_connect() {
+    let addr = spawn_dashboard().await;
+    let (mut socket, _) = connect_async(format!("ws://{}/ws", addr)).await.unwrap();
+
+    let frame = socket.next().await.unwrap().unwrap();
+    let text = match frame {
+        Message::Text(text) => text,
+        other => panic!("expected text frame, got {:?}", other),
+    };
+    let message: Value = serde_json::from_str(&text).unwrap();
+
+    assert_eq!(message["type"], json!("init"));
+    assert!(message["data"]["stats"]["rugDetector"].is_object());
+    assert!(message["data"]["recent_alerts"].is_array());
+}
+
+#[tokio::test]
+async fn websocket_closes_on_shutdown() {
+    let (shutdown, receiver) = watch::channel(false);
+    let addr = serve(build_dashboard(|_| {}).with_shutdown(receiver)).await;
+    let (mut socket, _) = connect_async(format!("ws://{}/ws", addr)).await.unwrap();
+    assert!(matches!(socket.next().await, Some(Ok(Message::Text(_)))));
+
+    shutdown.send_replace(true);
+    let close = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        // Skip the server's startup ping
+        loop {
+            match socket.next().await {
+                Some(Ok(Message::Ping(_))) => continue,
+                other => return other,
+            }
+        }
+    })
+    .await
+    .expect("close frame before timeout");
+    match close {
+        Some(Ok(Message::Close(Some(close)))) => assert_eq!(u16::from(close.code), 1001),
+        other => panic!("expected close frame, got {:?}", other),
+    }
+}
@@ -13,14 +13,30 @@ mod modules;
 mod utils;
 
 use anyhow::Result;
+use parking_lot::RwLock;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::signal;
+use tokio::sync::watch;
+use tokio::time::{Duration, Instant};
 use tracing::{error, info, warn};
 
 use config::Config;
 use dashboard::DashboardServer;
-use modules::{RugDetector, TokenMonitor, WhaleWatcher};
-use utils::{init_logger, AlertService, DatabaseService, MetricsService, SolanaService};
+use modules::whale_watcher::MoverSort;
+use modules::{CustomWatcher, DailyDigest, RugDetector, TokenMonitor, WhaleWatcher};
+use utils::leader::InstanceRole;
+use utils::logger::LogFilter;
+use utils::metrics::CounterTotals;
+use utils::preflight::{self, PreflightReport};
+use utils::{init_logger, AlertService, DatabaseService, LeaderElection, MetricsService, SolanaService};
+
+/// Movers considered per auto-watch poll
+const TOP_MOVER_SCAN_LIMIT: usize = 50;
+
+/// How often shutdown checks whether the analysis queues have drained
+const DRAIN_POLL_MS: u64 = 50;
 
 const BANNER: &str = r#"
     ╔═══════════════════════════════════════════════════════════════╗
@@ -48,18 +64,48 @@ pub struct PumpGuard {
     token_monitor: TokenMonitor,
     rug_detector: RugDetector,
     whale_watcher: WhaleWatcher,
+    daily_digest: DailyDigest,
+    custom_watcher: CustomWatcher,
+    election: Option<LeaderElection>,
+    preflight: Arc<RwLock<Option<PreflightReport>>>,
+    shutdown: watch::Sender<bool>,
+    log_filter: Arc<LogFilter>,
+    /// Set once the feed, links and database tasks are up; they outlive leadership changes
+    background_started: AtomicBool,
 }
 
 impl PumpGuard {
     /// Create a new PumpGuard instance
-    pub fn new() -> Result<Self> {
+    pub fn new(log_filter: LogFilter) -> Result<Self> {
         let config = Config::from_env();
+        info!(
+            target: "PUMPGUARD",
+            "Cluster: {:?} ({})",
+            config.cluster,
+            config::redact_url(&config.rpc_url)
+        );
+        for warning in config.endpoint_warnings() {
+            warn!(target: "PUMPGUARD", "{}", warning);
+        }
 
         // Initialize services
         let solana = Arc::new(SolanaService::new(config.clone()));
-        let database = Arc::new(DatabaseService::new("data/pumpguard.db")?);
-        let alerts = Arc::new(AlertService::new(config.clone()));
+        let database = Arc::new(
+            DatabaseService::new("data/pumpguard.db")?
+                .with_write_retry(config.db_write_retries, config.db_write_backoff_ms)
+                .with_transaction_batching(config.db_tx_batch_size),
+        );
+        let alerts = Arc::new(
+            AlertService::new(config.clone())
+                .with_restart_dedup(Arc::clone(&database), config.alert_dedup_window_secs),
+        );
         let metrics = Arc::new(MetricsService::new());
+        if config.metrics_persist_secs > 0 {
+            match database.load_metric_counters() {
+                Ok(saved) => metrics.restore_counters(&saved),
+                Err(e) => warn!(target: "PUMPGUARD", "Failed to restore metric counters: {}", e),
+            }
+        }
 
         // Initialize modules
         let token_monitor = TokenMonitor::new(
@@ -83,6 +129,15 @@ impl PumpGuard {
             Arc::clone(&database),
         );
 
+        let daily_digest = DailyDigest::new(config.clone(), Arc::clone(&alerts), Arc::clone(&database));
+        let custom_watcher = CustomWatcher::new(config.clone(), Arc::clone(&solana), Arc::clone(&alerts));
+
+        // With an instance id, instances sharing the database elect a single leader
+        let election = config
+            .instance_id
+            .clone()
+            .map(|id| LeaderElection::new(id, config.lease_ttl_secs, Arc::clone(&database)));
+
         Ok(Self {
             config,
             solana,
@@ -92,21 +147,96 @@ impl PumpGuard {
             token_monitor,
             rug_detector,
             whale_watcher,
+            daily_digest,
+            custom_watcher,
+            election,
+            preflight: Arc::new(RwLock::new(None)),
+            shutdown: watch::channel(false).0,
+            log_filter: Arc::new(log_filter),
+            background_started: AtomicBool::new(false),
         })
     }
 
+    /// Check configuration and connectivity; the report is kept for `/api/preflight`
+    pub async fn preflight(&self) -> PreflightReport {
+        info!(target: "PUMPGUARD", "Running preflight checks...");
+        let report = preflight::run(&self.config, &self.solana, &self.database, &self.alerts).await;
+        report.log();
+        *self.preflight.write() = Some(report.clone());
+        report
+    }
+
     /// Start PumpGuard
     pub async fn start(&self) -> Result<()> {
         println!("{}", BANNER);
 
         info!(target: "PUMPGUARD", "Initializing PumpGuard Monitor...");
 
-        // Link modules FIRST - subscribe to events before starting modules
-        // This ensures we don't miss any tokens during startup
-        self.link_modules();
+        let dashboard = DashboardServer::new(
+            self.config.clone(),
+            Arc::clone(&self.solana),
+            self.token_monitor.clone(),
+            self.rug_detector.clone(),
+            self.whale_watcher.clone(),
+            Arc::clone(&self.alerts),
+            Arc::clone(&self.database),
+            Arc::clone(&self.metrics),
+        )
+        .with_preflight(Arc::clone(&self.preflight))
+        .with_custom_watcher(self.custom_watcher.clone())
+        .with_shutdown(self.shutdown.subscribe())
+        .with_log_filter(Arc::clone(&self.log_filter));
+
+        let election = match &self.election {
+            Some(election) => election,
+            None => {
+                self.start_modules().await?;
+                return dashboard.start().await;
+            }
+        };
+
+        // Followers serve the dashboard only; detection starts once the lease is ours
+        let dashboard = dashboard.with_election(election.clone());
+        tokio::select! {
+            result = dashboard.start() => result,
+            result = self.lead(election) => result,
+        }
+    }
 
-        // Start Solana WebSocket subscription
-        self.solana.start_log_subscription().await?;
+    /// Wait for the leader lease and run detection while it is held. On losing it the
+    /// modules stop and this instance goes back to waiting as a follower.
+    async fn lead(&self, election: &LeaderElection) -> Result<()> {
+        loop {
+            election.acquire().await;
+            self.start_modules().await?;
+
+            if let Err(e) = election.hold().await {
+                // Another instance is alerting now; keep serving the dashboard meanwhile
+                warn!(target: "PUMPGUARD", "Lost leadership ({}), stopping detection modules", e);
+            }
+            self.stop_modules();
+        }
+    }
+
+    /// Subscribe to the feed and start the detection modules. The feed, module links
+    /// and database tasks are started on the first call only, so regaining the lease
+    /// doesn't spawn them twice.
+    async fn start_modules(&self) -> Result<()> {
+        if !self.background_started.swap(true, Ordering::SeqCst) {
+            // Link modules FIRST - subscribe to events before starting modules
+            // This ensures we don't miss any tokens during startup
+            self.link_modules();
+
+            // Start Solana WebSocket subscription
+            self.solana.start_log_subscription().await?;
+            self.solana.start_endpoint_probes();
+            self.database.start_dead_letter_drain(self.config.db_dead_letter_drain_secs);
+            self.database.start_transaction_flusher(self.config.db_tx_flush_ms);
+            self.database.start_size_limit(self.config.max_db_size_mb);
+            self.alerts.start_marker_prune();
+            self.alerts.start_file_flush();
+            self.start_counter_persistence();
+        }
 
         // Start all modules
         info!(target: "PUMPGUARD", "Starting monitoring modules...");
@@ -120,24 +250,84 @@ impl PumpGuard {
         tm_result?;
         rd_result?;
         ww_result?;
+        self.daily_digest.start()?;
+        self.custom_watcher.start().await?;
 
         info!(target: "PUMPGUARD", "✅ All modules started successfully!");
         info!(target: "PUMPGUARD", "Dashboard: http://localhost:{}", self.config.dashboard_port);
 
-        // Start dashboard server
-        let dashboard = DashboardServer::new(
-            self.config.clone(),
-            self.token_monitor.clone(),
-            self.rug_detector.clone(),
-            self.whale_watcher.clone(),
-            Arc::clone(&self.alerts),
-            Arc::clone(&self.database),
-            Arc::clone(&self.metrics),
-        );
+        Ok(())
+    }
 
-        dashboard.start().await?;
+    /// Mirror module counts into the persisted counters and save them
+    fn persist_counters(
+        metrics: &MetricsService,
+        database: &DatabaseService,
+        token_monitor: &TokenMonitor,
+        rug_detector: &RugDetector,
+        whale_watcher: &WhaleWatcher,
+    ) {
+        let token_stats = token_monitor.get_stats();
+        let rug_stats = rug_detector.get_stats();
+        let whale_stats = whale_watcher.get_stats();
+        metrics.sync_totals(&CounterTotals {
+            tokens_detected: token_stats.tokens_detected,
+            token_alerts: token_stats.alerts_sent,
+            rugs_detected: rug_stats.rugs_detected,
+            accumulation_alerts: whale_stats.accumulation_alerts,
+            dump_alerts: whale_stats.dump_alerts,
+        });
+        if let Err(e) = database.save_metric_counters(&metrics.counter_values()) {
+            warn!(target: "PUMPGUARD", "Failed to persist metric counters: {}", e);
+        }
+    }
 
-        Ok(())
+    /// Periodically save counter totals while this instance runs detection
+    fn start_counter_persistence(&self) {
+        let interval_secs = self.config.metrics_persist_secs;
+        if interval_secs == 0 {
+            return;
+        }
+        let metrics = Arc::clone(&self.metrics);
+        let database = Arc::clone(&self.database);
+        let token_monitor = self.token_monitor.clone();
+        let rug_detector = self.rug_detector.clone();
+        let whale_watcher = self.whale_watcher.clone();
+        let election = self.election.clone();
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_secs(interval_secs));
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                // While following, the leader owns the saved totals
+                if election.as_ref().is_some_and(|e| e.role() != InstanceRole::Leader) {
+                    continue;
+                }
+                Self::persist_counters(
+                    &metrics,
+                    &database,
+                    &token_monitor,
+                    &rug_detector,
+                    &whale_watcher,
+                );
+            }
+        });
+    }
+
+    fn stop_modules(&self) {
+        self.token_monitor.stop();
+        self.rug_detector.stop();
+        self.whale_watcher.stop();
+        self.daily_digest.stop();
+        self.custom_watcher.stop();
+    }
+
+    /// Analysis jobs still queued or running across the modules
+    fn pending_work(&self) -> u64 {
+        self.token_monitor.pending_work()
+            + self.rug_detector.pending_work()
+            + self.whale_watcher.pending_work()
     }
 
     /// Link modules together
@@ -146,6 +336,7 @@ impl PumpGuard {
         // IMPORTANT: This must be called BEFORE starting the token monitor
         let mut new_token_rx = self.token_monitor.subscribe_new_tokens();
         let rug_detector = self.rug_detector.clone();
+        let verify_dev_wallet = self.config.dev_wallet_from_curve;
 
         tokio::spawn(async move {
             info!(target: "PUMPGUARD", "Token->RugDetector link active, waiting for tokens...");
@@ -161,6 +352,15 @@ impl PumpGuard {
                                 &token.creator,
                                 token.initial_liquidity,
                             );
+                            rug_detector
+                                .set_launched_at(
+                                    &token.mint,
+                                    token.detected_at - token.detection_lag_ms.unwrap_or(0),
+                                )
+                                .await;
+                            if verify_dev_wallet {
+                                let _ = rug_detector.verify_dev_wallet(&token.mint).await;
+                            }
                         }
                     }
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
@@ -174,28 +374,221 @@ impl PumpGuard {
             }
         });
 
+        // Launch holder lookups finish after the token is watched; concentrated ones alert
+        let mut holding_rx = self.token_monitor.subscribe_launch_holdings();
+        let rug_detector = self.rug_detector.clone();
+
+        tokio::spawn(async move {
+            loop {
+                match holding_rx.recv().await {
+                    Ok(holding) => {
+                        rug_detector
+                            .check_launch_concentration(&holding.mint, holding.top_holder_percent)
+                            .await;
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                        warn!(target: "PUMPGUARD", "Launch holder link lagged {} messages", n);
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        // Creators trading their own token in size raise the token's suspicion score
+        let mut creator_rx = self.whale_watcher.subscribe_creator_activity();
+        let rug_detector = self.rug_detector.clone();
+
+        tokio::spawn(async move {
+            loop {
+                match creator_rx.recv().await {
+                    Ok(activity) => {
+                        rug_detector
+                            .record_creator_activity(
+                                &activity.mint,
+                                &activity.tx_type,
+                                activity.amount_sol,
+                            )
+                            .await;
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                        warn!(target: "PUMPGUARD", "Creator activity link lagged {} messages", n);
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        // Bundled buys right after launch arm the rug detector's bundle_rug escalation
+        let mut bundle_rx = self.whale_watcher.subscribe_coordinated_buys();
+        let rug_detector = self.rug_detector.clone();
+
+        tokio::spawn(async move {
+            loop {
+                match bundle_rx.recv().await {
+                    Ok(buy) => {
+                        rug_detector
+                            .record_coordinated_buy(
+                                &buy.mint,
+                                &buy.wallets,
+                                buy.total_sol,
+                                buy.timestamp,
+                            )
+                            .await;
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                        warn!(target: "PUMPGUARD", "Coordinated buy link lagged {} messages", n);
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        // Blacklist the creator of every token the rug detector raises a rug alert for
+        if self.config.auto_blacklist_ruggers {
+            self.token_monitor.link_rug_blacklist(self.rug_detector.subscribe_rugs());
+        }
+
         info!(target: "PUMPGUARD", "Modules linked - new tokens will be auto-watched by rug detector");
+
+        if self.config.auto_watch_top_movers {
+            self.link_top_movers();
+        }
+    }
+
+    /// Periodically hand high-volume whale watcher movers to the rug detector
+    fn link_top_movers(&self) {
+        let whale_watcher = self.whale_watcher.clone();
+        let rug_detector = self.rug_detector.clone();
+        let database = Arc::clone(&self.database);
+        let min_volume = self.config.auto_watch_min_volume_sol;
+        let poll_secs = self.config.auto_watch_interval_secs.max(1);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_secs(poll_secs));
+
+            loop {
+                ticker.tick().await;
+
+                for mover in
+                    whale_watcher.get_top_movers(TOP_MOVER_SCAN_LIMIT, min_volume, MoverSort::NetFlow)
+                {
+                    if rug_detector.watched_tokens.contains_key(&mover.mint) {
+                        continue;
+                    }
+
+                    // Name/creator come from the token record; tokens we never saw launch are skipped
+                    let record = match database.get_token(&mover.mint) {
+                        Ok(Some(record)) => record,
+                        Ok(None) => continue,
+                        Err(e) => {
+                            warn!(target: "PUMPGUARD", "Top mover lookup failed: {}", e);
+                            continue;
+                        }
+                    };
+                    if record.is_rugged {
+                        continue;
+                    }
+
+                    rug_detector.watch_token(
+                        &record.mint,
+                        &record.name,
+                        &record.symbol,
+                        &record.creator,
+                        record.current_liquidity,
+                    );
+                    info!(
+                        target: "PUMPGUARD",
+                        "Auto-watching top mover {} ({:.2} SOL volume)",
+                        record.symbol, mover.volume
+                    );
+                }
+            }
+        });
+
+        info!(
+            target: "PUMPGUARD",
+            "Top movers above {} SOL volume will be auto-watched by rug detector",
+            min_volume
+        );
     }
 
-    /// Graceful shutdown
-    pub async fn shutdown(&self) {
+    /// Graceful shutdown: stop taking new work, drain the analysis queues and flush the
+    /// database, then close the dashboard. `running` is the pending `start` future, which
+    /// keeps serving the dashboard until its connections have closed.
+    pub async fn shutdown(&self, running: impl Future<Output = Result<()>>) {
         info!(target: "PUMPGUARD", "Shutting down...");
+        let started = Instant::now();
 
-        self.token_monitor.stop();
-        self.rug_detector.stop();
-        self.whale_watcher.stop();
+        let phase = Instant::now();
+        self.stop_modules();
+        info!(target: "PUMPGUARD", "Stopped accepting new work in {:?}", phase.elapsed());
+
+        let phase = Instant::now();
+        let drain_timeout = Duration::from_secs(self.config.shutdown_drain_timeout_secs);
+        let drained = tokio::time::timeout(drain_timeout, async {
+            while self.pending_work() > 0 {
+                tokio::time::sleep(Duration::from_millis(DRAIN_POLL_MS)).await;
+            }
+        })
+        .await;
+        if drained.is_err() {
+            warn!(
+                target: "PUMPGUARD",
+                "Gave up on {} analysis jobs after {}s",
+                self.pending_work(),
+                drain_timeout.as_secs()
+            );
+        }
+        let flushed = self.database.flush_transactions();
+        if flushed > 0 {
+            info!(target: "PUMPGUARD", "Flushed {} buffered transactions", flushed);
+        }
+        self.alerts.flush_file().await;
+        let leader = self.election.as_ref().is_none_or(|e| e.role() == InstanceRole::Leader);
+        if self.config.metrics_persist_secs > 0 && leader {
+            Self::persist_counters(
+                &self.metrics,
+                &self.database,
+                &self.token_monitor,
+                &self.rug_detector,
+                &self.whale_watcher,
+            );
+        }
+        info!(
+            target: "PUMPGUARD",
+            "Drained queues and flushed the database in {:?}",
+            phase.elapsed()
+        );
+
+        let phase = Instant::now();
+        self.shutdown.send_replace(true);
+        let dashboard_timeout = Duration::from_secs(self.config.shutdown_dashboard_timeout_secs);
+        match tokio::time::timeout(dashboard_timeout, running).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!(target: "PUMPGUARD", "Dashboard stopped with an error: {}", e),
+            Err(_) => warn!(
+                target: "PUMPGUARD",
+                "Dashboard connections still open after {}s, closing anyway",
+                dashboard_timeout.as_secs()
+            ),
+        }
+        info!(target: "PUMPGUARD", "Closed the dashboard in {:?}", phase.elapsed());
 
-        info!(target: "PUMPGUARD", "✅ Shutdown complete");
+        if let Some(election) = &self.election {
+            election.release();
+        }
+
+        info!(target: "PUMPGUARD", "✅ Shutdown complete in {:?}", started.elapsed());
     }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logging
-    init_logger();
+    let log_filter = init_logger();
 
     // Create and start PumpGuard
-    let pumpguard = match PumpGuard::new() {
+    let pumpguard = match PumpGuard::new(log_filter) {
         Ok(pg) => pg,
         Err(e) => {
             error!(target: "PUMPGUARD", "Failed to initialize: {}", e);
@@ -203,6 +596,12 @@ async fn main() -> Result<()> {
         }
     };
 
+    // `--check` reports on configuration and connectivity, then exits without monitoring
+    let report = pumpguard.preflight().await;
+    if std::env::args().skip(1).any(|arg| arg == "--check") {
+        std::process::exit(if report.passed { 0 } else { 1 });
+    }
+
     // Setup shutdown signal handler
     let shutdown_signal = async {
         let ctrl_c = async {
@@ -228,15 +627,21 @@ async fn main() -> Result<()> {
         }
     };
 
-    // Run the application
+    // Run the application; on a signal the still-running dashboard is handed to shutdown
+    let running = pumpguard.start();
+    tokio::pin!(running);
     tokio::select! {
-        result = pumpguard.start() => {
+        result = &mut running => {
             if let Err(e) = result {
                 error!(target: "PUMPGUARD", "Fatal error: {}", e);
+                // The dashboard has already stopped, but queued work and buffered writes
+                // still get flushed; the error exit lets a supervisor restart us
+                pumpguard.shutdown(async { Ok(()) }).await;
+                return Err(e);
             }
         }
         _ = shutdown_signal => {
-            pumpguard.shutdown().await;
+            pumpguard.shutdown(running).await;
         }
     }
 
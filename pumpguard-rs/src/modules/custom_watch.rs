@@ -0,0 +1,232 @@
+//! Custom Watch - Generic alerts for log patterns in programs other than pump.fun
+//!
+//! Deliberately minimal: a matching log raises one alert. Nothing here is parsed,
+//! scored or handed to the pump-specific modules.
+
+use anyhow::Result;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tracing::{error, info, warn};
+
+use crate::config::Config;
+use crate::utils::error::{PumpError, PumpResult};
+use crate::utils::run_flag::RunFlag;
+use crate::utils::solana::ProgramLogEvent;
+use crate::utils::{AlertService, SolanaService};
+
+/// Alert when a log line of `program_id` contains `match_pattern`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomWatchRule {
+    pub program_id: String,
+    /// Substring to look for; empty matches every transaction of the program
+    #[serde(default)]
+    pub match_pattern: String,
+    pub alert_title: String,
+}
+
+impl CustomWatchRule {
+    /// Parse a `program|pattern|title` spec, e.g. `LanchP...|Instruction: Launch|New launchpad token`
+    pub fn parse(spec: &str) -> Option<Self> {
+        let mut parts = spec.splitn(3, '|').map(str::trim);
+        let rule = Self {
+            program_id: parts.next()?.to_string(),
+            match_pattern: parts.next()?.to_string(),
+            alert_title: parts.next()?.to_string(),
+        };
+        (!rule.program_id.is_empty() && !rule.alert_title.is_empty()).then_some(rule)
+    }
+
+    /// The rule with its program ID normalized, or why it can't be used
+    fn validated(self) -> PumpResult<Self> {
+        let program = Pubkey::from_str(self.program_id.trim())
+            .map_err(|e| PumpError::InvalidInput(format!("{}: {}", self.program_id, e)))?;
+        if self.alert_title.trim().is_empty() {
+            return Err(PumpError::InvalidInput(format!(
+                "Rule for {} needs an alert_title",
+                program
+            )));
+        }
+        Ok(Self {
+            program_id: program.to_string(),
+            ..self
+        })
+    }
+
+    /// The first log line the rule matches
+    fn matching_log<'a>(&self, logs: &'a [String]) -> Option<&'a String> {
+        logs.iter().find(|line| line.contains(&self.match_pattern))
+    }
+}
+
+/// Custom watcher statistics
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomWatchStats {
+    pub is_running: bool,
+    pub rules: usize,
+    pub programs: usize,
+    pub logs_received: u64,
+    pub alerts_sent: u64,
+}
+
+/// Custom Watch module
+pub struct CustomWatcher {
+    solana: Arc<SolanaService>,
+    alerts: Arc<AlertService>,
+    is_running: RunFlag,
+    rules: Arc<RwLock<Vec<CustomWatchRule>>>,
+    logs_received: Arc<AtomicU64>,
+    alerts_sent: Arc<AtomicU64>,
+}
+
+impl CustomWatcher {
+    /// Create a new custom watcher; rules with an invalid program ID are dropped
+    pub fn new(config: Config, solana: Arc<SolanaService>, alerts: Arc<AlertService>) -> Self {
+        let watcher = Self {
+            solana,
+            alerts,
+            is_running: RunFlag::default(),
+            rules: Arc::new(RwLock::new(Vec::new())),
+            logs_received: Arc::new(AtomicU64::new(0)),
+            alerts_sent: Arc::new(AtomicU64::new(0)),
+        };
+
+        let valid: Vec<CustomWatchRule> = config
+            .custom_watch_rules
+            .into_iter()
+            .filter_map(|rule| match rule.clone().validated() {
+                Ok(rule) => Some(rule),
+                Err(e) => {
+                    warn!(target: "CUSTOM_WATCH", "Ignoring rule \"{}\": {}", rule.alert_title, e);
+                    None
+                }
+            })
+            .collect();
+        if valid.is_empty() {
+            return watcher;
+        }
+        if let Err(e) = watcher.set_rules(valid) {
+            warn!(target: "CUSTOM_WATCH", "Failed to apply custom watch rules: {}", e);
+        }
+        watcher
+    }
+
+    /// Start matching custom program logs
+    pub async fn start(&self) -> Result<()> {
+        let Some(run) = self.is_running.start() else {
+            warn!(target: "CUSTOM_WATCH", "Already running");
+            return Ok(());
+        };
+
+        let mut log_receiver = self.solana.subscribe_custom_logs();
+        let watcher = self.clone();
+
+        tokio::spawn(async move {
+            while run.is_current() {
+                match log_receiver.recv().await {
+                    Ok(event) => watcher.handle_log(event).await,
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!(target: "CUSTOM_WATCH", "Lagged {} messages", n);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        let rules = self.rules.read().len();
+        if rules > 0 {
+            info!(target: "CUSTOM_WATCH", "Custom watch active - {} rule(s)", rules);
+        }
+        Ok(())
+    }
+
+    /// Stop matching
+    pub fn stop(&self) {
+        self.is_running.stop();
+    }
+
+    async fn handle_log(&self, event: ProgramLogEvent) {
+        self.logs_received.fetch_add(1, Ordering::SeqCst);
+
+        let matches: Vec<(CustomWatchRule, String)> = self
+            .rules
+            .read()
+            .iter()
+            .filter(|rule| rule.program_id == event.program)
+            .filter_map(|rule| Some((rule.clone(), rule.matching_log(&event.logs)?.clone())))
+            .collect();
+
+        for (rule, line) in matches {
+            let message = format!(
+                "Program: `{}`\nLog: {}\nTx: `{}`",
+                rule.program_id, line, event.signature
+            );
+            let data = serde_json::json!({
+                "program_id": rule.program_id,
+                "match_pattern": rule.match_pattern,
+                "signature": event.signature,
+                "log": line,
+            });
+            match self.alerts.send_alert("custom_watch", &rule.alert_title, &message, data).await {
+                Ok(_) => {
+                    self.alerts_sent.fetch_add(1, Ordering::SeqCst);
+                }
+                Err(e) => error!(target: "CUSTOM_WATCH", "Failed to send alert: {}", e),
+            }
+        }
+    }
+
+    /// Current rules
+    pub fn get_rules(&self) -> Vec<CustomWatchRule> {
+        self.rules.read().clone()
+    }
+
+    /// Replace the rules and re-subscribe to their programs on the live WebSocket
+    pub fn set_rules(&self, rules: Vec<CustomWatchRule>) -> PumpResult<Vec<CustomWatchRule>> {
+        let rules = rules
+            .into_iter()
+            .map(CustomWatchRule::validated)
+            .collect::<PumpResult<Vec<_>>>()?;
+        let programs = self
+            .solana
+            .set_custom_programs(rules.iter().map(|rule| rule.program_id.clone()).collect())?;
+
+        info!(
+            target: "CUSTOM_WATCH",
+            "{} custom watch rule(s) across {} program(s)",
+            rules.len(),
+            programs.len()
+        );
+        *self.rules.write() = rules.clone();
+        Ok(rules)
+    }
+
+    /// Get watcher statistics
+    pub fn get_stats(&self) -> CustomWatchStats {
+        CustomWatchStats {
+            is_running: self.is_running.is_running(),
+            rules: self.rules.read().len(),
+            programs: self.solana.custom_programs().len(),
+            logs_received: self.logs_received.load(Ordering::SeqCst),
+            alerts_sent: self.alerts_sent.load(Ordering::SeqCst),
+        }
+    }
+}
+
+impl Clone for CustomWatcher {
+    fn clone(&self) -> Self {
+        Self {
+            solana: Arc::clone(&self.solana),
+            alerts: Arc::clone(&self.alerts),
+            is_running: self.is_running.clone(),
+            rules: Arc::clone(&self.rules),
+            logs_received: Arc::clone(&self.logs_received),
+            alerts_sent: Arc::clone(&self.alerts_sent),
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! Daily Digest - Once-a-day summary of persisted activity sent through the alert channels
+
+use anyhow::{anyhow, Result};
+use chrono::{Duration as ChronoDuration, NaiveTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{error, info};
+
+use crate::config::Config;
+use crate::utils::database::{TokenRecord, TokenVolume, WalletVolume};
+use crate::utils::error::PumpResult;
+use crate::utils::format::{format_sol, format_sol_signed};
+use crate::utils::run_flag::RunFlag;
+use crate::utils::{AlertService, DatabaseService, SolanaService};
+
+/// Rows listed per digest section
+const DIGEST_SECTION_LIMIT: i64 = 5;
+
+/// Aggregates for the last 24h; sections left out by config are `None`
+#[derive(Debug, Clone, Serialize)]
+pub struct DigestReport {
+    pub since: String,
+    pub tokens_detected: Option<i64>,
+    pub rugs_confirmed: Option<i64>,
+    /// The most recent of `rugs_confirmed`, at most `DIGEST_SECTION_LIMIT`
+    pub rugs: Option<Vec<TokenRecord>>,
+    pub top_whales: Option<Vec<WalletVolume>>,
+    pub top_movers: Option<Vec<TokenVolume>>,
+}
+
+/// Daily Digest module
+pub struct DailyDigest {
+    config: Config,
+    alerts: Arc<AlertService>,
+    database: Arc<DatabaseService>,
+    is_running: RunFlag,
+}
+
+impl DailyDigest {
+    /// Create a new daily digest
+    pub fn new(config: Config, alerts: Arc<AlertService>, database: Arc<DatabaseService>) -> Self {
+        Self {
+            config,
+            alerts,
+            database,
+            is_running: RunFlag::default(),
+        }
+    }
+
+    /// Start the scheduler; does nothing when `DIGEST_TIME` is unset
+    pub fn start(&self) -> Result<()> {
+        let time = match &self.config.digest_time {
+            Some(time) => time,
+            None => return Ok(()),
+        };
+        let send_at = NaiveTime::parse_from_str(time.trim(), "%H:%M")
+            .map_err(|_| anyhow!("DIGEST_TIME must be HH:MM (UTC), got {:?}", time))?;
+
+        let Some(run) = self.is_running.start() else {
+            return Ok(());
+        };
+
+        let digest = self.clone();
+        tokio::spawn(async move {
+            while run.is_current() {
+                let now = Utc::now();
+                let mut next = now.date_naive().and_time(send_at).and_utc();
+                if next <= now {
+                    next += ChronoDuration::days(1);
+                }
+                let wait = (next - now).to_std().unwrap_or_default();
+                tokio::time::sleep(wait).await;
+
+                if !run.is_current() {
+                    break;
+                }
+                if let Err(e) = digest.send().await {
+                    error!(target: "DIGEST", "Failed to send daily digest: {}", e);
+                }
+            }
+        });
+
+        info!(target: "DIGEST", "Daily digest scheduled for {} UTC", send_at.format("%H:%M"));
+        Ok(())
+    }
+
+    /// Stop the scheduler
+    pub fn stop(&self) {
+        self.is_running.stop();
+    }
+
+    /// Compile and send the digest for the last 24h
+    pub async fn send(&self) -> Result<()> {
+        let report = self.compile()?;
+        let message = Self::format(&report, self.config.alert_sol_digits);
+        self.alerts
+            .send_alert("digest", "Daily Digest", &message, serde_json::to_value(&report)?)
+            .await?;
+        info!(target: "DIGEST", "Daily digest sent");
+        Ok(())
+    }
+
+    /// Query the configured sections over the last 24h
+    pub fn compile(&self) -> PumpResult<DigestReport> {
+        let since = (Utc::now() - ChronoDuration::hours(24)).to_rfc3339();
+        let include = |section: &str| self.config.digest_sections.iter().any(|s| s == section);
+
+        Ok(DigestReport {
+            tokens_detected: if include("tokens") {
+                Some(self.database.count_tokens_since(&since)?)
+            } else {
+                None
+            },
+            rugs_confirmed: if include("rugs") {
+                Some(self.database.count_rugged_since(&since)?)
+            } else {
+                None
+            },
+            rugs: if include("rugs") {
+                Some(self.database.get_rugged_since(&since, DIGEST_SECTION_LIMIT)?)
+            } else {
+                None
+            },
+            top_whales: if include("whales") {
+                Some(self.database.get_top_wallets_since(&since, DIGEST_SECTION_LIMIT)?)
+            } else {
+                None
+            },
+            top_movers: if include("movers") {
+                Some(self.database.get_top_tokens_since(&since, DIGEST_SECTION_LIMIT)?)
+            } else {
+                None
+            },
+            since,
+        })
+    }
+
+    fn format(report: &DigestReport, sol_digits: u32) -> String {
+        let mut sections = Vec::new();
+
+        if let Some(count) = report.tokens_detected {
+            sections.push(format!("Tokens detected: {}", count));
+        }
+        if let Some(rugs) = &report.rugs {
+            let confirmed = report.rugs_confirmed.unwrap_or(rugs.len() as i64);
+            let mut lines = vec![format!("Rugs confirmed: {}", confirmed)];
+            for token in rugs {
+                lines.push(format!(
+                    "• {} - {}",
+                    token.symbol,
+                    token.rug_reason.as_deref().unwrap_or("unknown")
+                ));
+            }
+            sections.push(lines.join("\n"));
+        }
+        if let Some(whales) = &report.top_whales {
+            let mut lines = vec!["Top whales:".to_string()];
+            for whale in whales {
+                lines.push(format!(
+                    "• `{}` {} ({} trades)",
+                    SolanaService::shorten_address(&whale.wallet, 4),
+                    format_sol(whale.volume_sol, sol_digits),
+                    whale.trades
+                ));
+            }
+            sections.push(lines.join("\n"));
+        }
+        if let Some(movers) = &report.top_movers {
+            let mut lines = vec!["Biggest movers:".to_string()];
+            for mover in movers {
+                let name = mover
+                    .symbol
+                    .clone()
+                    .unwrap_or_else(|| SolanaService::shorten_address(&mover.mint, 4));
+                lines.push(format!(
+                    "• {} {} volume, net {}",
+                    name,
+                    format_sol(mover.volume_sol, sol_digits),
+                    format_sol_signed(mover.net_flow_sol, sol_digits)
+                ));
+            }
+            sections.push(lines.join("\n"));
+        }
+
+        if sections.is_empty() {
+            "No digest sections enabled".to_string()
+        } else {
+            sections.join("\n\n")
+        }
+    }
+}
+
+impl Clone for DailyDigest {
+    fn clone(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            alerts: Arc::clone(&self.alerts),
+            database: Arc::clone(&self.database),
+            is_running: self.is_running.clone(),
+        }
+    }
+}
@@ -1,9 +1,13 @@
 //! PumpGuard monitoring modules
 
+pub mod custom_watch;
+pub mod daily_digest;
 pub mod rug_detector;
 pub mod token_monitor;
 pub mod whale_watcher;
 
+pub use custom_watch::CustomWatcher;
+pub use daily_digest::DailyDigest;
 pub use rug_detector::RugDetector;
 pub use token_monitor::TokenMonitor;
 pub use whale_watcher::WhaleWatcher;
@@ -7,21 +7,28 @@ use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 use solana_transaction_status::{
-    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiMessage,
-    option_serializer::OptionSerializer,
+    EncodedConfirmedTransactionWithStatusMeta,
+    UiTransactionStatusMeta, option_serializer::OptionSerializer,
 };
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::str::FromStr;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::broadcast;
 use tokio::time::{interval, Duration};
 use tracing::{error, info, warn};
 
 use crate::config::Config;
-use crate::utils::alerts::TokenAlertInfo;
-use crate::utils::database::TransactionRecord;
-use crate::utils::{AlertService, DatabaseService, SolanaService};
+use crate::utils::alerts::{RugWebhookPayload, TokenAlertInfo};
+use crate::utils::database::{
+    PendingWrite, TokenQuery, TokenRecord, TransactionRecord, TxLogRecord,
+};
+use crate::utils::error::PumpResult;
+use crate::utils::solana::{CurveBalance, TxLossStats, PUMP_TOKEN_SUPPLY};
+use crate::utils::tx_kind::{classify_logs, instruction_kinds, TxKind};
+use crate::utils::run_flag::RunFlag;
+use crate::utils::work_queue::{KeyedLocks, QueueStats, WorkQueue};
+use crate::utils::{AlertService, DatabaseService, PumpError, SolanaService};
 
 /// Sell transaction info
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,12 +40,21 @@ pub struct SellInfo {
     pub timestamp: i64,
 }
 
+/// Bonding curve balance read by a health check
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LiquiditySample {
+    pub timestamp: i64,
+    pub liquidity_sol: f64,
+}
+
 /// Alert info for rug detection
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RugAlert {
     pub alert_type: String,
     pub message: String,
     pub severity: String,
+    #[serde(default)]
+    pub muted: bool,
 }
 
 /// Watched token with rug detection data
@@ -47,9 +63,13 @@ pub struct WatchedToken {
     pub mint: String,
     pub name: String,
     pub symbol: String,
+    /// Fee payer of the creation transaction. Never re-derived: creator blacklists,
+    /// trust and reputation key on it because it is what later launches are seen with.
     pub creator: String,
     pub initial_liquidity: f64,
     pub current_liquidity: f64,
+    /// Wallet whose sells count as dev sells; starts as `creator` and may be replaced by
+    /// the bonding curve creator (see `dev_wallet_source`)
     pub dev_wallet: String,
     pub sell_history: VecDeque<SellInfo>,
     pub last_check: i64,
@@ -57,19 +77,314 @@ pub struct WatchedToken {
     pub alerts: Vec<RugAlert>,
     pub is_rugged: bool,
     pub rug_reason: Option<String>,
+    #[serde(default)]
+    pub muted: bool,
+    #[serde(default = "now_millis")]
+    pub last_transaction_at: i64,
+    /// Set when trading died out after launch; cleared if a trade comes in
+    #[serde(default)]
+    pub abandoned_at: Option<i64>,
+    #[serde(default)]
+    pub holder_count: u64,
+    /// Highest holder milestone already alerted on
+    #[serde(default)]
+    pub holder_milestone: u64,
+    /// Highest market cap milestone (USD) already alerted on
+    #[serde(default)]
+    pub mcap_milestone: f64,
+    /// Bonding curve completed and the token moved to its AMM pool; the graduated
+    /// threshold profile applies and `current_liquidity` holds the SOL migrated
+    #[serde(default)]
+    pub graduated: bool,
+    #[serde(default)]
+    pub graduated_at: Option<i64>,
+    /// Latest safety classification; unset until the first re-evaluation
+    #[serde(default)]
+    pub safety: Option<SafetyClass>,
+    /// Classification before the latest change
+    #[serde(default)]
+    pub previous_safety: Option<SafetyClass>,
+    #[serde(default)]
+    pub safety_checked_at: i64,
+    /// Creation block time (millis); unknown for tokens not seen launching
+    #[serde(default)]
+    pub launched_at: Option<i64>,
+    /// Block time (millis) of the dev wallet's first sell
+    #[serde(default)]
+    pub dev_first_sell_at: Option<i64>,
+    /// Mint supply in UI units, fetched on the first dev sell
+    #[serde(default)]
+    pub total_supply: Option<f64>,
+    #[serde(default)]
+    pub dev_wallet_source: DevWalletSource,
+    /// Creator is on the trusted list: no suspicion scoring, only explicit rug signals
+    #[serde(default)]
+    pub trusted: bool,
+    /// When the health loop next checks this token (millis); 0 = due now
+    #[serde(default)]
+    pub next_check_at: i64,
+    /// Interval the latest health check scheduled
+    #[serde(default)]
+    pub check_interval_secs: u64,
+    /// Recent curve balances, oldest first, at most `LIQUIDITY_SAMPLE_CAPACITY`
+    #[serde(default)]
+    pub liquidity_samples: VecDeque<LiquiditySample>,
+    /// Last `liquidity_oscillation` alert (millis)
+    #[serde(default)]
+    pub oscillation_alerted_at: Option<i64>,
+    /// Coordinated buy right after launch, kept until a dump by the dev or a bundle wallet
+    #[serde(default)]
+    pub launch_bundle: Option<LaunchBundle>,
+}
+
+impl WatchedToken {
+    /// The token as shown in outbound alerts
+    pub fn alert_info(&self) -> TokenAlertInfo {
+        TokenAlertInfo {
+            mint: self.mint.clone(),
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            creator: self.creator.clone(),
+            initial_liquidity: Some(self.initial_liquidity),
+        }
+    }
+}
+
+/// Wallets that bought together right after launch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchBundle {
+    pub wallets: Vec<String>,
+    pub total_sol: f64,
+    /// Millis of the earliest bundled buy
+    pub bought_at: i64,
+    /// A dump already escalated this bundle to `bundle_rug`
+    #[serde(default)]
+    pub escalated: bool,
+}
+
+/// Where a token's `dev_wallet` came from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DevWalletSource {
+    /// Creator field of the pump.fun bonding curve account
+    BondingCurve,
+    /// Fee payer of the creation transaction, which may be a relayer
+    #[default]
+    FeePayer,
+}
+
+/// Dev wallet of a watched token as served at `/api/rug/token/:mint/dev`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevWalletInfo {
+    pub mint: String,
+    pub dev_wallet: String,
+    pub source: DevWalletSource,
+    pub fee_payer: String,
+}
+
+/// Coarse safety classification of a watched token, ordered from safest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SafetyClass {
+    Safe,
+    Caution,
+    Danger,
+}
+
+impl SafetyClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SafetyClass::Safe => "safe",
+            SafetyClass::Caution => "caution",
+            SafetyClass::Danger => "danger",
+        }
+    }
+}
+
+fn now_millis() -> i64 {
+    Utc::now().timestamp_millis()
+}
+
+/// Bounds for adaptive health check scheduling
+#[derive(Debug, Clone, Copy)]
+struct HealthSchedule {
+    base_secs: u64,
+    min_secs: u64,
+    max_secs: u64,
+}
+
+impl HealthSchedule {
+    fn from_config(config: &Config) -> Self {
+        let min_secs = config.health_check_min_secs.max(1);
+        let base_secs = config.health_check_secs.max(min_secs);
+        Self {
+            base_secs,
+            min_secs,
+            max_secs: config.health_check_max_secs.max(base_secs),
+        }
+    }
+
+    /// Seconds until `token` is due again: suspicious tokens get the minimum, tokens
+    /// traded within the base interval half of it, and tokens idle for four base
+    /// intervals the maximum
+    fn interval_secs(&self, token: &WatchedToken, caution_score: i32, now: i64) -> u64 {
+        let idle_secs = ((now - token.last_transaction_at) / 1000).max(0) as u64;
+        let secs = if token.suspicion_score >= caution_score {
+            self.min_secs
+        } else if idle_secs < self.base_secs {
+            self.base_secs / 2
+        } else if idle_secs >= self.base_secs * 4 {
+            self.max_secs
+        } else {
+            self.base_secs
+        };
+        secs.clamp(self.min_secs, self.max_secs)
+    }
 }
 
 /// Rug detection thresholds
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RugThresholds {
     pub lp_removal_percent: f64,
     pub suspicious_sell_percent: f64,
+    pub graduated_lp_removal_percent: f64,
+    pub graduated_suspicious_sell_percent: f64,
     pub dev_wallet_sell_alert: bool,
     pub max_dev_sell_percent: f64,
-    pub min_time_between_sells: i64,
+    /// A first dev sell sooner than this after launch raises `instant_dev_sell` (0 = off)
+    pub min_dev_hold_secs: u64,
+    pub sell_windows: Vec<SellWindow>,
     pub holder_concentration_alert: f64,
+    /// Curve balance rise between health checks that raises `liquidity_added` (0 = off)
+    pub liquidity_added_percent: f64,
+    /// Liquidity direction flips within `oscillation_window_secs` that raise
+    /// `liquidity_oscillation` (0 = off)
+    #[serde(default)]
+    pub oscillation_min_flips: u32,
+    #[serde(default)]
+    pub oscillation_window_secs: u64,
+    /// Moves smaller than this percent of the previous sample are trading noise, not a flip
+    #[serde(default)]
+    pub oscillation_min_change_percent: f64,
+    /// A coordinated buy this soon after launch is the token's launch bundle
+    #[serde(default)]
+    pub bundle_launch_window_secs: u64,
+    /// A dev or bundle wallet sell this soon after the bundle is a `bundle_rug` (0 = off)
+    #[serde(default)]
+    pub bundle_dump_window_secs: u64,
+    #[serde(default)]
+    pub bundle_dump_min_sol: f64,
+    pub rug_score_threshold: i32,
+    pub confirm_recheck: bool,
+    pub confirm_delay_ms: u64,
+    /// Only trust a curve balance when the account is owned by the pump program
+    pub verify_curve_owner: bool,
+    pub abandon_after_secs: u64,
+    pub abandon_grace_secs: u64,
+    /// Drop tokens graduated or without trades for this long from the watch list (0 = never)
+    pub auto_unwatch_after_secs: u64,
+    /// Also drop rugged tokens and tokens with a suspicion score
+    pub auto_unwatch_flagged: bool,
+    /// Expiry for tokens with a suspicion score while `auto_unwatch_flagged` is off (0 = never)
+    #[serde(default)]
+    pub auto_unwatch_suspicious_after_secs: u64,
+    /// How often a token's safety classification is re-evaluated (0 = off)
+    pub safety_recheck_secs: u64,
+    /// Risk score at which a token drops from safe to caution; danger starts at `rug_score_threshold`
+    pub safety_caution_score: i32,
+    /// Points below a boundary the risk score must fall before a token is upgraded again
+    pub safety_hysteresis: i32,
+    pub mcap_milestones_usd: Vec<f64>,
+    pub sol_usd_price: f64,
+}
+
+/// Sell-pressure window: trips when at least `min_sells` sells totalling more than
+/// `volume_percent` of the token's liquidity baseline land within `window_secs`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SellWindow {
+    pub window_secs: u64,
+    pub min_sells: usize,
+    pub volume_percent: f64,
+}
+
+impl SellWindow {
+    /// Parse a `secs:sells:percent` spec, e.g. `300:6:50`
+    pub fn parse(spec: &str) -> Option<Self> {
+        let mut parts = spec.split(':').map(str::trim);
+        let window = Self {
+            window_secs: parts.next()?.parse().ok()?,
+            min_sells: parts.next()?.parse().ok()?,
+            volume_percent: parts.next()?.parse().ok()?,
+        };
+        (parts.next().is_none() && window.window_secs > 0).then_some(window)
+    }
+
+    /// The `secs:sells:percent` spec this window parses from
+    pub fn spec(&self) -> String {
+        format!("{}:{}:{}", self.window_secs, self.min_sells, self.volume_percent)
+    }
+
+    /// Short label for alert messages, e.g. `5m` or `90s`
+    pub fn label(&self) -> String {
+        if self.window_secs.is_multiple_of(60) {
+            format!("{}m", self.window_secs / 60)
+        } else {
+            format!("{}s", self.window_secs)
+        }
+    }
+
+    /// Sell count and SOL volume inside the window, when they trip it
+    fn tripped(&self, sells: &VecDeque<SellInfo>, baseline: f64, now: i64) -> Option<(usize, f64)> {
+        let since = now - (self.window_secs * 1000) as i64;
+        let (count, volume) = sells
+            .iter()
+            .filter(|s| s.timestamp >= since)
+            .fold((0, 0.0), |(count, volume), s| (count + 1, volume + s.amount_sol));
+        (count >= self.min_sells && volume > baseline * (self.volume_percent / 100.0))
+            .then_some((count, volume))
+    }
+}
+
+/// Thresholds that differ between bonding curve and graduated tokens
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ThresholdProfile {
+    pub name: &'static str,
+    pub lp_removal_percent: f64,
+    pub suspicious_sell_percent: f64,
 }
 
+impl RugThresholds {
+    /// Profile for a token depending on whether it has graduated
+    pub fn profile(&self, graduated: bool) -> ThresholdProfile {
+        if graduated {
+            ThresholdProfile {
+                name: "graduated",
+                lp_removal_percent: self.graduated_lp_removal_percent,
+                suspicious_sell_percent: self.graduated_suspicious_sell_percent,
+            }
+        } else {
+            ThresholdProfile {
+                name: "bonding_curve",
+                lp_removal_percent: self.lp_removal_percent,
+                suspicious_sell_percent: self.suspicious_sell_percent,
+            }
+        }
+    }
+}
+
+/// Liquidity samples kept per watched token for oscillation detection
+const LIQUIDITY_SAMPLE_CAPACITY: usize = 120;
+
+/// Shards of the per-mint analysis lock
+const TOKEN_LOCK_SHARDS: usize = 64;
+
+/// Most tokens put back on the watch list at startup, newest first
+const MAX_RESTORED_TOKENS: i64 = 1000;
+
+/// Wrapped SOL mint; AMM pools hold their SOL side as a wSOL token account
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
 /// Rug detector statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -77,16 +392,57 @@ pub struct RugDetectorStats {
     pub tokens_watched: u64,
     pub rugs_detected: u64,
     pub alerts_sent: u64,
+    pub fetches_avoided: u64,
+    pub lp_logs_ignored: u64,
+    pub failed_txs_skipped: u64,
+    pub tx_fetch_none: u64,
+    pub tx_parse_none: u64,
+    pub auto_unwatched: u64,
+    pub safety_downgrades: u64,
+    /// Mean scheduled health check interval over tokens checked at least once
+    pub avg_check_interval_secs: f64,
+    pub queue_depth: u64,
+    pub queue_dropped: u64,
+    pub rug_score_threshold: i32,
     pub watched_tokens: usize,
     pub is_running: bool,
 }
 
+/// Outcome of scanning a log set for LP-removal markers
+enum LpLogMatch {
+    None,
+    Removal,
+    /// Only allowlisted programs emitted withdraw/migrate logs
+    Ignored,
+}
+
+/// Rug alert that was raised for a token, broadcast so other modules can react
+#[derive(Debug, Clone)]
+pub struct ConfirmedRug {
+    pub mint: String,
+    pub symbol: String,
+    pub creator: String,
+    pub reason: String,
+    pub trusted: bool,
+}
+
+/// Matched log waiting for an analysis worker
+struct LogJob {
+    signature: String,
+    is_sell: bool,
+    is_lp_removal: bool,
+    /// Program logs, carried only when `STORE_RAW_LOGS` is on
+    logs: Option<Vec<String>>,
+}
+
 /// Parsed sell info from transaction
 struct ParsedSellInfo {
     mint: String,
     wallet: String,
     amount_sol: f64,
     amount_tokens: f64,
+    /// Block time in millis, falling back to when the sell was fetched
+    timestamp: i64,
 }
 
 /// Rug Pull Detector module
@@ -96,13 +452,26 @@ pub struct RugDetector {
     alerts: Arc<AlertService>,
     database: Arc<DatabaseService>,
 
-    is_running: Arc<AtomicBool>,
+    is_running: RunFlag,
     pub watched_tokens: Arc<DashMap<String, WatchedToken>>,
+    /// Held while a token is copied out, analyzed across awaits and written back, so
+    /// concurrent workers for the same mint don't overwrite each other's updates
+    token_locks: Arc<KeyedLocks>,
     thresholds: Arc<RwLock<RugThresholds>>,
+    lp_ignore_programs: Arc<RwLock<Vec<String>>>,
+    trusted_creators: Arc<RwLock<HashSet<String>>>,
 
     tokens_watched: Arc<AtomicU64>,
     rugs_detected: Arc<AtomicU64>,
     alerts_sent: Arc<AtomicU64>,
+    fetches_avoided: Arc<AtomicU64>,
+    lp_logs_ignored: Arc<AtomicU64>,
+    failed_txs_skipped: Arc<AtomicU64>,
+    tx_loss: TxLossStats,
+    auto_unwatched: Arc<AtomicU64>,
+    safety_downgrades: Arc<AtomicU64>,
+    analysis_queue: QueueStats,
+    rug_sender: broadcast::Sender<ConfirmedRug>,
 }
 
 impl RugDetector {
@@ -116,26 +485,83 @@ impl RugDetector {
         let thresholds = RugThresholds {
             lp_removal_percent: config.lp_removal_threshold_percent,
             suspicious_sell_percent: config.suspicious_sell_percent,
+            graduated_lp_removal_percent: config.graduated_lp_removal_percent,
+            graduated_suspicious_sell_percent: config.graduated_suspicious_sell_percent,
             dev_wallet_sell_alert: config.dev_wallet_sell_alert,
             max_dev_sell_percent: 20.0,
-            min_time_between_sells: 60000, // 1 minute
+            min_dev_hold_secs: config.min_dev_hold_secs,
+            sell_windows: {
+                let mut windows = config.sell_windows.clone();
+                windows.sort_by_key(|w| w.window_secs);
+                windows
+            },
             holder_concentration_alert: 80.0,
+            liquidity_added_percent: config.liquidity_added_alert_percent,
+            oscillation_min_flips: config.oscillation_min_flips,
+            oscillation_window_secs: config.oscillation_window_secs,
+            oscillation_min_change_percent: config.oscillation_min_change_percent,
+            bundle_launch_window_secs: config.bundle_launch_window_secs,
+            bundle_dump_window_secs: config.bundle_dump_window_secs,
+            bundle_dump_min_sol: config.bundle_dump_min_sol,
+            rug_score_threshold: 80,
+            confirm_recheck: config.rug_confirm_recheck,
+            verify_curve_owner: config.verify_curve_owner,
+            confirm_delay_ms: config.rug_confirm_delay_ms,
+            abandon_after_secs: config.rug_abandon_after_secs,
+            abandon_grace_secs: config.rug_abandon_grace_secs,
+            auto_unwatch_after_secs: config.auto_unwatch_after_secs,
+            auto_unwatch_flagged: config.auto_unwatch_flagged,
+            auto_unwatch_suspicious_after_secs: config.auto_unwatch_suspicious_after_secs,
+            safety_recheck_secs: config.safety_recheck_secs,
+            safety_caution_score: config.safety_caution_score,
+            safety_hysteresis: config.safety_hysteresis,
+            mcap_milestones_usd: {
+                let mut milestones = config.mcap_milestones_usd.clone();
+                milestones.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+                milestones
+            },
+            sol_usd_price: config.sol_usd_price,
         };
 
+        let lp_ignore_programs = Arc::new(RwLock::new(config.lp_ignore_programs.clone()));
+
+        let mut trusted_creators: HashSet<String> = config.trusted_creators.iter().cloned().collect();
+        match database.get_trusted_creators() {
+            Ok(saved) => trusted_creators.extend(saved),
+            Err(e) => warn!(target: "RUG_DETECTOR", "Failed to load trusted creators: {}", e),
+        }
+        let (rug_sender, _) = broadcast::channel(100);
+
         Self {
             config,
             solana,
             alerts,
             database,
-            is_running: Arc::new(AtomicBool::new(false)),
+            is_running: RunFlag::default(),
             watched_tokens: Arc::new(DashMap::new()),
+            token_locks: Arc::new(KeyedLocks::new(TOKEN_LOCK_SHARDS)),
             thresholds: Arc::new(RwLock::new(thresholds)),
+            lp_ignore_programs,
+            trusted_creators: Arc::new(RwLock::new(trusted_creators)),
             tokens_watched: Arc::new(AtomicU64::new(0)),
             rugs_detected: Arc::new(AtomicU64::new(0)),
             alerts_sent: Arc::new(AtomicU64::new(0)),
+            fetches_avoided: Arc::new(AtomicU64::new(0)),
+            lp_logs_ignored: Arc::new(AtomicU64::new(0)),
+            failed_txs_skipped: Arc::new(AtomicU64::new(0)),
+            tx_loss: TxLossStats::default(),
+            auto_unwatched: Arc::new(AtomicU64::new(0)),
+            safety_downgrades: Arc::new(AtomicU64::new(0)),
+            analysis_queue: QueueStats::default(),
+            rug_sender,
         }
     }
 
+    /// Get a receiver for rug alerts as they are raised
+    pub fn subscribe_rugs(&self) -> broadcast::Receiver<ConfirmedRug> {
+        self.rug_sender.subscribe()
+    }
+
     /// Watch a token for rug detection
     pub fn watch_token(
         &self,
@@ -163,6 +589,35 @@ impl RugDetector {
             alerts: Vec::new(),
             is_rugged: false,
             rug_reason: None,
+            muted: self
+                .database
+                .get_token(mint)
+                .ok()
+                .flatten()
+                .map(|t| t.is_muted)
+                .unwrap_or(false),
+            last_transaction_at: Utc::now().timestamp_millis(),
+            abandoned_at: None,
+            graduated: false,
+            graduated_at: None,
+            safety: None,
+            previous_safety: None,
+            safety_checked_at: 0,
+            launched_at: None,
+            dev_first_sell_at: None,
+            total_supply: None,
+            dev_wallet_source: DevWalletSource::FeePayer,
+            trusted: self.trusted_creators.read().contains(creator),
+            // Fresh launches are the most volatile, so the first check comes soonest
+            next_check_at: Utc::now().timestamp_millis()
+                + HealthSchedule::from_config(&self.config).min_secs as i64 * 1000,
+            check_interval_secs: 0,
+            liquidity_samples: VecDeque::new(),
+            oscillation_alerted_at: None,
+            launch_bundle: None,
+            holder_count: 0,
+            holder_milestone: 0,
+            mcap_milestone: 0.0,
         };
 
         self.watched_tokens.insert(mint.to_string(), token);
@@ -176,30 +631,360 @@ impl RugDetector {
         );
     }
 
-    /// Unwatch a token
-    pub fn unwatch_token(&self, mint: &str) {
-        self.watched_tokens.remove(mint);
+    /// Mute or unmute outbound alerts for a watched token (it is still tracked and scored)
+    pub async fn set_muted(&self, mint: &str, muted: bool) -> Option<WatchedToken> {
+        let _lock = self.token_locks.lock(mint).await;
+        let token = {
+            let mut entry = self.watched_tokens.get_mut(mint)?;
+            entry.muted = muted;
+            entry.clone()
+        };
+
+        let record = TokenRecord {
+            mint: token.mint.clone(),
+            name: token.name.clone(),
+            symbol: token.symbol.clone(),
+            creator: token.creator.clone(),
+            created_at: token
+                .launched_at
+                .and_then(chrono::DateTime::from_timestamp_millis)
+                .unwrap_or_else(Utc::now)
+                .to_rfc3339(),
+            initial_liquidity: token.initial_liquidity,
+            current_liquidity: token.current_liquidity,
+            holder_count: token.holder_count as i32,
+            is_rugged: token.is_rugged,
+            rug_reason: token.rug_reason.clone(),
+            last_updated: Utc::now().to_rfc3339(),
+            is_muted: muted,
+            is_graduated: token.graduated,
+        };
+        if let Err(e) = self.database.set_token_muted(&record) {
+            warn!(target: "RUG_DETECTOR", "Failed to persist mute for {}: {}", token.symbol, e);
+        }
+
         info!(
             target: "RUG_DETECTOR",
-            "Stopped watching: {}",
+            "{} alerts for {}",
+            if muted { "Muted" } else { "Unmuted" },
             SolanaService::shorten_address(mint, 4)
         );
+
+        Some(token)
+    }
+
+    /// Current dev wallet of a watched token, without any RPC calls
+    pub fn dev_wallet_info(&self, mint: &str) -> PumpResult<DevWalletInfo> {
+        self.watched_tokens
+            .get(mint)
+            .map(|token| DevWalletInfo {
+                mint: token.mint.clone(),
+                dev_wallet: token.dev_wallet.clone(),
+                source: token.dev_wallet_source,
+                fee_payer: token.creator.clone(),
+            })
+            .ok_or_else(|| PumpError::NotFound("Watched token".to_string()))
+    }
+
+    /// Re-derive a watched token's dev wallet from its bonding curve creator, keeping the
+    /// creation fee payer when the curve has no creator or can't be read. Only
+    /// `dev_wallet` changes; `creator` stays the fee payer.
+    pub async fn verify_dev_wallet(&self, mint: &str) -> PumpResult<DevWalletInfo> {
+        let _lock = self.token_locks.lock(mint).await;
+        let fee_payer = self
+            .watched_tokens
+            .get(mint)
+            .map(|token| token.creator.clone())
+            .ok_or_else(|| PumpError::NotFound("Watched token".to_string()))?;
+
+        let (dev_wallet, source) = match self.solana.get_curve_creator(mint).await {
+            Ok(Some(creator)) => (creator, DevWalletSource::BondingCurve),
+            Ok(None) => (fee_payer.clone(), DevWalletSource::FeePayer),
+            Err(e) => {
+                warn!(target: "RUG_DETECTOR", "Curve creator lookup failed for {}: {}", mint, e);
+                (fee_payer.clone(), DevWalletSource::FeePayer)
+            }
+        };
+
+        if let Some(mut token) = self.watched_tokens.get_mut(mint) {
+            if token.dev_wallet != dev_wallet {
+                info!(
+                    target: "RUG_DETECTOR",
+                    "{}: dev wallet is {} (fee payer {})",
+                    token.symbol,
+                    SolanaService::shorten_address(&dev_wallet, 4),
+                    SolanaService::shorten_address(&fee_payer, 4)
+                );
+            }
+            token.dev_wallet = dev_wallet.clone();
+            token.dev_wallet_source = source;
+        }
+
+        Ok(DevWalletInfo {
+            mint: mint.to_string(),
+            dev_wallet,
+            source,
+            fee_payer,
+        })
+    }
+
+    /// Re-watch tokens detected within `RESTORE_WATCHED_HOURS` that haven't rugged or
+    /// graduated, resuming from their stored liquidity. Returns how many were added.
+    fn restore_watched_tokens(&self) -> PumpResult<usize> {
+        let hours = self.config.restore_watched_hours;
+        if hours == 0 {
+            return Ok(0);
+        }
+        let query = TokenQuery {
+            rugged: Some(false),
+            graduated: Some(false),
+            min_liquidity: None,
+        };
+        let cutoff = Utc::now() - chrono::Duration::hours(hours as i64);
+
+        let mut restored = 0;
+        // Newest first, so everything after the first token older than the cutoff is older too
+        for record in self.database.get_recent_tokens(MAX_RESTORED_TOKENS, &query)? {
+            let created = match chrono::DateTime::parse_from_rfc3339(&record.created_at) {
+                Ok(created) if created >= cutoff => created,
+                Ok(_) => break,
+                Err(_) => continue,
+            };
+            if self.watched_tokens.contains_key(&record.mint) {
+                continue;
+            }
+            self.watch_token(
+                &record.mint,
+                &record.name,
+                &record.symbol,
+                &record.creator,
+                record.initial_liquidity,
+            );
+            if let Some(mut token) = self.watched_tokens.get_mut(&record.mint) {
+                token.current_liquidity = record.current_liquidity;
+                token.launched_at = Some(created.timestamp_millis());
+            }
+            restored += 1;
+        }
+        Ok(restored)
+    }
+
+    /// Record when a watched token was created, enabling the dev hold-time check
+    pub async fn set_launched_at(&self, mint: &str, launched_at: i64) {
+        let _lock = self.token_locks.lock(mint).await;
+        if let Some(mut token) = self.watched_tokens.get_mut(mint) {
+            token.launched_at = Some(launched_at);
+        }
+    }
+
+    /// Record the launch-time share of supply held by the largest non-curve wallet.
+    /// Above the threshold this raises a `concentrated_launch` alert and bumps the suspicion score.
+    pub async fn check_launch_concentration(&self, mint: &str, top_holder_percent: f64) {
+        let threshold = self.config.launch_holder_alert_percent;
+        if threshold <= 0.0 || top_holder_percent < threshold {
+            return;
+        }
+
+        let _lock = self.token_locks.lock(mint).await;
+        let (alert, token_info) = {
+            let Some(mut token) = self.watched_tokens.get_mut(mint) else {
+                return;
+            };
+            if token.trusted {
+                return;
+            }
+            let alert = RugAlert {
+                alert_type: "concentrated_launch".to_string(),
+                message: format!(
+                    "One wallet holds {:.1}% of supply at launch",
+                    top_holder_percent
+                ),
+                severity: "high".to_string(),
+                muted: token.muted,
+            };
+            token.suspicion_score += 30;
+            Self::push_alert(&self.database, &mut token, alert.clone());
+            (alert, token.alert_info())
+        };
+
+        warn!(target: "RUG_DETECTOR", "{}: {}", token_info.symbol, alert.message);
+        if alert.muted {
+            return;
+        }
+        self.alerts_sent.fetch_add(1, Ordering::SeqCst);
+        let message = format!(
+            "Token: {}\nMint: `{}`\n{} (threshold {:.1}%)",
+            token_info.symbol, token_info.mint, alert.message, threshold
+        );
+        let _ = self
+            .alerts
+            .send_alert(
+                "concentrated_launch",
+                "Concentrated Launch",
+                &message,
+                serde_json::json!({
+                    "mint": token_info.mint,
+                    "symbol": token_info.symbol,
+                    "creator": token_info.creator,
+                    "top_holder_percent": top_holder_percent,
+                    "threshold": threshold,
+                }),
+            )
+            .await;
+    }
+
+    /// Score a whale trade the token's creator made in it. The whale watcher has already
+    /// alerted, so this only records the signal on the watched token.
+    pub async fn record_creator_activity(&self, mint: &str, tx_type: &str, amount_sol: f64) {
+        let _lock = self.token_locks.lock(mint).await;
+        let Some(mut token) = self.watched_tokens.get_mut(mint) else {
+            return;
+        };
+        token.last_transaction_at = Utc::now().timestamp_millis();
+        if token.trusted {
+            return;
+        }
+        let (severity, weight) = if tx_type == "sell" { ("critical", 35) } else { ("high", 15) };
+        let alert = RugAlert {
+            alert_type: "creator_whale_activity".to_string(),
+            message: format!("Creator made a {:.2} SOL whale {}", amount_sol, tx_type),
+            severity: severity.to_string(),
+            muted: token.muted,
+        };
+        token.suspicion_score += weight;
+        token.alerts.push(alert);
+    }
+
+    /// Keep a coordinated buy landing within `bundle_launch_window_secs` of launch as the
+    /// token's launch bundle. Alone it only adds to the suspicion score; a dump by the dev
+    /// or a bundle wallet later escalates it to a `bundle_rug`.
+    pub async fn record_coordinated_buy(
+        &self,
+        mint: &str,
+        wallets: &[String],
+        total_sol: f64,
+        bought_at: i64,
+    ) {
+        let (launch_window_secs, dump_window_secs) = {
+            let thresholds = self.thresholds.read();
+            (thresholds.bundle_launch_window_secs, thresholds.bundle_dump_window_secs)
+        };
+        if dump_window_secs == 0 {
+            return;
+        }
+        let _lock = self.token_locks.lock(mint).await;
+        let Some(mut token) = self.watched_tokens.get_mut(mint) else {
+            return;
+        };
+        let Some(launched_at) = token.launched_at else {
+            return;
+        };
+        let after_launch_ms = (bought_at - launched_at).max(0);
+        if token.trusted
+            || token.launch_bundle.is_some()
+            || after_launch_ms > (launch_window_secs * 1000) as i64
+        {
+            return;
+        }
+
+        let alert = RugAlert {
+            alert_type: "coordinated_buying".to_string(),
+            message: format!(
+                "{} wallets bought {:.2} SOL together {}s after launch",
+                wallets.len(),
+                total_sol,
+                after_launch_ms / 1000
+            ),
+            severity: "high".to_string(),
+            muted: token.muted,
+        };
+        warn!(target: "RUG_DETECTOR", "{}: {}", token.symbol, alert.message);
+        token.suspicion_score += 20;
+        token.alerts.push(alert);
+        token.launch_bundle = Some(LaunchBundle {
+            wallets: wallets.to_vec(),
+            total_sol,
+            bought_at,
+            escalated: false,
+        });
+    }
+
+    /// Operator override: mark a watched token rugged (sending the rug alert) or clear it.
+    /// Either way the decision is recorded in the alerts table.
+    pub async fn flag_token(
+        &self,
+        mint: &str,
+        rugged: bool,
+        reason: &str,
+    ) -> PumpResult<WatchedToken> {
+        let _lock = self.token_locks.lock(mint).await;
+        let mut token = self
+            .watched_tokens
+            .get(mint)
+            .map(|e| e.value().clone())
+            .ok_or_else(|| PumpError::NotFound("Watched token".to_string()))?;
+
+        if rugged {
+            // The rug is recorded either way, so a failed send doesn't fail the flag
+            if let Err(e) = Self::trigger_rug_alert(
+                &self.alerts,
+                &self.database,
+                &self.rugs_detected,
+                &self.alerts_sent,
+                &self.rug_sender,
+                &mut token,
+                reason,
+            )
+            .await
+            {
+                warn!(target: "RUG_DETECTOR", "Rug alert for flagged {} failed: {}", token.symbol, e);
+            }
+        } else {
+            self.database.clear_rugged(mint)?;
+            token.is_rugged = false;
+            token.rug_reason = None;
+            info!(target: "RUG_DETECTOR", "Rug flag cleared for {}: {}", token.symbol, reason);
+        }
+
+        let (alert_type, title) = if rugged {
+            ("rug_flag", "Token flagged as rugged")
+        } else {
+            ("rug_clear", "Token flagged as safe")
+        };
+        self.database.save_alert(
+            alert_type,
+            title,
+            &format!("{} ({}): {}", token.symbol, token.mint, reason),
+            &serde_json::json!({ "mint": token.mint, "rugged": rugged, "reason": reason }).to_string(),
+            false,
+        )?;
+
+        Self::write_back(&self.watched_tokens, token.clone());
+        Ok(token)
     }
 
     /// Start the rug detector
     pub async fn start(&self) -> Result<()> {
-        if self.is_running.load(Ordering::SeqCst) {
+        let Some(run) = self.is_running.start() else {
             warn!(target: "RUG_DETECTOR", "Already running");
             return Ok(());
-        }
-
-        self.is_running.store(true, Ordering::SeqCst);
+        };
         info!(target: "RUG_DETECTOR", "🔍 Starting Rug Pull Detector...");
 
+        match self.restore_watched_tokens() {
+            Ok(0) => {}
+            Ok(restored) => info!(
+                target: "RUG_DETECTOR",
+                "Restored {} watched token(s) from the last {}h",
+                restored,
+                self.config.restore_watched_hours
+            ),
+            Err(e) => warn!(target: "RUG_DETECTOR", "Failed to restore watched tokens: {}", e),
+        }
+
         // Subscribe to Solana logs for sell events
         let mut log_receiver = self.solana.subscribe_logs();
 
-        let is_running = Arc::clone(&self.is_running);
         let solana = Arc::clone(&self.solana);
         let alerts = Arc::clone(&self.alerts);
         let database = Arc::clone(&self.database);
@@ -207,70 +992,68 @@ impl RugDetector {
         let thresholds = Arc::clone(&self.thresholds);
         let rugs_detected = Arc::clone(&self.rugs_detected);
         let alerts_sent = Arc::clone(&self.alerts_sent);
+        let fetches_avoided = Arc::clone(&self.fetches_avoided);
+        let prefilter_logs = self.config.rug_prefilter_logs;
+        let store_raw_logs = self.config.store_raw_logs;
+        let lp_ignore_programs = Arc::clone(&self.lp_ignore_programs);
+        let lp_logs_ignored = Arc::clone(&self.lp_logs_ignored);
+
+        // Analysis runs on a worker pool so slow RPC fetches don't hold up log reception
+        let detector = Arc::new(self.clone());
+        let queue = WorkQueue::spawn(
+            "rug_detector",
+            self.config.analysis_queue_size,
+            self.config.analysis_workers,
+            self.analysis_queue.clone(),
+            move |job: LogJob| {
+                let detector = Arc::clone(&detector);
+                async move { detector.process_log_job(job).await }
+            },
+        );
 
         // Log handler task
         tokio::spawn({
-            let is_running = Arc::clone(&is_running);
+            let run = run.clone();
             let watched_tokens = Arc::clone(&watched_tokens);
-            let solana = Arc::clone(&solana);
-            let alerts = Arc::clone(&alerts);
-            let database = Arc::clone(&database);
-            let thresholds = Arc::clone(&thresholds);
-            let rugs_detected = Arc::clone(&rugs_detected);
-            let alerts_sent = Arc::clone(&alerts_sent);
 
             async move {
-                while is_running.load(Ordering::SeqCst) {
+                while run.is_current() {
                     match log_receiver.recv().await {
                         Ok(log_event) => {
                             // Check for sell events
-                            let is_sell = log_event
-                                .logs
-                                .iter()
-                                .any(|log| log.contains("Program log: Instruction: Sell"));
-
-                            if is_sell {
-                                // Throttle processing
-                                tokio::time::sleep(Duration::from_millis(100)).await;
-                                
-                                if let Err(e) = Self::analyze_sell_transaction(
-                                    &solana,
-                                    &alerts,
-                                    &database,
-                                    &watched_tokens,
-                                    &thresholds,
-                                    &rugs_detected,
-                                    &alerts_sent,
-                                    &log_event.signature,
-                                )
-                                .await
-                                {
-                                    error!(target: "RUG_DETECTOR", "Error analyzing sell: {}", e);
-                                }
-                            }
+                            let is_sell =
+                                instruction_kinds(&log_event.logs).contains(&TxKind::Sell);
 
                             // Check for LP removal
-                            let is_lp_removal = log_event.logs.iter().any(|log| {
-                                log.contains("withdraw")
-                                    || log.contains("remove_liquidity")
-                                    || log.contains("migrate")
-                            });
-
-                            if is_lp_removal {
-                                if let Err(e) = Self::analyze_lp_removal(
-                                    &solana,
-                                    &alerts,
-                                    &database,
-                                    &watched_tokens,
-                                    &thresholds,
-                                    &rugs_detected,
-                                    &alerts_sent,
-                                    &log_event.signature,
-                                )
-                                .await
-                                {
-                                    error!(target: "RUG_DETECTOR", "Error analyzing LP removal: {}", e);
+                            let is_lp_removal = match Self::lp_removal_logs(
+                                &log_event.logs,
+                                &lp_ignore_programs.read(),
+                            ) {
+                                LpLogMatch::Removal => true,
+                                LpLogMatch::Ignored => {
+                                    lp_logs_ignored.fetch_add(1, Ordering::SeqCst);
+                                    false
                                 }
+                                LpLogMatch::None => false,
+                            };
+
+                            // Skip the RPC fetch entirely when the logs don't touch a watched token
+                            if (is_sell || is_lp_removal)
+                                && prefilter_logs
+                                && !Self::logs_mention_watched_token(&log_event.logs, &watched_tokens)
+                            {
+                                let skipped = is_sell as u64 + is_lp_removal as u64;
+                                fetches_avoided.fetch_add(skipped, Ordering::SeqCst);
+                                continue;
+                            }
+
+                            if is_sell || is_lp_removal {
+                                queue.push(LogJob {
+                                    signature: log_event.signature,
+                                    is_sell,
+                                    is_lp_removal,
+                                    logs: store_raw_logs.then_some(log_event.logs),
+                                });
                             }
                         }
                         Err(broadcast::error::RecvError::Lagged(n)) => {
@@ -286,31 +1069,63 @@ impl RugDetector {
 
         // Health check task
         tokio::spawn({
-            let is_running = Arc::clone(&is_running);
+            let run = run.clone();
             let watched_tokens = Arc::clone(&watched_tokens);
+            let token_locks = Arc::clone(&self.token_locks);
             let solana = Arc::clone(&solana);
             let alerts = Arc::clone(&alerts);
             let database = Arc::clone(&database);
             let thresholds = Arc::clone(&thresholds);
             let rugs_detected = Arc::clone(&rugs_detected);
             let alerts_sent = Arc::clone(&alerts_sent);
+            let auto_unwatched = Arc::clone(&self.auto_unwatched);
+            let safety_downgrades = Arc::clone(&self.safety_downgrades);
+            let rug_sender = self.rug_sender.clone();
+            let schedule = HealthSchedule::from_config(&self.config);
 
             async move {
-                let mut interval = interval(Duration::from_secs(30));
+                let mut interval = interval(Duration::from_secs(schedule.min_secs));
 
-                while is_running.load(Ordering::SeqCst) {
+                while run.is_current() {
                     interval.tick().await;
 
-                    for entry in watched_tokens.iter() {
-                        let mint = entry.key().clone();
-                        let mut token = entry.value().clone();
+                    // Snapshot the due keys so entries can be written back or removed below
+                    let due = Self::due_for_check(&watched_tokens, Utc::now().timestamp_millis());
 
-                        // Skip if recently checked
-                        if Utc::now().timestamp_millis() - token.last_check < 25000 {
+                    for mint in due {
+                        let _lock = token_locks.lock(&mint).await;
+                        let mut token = match watched_tokens.get(&mint) {
+                            Some(entry) => entry.value().clone(),
+                            None => continue,
+                        };
+
+                        if let Some(reason) = Self::auto_unwatch_reason(&thresholds, &token) {
+                            watched_tokens.remove(&mint);
+                            auto_unwatched.fetch_add(1, Ordering::SeqCst);
+                            info!(target: "RUG_DETECTOR", "Auto-unwatched {}: {}", token.symbol, reason);
                             continue;
                         }
 
                         token.last_check = Utc::now().timestamp_millis();
+                        let caution_score = thresholds.read().safety_caution_score;
+                        token.check_interval_secs =
+                            schedule.interval_secs(&token, caution_score, token.last_check);
+                        token.next_check_at =
+                            token.last_check + token.check_interval_secs as i64 * 1000;
+
+                        // Abandoned tokens stop costing RPC once the grace period is over
+                        let abandoned = Self::check_abandoned(
+                            &alerts,
+                            &database,
+                            &thresholds,
+                            &alerts_sent,
+                            &mut token,
+                        )
+                        .await;
+                        if abandoned {
+                            Self::write_back(&watched_tokens, token);
+                            continue;
+                        }
 
                         // Check liquidity health
                         if let Err(e) = Self::check_liquidity_health(
@@ -320,6 +1135,7 @@ impl RugDetector {
                             &thresholds,
                             &rugs_detected,
                             &alerts_sent,
+                            &rug_sender,
                             &mut token,
                         )
                         .await
@@ -327,50 +1143,284 @@ impl RugDetector {
                             error!(target: "RUG_DETECTOR", "Health check failed for {}: {}", token.symbol, e);
                         }
 
-                        watched_tokens.insert(mint, token);
+                        Self::check_safety(
+                            &alerts,
+                            &database,
+                            &thresholds,
+                            &alerts_sent,
+                            &safety_downgrades,
+                            &mut token,
+                        )
+                        .await;
+
+                        Self::write_back(&watched_tokens, token);
                     }
                 }
             }
         });
 
+        // Holder snapshot task
+        let snapshot_interval_secs = self.config.holder_snapshot_interval_secs;
+        if snapshot_interval_secs > 0 {
+            let run = run.clone();
+            let watched_tokens = Arc::clone(&watched_tokens);
+            let token_locks = Arc::clone(&self.token_locks);
+            let solana = Arc::clone(&solana);
+            let alerts = Arc::clone(&alerts);
+            let database = Arc::clone(&database);
+            let alerts_sent = Arc::clone(&alerts_sent);
+            let retention_hours = self.config.holder_snapshot_retention_hours;
+            let mut milestones = self.config.holder_milestones.clone();
+            milestones.sort_unstable();
+
+            tokio::spawn(async move {
+                let mut interval = interval(Duration::from_secs(snapshot_interval_secs));
+
+                while run.is_current() {
+                    interval.tick().await;
+
+                    let mints: Vec<String> = watched_tokens
+                        .iter()
+                        .filter(|e| !e.is_rugged && e.abandoned_at.is_none())
+                        .map(|e| e.key().clone())
+                        .collect();
+
+                    for mint in mints {
+                        let holder_count = match solana.get_holder_count(&mint).await {
+                            Ok(count) => count,
+                            Err(e) => {
+                                warn!(target: "RUG_DETECTOR", "Holder snapshot failed for {}: {}", mint, e);
+                                continue;
+                            }
+                        };
+                        database.write(PendingWrite::HolderSnapshot {
+                            mint: mint.clone(),
+                            holder_count: holder_count as i64,
+                        });
+
+                        let lock = token_locks.lock(&mint).await;
+                        let token = match watched_tokens.get_mut(&mint) {
+                            Some(mut entry) => {
+                                entry.holder_count = holder_count;
+                                let reached = milestones
+                                    .iter()
+                                    .rev()
+                                    .find(|m| holder_count >= **m && **m > entry.holder_milestone)
+                                    .copied();
+                                match reached {
+                                    Some(milestone) => {
+                                        entry.holder_milestone = milestone;
+                                        Some((entry.clone(), milestone))
+                                    }
+                                    None => None,
+                                }
+                            }
+                            None => None,
+                        };
+                        drop(lock);
+
+                        if let Some((token, milestone)) = token {
+                            info!(
+                                target: "RUG_DETECTOR",
+                                "{}: {} holders (passed {})",
+                                token.symbol, holder_count, milestone
+                            );
+                            if !token.muted {
+                                alerts_sent.fetch_add(1, Ordering::SeqCst);
+                                let _ = alerts
+                                    .alert_holder_growth(
+                                        &token.alert_info(),
+                                        holder_count,
+                                        milestone,
+                                    )
+                                    .await;
+                            }
+                        }
+                    }
+
+                    if let Ok(removed) = database.prune_holder_snapshots(retention_hours) {
+                        if removed > 0 {
+                            info!(target: "RUG_DETECTOR", "Pruned {} old holder snapshots", removed);
+                        }
+                    }
+                }
+            });
+        }
+
         info!(target: "RUG_DETECTOR", "Rug Pull Detector active - monitoring for suspicious activity");
         Ok(())
     }
 
+    /// Analyze one matched log on a worker
+    async fn process_log_job(&self, job: LogJob) {
+        if job.is_sell {
+            // Throttle processing
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            if let Err(e) = Self::analyze_sell_transaction(
+                &self.solana,
+                &self.alerts,
+                &self.database,
+                &self.watched_tokens,
+                &self.token_locks,
+                &self.thresholds,
+                &self.rugs_detected,
+                &self.alerts_sent,
+                &self.rug_sender,
+                &self.failed_txs_skipped,
+                &self.tx_loss,
+                &job.signature,
+                job.logs.as_deref(),
+            )
+            .await
+            {
+                error!(target: "RUG_DETECTOR", "Error analyzing sell: {}", e);
+            }
+        }
+
+        if job.is_lp_removal {
+            if let Err(e) = Self::analyze_lp_removal(
+                &self.solana,
+                &self.alerts,
+                &self.database,
+                &self.watched_tokens,
+                &self.token_locks,
+                &self.thresholds,
+                &self.rugs_detected,
+                &self.alerts_sent,
+                &self.rug_sender,
+                &self.failed_txs_skipped,
+                &self.tx_loss,
+                &job.signature,
+                job.logs.as_deref(),
+            )
+            .await
+            {
+                error!(target: "RUG_DETECTOR", "Error analyzing LP removal: {}", e);
+            }
+        }
+    }
+
     /// Stop the rug detector
     pub fn stop(&self) {
-        self.is_running.store(false, Ordering::SeqCst);
+        self.is_running.stop();
         info!(target: "RUG_DETECTOR", "Rug Pull Detector stopping...");
     }
 
-    async fn analyze_sell_transaction(
-        solana: &Arc<SolanaService>,
-        alerts: &Arc<AlertService>,
-        database: &Arc<DatabaseService>,
-        watched_tokens: &Arc<DashMap<String, WatchedToken>>,
-        thresholds: &Arc<RwLock<RugThresholds>>,
-        rugs_detected: &Arc<AtomicU64>,
-        alerts_sent: &Arc<AtomicU64>,
-        signature: &str,
-    ) -> Result<()> {
-        tokio::time::sleep(Duration::from_millis(300)).await;
+    /// Classify withdraw/remove_liquidity/migrate logs, skipping lines emitted by an
+    /// allowlisted program (tracked via the invoke stack) or containing an allowlisted pattern
+    fn lp_removal_logs(logs: &[String], ignore: &[String]) -> LpLogMatch {
+        let mut ignored = false;
 
-        let tx = match solana.get_transaction(signature).await? {
-            Some(tx) => tx,
-            None => return Ok(()),
-        };
+        for log in classify_logs(logs) {
+            if log.kind != TxKind::RemoveLiquidity {
+                continue;
+            }
 
-        let sell_info = match Self::parse_sell_transaction(&tx) {
+            let benign = ignore.iter().any(|entry| {
+                log.program == Some(entry.as_str()) || log.line.contains(entry.as_str())
+            });
+            if !benign {
+                return LpLogMatch::Removal;
+            }
+            ignored = true;
+        }
+
+        if ignored {
+            LpLogMatch::Ignored
+        } else {
+            LpLogMatch::None
+        }
+    }
+
+    /// Cheap check whether a log set references any watched mint, either as plain
+    /// text or inside a base64 `Program data:` event payload
+    fn logs_mention_watched_token(
+        logs: &[String],
+        watched_tokens: &DashMap<String, WatchedToken>,
+    ) -> bool {
+        use base64::Engine;
+
+        if watched_tokens.is_empty() {
+            return false;
+        }
+
+        for log in logs {
+            if let Some(payload) = log.strip_prefix("Program data: ") {
+                let bytes = match base64::engine::general_purpose::STANDARD.decode(payload.trim()) {
+                    Ok(bytes) => bytes,
+                    Err(_) => continue,
+                };
+                for window in bytes.windows(32) {
+                    if let Ok(key) = <[u8; 32]>::try_from(window) {
+                        if watched_tokens.contains_key(&Pubkey::new_from_array(key).to_string()) {
+                            return true;
+                        }
+                    }
+                }
+            } else if log
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .any(|word| word.len() >= 32 && watched_tokens.contains_key(word))
+            {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn analyze_sell_transaction(
+        solana: &Arc<SolanaService>,
+        alerts: &Arc<AlertService>,
+        database: &Arc<DatabaseService>,
+        watched_tokens: &Arc<DashMap<String, WatchedToken>>,
+        token_locks: &KeyedLocks,
+        thresholds: &Arc<RwLock<RugThresholds>>,
+        rugs_detected: &Arc<AtomicU64>,
+        alerts_sent: &Arc<AtomicU64>,
+        rug_sender: &broadcast::Sender<ConfirmedRug>,
+        failed_txs_skipped: &Arc<AtomicU64>,
+        tx_loss: &TxLossStats,
+        signature: &str,
+        raw_logs: Option<&[String]>,
+    ) -> Result<()> {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        let tx = match solana.get_transaction(signature).await? {
+            Some(tx) => tx,
+            None => {
+                tx_loss.record_fetch_none();
+                return Ok(());
+            }
+        };
+
+        // A failed sell never left the seller's wallet, so it must not move the score
+        if SolanaService::transaction_failed(&tx) {
+            failed_txs_skipped.fetch_add(1, Ordering::SeqCst);
+            return Ok(());
+        }
+
+        let sell_info = match Self::parse_sell_transaction(&tx) {
             Some(info) => info,
-            None => return Ok(()),
+            None => {
+                tx_loss.record_parse_none();
+                return Ok(());
+            }
         };
 
         // Check if this token is being watched
+        let _lock = token_locks.lock(&sell_info.mint).await;
         let mut token = match watched_tokens.get(&sell_info.mint) {
             Some(entry) => entry.value().clone(),
             None => return Ok(()),
         };
 
+        token.last_transaction_at = Utc::now().timestamp_millis();
+        if token.abandoned_at.take().is_some() {
+            info!(target: "RUG_DETECTOR", "{}: trading resumed, no longer abandoned", token.symbol);
+        }
+
         // Record the sell
         token.sell_history.push_back(SellInfo {
             signature: signature.to_string(),
@@ -386,7 +1436,7 @@ impl RugDetector {
         }
 
         // Save transaction
-        let _ = database.save_transaction(&TransactionRecord {
+        database.write(PendingWrite::Transaction(TransactionRecord {
             signature: signature.to_string(),
             mint: sell_info.mint.clone(),
             wallet: sell_info.wallet.clone(),
@@ -394,21 +1444,46 @@ impl RugDetector {
             amount_sol: sell_info.amount_sol,
             amount_tokens: sell_info.amount_tokens,
             timestamp: Utc::now().to_rfc3339(),
-        });
+        }));
+
+        // Dev sells are sized against the real supply; fall back to the pump.fun default
+        if sell_info.wallet == token.dev_wallet && token.total_supply.is_none() {
+            match solana.get_token_supply(&token.mint).await {
+                Ok(supply) if supply > 0.0 => token.total_supply = Some(supply),
+                Ok(_) => {}
+                Err(e) => warn!(target: "RUG_DETECTOR", "Supply lookup failed for {}: {}", token.symbol, e),
+            }
+        }
 
         // Check for suspicious patterns
+        let alerts_before = token.alerts.len();
+        let was_rugged = token.is_rugged;
         Self::check_suspicious_patterns(
             alerts,
             database,
             thresholds,
             rugs_detected,
             alerts_sent,
+            rug_sender,
             &mut token,
             &sell_info,
         )
         .await?;
 
-        watched_tokens.insert(sell_info.mint.clone(), token);
+        if let Some(logs) = raw_logs {
+            let new_alerts: Vec<&str> = token.alerts[alerts_before..]
+                .iter()
+                .map(|alert| alert.alert_type.as_str())
+                .collect();
+            if !new_alerts.is_empty() {
+                Self::save_raw_logs(database, signature, &token.mint, &new_alerts.join(","), logs);
+            } else if token.is_rugged && !was_rugged {
+                let reason = token.rug_reason.clone().unwrap_or_default();
+                Self::save_raw_logs(database, signature, &token.mint, &reason, logs);
+            }
+        }
+
+        Self::write_back(watched_tokens, token);
 
         Ok(())
     }
@@ -416,65 +1491,124 @@ impl RugDetector {
     fn parse_sell_transaction(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Option<ParsedSellInfo> {
         let meta = tx.transaction.meta.as_ref()?;
 
-        // Get wallet from first signer
-        let wallet = match &tx.transaction.transaction {
-            EncodedTransaction::Json(ui_tx) => match &ui_tx.message {
-                UiMessage::Parsed(msg) => msg.account_keys.first().map(|k| k.pubkey.clone()),
-                UiMessage::Raw(msg) => msg.account_keys.first().cloned(),
-            },
-            _ => None,
-        }?;
-
-        // Get mint from token balances
-        let mint = match &meta.pre_token_balances {
-            OptionSerializer::Some(balances) => balances.first().map(|b| b.mint.clone()),
-            _ => None,
-        }?;
+        let account_keys = SolanaService::account_keys(tx);
+
+        // The seller is whoever's token balance went down; the fee payer may just be a relayer
+        let (mint, wallet, amount_tokens) = match Self::find_token_seller(meta) {
+            Some(seller) => seller,
+            None => {
+                let mint = match &meta.pre_token_balances {
+                    OptionSerializer::Some(balances) => balances.first().map(|b| b.mint.clone()),
+                    _ => None,
+                }?;
+                (mint, account_keys.first()?.clone(), 0.0)
+            }
+        };
 
-        // Calculate SOL amount
+        // Calculate SOL amount from the seller's account, falling back to the fee payer
         let amount_sol = {
+            let index = account_keys.iter().position(|k| *k == wallet).unwrap_or(0);
             let pre = &meta.pre_balances;
             let post = &meta.post_balances;
-            if !pre.is_empty() && !post.is_empty() {
-                let change = post[0] as i64 - pre[0] as i64;
+            if index < pre.len() && index < post.len() {
+                let change = post[index] as i64 - pre[index] as i64;
                 (change.abs() as f64) / 1_000_000_000.0
             } else {
                 0.0
             }
         };
 
-        // Calculate token amount (simplified)
-        let amount_tokens = 0.0;
-
         Some(ParsedSellInfo {
             mint,
             wallet,
             amount_sol,
             amount_tokens,
+            timestamp: tx
+                .block_time
+                .map(|t| t * 1000)
+                .unwrap_or_else(|| Utc::now().timestamp_millis()),
         })
     }
 
+    /// Find the owner whose token balance decreased the most, as (mint, owner, amount)
+    fn find_token_seller(meta: &UiTransactionStatusMeta) -> Option<(String, String, f64)> {
+        let pre_balances = match &meta.pre_token_balances {
+            OptionSerializer::Some(balances) => balances,
+            _ => return None,
+        };
+        let post_balances = match &meta.post_token_balances {
+            OptionSerializer::Some(balances) => balances.as_slice(),
+            _ => &[],
+        };
+
+        pre_balances
+            .iter()
+            .filter_map(|pre| {
+                let owner = match &pre.owner {
+                    OptionSerializer::Some(owner) => owner.clone(),
+                    _ => return None,
+                };
+                let before = pre.ui_token_amount.ui_amount.unwrap_or(0.0);
+                // A closed token account has no post balance
+                let after = post_balances
+                    .iter()
+                    .find(|post| post.account_index == pre.account_index)
+                    .and_then(|post| post.ui_token_amount.ui_amount)
+                    .unwrap_or(0.0);
+                let decrease = before - after;
+                (decrease > 0.0).then(|| (pre.mint.clone(), owner, decrease))
+            })
+            .max_by(|a, b| a.2.total_cmp(&b.2))
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn check_suspicious_patterns(
         alerts: &Arc<AlertService>,
         database: &Arc<DatabaseService>,
         thresholds: &Arc<RwLock<RugThresholds>>,
         rugs_detected: &Arc<AtomicU64>,
         alerts_sent: &Arc<AtomicU64>,
+        rug_sender: &broadcast::Sender<ConfirmedRug>,
         token: &mut WatchedToken,
         sell_info: &ParsedSellInfo,
     ) -> Result<()> {
+        // Trusted creators are only alerted on explicit rug signals (LP pulls, liquidity drops)
+        if token.trusted {
+            return Ok(());
+        }
         let thresholds = thresholds.read().clone();
         let mut rug_alerts = Vec::new();
 
         // 1. Dev wallet selling
         if sell_info.wallet == token.dev_wallet {
-            let sell_percent = (sell_info.amount_tokens / 1_000_000_000.0) * 100.0;
+            // Dumping right after launch is a stronger signal than the sell size
+            if token.dev_first_sell_at.is_none() {
+                token.dev_first_sell_at = Some(sell_info.timestamp);
+                let held_ms = token.launched_at.map(|launched| (sell_info.timestamp - launched).max(0));
+                if let Some(held_ms) = held_ms {
+                    if thresholds.min_dev_hold_secs > 0
+                        && held_ms < (thresholds.min_dev_hold_secs * 1000) as i64
+                    {
+                        rug_alerts.push(RugAlert {
+                            alert_type: "instant_dev_sell".to_string(),
+                            message: format!("Developer first sold {}s after launch", held_ms / 1000),
+                            severity: "critical".to_string(),
+                            muted: false,
+                        });
+                        token.suspicion_score += 40;
+                    }
+                }
+            }
+
+            let supply = token.total_supply.unwrap_or(PUMP_TOKEN_SUPPLY);
+            let sell_percent = (sell_info.amount_tokens / supply) * 100.0;
 
             if sell_percent >= thresholds.max_dev_sell_percent {
                 rug_alerts.push(RugAlert {
                     alert_type: "dev_dump".to_string(),
                     message: format!("Developer sold {:.2}% of supply", sell_percent),
                     severity: "critical".to_string(),
+                    muted: false,
                 });
                 token.suspicion_score += 50;
             } else if thresholds.dev_wallet_sell_alert {
@@ -482,39 +1616,57 @@ impl RugDetector {
                     alert_type: "dev_sell".to_string(),
                     message: format!("Developer sold {:.4} SOL worth", sell_info.amount_sol),
                     severity: "medium".to_string(),
+                    muted: false,
                 });
                 token.suspicion_score += 20;
             }
         }
 
-        // 2. Rapid selling pattern
-        let now = Utc::now().timestamp_millis();
-        let recent_sells: Vec<_> = token
-            .sell_history
-            .iter()
-            .filter(|s| now - s.timestamp < thresholds.min_time_between_sells)
-            .collect();
+        // 1b. A launch bundle being dumped is a rug whatever the score says
+        let bundle_rug = Self::bundle_dump(&thresholds, token, sell_info);
+        if let Some(reason) = &bundle_rug {
+            token.alerts.push(RugAlert {
+                alert_type: "bundle_rug".to_string(),
+                message: reason.clone(),
+                severity: "critical".to_string(),
+                muted: token.muted,
+            });
+            token.suspicion_score += 50;
+        }
 
-        if recent_sells.len() >= 3 {
-            let total_sold_sol: f64 = recent_sells.iter().map(|s| s.amount_sol).sum();
-            if total_sold_sol > token.initial_liquidity * 0.3 {
-                rug_alerts.push(RugAlert {
-                    alert_type: "rapid_selling".to_string(),
-                    message: format!(
-                        "Rapid selling detected: {:.2} SOL in {} txs",
-                        total_sold_sol,
-                        recent_sells.len()
-                    ),
-                    severity: "high".to_string(),
-                });
-                token.suspicion_score += 30;
-            }
+        // 2. Sell pressure; windows run shortest first so flash dumps report their own timeframe
+        // and slower bleed-outs are still caught by the longer ones
+        let now = Utc::now().timestamp_millis();
+        // Launch liquidity says nothing about a pool's depth after migration
+        let baseline = if token.graduated {
+            token.current_liquidity
+        } else {
+            token.initial_liquidity
+        };
+        let tripped = thresholds.sell_windows.iter().find_map(|window| {
+            window
+                .tripped(&token.sell_history, baseline, now)
+                .map(|(count, volume)| (window, count, volume))
+        });
+        if let Some((window, count, volume)) = tripped {
+            rug_alerts.push(RugAlert {
+                alert_type: "rapid_selling".to_string(),
+                message: format!(
+                    "Sell pressure over {}: {:.2} SOL in {} txs",
+                    window.label(),
+                    volume,
+                    count
+                ),
+                severity: "high".to_string(),
+                muted: false,
+            });
+            token.suspicion_score += 30;
         }
 
         // 3. Large single sell
+        let suspicious_sell_percent = thresholds.profile(token.graduated).suspicious_sell_percent;
         if token.current_liquidity > 0.0
-            && sell_info.amount_sol
-                > token.current_liquidity * (thresholds.suspicious_sell_percent / 100.0)
+            && sell_info.amount_sol > token.current_liquidity * (suspicious_sell_percent / 100.0)
         {
             let percent = (sell_info.amount_sol / token.current_liquidity) * 100.0;
             rug_alerts.push(RugAlert {
@@ -524,35 +1676,43 @@ impl RugDetector {
                     sell_info.amount_sol, percent
                 ),
                 severity: "medium".to_string(),
+                muted: false,
             });
             token.suspicion_score += 15;
         }
 
         // 4. Check if this triggers rug threshold
-        if token.suspicion_score >= 80 {
+        let rug_reason = match &bundle_rug {
+            Some(reason) => Some(reason.as_str()),
+            None => (token.suspicion_score >= thresholds.rug_score_threshold)
+                .then_some("High suspicion score reached"),
+        };
+        if let Some(reason) = rug_reason {
             Self::trigger_rug_alert(
                 alerts,
                 database,
                 rugs_detected,
                 alerts_sent,
+                rug_sender,
                 token,
-                "High suspicion score reached",
+                reason,
             )
             .await?;
         }
 
         // Send alerts
         for alert in &rug_alerts {
-            token.alerts.push(alert.clone());
+            let mut alert = alert.clone();
+            alert.muted = token.muted;
+            Self::push_alert(database, token, alert.clone());
+
+            // Muted tokens keep their alert history but nothing goes out
+            if token.muted {
+                continue;
+            }
             alerts_sent.fetch_add(1, Ordering::SeqCst);
 
-            let token_info = TokenAlertInfo {
-                mint: token.mint.clone(),
-                name: token.name.clone(),
-                symbol: token.symbol.clone(),
-                creator: token.creator.clone(),
-                initial_liquidity: Some(token.initial_liquidity),
-            };
+            let token_info = token.alert_info();
 
             if alert.severity == "critical" {
                 error!(target: "RUG_ALERT", "🚨 {}: {}", token.symbol, alert.message);
@@ -568,11 +1728,74 @@ impl RugDetector {
         Ok(())
     }
 
+    /// Rug reason when `sell` dumps the token's launch bundle: the dev or a bundle wallet
+    /// selling at least `bundle_dump_min_sol` within `bundle_dump_window_secs` of the
+    /// bundled buys. A bundle escalates once.
+    fn bundle_dump(
+        thresholds: &RugThresholds,
+        token: &mut WatchedToken,
+        sell: &ParsedSellInfo,
+    ) -> Option<String> {
+        if thresholds.bundle_dump_window_secs == 0
+            || token.is_rugged
+            || sell.amount_sol < thresholds.bundle_dump_min_sol
+        {
+            return None;
+        }
+        let bundle = token.launch_bundle.as_mut().filter(|bundle| !bundle.escalated)?;
+        let elapsed_ms = (sell.timestamp - bundle.bought_at).max(0);
+        if elapsed_ms > (thresholds.bundle_dump_window_secs * 1000) as i64 {
+            return None;
+        }
+        let seller = if sell.wallet == token.dev_wallet {
+            "the developer"
+        } else if bundle.wallets.contains(&sell.wallet) {
+            "a bundle wallet"
+        } else {
+            return None;
+        };
+
+        bundle.escalated = true;
+        Some(format!(
+            "Bundle rug: {} wallets bought {:.2} SOL at launch, {} sold {:.2} SOL {}s later",
+            bundle.wallets.len(),
+            bundle.total_sol,
+            seller,
+            sell.amount_sol,
+            elapsed_ms / 1000
+        ))
+    }
+
+    /// Add an alert to the token's history. A muted token's alert never reaches the alert
+    /// service, so it is written to the alerts table here instead.
+    fn push_alert(database: &DatabaseService, token: &mut WatchedToken, alert: RugAlert) {
+        if alert.muted {
+            Self::record_muted_alert(database, token, &alert);
+        }
+        token.alerts.push(alert);
+    }
+
+    fn record_muted_alert(database: &DatabaseService, token: &WatchedToken, alert: &RugAlert) {
+        database.write(PendingWrite::Alert {
+            alert_type: alert.alert_type.clone(),
+            title: token.symbol.clone(),
+            message: alert.message.clone(),
+            data: serde_json::json!({
+                "mint": token.mint,
+                "symbol": token.symbol,
+                "severity": alert.severity,
+            })
+            .to_string(),
+            muted: true,
+        });
+    }
+
     async fn trigger_rug_alert(
         alerts: &Arc<AlertService>,
         database: &Arc<DatabaseService>,
         rugs_detected: &Arc<AtomicU64>,
         alerts_sent: &Arc<AtomicU64>,
+        rug_sender: &broadcast::Sender<ConfirmedRug>,
         token: &mut WatchedToken,
         reason: &str,
     ) -> Result<()> {
@@ -580,24 +1803,48 @@ impl RugDetector {
 
         error!(target: "RUG_ALERT", "🚨 RUG DETECTED: {} - {}", token.symbol, reason);
 
+        // Automations get the rug first; the webhook send runs in the background
+        if !token.muted {
+            alerts.send_rug_webhook(&RugWebhookPayload {
+                mint: token.mint.clone(),
+                reason: reason.to_string(),
+                severity: "critical".to_string(),
+                timestamp: Utc::now().timestamp_millis(),
+                liquidity: token.current_liquidity,
+            });
+        }
+
         // Mark as rugged in database
-        let _ = database.mark_as_rugged(&token.mint, reason);
+        database.write(PendingWrite::Rugged {
+            mint: token.mint.clone(),
+            reason: reason.to_string(),
+        });
+
+        // No subscribers is fine: nothing is linked to rugs unless configured
+        let _ = rug_sender.send(ConfirmedRug {
+            mint: token.mint.clone(),
+            symbol: token.symbol.clone(),
+            creator: token.creator.clone(),
+            reason: reason.to_string(),
+            trusted: token.trusted,
+        });
 
         // Send critical alert
-        alerts_sent.fetch_add(1, Ordering::SeqCst);
-        let _ = alerts
-            .alert_rug_pull(
-                &TokenAlertInfo {
-                    mint: token.mint.clone(),
-                    name: token.name.clone(),
-                    symbol: token.symbol.clone(),
-                    creator: token.creator.clone(),
-                    initial_liquidity: Some(token.initial_liquidity),
+        if token.muted {
+            Self::record_muted_alert(
+                database,
+                token,
+                &RugAlert {
+                    alert_type: "rug".to_string(),
+                    message: reason.to_string(),
+                    severity: "critical".to_string(),
+                    muted: true,
                 },
-                reason,
-                "critical",
-            )
-            .await;
+            );
+        } else {
+            alerts_sent.fetch_add(1, Ordering::SeqCst);
+            let _ = alerts.alert_rug_pull(&token.alert_info(), reason, "critical").await;
+        }
 
         // Update token status
         token.is_rugged = true;
@@ -606,41 +1853,89 @@ impl RugDetector {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn analyze_lp_removal(
         solana: &Arc<SolanaService>,
         alerts: &Arc<AlertService>,
         database: &Arc<DatabaseService>,
         watched_tokens: &Arc<DashMap<String, WatchedToken>>,
+        token_locks: &KeyedLocks,
         thresholds: &Arc<RwLock<RugThresholds>>,
         rugs_detected: &Arc<AtomicU64>,
         alerts_sent: &Arc<AtomicU64>,
+        rug_sender: &broadcast::Sender<ConfirmedRug>,
+        failed_txs_skipped: &Arc<AtomicU64>,
+        tx_loss: &TxLossStats,
         signature: &str,
+        raw_logs: Option<&[String]>,
     ) -> Result<()> {
         let tx = match solana.get_transaction(signature).await? {
             Some(tx) => tx,
-            None => return Ok(()),
+            None => {
+                tx_loss.record_fetch_none();
+                return Ok(());
+            }
         };
 
+        if SolanaService::transaction_failed(&tx) {
+            failed_txs_skipped.fetch_add(1, Ordering::SeqCst);
+            return Ok(());
+        }
+
         // Check if this affects any watched tokens
         if let Some(meta) = &tx.transaction.meta {
             if let OptionSerializer::Some(pre_balances) = &meta.pre_token_balances {
                 for balance in pre_balances {
-                    if let Some(entry) = watched_tokens.get(&balance.mint) {
+                    if !watched_tokens.contains_key(&balance.mint) {
+                        continue;
+                    }
+                    let _lock = token_locks.lock(&balance.mint).await;
+                    if let Some(mut entry) = watched_tokens.get_mut(&balance.mint) {
+                        // Any matched transaction counts as activity, even one that alerts nothing
+                        entry.last_transaction_at = Utc::now().timestamp_millis();
                         let mut token = entry.value().clone();
+                        drop(entry);
                         let thresholds = thresholds.read().clone();
+                        let profile = thresholds.profile(token.graduated);
 
-                        // Calculate liquidity change
+                        // Calculate liquidity change; after migration the SOL leaves the pool's wSOL vault
                         let pre = &meta.pre_balances;
                         let post = &meta.post_balances;
-                        if !pre.is_empty() && !post.is_empty() {
-                            let lp_change =
-                                (pre[0] as i64 - post[0] as i64) as f64 / 1_000_000_000.0;
+                        if token.graduated || (!pre.is_empty() && !post.is_empty()) {
+                            let lp_change = if token.graduated {
+                                Self::wsol_outflow(meta)
+                            } else {
+                                (pre[0] as i64 - post[0] as i64) as f64 / 1_000_000_000.0
+                            };
 
                             if token.current_liquidity > 0.0
                                 && lp_change
-                                    > token.current_liquidity
-                                        * (thresholds.lp_removal_percent / 100.0)
+                                    > token.current_liquidity * (profile.lp_removal_percent / 100.0)
                             {
+                                // The migration itself drains the curve; that's a graduation, not a rug
+                                if !token.graduated
+                                    && solana.is_curve_complete(&token.mint).await.unwrap_or(false)
+                                {
+                                    let migrated_sol = token.current_liquidity;
+                                    Self::mark_graduated(database, &mut token, migrated_sol);
+                                    Self::write_back(watched_tokens, token);
+                                    continue;
+                                }
+
+                                // The curve re-read only means something before graduation
+                                if !token.graduated
+                                    && thresholds.confirm_recheck
+                                    && !Self::confirm_liquidity_drop(
+                                        solana,
+                                        &token,
+                                        token.current_liquidity,
+                                        &thresholds,
+                                    )
+                                    .await?
+                                {
+                                    continue;
+                                }
+
                                 let reason = format!(
                                     "LP removed: {:.2} SOL ({:.1}%)",
                                     lp_change,
@@ -651,12 +1946,16 @@ impl RugDetector {
                                     database,
                                     rugs_detected,
                                     alerts_sent,
+                                    rug_sender,
                                     &mut token,
                                     &reason,
                                 )
                                 .await?;
 
-                                watched_tokens.insert(balance.mint.clone(), token);
+                                if let Some(logs) = raw_logs {
+                                    Self::save_raw_logs(database, signature, &token.mint, &reason, logs);
+                                }
+                                Self::write_back(watched_tokens, token);
                             }
                         }
                     }
@@ -667,6 +1966,287 @@ impl RugDetector {
         Ok(())
     }
 
+    /// Store an analyzed copy of a token back, unless it was unwatched or pruned meanwhile.
+    /// Callers hold the token's lock from the copy to here.
+    fn write_back(watched_tokens: &DashMap<String, WatchedToken>, token: WatchedToken) {
+        if let Some(mut entry) = watched_tokens.get_mut(&token.mint) {
+            *entry = token;
+        }
+    }
+
+    /// Keep the program logs of a transaction that raised an alert, for post-mortems
+    fn save_raw_logs(
+        database: &DatabaseService,
+        signature: &str,
+        mint: &str,
+        reason: &str,
+        logs: &[String],
+    ) {
+        database.write(PendingWrite::TxLogs(TxLogRecord {
+            signature: signature.to_string(),
+            mint: mint.to_string(),
+            reason: reason.to_string(),
+            logs: logs.to_vec(),
+            created_at: Utc::now().to_rfc3339(),
+        }));
+    }
+
+    /// Why a token should leave the watch list, if it should: it graduated or went quiet
+    /// longer than `auto_unwatch_after_secs` ago. Rugged tokens stay for the record and
+    /// suspicious ones get `auto_unwatch_suspicious_after_secs` instead.
+    fn auto_unwatch_reason(
+        thresholds: &Arc<RwLock<RugThresholds>>,
+        token: &WatchedToken,
+    ) -> Option<&'static str> {
+        let after_secs = {
+            let thresholds = thresholds.read();
+            if thresholds.auto_unwatch_after_secs == 0
+                || (token.is_rugged && !thresholds.auto_unwatch_flagged)
+            {
+                return None;
+            }
+            if token.suspicion_score > 0 && !thresholds.auto_unwatch_flagged {
+                thresholds.auto_unwatch_suspicious_after_secs
+            } else {
+                thresholds.auto_unwatch_after_secs
+            }
+        };
+        if after_secs == 0 {
+            return None;
+        }
+
+        let cutoff = Utc::now().timestamp_millis() - (after_secs * 1000) as i64;
+        if token.graduated_at.is_some_and(|at| at <= cutoff) {
+            Some("graduated")
+        } else if token.last_transaction_at <= cutoff {
+            Some("inactive")
+        } else {
+            None
+        }
+    }
+
+    /// Risk score behind the safety classification: the suspicion score plus half the
+    /// liquidity lost since launch while the token is still on its bonding curve
+    fn risk_score(token: &WatchedToken) -> i32 {
+        let drawdown = if !token.graduated && token.initial_liquidity > 0.0 {
+            ((token.initial_liquidity - token.current_liquidity) / token.initial_liquidity * 100.0)
+                .max(0.0)
+        } else {
+            0.0
+        };
+        token.suspicion_score + (drawdown / 2.0) as i32
+    }
+
+    /// Classify a token. Moving back to a safer class needs the risk score to clear the
+    /// boundary by `safety_hysteresis` points, so borderline tokens don't flap.
+    fn classify_safety(thresholds: &RugThresholds, token: &WatchedToken) -> SafetyClass {
+        if token.is_rugged {
+            return SafetyClass::Danger;
+        }
+
+        let class_for = |risk: i32| {
+            if risk >= thresholds.rug_score_threshold {
+                SafetyClass::Danger
+            } else if risk >= thresholds.safety_caution_score {
+                SafetyClass::Caution
+            } else {
+                SafetyClass::Safe
+            }
+        };
+        let risk = Self::risk_score(token);
+        let class = class_for(risk);
+        match token.safety {
+            Some(current) if class < current => {
+                class_for(risk + thresholds.safety_hysteresis).min(current)
+            }
+            _ => class,
+        }
+    }
+
+    /// Re-classify a token once per `safety_recheck_secs` and send a `safety_downgrade`
+    /// alert when a token previously classified safe gets worse
+    async fn check_safety(
+        alerts: &Arc<AlertService>,
+        database: &Arc<DatabaseService>,
+        thresholds: &Arc<RwLock<RugThresholds>>,
+        alerts_sent: &Arc<AtomicU64>,
+        safety_downgrades: &Arc<AtomicU64>,
+        token: &mut WatchedToken,
+    ) {
+        let thresholds = thresholds.read().clone();
+        let now = Utc::now().timestamp_millis();
+        if thresholds.safety_recheck_secs == 0
+            || now - token.safety_checked_at < (thresholds.safety_recheck_secs * 1000) as i64
+        {
+            return;
+        }
+        token.safety_checked_at = now;
+
+        let class = Self::classify_safety(&thresholds, token);
+        let Some(current) = token.safety else {
+            token.safety = Some(class);
+            return;
+        };
+        if class == current {
+            return;
+        }
+
+        // Safe -> caution -> danger still counts as a downgrade of a safe token
+        let was_safe =
+            current == SafetyClass::Safe || token.previous_safety == Some(SafetyClass::Safe);
+        token.previous_safety = Some(current);
+        token.safety = Some(class);
+        info!(
+            target: "RUG_DETECTOR",
+            "{}: safety {} -> {}",
+            token.symbol,
+            current.as_str(),
+            class.as_str()
+        );
+
+        // A rug has already been alerted on its own
+        if class < current || !was_safe || token.is_rugged {
+            return;
+        }
+
+        let risk_score = Self::risk_score(token);
+        safety_downgrades.fetch_add(1, Ordering::SeqCst);
+        let alert = RugAlert {
+            alert_type: "safety_downgrade".to_string(),
+            message: format!(
+                "Safety dropped from {} to {} (risk score {})",
+                current.as_str(),
+                class.as_str(),
+                risk_score
+            ),
+            severity: if class == SafetyClass::Danger { "critical" } else { "high" }.to_string(),
+            muted: token.muted,
+        };
+        Self::push_alert(database, token, alert);
+
+        if !token.muted {
+            alerts_sent.fetch_add(1, Ordering::SeqCst);
+            let _ = alerts
+                .alert_safety_downgrade(
+                    &token.alert_info(),
+                    current.as_str(),
+                    class.as_str(),
+                    risk_score,
+                )
+                .await;
+        }
+    }
+
+    /// Flag tokens that never traded after launch, distinct from an LP pull. Returns
+    /// true once the token has been abandoned past the grace period.
+    async fn check_abandoned(
+        alerts: &Arc<AlertService>,
+        database: &Arc<DatabaseService>,
+        thresholds: &Arc<RwLock<RugThresholds>>,
+        alerts_sent: &Arc<AtomicU64>,
+        token: &mut WatchedToken,
+    ) -> bool {
+        let (abandon_after_secs, abandon_grace_secs) = {
+            let thresholds = thresholds.read();
+            (thresholds.abandon_after_secs, thresholds.abandon_grace_secs)
+        };
+        if abandon_after_secs == 0 || token.is_rugged {
+            return false;
+        }
+
+        let now = Utc::now().timestamp_millis();
+        if let Some(abandoned_at) = token.abandoned_at {
+            return now - abandoned_at >= (abandon_grace_secs * 1000) as i64;
+        }
+
+        let idle_secs = ((now - token.last_transaction_at) / 1000).max(0) as u64;
+        if idle_secs < abandon_after_secs {
+            return false;
+        }
+
+        token.abandoned_at = Some(now);
+        let message = format!("No trades for {} min after launch", idle_secs / 60);
+        info!(target: "RUG_DETECTOR", "{}: abandoned - {}", token.symbol, message);
+        let alert = RugAlert {
+            alert_type: "abandoned".to_string(),
+            message,
+            severity: "info".to_string(),
+            muted: token.muted,
+        };
+        Self::push_alert(database, token, alert);
+
+        if !token.muted {
+            alerts_sent.fetch_add(1, Ordering::SeqCst);
+            let _ = alerts.alert_abandoned(&token.alert_info(), idle_secs).await;
+        }
+
+        false
+    }
+
+    /// The token's bonding curve balance. With `verify`, `None` when the derived account is
+    /// missing or not owned by the pump program, since its balance says nothing about liquidity.
+    async fn curve_balance(
+        solana: &Arc<SolanaService>,
+        token: &WatchedToken,
+        verify: bool,
+    ) -> Result<Option<f64>> {
+        let mint_pubkey = Pubkey::from_str(&token.mint)?;
+        if !verify {
+            let bonding_curve = solana.derive_bonding_curve(&mint_pubkey);
+            return Ok(Some(solana.get_balance(&bonding_curve.to_string()).await?));
+        }
+
+        match solana.get_curve_balance(&mint_pubkey).await? {
+            CurveBalance::Verified(balance) => Ok(Some(balance)),
+            CurveBalance::Missing => {
+                warn!(
+                    target: "RUG_DETECTOR",
+                    "{}: no bonding curve account at the derived address, liquidity check skipped",
+                    token.symbol
+                );
+                Ok(None)
+            }
+            CurveBalance::WrongOwner(owner) => {
+                warn!(
+                    target: "RUG_DETECTOR",
+                    "{}: bonding curve owned by {}, not the pump program; liquidity check skipped",
+                    token.symbol,
+                    owner
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// Re-read the bonding curve after the confirmation delay and report whether
+    /// liquidity is still at least `lp_removal_percent` below `baseline`
+    async fn confirm_liquidity_drop(
+        solana: &Arc<SolanaService>,
+        token: &WatchedToken,
+        baseline: f64,
+        thresholds: &RugThresholds,
+    ) -> Result<bool> {
+        tokio::time::sleep(Duration::from_millis(thresholds.confirm_delay_ms)).await;
+
+        let Some(balance) = Self::curve_balance(solana, token, thresholds.verify_curve_owner).await?
+        else {
+            return Ok(false);
+        };
+
+        let drop_percent = ((baseline - balance) / baseline) * 100.0;
+        let confirmed = drop_percent >= thresholds.lp_removal_percent;
+        if !confirmed {
+            info!(
+                target: "RUG_DETECTOR",
+                "{}: liquidity drop not confirmed on re-check ({:.1}% now), skipping alert",
+                token.symbol, drop_percent
+            );
+        }
+
+        Ok(confirmed)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn check_liquidity_health(
         solana: &Arc<SolanaService>,
         alerts: &Arc<AlertService>,
@@ -674,15 +2254,33 @@ impl RugDetector {
         thresholds: &Arc<RwLock<RugThresholds>>,
         rugs_detected: &Arc<AtomicU64>,
         alerts_sent: &Arc<AtomicU64>,
+        rug_sender: &broadcast::Sender<ConfirmedRug>,
         token: &mut WatchedToken,
     ) -> Result<()> {
-        // Get bonding curve balance
-        let mint_pubkey = Pubkey::from_str(&token.mint)?;
-        let bonding_curve = solana.derive_bonding_curve(&mint_pubkey);
-        let balance = solana.get_balance(&bonding_curve.to_string()).await?;
+        // A graduated token's curve is empty; its pool is checked through LP-removal analysis
+        if token.graduated {
+            return Ok(());
+        }
+
+        // Get bonding curve balance; an unverifiable read is skipped rather than taken as a drain
+        let verify = thresholds.read().verify_curve_owner;
+        let Some(balance) = Self::curve_balance(solana, token, verify).await? else {
+            return Ok(());
+        };
 
         let previous_liquidity = token.current_liquidity;
+        // Buys aren't matched by the log listener, but they move the curve balance
+        if balance != previous_liquidity {
+            token.last_transaction_at = Utc::now().timestamp_millis();
+        }
         token.current_liquidity = balance;
+        token.liquidity_samples.push_back(LiquiditySample {
+            timestamp: Utc::now().timestamp_millis(),
+            liquidity_sol: balance,
+        });
+        while token.liquidity_samples.len() > LIQUIDITY_SAMPLE_CAPACITY {
+            token.liquidity_samples.pop_front();
+        }
 
         // Check for significant drop
         if previous_liquidity > 0.0 {
@@ -690,30 +2288,410 @@ impl RugDetector {
             let thresholds = thresholds.read().clone();
 
             if drop_percent >= thresholds.lp_removal_percent {
+                if solana.is_curve_complete(&token.mint).await.unwrap_or(false) {
+                    Self::mark_graduated(database, token, previous_liquidity);
+                    return Ok(());
+                }
+
+                if thresholds.confirm_recheck
+                    && !Self::confirm_liquidity_drop(solana, token, previous_liquidity, &thresholds)
+                        .await?
+                {
+                    // Keep the pre-drop baseline so a real drop is still caught next pass
+                    token.current_liquidity = previous_liquidity;
+                    return Ok(());
+                }
+
                 let reason = format!("Liquidity dropped {:.1}%", drop_percent);
                 Self::trigger_rug_alert(
                     alerts,
                     database,
                     rugs_detected,
                     alerts_sent,
+                    rug_sender,
                     token,
                     &reason,
                 )
                 .await?;
             }
+
+            // Money flowing back in can be a re-seed to bait buyers after a partial pull
+            let rise_percent = -drop_percent;
+            if thresholds.liquidity_added_percent > 0.0
+                && rise_percent >= thresholds.liquidity_added_percent
+                && !token.is_rugged
+            {
+                info!(
+                    target: "RUG_DETECTOR",
+                    "{}: liquidity up {:.1}% ({:.2} -> {:.2} SOL)",
+                    token.symbol, rise_percent, previous_liquidity, balance
+                );
+                let alert = RugAlert {
+                    alert_type: "liquidity_added".to_string(),
+                    message: format!(
+                        "Liquidity rose {:.1}%: {:.2} -> {:.2} SOL",
+                        rise_percent, previous_liquidity, balance
+                    ),
+                    severity: "info".to_string(),
+                    muted: token.muted,
+                };
+                Self::push_alert(database, token, alert);
+                if !token.muted {
+                    alerts_sent.fetch_add(1, Ordering::SeqCst);
+                    let _ = alerts
+                        .alert_liquidity_added(&token.alert_info(), previous_liquidity, balance)
+                        .await;
+                }
+            }
+        }
+
+        if !token.is_rugged {
+            let thresholds = thresholds.read().clone();
+            Self::check_liquidity_oscillation(alerts, database, alerts_sent, &thresholds, token)
+                .await;
+            Self::check_mcap_milestone(alerts, alerts_sent, &thresholds, token).await;
         }
 
         Ok(())
     }
 
+    /// Direction flips and SOL amplitude (max - min) of the samples since `since`.
+    /// A move under `min_change_percent` of the previous sample neither counts nor
+    /// resets the direction, so small trading noise can't add up to a flip.
+    fn liquidity_oscillation(
+        samples: &VecDeque<LiquiditySample>,
+        since: i64,
+        min_change_percent: f64,
+    ) -> (u32, f64) {
+        let window: Vec<f64> = samples
+            .iter()
+            .filter(|s| s.timestamp >= since)
+            .map(|s| s.liquidity_sol)
+            .collect();
+
+        let mut flips = 0;
+        let mut direction: Option<bool> = None;
+        for pair in window.windows(2) {
+            let (before, after) = (pair[0], pair[1]);
+            if (after - before).abs() < before * (min_change_percent / 100.0) {
+                continue;
+            }
+            let rising = after > before;
+            if direction.is_some_and(|previous| previous != rising) {
+                flips += 1;
+            }
+            direction = Some(rising);
+        }
+
+        let max = window.iter().copied().fold(f64::MIN, f64::max);
+        let min = window.iter().copied().fold(f64::MAX, f64::min);
+        (flips, if window.is_empty() { 0.0 } else { max - min })
+    }
+
+    /// Alert once per window when liquidity keeps reversing direction, a sign of
+    /// add/remove cycling to fake activity
+    async fn check_liquidity_oscillation(
+        alerts: &Arc<AlertService>,
+        database: &Arc<DatabaseService>,
+        alerts_sent: &Arc<AtomicU64>,
+        thresholds: &RugThresholds,
+        token: &mut WatchedToken,
+    ) {
+        if thresholds.oscillation_min_flips == 0 || thresholds.oscillation_window_secs == 0 {
+            return;
+        }
+        let now = Utc::now().timestamp_millis();
+        let window_ms = thresholds.oscillation_window_secs as i64 * 1000;
+        if token.oscillation_alerted_at.is_some_and(|at| now - at < window_ms) {
+            return;
+        }
+
+        let (flips, amplitude_sol) = Self::liquidity_oscillation(
+            &token.liquidity_samples,
+            now - window_ms,
+            thresholds.oscillation_min_change_percent,
+        );
+        if flips < thresholds.oscillation_min_flips {
+            return;
+        }
+
+        token.oscillation_alerted_at = Some(now);
+        let amplitude_percent = if token.current_liquidity > 0.0 {
+            amplitude_sol / token.current_liquidity * 100.0
+        } else {
+            0.0
+        };
+        warn!(
+            target: "RUG_DETECTOR",
+            "{}: liquidity reversed {} times in {}s (amplitude {:.2} SOL)",
+            token.symbol, flips, thresholds.oscillation_window_secs, amplitude_sol
+        );
+        let alert = RugAlert {
+            alert_type: "liquidity_oscillation".to_string(),
+            message: format!(
+                "Liquidity reversed direction {} times in {}s, amplitude {:.2} SOL ({:.1}%)",
+                flips, thresholds.oscillation_window_secs, amplitude_sol, amplitude_percent
+            ),
+            severity: "medium".to_string(),
+            muted: token.muted,
+        };
+        Self::push_alert(database, token, alert);
+
+        if !token.muted {
+            alerts_sent.fetch_add(1, Ordering::SeqCst);
+            let _ = alerts
+                .alert_liquidity_oscillation(
+                    &token.alert_info(),
+                    flips,
+                    amplitude_sol,
+                    thresholds.oscillation_window_secs,
+                )
+                .await;
+        }
+    }
+
+    /// Switch a token to the graduated profile; `migrated_sol` is the curve balance
+    /// just before migration, the best estimate of the pool's SOL side
+    fn mark_graduated(database: &Arc<DatabaseService>, token: &mut WatchedToken, migrated_sol: f64) {
+        database.write(PendingWrite::Graduated {
+            mint: token.mint.clone(),
+        });
+        token.graduated = true;
+        token.graduated_at = Some(Utc::now().timestamp_millis());
+        token.current_liquidity = migrated_sol;
+        info!(
+            target: "RUG_DETECTOR",
+            "{}: bonding curve complete, graduated with {:.2} SOL",
+            token.symbol, migrated_sol
+        );
+        token.alerts.push(RugAlert {
+            alert_type: "graduated".to_string(),
+            message: format!("Graduated to AMM pool with {:.2} SOL", migrated_sol),
+            severity: "info".to_string(),
+            muted: token.muted,
+        });
+    }
+
+    /// Largest wSOL balance decrease in a transaction, i.e. SOL pulled from a pool vault
+    fn wsol_outflow(meta: &UiTransactionStatusMeta) -> f64 {
+        let (pre_balances, post_balances) = match (&meta.pre_token_balances, &meta.post_token_balances) {
+            (OptionSerializer::Some(pre), OptionSerializer::Some(post)) => (pre, post.as_slice()),
+            (OptionSerializer::Some(pre), _) => (pre, &[][..]),
+            _ => return 0.0,
+        };
+
+        pre_balances
+            .iter()
+            .filter(|pre| pre.mint == WSOL_MINT)
+            .map(|pre| {
+                let before = pre.ui_token_amount.ui_amount.unwrap_or(0.0);
+                let after = post_balances
+                    .iter()
+                    .find(|post| post.account_index == pre.account_index)
+                    .and_then(|post| post.ui_token_amount.ui_amount)
+                    .unwrap_or(0.0);
+                before - after
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// Alert once per market cap milestone; the high-water mark never moves down,
+    /// so a cap oscillating around a milestone doesn't re-alert
+    async fn check_mcap_milestone(
+        alerts: &Arc<AlertService>,
+        alerts_sent: &Arc<AtomicU64>,
+        thresholds: &RugThresholds,
+        token: &mut WatchedToken,
+    ) {
+        let mcap_usd =
+            SolanaService::implied_market_cap_sol(token.current_liquidity) * thresholds.sol_usd_price;
+        let reached = thresholds
+            .mcap_milestones_usd
+            .iter()
+            .rev()
+            .find(|m| mcap_usd >= **m && **m > token.mcap_milestone)
+            .copied();
+        let milestone = match reached {
+            Some(milestone) => milestone,
+            None => return,
+        };
+        token.mcap_milestone = milestone;
+
+        info!(
+            target: "RUG_DETECTOR",
+            "{}: implied market cap ${:.0} (passed ${:.0})",
+            token.symbol, mcap_usd, milestone
+        );
+        if token.muted {
+            return;
+        }
+
+        alerts_sent.fetch_add(1, Ordering::SeqCst);
+        let _ = alerts.alert_mcap_milestone(&token.alert_info(), mcap_usd, milestone).await;
+    }
+
+    /// Get current thresholds
+    pub fn get_thresholds(&self) -> RugThresholds {
+        self.thresholds.read().clone()
+    }
+
+    /// Set a threshold value
+    pub fn set_threshold(&self, key: &str, value: f64) -> bool {
+        let mut thresholds = self.thresholds.write();
+        match key {
+            "lp_removal_percent" => thresholds.lp_removal_percent = value,
+            "suspicious_sell_percent" => thresholds.suspicious_sell_percent = value,
+            "graduated_lp_removal_percent" => thresholds.graduated_lp_removal_percent = value,
+            "graduated_suspicious_sell_percent" => thresholds.graduated_suspicious_sell_percent = value,
+            "dev_wallet_sell_alert" => thresholds.dev_wallet_sell_alert = value != 0.0,
+            "max_dev_sell_percent" => thresholds.max_dev_sell_percent = value,
+            "min_dev_hold_secs" => thresholds.min_dev_hold_secs = value as u64,
+            "holder_concentration_alert" => thresholds.holder_concentration_alert = value,
+            "liquidity_added_percent" => thresholds.liquidity_added_percent = value,
+            "oscillation_min_flips" => thresholds.oscillation_min_flips = value as u32,
+            "oscillation_window_secs" => thresholds.oscillation_window_secs = value as u64,
+            "oscillation_min_change_percent" => thresholds.oscillation_min_change_percent = value,
+            "bundle_launch_window_secs" => thresholds.bundle_launch_window_secs = value as u64,
+            "bundle_dump_window_secs" => thresholds.bundle_dump_window_secs = value as u64,
+            "bundle_dump_min_sol" => thresholds.bundle_dump_min_sol = value,
+            "rug_score_threshold" => thresholds.rug_score_threshold = value as i32,
+            "confirm_recheck" => thresholds.confirm_recheck = value != 0.0,
+            "verify_curve_owner" => thresholds.verify_curve_owner = value != 0.0,
+            "confirm_delay_ms" => thresholds.confirm_delay_ms = value as u64,
+            "abandon_after_secs" => thresholds.abandon_after_secs = value as u64,
+            "abandon_grace_secs" => thresholds.abandon_grace_secs = value as u64,
+            "auto_unwatch_after_secs" => thresholds.auto_unwatch_after_secs = value as u64,
+            "auto_unwatch_flagged" => thresholds.auto_unwatch_flagged = value != 0.0,
+            "auto_unwatch_suspicious_after_secs" => {
+                thresholds.auto_unwatch_suspicious_after_secs = value as u64
+            }
+            "safety_recheck_secs" => thresholds.safety_recheck_secs = value as u64,
+            "safety_caution_score" => thresholds.safety_caution_score = value as i32,
+            "safety_hysteresis" => thresholds.safety_hysteresis = value as i32,
+            "sol_usd_price" => thresholds.sol_usd_price = value,
+            // Single-window key from before sell windows; in millis, retargets the shortest window
+            "min_time_between_sells" => match thresholds.sell_windows.first_mut() {
+                Some(window) => {
+                    window.window_secs = ((value / 1000.0).round() as u64).max(1);
+                    thresholds.sell_windows.sort_by_key(|w| w.window_secs);
+                }
+                None => return false,
+            },
+            _ => return false,
+        }
+        info!(target: "RUG_DETECTOR", "Threshold updated: {} = {}", key, value);
+        true
+    }
+
+    /// Sell-pressure windows as `secs:sells:percent` specs, shortest first
+    pub fn get_sell_windows(&self) -> Vec<String> {
+        self.thresholds.read().sell_windows.iter().map(SellWindow::spec).collect()
+    }
+
+    /// Replace the sell-pressure windows from `secs:sells:percent` specs
+    pub fn set_sell_windows(&self, specs: &[String]) -> PumpResult<Vec<String>> {
+        let mut windows = specs
+            .iter()
+            .map(|spec| {
+                SellWindow::parse(spec).ok_or_else(|| {
+                    let message = format!("sell window must be secs:sells:percent, got {}", spec);
+                    PumpError::InvalidInput(message)
+                })
+            })
+            .collect::<PumpResult<Vec<_>>>()?;
+        windows.sort_by_key(|w| w.window_secs);
+        self.thresholds.write().sell_windows = windows;
+        info!(target: "RUG_DETECTOR", "Sell windows updated: {} windows", specs.len());
+        Ok(self.get_sell_windows())
+    }
+
+    /// Program IDs/log patterns whose withdraw/migrate logs are treated as benign
+    pub fn get_lp_ignore_programs(&self) -> Vec<String> {
+        self.lp_ignore_programs.read().clone()
+    }
+
+    /// Replace the LP-removal allowlist; empty entries are dropped
+    pub fn set_lp_ignore_programs(&self, entries: Vec<String>) -> Vec<String> {
+        let entries: Vec<String> = entries
+            .into_iter()
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty())
+            .collect();
+        *self.lp_ignore_programs.write() = entries.clone();
+        info!(target: "RUG_DETECTOR", "LP-removal allowlist updated: {} entries", entries.len());
+        entries
+    }
+
+    /// Creators whose tokens skip suspicion scoring, sorted
+    pub fn get_trusted_creators(&self) -> Vec<String> {
+        let mut creators: Vec<String> = self.trusted_creators.read().iter().cloned().collect();
+        creators.sort();
+        creators
+    }
+
+    /// Replace and save the trusted creator list, re-labelling watched tokens to match
+    pub fn set_trusted_creators(&self, entries: Vec<String>) -> PumpResult<Vec<String>> {
+        let creators: HashSet<String> = entries
+            .into_iter()
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty())
+            .collect();
+        let mut sorted: Vec<String> = creators.iter().cloned().collect();
+        sorted.sort();
+        self.database.save_trusted_creators(&sorted)?;
+
+        for mut token in self.watched_tokens.iter_mut() {
+            token.trusted = creators.contains(&token.creator);
+        }
+        *self.trusted_creators.write() = creators;
+        info!(target: "RUG_DETECTOR", "Trusted creators updated: {} entries", sorted.len());
+        Ok(sorted)
+    }
+
     /// Get detector statistics
     pub fn get_stats(&self) -> RugDetectorStats {
         RugDetectorStats {
             tokens_watched: self.tokens_watched.load(Ordering::SeqCst),
             rugs_detected: self.rugs_detected.load(Ordering::SeqCst),
             alerts_sent: self.alerts_sent.load(Ordering::SeqCst),
+            fetches_avoided: self.fetches_avoided.load(Ordering::SeqCst),
+            lp_logs_ignored: self.lp_logs_ignored.load(Ordering::SeqCst),
+            failed_txs_skipped: self.failed_txs_skipped.load(Ordering::SeqCst),
+            tx_fetch_none: self.tx_loss.fetch_none(),
+            tx_parse_none: self.tx_loss.parse_none(),
+            auto_unwatched: self.auto_unwatched.load(Ordering::SeqCst),
+            safety_downgrades: self.safety_downgrades.load(Ordering::SeqCst),
+            avg_check_interval_secs: self.avg_check_interval_secs(),
+            queue_depth: self.analysis_queue.depth(),
+            queue_dropped: self.analysis_queue.dropped(),
+            rug_score_threshold: self.thresholds.read().rug_score_threshold,
             watched_tokens: self.watched_tokens.len(),
-            is_running: self.is_running.load(Ordering::SeqCst),
+            is_running: self.is_running.is_running(),
+        }
+    }
+
+    /// Mints whose health check is due at `now`, most suspicious first so a slow RPC
+    /// round spends its budget where it matters
+    fn due_for_check(watched_tokens: &DashMap<String, WatchedToken>, now: i64) -> Vec<String> {
+        let mut due: Vec<(i32, String)> = watched_tokens
+            .iter()
+            .filter(|e| e.next_check_at <= now)
+            .map(|e| (e.suspicion_score, e.key().clone()))
+            .collect();
+        due.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+        due.into_iter().map(|(_, mint)| mint).collect()
+    }
+
+    fn avg_check_interval_secs(&self) -> f64 {
+        let (total, count) = self
+            .watched_tokens
+            .iter()
+            .filter(|e| e.check_interval_secs > 0)
+            .fold((0u64, 0u64), |(total, count), e| (total + e.check_interval_secs, count + 1));
+        if count == 0 {
+            0.0
+        } else {
+            total as f64 / count as f64
         }
     }
 
@@ -725,6 +2703,39 @@ impl RugDetector {
             .collect()
     }
 
+    /// Export watched tokens with sell history bounded to the most recent `max_sells`
+    pub fn export_watched_tokens(&self, max_sells: usize) -> Vec<WatchedToken> {
+        self.watched_tokens
+            .iter()
+            .map(|e| {
+                let mut token = e.value().clone();
+                while token.sell_history.len() > max_sells {
+                    token.sell_history.pop_front();
+                }
+                token
+            })
+            .collect()
+    }
+
+    /// Merge imported tokens into the watch list, keeping any already being watched.
+    /// Returns the number of tokens added.
+    pub fn import_watched_tokens(&self, tokens: Vec<WatchedToken>) -> usize {
+        let mut added = 0;
+        let trusted_creators = self.trusted_creators.read().clone();
+        for mut token in tokens {
+            if self.watched_tokens.contains_key(&token.mint) {
+                continue;
+            }
+            // Trust follows this instance's list, not the exporter's
+            token.trusted = trusted_creators.contains(&token.creator);
+            self.watched_tokens.insert(token.mint.clone(), token);
+            self.tokens_watched.fetch_add(1, Ordering::SeqCst);
+            added += 1;
+        }
+        info!(target: "RUG_DETECTOR", "Imported {} watched tokens", added);
+        added
+    }
+
     /// Get details for a specific token
     pub fn get_token_details(&self, mint: &str) -> Option<WatchedToken> {
         self.watched_tokens.get(mint).map(|e| e.value().clone())
@@ -732,7 +2743,12 @@ impl RugDetector {
 
     /// Check if running
     pub fn is_running(&self) -> bool {
-        self.is_running.load(Ordering::SeqCst)
+        self.is_running.is_running()
+    }
+
+    /// Queued and in-progress analysis jobs, which still finish after `stop`
+    pub fn pending_work(&self) -> u64 {
+        self.analysis_queue.pending()
     }
 }
 
@@ -743,12 +2759,614 @@ impl Clone for RugDetector {
             solana: Arc::clone(&self.solana),
             alerts: Arc::clone(&self.alerts),
             database: Arc::clone(&self.database),
-            is_running: Arc::clone(&self.is_running),
+            is_running: self.is_running.clone(),
             watched_tokens: Arc::clone(&self.watched_tokens),
+            token_locks: Arc::clone(&self.token_locks),
             thresholds: Arc::clone(&self.thresholds),
+            lp_ignore_programs: Arc::clone(&self.lp_ignore_programs),
+            trusted_creators: Arc::clone(&self.trusted_creators),
             tokens_watched: Arc::clone(&self.tokens_watched),
             rugs_detected: Arc::clone(&self.rugs_detected),
             alerts_sent: Arc::clone(&self.alerts_sent),
+            fetches_avoided: Arc::clone(&self.fetches_avoided),
+            lp_logs_ignored: Arc::clone(&self.lp_logs_ignored),
+            failed_txs_skipped: Arc::clone(&self.failed_txs_skipped),
+            tx_loss: self.tx_loss.clone(),
+            auto_unwatched: Arc::clone(&self.auto_unwatched),
+            safety_downgrades: Arc::clone(&self.safety_downgrades),
+            analysis_queue: self.analysis_queue.clone(),
+            rug_sender: self.rug_sender.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    /// Detector over an in-memory database and an unreachable RPC endpoint
+    fn detector(configure: impl FnOnce(&mut Config)) -> RugDetector {
+        let mut config = Config::for_tests();
+        config.rpc_url = "http://127.0.0.1:1".to_string();
+        config.rpc_urls = vec![config.rpc_url.clone()];
+        config.telegram_bot_token = None;
+        config.alert_file_path = None;
+        configure(&mut config);
+
+        let solana = Arc::new(SolanaService::new(config.clone()));
+        let alerts = Arc::new(AlertService::new(config.clone()));
+        let database = Arc::new(DatabaseService::new(":memory:").unwrap());
+        RugDetector::new(config, solana, alerts, database)
+    }
+
+    /// Watch `mint` and return a copy of its entry after `update`
+    fn watched(
+        detector: &RugDetector,
+        mint: &str,
+        update: impl FnOnce(&mut WatchedToken),
+    ) -> WatchedToken {
+        detector.watch_token(mint, "Test", "TST", "creator", 10.0);
+        let mut token = detector.watched_tokens.get_mut(mint).unwrap();
+        update(&mut token);
+        token.clone()
+    }
+
+    /// Run the sell pattern checks on `token` for a sell of `amount_tokens` by `wallet` at
+    /// `timestamp`, returning the alert types raised
+    async fn check_sell(
+        detector: &RugDetector,
+        token: &mut WatchedToken,
+        wallet: &str,
+        amount_tokens: f64,
+        timestamp: i64,
+    ) -> Vec<String> {
+        let alerts_before = token.alerts.len();
+        let sell = ParsedSellInfo {
+            mint: token.mint.clone(),
+            wallet: wallet.to_string(),
+            amount_sol: 0.01,
+            amount_tokens,
+            timestamp,
+        };
+        RugDetector::check_suspicious_patterns(
+            &detector.alerts,
+            &detector.database,
+            &detector.thresholds,
+            &detector.rugs_detected,
+            &detector.alerts_sent,
+            &detector.rug_sender,
+            token,
+            &sell,
+        )
+        .await
+        .unwrap();
+        token.alerts[alerts_before..].iter().map(|alert| alert.alert_type.clone()).collect()
+    }
+
+    #[test]
+    fn suspicious_tokens_are_checked_first_and_most_often() {
+        let detector = detector(|config| {
+            config.health_check_secs = 30;
+            config.health_check_min_secs = 5;
+            config.health_check_max_secs = 300;
+        });
+        let schedule = HealthSchedule::from_config(&detector.config);
+        let now = Utc::now().timestamp_millis();
+        let caution_score = detector.thresholds.read().safety_caution_score;
+
+        let suspicious = watched(&detector, "suspicious", |t| {
+            t.suspicion_score = caution_score;
+            t.last_transaction_at = now - 600_000;
+            t.next_check_at = now;
+        });
+        let active = watched(&detector, "active", |t| {
+            t.last_transaction_at = now - 10_000;
+            t.next_check_at = now - 1_000;
+        });
+        let quiet = watched(&detector, "quiet", |t| {
+            t.last_transaction_at = now - 600_000;
+            t.next_check_at = now - 2_000;
+        });
+        watched(&detector, "later", |t| {
+            t.suspicion_score = caution_score + 10;
+            t.next_check_at = now + 1_000;
+        });
+
+        assert_eq!(schedule.interval_secs(&suspicious, caution_score, now), 5);
+        assert_eq!(schedule.interval_secs(&active, caution_score, now), 15);
+        assert_eq!(schedule.interval_secs(&quiet, caution_score, now), 300);
+
+        // Tokens that aren't due yet wait however suspicious they are
+        let due = RugDetector::due_for_check(&detector.watched_tokens, now);
+        assert_eq!(due[0], "suspicious");
+        assert_eq!(due.len(), 3);
+        assert!(!due.contains(&"later".to_string()));
+    }
+
+    #[tokio::test]
+    async fn dev_sell_soon_after_launch_is_an_instant_dev_sell() {
+        let detector = detector(|config| config.min_dev_hold_secs = 60);
+        let launched_at = Utc::now().timestamp_millis() - 3_600_000;
+        let launched = |mint| watched(&detector, mint, |t| t.launched_at = Some(launched_at));
+
+        let mut quick = launched("quick");
+        let raised = check_sell(&detector, &mut quick, "creator", 1000.0, launched_at + 5_000).await;
+        assert!(raised.contains(&"instant_dev_sell".to_string()));
+        assert_eq!(quick.dev_first_sell_at, Some(launched_at + 5_000));
+
+        let mut patient = launched("patient");
+        let an_hour_later = launched_at + 3_600_000;
+        let raised = check_sell(&detector, &mut patient, "creator", 1000.0, an_hour_later).await;
+        assert!(!raised.contains(&"instant_dev_sell".to_string()));
+        assert_eq!(patient.dev_first_sell_at, Some(an_hour_later));
+
+        // Only the first dev sell is timed
+        let raised = check_sell(&detector, &mut quick, "creator", 1000.0, launched_at + 6_000).await;
+        assert!(!raised.contains(&"instant_dev_sell".to_string()));
+    }
+
+    #[tokio::test]
+    async fn creator_whale_sells_weigh_more_than_buys() {
+        let detector = detector(|_| {});
+        watched(&detector, "mint", |_| {});
+
+        detector.record_creator_activity("mint", "buy", 50.0).await;
+        detector.record_creator_activity("mint", "sell", 50.0).await;
+
+        let token = detector.watched_tokens.get("mint").unwrap().clone();
+        assert_eq!(token.suspicion_score, 50);
+        let severities: Vec<&str> = token.alerts.iter().map(|a| a.severity.as_str()).collect();
+        assert_eq!(severities, ["high", "critical"]);
+        assert_eq!(token.alerts[1].message, "Creator made a 50.00 SOL whale sell");
+    }
+
+    #[tokio::test]
+    async fn dev_sell_percent_uses_the_real_supply() {
+        let detector = detector(|_| {});
+        let now = Utc::now().timestamp_millis();
+
+        // 3M tokens is 30% of a 10M supply...
+        let mut small_supply = watched(&detector, "small", |t| t.total_supply = Some(10_000_000.0));
+        let raised = check_sell(&detector, &mut small_supply, "creator", 3_000_000.0, now).await;
+        assert!(raised.contains(&"dev_dump".to_string()));
+        let dump = small_supply.alerts.iter().find(|a| a.alert_type == "dev_dump").unwrap();
+        assert_eq!(dump.message, "Developer sold 30.00% of supply");
+
+        // ...but only 0.3% of the pump.fun default
+        let mut default_supply = watched(&detector, "default", |_| {});
+        let raised = check_sell(&detector, &mut default_supply, "creator", 3_000_000.0, now).await;
+        assert!(!raised.contains(&"dev_dump".to_string()));
+    }
+
+    #[test]
+    fn suspicious_tokens_expire_after_the_longer_ttl() {
+        let detector = detector(|config| {
+            config.auto_unwatch_after_secs = 3600;
+            config.auto_unwatch_suspicious_after_secs = 86400;
+        });
+        let hours_ago = |hours: i64| Utc::now().timestamp_millis() - hours * 3_600_000;
+        let reason = |token: &WatchedToken| {
+            RugDetector::auto_unwatch_reason(&detector.thresholds, token)
+        };
+
+        let quiet = watched(&detector, "quiet", |t| t.last_transaction_at = hours_ago(2));
+        assert_eq!(reason(&quiet), Some("inactive"));
+
+        let suspicious = watched(&detector, "suspicious", |t| {
+            t.suspicion_score = 30;
+            t.last_transaction_at = hours_ago(2);
+        });
+        assert_eq!(reason(&suspicious), None);
+        let stale = WatchedToken { last_transaction_at: hours_ago(25), ..suspicious };
+        assert_eq!(reason(&stale), Some("inactive"));
+
+        let rugged = WatchedToken { is_rugged: true, ..stale };
+        assert_eq!(reason(&rugged), None);
+    }
+
+    #[test]
+    fn lp_removal_from_an_allowlisted_program_is_ignored() {
+        let logs: Vec<String> = [
+            "Program Vault111111111111111111111111111111111111 invoke [1]",
+            "Program log: Instruction: Withdraw",
+            "Program Vault111111111111111111111111111111111111 success",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+        let vault = vec!["Vault111111111111111111111111111111111111".to_string()];
+
+        assert!(matches!(RugDetector::lp_removal_logs(&logs, &[]), LpLogMatch::Removal));
+        assert!(matches!(RugDetector::lp_removal_logs(&logs, &vault), LpLogMatch::Ignored));
+    }
+
+    const MINT: &str = "So11111111111111111111111111111111111111112";
+    const RELAYER: &str = "Re1ayer111111111111111111111111111111111111";
+    const DEV: &str = "Dev1111111111111111111111111111111111111111";
+    const CURVE: &str = "Curve11111111111111111111111111111111111111";
+
+    /// A sell where `RELAYER` pays the fee and `DEV` sells 1,000 tokens for 0.5 SOL
+    fn relayed_sell(message: Value) -> Value {
+        let token_balance = |index: u8, owner: &str, amount: f64| {
+            json!({
+                "accountIndex": index,
+                "mint": MINT,
+                "owner": owner,
+                "uiTokenAmount": {
+                    "uiAmount": amount,
+                    "decimals": 6,
+                    "amount": format!("{}", amount * 1e6),
+                    "uiAmountString": amount.to_string(),
+                },
+            })
+        };
+        json!({
+            "slot": 1,
+            "blockTime": 1_700_000_000,
+            "transaction": { "signatures": ["sig"], "message": message },
+            "meta": {
+                "err": null,
+                "status": { "Ok": null },
+                "fee": 5000,
+                "preBalances": [1_000_000_000u64, 100_000_000u64, 5_000_000_000u64],
+                "postBalances": [999_995_000u64, 600_000_000u64, 4_500_000_000u64],
+                "preTokenBalances": [token_balance(1, DEV, 1000.0), token_balance(2, CURVE, 0.0)],
+                "postTokenBalances": [token_balance(1, DEV, 0.0), token_balance(2, CURVE, 1000.0)],
+            },
+        })
+    }
+
+    fn raw_message() -> Value {
+        json!({
+            "header": {
+                "numRequiredSignatures": 2,
+                "numReadonlySignedAccounts": 0,
+                "numReadonlyUnsignedAccounts": 0,
+            },
+            "accountKeys": [RELAYER, DEV, CURVE],
+            "recentBlockhash": "11111111111111111111111111111111",
+            "instructions": [],
+        })
+    }
+
+    fn assert_dev_is_seller(tx: Value) {
+        let tx = serde_json::from_value(tx).unwrap();
+        let sell = RugDetector::parse_sell_transaction(&tx).unwrap();
+        assert_eq!(sell.mint, MINT);
+        assert_eq!(sell.wallet, DEV);
+        assert_eq!(sell.amount_tokens, 1000.0);
+        assert_eq!(sell.amount_sol, 0.5);
+        assert_eq!(sell.timestamp, 1_700_000_000_000);
+    }
+
+    #[test]
+    fn relayed_sell_is_attributed_to_the_seller_in_raw_messages() {
+        assert_dev_is_seller(relayed_sell(raw_message()));
+    }
+
+    #[test]
+    fn relayed_sell_is_attributed_to_the_seller_in_parsed_messages() {
+        let account = |pubkey: &str, signer: bool| {
+            json!({ "pubkey": pubkey, "writable": true, "signer": signer })
+        };
+        assert_dev_is_seller(relayed_sell(json!({
+            "accountKeys": [account(RELAYER, true), account(DEV, true), account(CURVE, false)],
+            "recentBlockhash": "11111111111111111111111111111111",
+            "instructions": [],
+        })));
+    }
+
+    /// JSON-RPC endpoint answering `getBalance` with `lamports` in order, `getTransaction`
+    /// with `transaction` and every other method besides the client's version probe with an
+    /// empty value
+    async fn scripted_rpc(lamports: Vec<u64>, transaction: Value) -> String {
+        use axum::{routing::post, Json, Router};
+
+        let lamports = Arc::new(parking_lot::Mutex::new(VecDeque::from(lamports)));
+        let app = Router::new().route(
+            "/",
+            post(move |Json(request): Json<Value>| async move {
+                let result = match request["method"].as_str() {
+                    Some("getVersion") => json!({ "solana-core": "1.18.0" }),
+                    Some("getBalance") => json!({
+                        "context": { "slot": 1 },
+                        "value": lamports.lock().pop_front().unwrap_or(0),
+                    }),
+                    Some("getTransaction") => transaction,
+                    _ => json!({ "context": { "slot": 1 }, "value": null }),
+                };
+                Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        url
+    }
+
+    /// Run one liquidity health check for a 10 SOL token against curve reads of `lamports`
+    async fn liquidity_check(lamports: Vec<u64>) -> (RugDetector, WatchedToken) {
+        let rpc_url = scripted_rpc(lamports, Value::Null).await;
+        let detector = detector(|config| {
+            config.rpc_url = rpc_url.clone();
+            config.rpc_urls = vec![rpc_url];
+            config.rug_confirm_recheck = true;
+            config.rug_confirm_delay_ms = 0;
+            config.verify_curve_owner = false;
+        });
+        let mut token = watched(&detector, MINT, |_| {});
+        RugDetector::check_liquidity_health(
+            &detector.solana,
+            &detector.alerts,
+            &detector.database,
+            &detector.thresholds,
+            &detector.rugs_detected,
+            &detector.alerts_sent,
+            &detector.rug_sender,
+            &mut token,
+        )
+        .await
+        .unwrap();
+        (detector, token)
+    }
+
+    #[tokio::test]
+    async fn transient_liquidity_drop_is_not_alerted() {
+        // 10 SOL reads as 2 SOL once, then is back on the re-check
+        let (detector, token) = liquidity_check(vec![2_000_000_000, 9_900_000_000]).await;
+
+        assert!(!token.is_rugged);
+        assert_eq!(detector.rugs_detected.load(Ordering::SeqCst), 0);
+        // The pre-drop baseline stays so a real drop is still caught next pass
+        assert_eq!(token.current_liquidity, 10.0);
+    }
+
+    #[tokio::test]
+    async fn liquidity_drop_confirmed_on_re_check_is_alerted() {
+        let (detector, token) = liquidity_check(vec![2_000_000_000, 2_000_000_000]).await;
+
+        assert!(token.is_rugged);
+        assert_eq!(detector.rugs_detected.load(Ordering::SeqCst), 1);
+    }
+
+    /// Analyze a sell of a watched `MINT` whose fetched transaction is `transaction`
+    async fn analyze_sell(transaction: Value) -> (RugDetector, WatchedToken) {
+        let rpc_url = scripted_rpc(Vec::new(), transaction).await;
+        let detector = detector(|config| {
+            config.rpc_url = rpc_url.clone();
+            config.rpc_urls = vec![rpc_url];
+        });
+        watched(&detector, MINT, |_| {});
+        RugDetector::analyze_sell_transaction(
+            &detector.solana,
+            &detector.alerts,
+            &detector.database,
+            &detector.watched_tokens,
+            &detector.token_locks,
+            &detector.thresholds,
+            &detector.rugs_detected,
+            &detector.alerts_sent,
+            &detector.rug_sender,
+            &detector.failed_txs_skipped,
+            &detector.tx_loss,
+            &solana_sdk::signature::Signature::default().to_string(),
+            None,
+        )
+        .await
+        .unwrap();
+        let token = detector.watched_tokens.get(MINT).unwrap().clone();
+        (detector, token)
+    }
+
+    #[tokio::test]
+    async fn sells_that_failed_on_chain_are_skipped() {
+        let mut failed = relayed_sell(raw_message());
+        let error = json!({ "InstructionError": [0, { "Custom": 6001 }] });
+        failed["meta"]["err"] = error.clone();
+        failed["meta"]["status"] = json!({ "Err": error });
+
+        let (detector, token) = analyze_sell(failed).await;
+        assert_eq!(detector.failed_txs_skipped.load(Ordering::SeqCst), 1);
+        assert!(token.sell_history.is_empty());
+
+        let (detector, token) = analyze_sell(relayed_sell(raw_message())).await;
+        assert_eq!(detector.failed_txs_skipped.load(Ordering::SeqCst), 0);
+        assert_eq!(token.sell_history.len(), 1);
+        assert_eq!(token.sell_history[0].wallet, DEV);
+    }
+
+    #[test]
+    fn recent_active_tokens_are_restored_to_the_watch_list() {
+        let detector = detector(|config| config.restore_watched_hours = 6);
+        let saved = |mint: &str, hours_ago: i64| {
+            let created_at = (Utc::now() - chrono::Duration::hours(hours_ago)).to_rfc3339();
+            let record = TokenRecord {
+                mint: mint.to_string(),
+                name: "Test".to_string(),
+                symbol: "TST".to_string(),
+                creator: "creator".to_string(),
+                created_at: created_at.clone(),
+                initial_liquidity: 10.0,
+                current_liquidity: 4.0,
+                holder_count: 0,
+                is_rugged: false,
+                rug_reason: None,
+                last_updated: created_at,
+                is_muted: false,
+                is_graduated: false,
+            };
+            detector.database.save_token(&record).unwrap();
+        };
+        saved("active", 1);
+        saved("rugged", 1);
+        saved("graduated", 2);
+        saved("stale", 12);
+        saved("watched", 3);
+        detector.database.mark_as_rugged("rugged", "LP removed").unwrap();
+        detector.database.mark_graduated("graduated").unwrap();
+        let live = watched(&detector, "watched", |token| token.current_liquidity = 9.0);
+
+        assert_eq!(detector.restore_watched_tokens().unwrap(), 1);
+
+        let restored = detector.watched_tokens.get("active").unwrap().clone();
+        assert_eq!((restored.initial_liquidity, restored.current_liquidity), (10.0, 4.0));
+        let launched_at = restored.launched_at.unwrap();
+        let expected = (Utc::now() - chrono::Duration::hours(1)).timestamp_millis();
+        assert!((launched_at - expected).abs() < 60_000);
+        for mint in ["rugged", "graduated", "stale"] {
+            assert!(!detector.watched_tokens.contains_key(mint), "{}", mint);
+        }
+        // A token already watched keeps its live state
+        let kept = detector.watched_tokens.get("watched").unwrap().current_liquidity;
+        assert_eq!(kept, live.current_liquidity);
+    }
+
+    #[tokio::test]
+    async fn launch_bundle_dump_escalates_to_bundle_rug() {
+        let detector = detector(|config| {
+            config.bundle_launch_window_secs = 60;
+            config.bundle_dump_window_secs = 600;
+            config.bundle_dump_min_sol = 0.0;
+        });
+        let launched_at = Utc::now().timestamp_millis() - 3_600_000;
+        let bundle = vec!["bundler1".to_string(), "bundler2".to_string()];
+        let bundled = |mint: &'static str, bought_after_secs: i64| {
+            let (detector, bundle) = (&detector, &bundle);
+            async move {
+                watched(detector, mint, |t| t.launched_at = Some(launched_at));
+                let bought_at = launched_at + bought_after_secs * 1000;
+                detector.record_coordinated_buy(mint, bundle, 12.0, bought_at).await;
+                detector.watched_tokens.get(mint).unwrap().clone()
+            }
+        };
+
+        let mut token = bundled("bundled", 10).await;
+        let launch_bundle = token.launch_bundle.clone().unwrap();
+        assert_eq!(launch_bundle.wallets, bundle);
+        assert_eq!(token.alerts.last().unwrap().alert_type, "coordinated_buying");
+        // The bundle alone stays under the rug threshold
+        assert!(token.suspicion_score < detector.thresholds.read().rug_score_threshold);
+
+        // A sell by a wallet outside the bundle isn't the dump
+        let sold_at = launch_bundle.bought_at + 120_000;
+        let raised = check_sell(&detector, &mut token, "outsider", 1.0, sold_at).await;
+        assert!(!raised.contains(&"bundle_rug".to_string()));
+
+        let raised = check_sell(&detector, &mut token, "bundler2", 1.0, sold_at).await;
+        assert!(raised.contains(&"bundle_rug".to_string()), "{:?}", raised);
+        assert!(token.is_rugged);
+        assert!(token.launch_bundle.as_ref().unwrap().escalated);
+
+        // Bought together long after launch isn't a launch bundle
+        assert!(bundled("late", 300).await.launch_bundle.is_none());
+
+        // A dump after the window has passed isn't escalated
+        let mut slow = bundled("slow", 10).await;
+        let too_late = slow.launch_bundle.as_ref().unwrap().bought_at + 601_000;
+        let raised = check_sell(&detector, &mut slow, "bundler1", 1.0, too_late).await;
+        assert!(!raised.contains(&"bundle_rug".to_string()));
+        assert!(!slow.is_rugged);
+    }
+
+    /// Sell-pressure alert message raised by seven 0.9 SOL sells 45s apart on a 10 SOL
+    /// token: never three inside a minute, but 6.3 SOL within five
+    async fn slow_drip_alert(sell_windows: &[&str]) -> Option<String> {
+        let windows = sell_windows.iter().filter_map(|spec| SellWindow::parse(spec)).collect();
+        let detector = detector(|config| config.sell_windows = windows);
+        let now = Utc::now().timestamp_millis();
+        let mut token = watched(&detector, MINT, |token| {
+            token.sell_history = (0..7)
+                .map(|i| SellInfo {
+                    signature: format!("sig{}", i),
+                    wallet: format!("seller{}", i),
+                    amount_sol: 0.9,
+                    amount_tokens: 1000.0,
+                    timestamp: now - (6 - i) * 45_000,
+                })
+                .collect();
+        });
+        let sell = ParsedSellInfo {
+            mint: token.mint.clone(),
+            wallet: "seller6".to_string(),
+            amount_sol: 0.9,
+            amount_tokens: 1000.0,
+            timestamp: now,
+        };
+        RugDetector::check_suspicious_patterns(
+            &detector.alerts,
+            &detector.database,
+            &detector.thresholds,
+            &detector.rugs_detected,
+            &detector.alerts_sent,
+            &detector.rug_sender,
+            &mut token,
+            &sell,
+        )
+        .await
+        .unwrap();
+        token
+            .alerts
+            .iter()
+            .find(|alert| alert.alert_type == "rapid_selling")
+            .map(|alert| alert.message.clone())
+    }
+
+    #[tokio::test]
+    async fn slow_drip_trips_only_a_longer_sell_window() {
+        let message = slow_drip_alert(&["60:3:30", "300:6:50", "900:10:70"]).await.unwrap();
+        assert!(message.starts_with("Sell pressure over 5m:"), "{}", message);
+        assert!(message.contains("in 7 txs"), "{}", message);
+
+        assert_eq!(slow_drip_alert(&["60:3:30"]).await, None);
+    }
+
+    #[tokio::test]
+    async fn concentrated_launch_is_sent_as_its_own_alert_type() {
+        let detector = detector(|config| config.launch_holder_alert_percent = 20.0);
+        let mut sent = detector.alerts.subscribe();
+        watched(&detector, MINT, |_| {});
+
+        detector.check_launch_concentration(MINT, 10.0).await;
+        assert!(sent.try_recv().is_err());
+
+        detector.check_launch_concentration(MINT, 45.0).await;
+        let alert = sent.try_recv().unwrap();
+        assert_eq!(alert.alert_type, "concentrated_launch");
+        assert_eq!(alert.data["mint"], MINT);
+        assert_eq!(alert.data["top_holder_percent"], 45.0);
+        let token = detector.watched_tokens.get(MINT).unwrap();
+        assert_eq!(token.suspicion_score, 30);
+        assert_eq!(token.alerts.last().unwrap().alert_type, "concentrated_launch");
+    }
+
+    #[tokio::test]
+    async fn muted_token_alerts_are_recorded_but_not_sent() {
+        let detector = detector(|config| config.launch_holder_alert_percent = 20.0);
+        let mut sent = detector.alerts.subscribe();
+        let mut token = watched(&detector, MINT, |token| token.muted = true);
+
+        detector.check_launch_concentration(MINT, 45.0).await;
+        RugDetector::trigger_rug_alert(
+            &detector.alerts,
+            &detector.database,
+            &detector.rugs_detected,
+            &detector.alerts_sent,
+            &detector.rug_sender,
+            &mut token,
+            "Liquidity pulled",
+        )
+        .await
+        .unwrap();
+
+        assert!(sent.try_recv().is_err());
+        assert_eq!(detector.alerts_sent.load(Ordering::SeqCst), 0);
+        let recorded = detector.database.get_recent_alerts(10).unwrap();
+        let mut types: Vec<_> = recorded.iter().map(|alert| alert.alert_type.as_str()).collect();
+        types.sort_unstable();
+        assert_eq!(types, ["concentrated_launch", "rug"]);
+        assert!(recorded.iter().all(|alert| alert.muted && alert.title == "TST"));
+        let rug = recorded.iter().find(|alert| alert.alert_type == "rug").unwrap();
+        assert_eq!(rug.message, "Liquidity pulled");
+    }
+}
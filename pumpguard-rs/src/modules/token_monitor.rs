@@ -5,19 +5,27 @@ use chrono::Utc;
 use dashmap::DashMap;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
 use solana_transaction_status::{
-    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiMessage,
+    EncodedConfirmedTransactionWithStatusMeta,
     option_serializer::OptionSerializer,
 };
-use std::collections::{HashSet, VecDeque};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::broadcast;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 
 use crate::config::Config;
-use crate::utils::alerts::TokenAlertInfo;
-use crate::utils::database::TokenRecord;
+use crate::modules::rug_detector::ConfirmedRug;
+use crate::utils::alerts::{CopycatMatch, InstanceTagged, TokenAlertInfo};
+use crate::utils::database::{PendingWrite, TokenRecord};
+use crate::utils::error::PumpResult;
+use crate::utils::solana::TxLossStats;
+use crate::utils::tx_kind::{instruction_kinds, LaunchInstruction};
+use crate::utils::run_flag::RunFlag;
+use crate::utils::work_queue::{QueueStats, WorkQueue};
 use crate::utils::{AlertService, DatabaseService, SolanaService};
 
 /// Token information detected by the monitor
@@ -31,12 +39,85 @@ pub struct DetectedToken {
     pub signature: String,
     pub initial_liquidity: f64,
     pub detected_at: i64,
+    /// Milliseconds between the creation block time and detection; `None` without a block time
+    #[serde(default)]
+    pub detection_lag_ms: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub possible_copycat: Option<CopycatMatch>,
+    /// Supply share of the largest non-curve holder right after launch, when checked
+    #[serde(default)]
+    pub top_holder_percent: Option<f64>,
+    /// Why no new-token alert was sent; `None` when it was alerted
+    #[serde(default)]
+    pub alert_skip_reason: Option<AlertSkipReason>,
+    /// Name/symbol or liquidity was still missing after resolution retries
+    #[serde(default)]
+    pub incomplete_metadata: bool,
 }
 
-/// Token monitor filters
+impl DetectedToken {
+    /// Whether the logs (or a later lookup) gave a real name, symbol and liquidity
+    pub fn has_complete_metadata(&self) -> bool {
+        self.name != UNKNOWN_NAME && self.symbol != UNKNOWN_SYMBOL && self.initial_liquidity > 0.0
+    }
+
+    /// No liquidity and only placeholder name and symbol: a log that matched the create
+    /// pattern but wasn't really a launch, rather than a token worth tracking
+    pub fn is_parse_garbage(&self) -> bool {
+        self.initial_liquidity == 0.0 && self.name == UNKNOWN_NAME && self.symbol == UNKNOWN_SYMBOL
+    }
+}
+
+/// Launch-time supply share of a token's largest non-curve holder, looked up after the
+/// new-token alert so the RPC call doesn't delay it
 #[derive(Debug, Clone)]
+pub struct LaunchHolding {
+    pub mint: String,
+    pub top_holder_percent: f64,
+}
+
+/// Reason a detected token did not produce a new-token alert
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSkipReason {
+    BelowMinLiquidity,
+    AboveMaxLiquidity,
+    BlacklistedCreator,
+    NotWhitelisted,
+    RateLimited,
+    AlertsDisabled,
+    IncompleteMetadata,
+    SpamCreator,
+}
+
+/// Signature waiting for an analysis worker
+enum LaunchJob {
+    /// Carries the configured launch instruction
+    Launch(String),
+    /// Carries only the other instruction; may fill in a detected token's liquidity
+    Enrich(String),
+}
+
+impl LaunchJob {
+    /// Job for a transaction's logs; a launch usually carries both instructions, and only
+    /// the configured one starts a detection so split transactions aren't detected twice
+    fn for_logs(instruction: LaunchInstruction, logs: &[String], signature: String) -> Option<Self> {
+        let kinds = instruction_kinds(logs);
+        if kinds.contains(&instruction.launch_kind()) {
+            Some(Self::Launch(signature))
+        } else if kinds.contains(&instruction.enrich_kind()) {
+            Some(Self::Enrich(signature))
+        } else {
+            None
+        }
+    }
+}
+
+/// Token monitor filters
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenFilters {
     pub min_liquidity_sol: f64,
+    #[serde(serialize_with = "serialize_unbounded", deserialize_with = "deserialize_unbounded")]
     pub max_liquidity_sol: f64,
     pub blacklisted_creators: HashSet<String>,
     pub whitelisted_creators: HashSet<String>,
@@ -44,6 +125,19 @@ pub struct TokenFilters {
     pub alert_new_tokens: bool,
 }
 
+/// JSON has no infinity, so an unbounded limit round-trips as `null`
+fn serialize_unbounded<S: serde::Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+    if value.is_finite() {
+        serializer.serialize_some(value)
+    } else {
+        serializer.serialize_none()
+    }
+}
+
+fn deserialize_unbounded<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::INFINITY))
+}
+
 impl TokenFilters {
     pub fn from_config(config: &Config) -> Self {
         Self {
@@ -78,6 +172,24 @@ pub struct TokenMonitorStats {
     pub alerts_sent: u64,
     pub alerts_skipped: u64,
     pub tokens_tracked: usize,
+    pub tokens_per_minute: usize,
+    /// Launches per minute over the surge baseline window; `None` until enough history
+    #[serde(default)]
+    pub launch_baseline_mean: Option<f64>,
+    #[serde(default)]
+    pub launch_baseline_std_dev: Option<f64>,
+    pub avg_detection_lag_ms: Option<f64>,
+    pub sampling_active: bool,
+    pub sampling_drop_fraction: f64,
+    pub tokens_sampled_out: u64,
+    pub webhook_failures: u64,
+    pub tx_fetch_none: u64,
+    pub tx_parse_none: u64,
+    /// Detections dropped as parse failures by `REJECT_UNPARSED_TOKENS`
+    #[serde(default)]
+    pub parse_rejected: u64,
+    pub queue_depth: u64,
+    pub queue_dropped: u64,
     pub is_running: bool,
 }
 
@@ -121,6 +233,358 @@ impl AlertRateLimiter {
     }
 }
 
+/// Creator whose alerts are suppressed for launching too fast
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuppressedCreator {
+    pub creator: String,
+    /// Launches in the window when the limit was crossed
+    pub launches: usize,
+    pub suppressed_at: String,
+    pub expires_at: String,
+    #[serde(skip)]
+    expires_ms: i64,
+}
+
+/// Result of counting a launch against its creator's rate
+#[derive(Debug, Clone)]
+enum CreatorLaunch {
+    Allowed,
+    Suppressed,
+    /// This launch crossed the limit
+    NewlySuppressed(SuppressedCreator),
+}
+
+/// Sliding-window launch counts per creator, suppressing creators above the limit
+struct CreatorRateLimiter {
+    launches: HashMap<String, VecDeque<i64>>,
+    suppressed: HashMap<String, SuppressedCreator>,
+    max_launches: u32,
+    window_ms: i64,
+    suppress_ms: i64,
+}
+
+impl CreatorRateLimiter {
+    fn new(max_launches: u32, window_secs: u64, suppress_secs: u64) -> Self {
+        Self {
+            launches: HashMap::new(),
+            suppressed: HashMap::new(),
+            max_launches,
+            window_ms: window_secs as i64 * 1000,
+            suppress_ms: suppress_secs as i64 * 1000,
+        }
+    }
+
+    fn record(&mut self, creator: &str) -> CreatorLaunch {
+        if self.max_launches == 0 {
+            return CreatorLaunch::Allowed;
+        }
+        let now = chrono::Utc::now().timestamp_millis();
+        self.prune(now);
+        if self.suppressed.contains_key(creator) {
+            return CreatorLaunch::Suppressed;
+        }
+
+        let window = self.launches.entry(creator.to_string()).or_default();
+        window.push_back(now);
+        let count = window.len();
+        if count <= self.max_launches as usize {
+            return CreatorLaunch::Allowed;
+        }
+
+        self.launches.remove(creator);
+        let suppressed_at = chrono::DateTime::from_timestamp_millis(now).unwrap_or_default();
+        let expires_ms = now + self.suppress_ms;
+        let expires_at = chrono::DateTime::from_timestamp_millis(expires_ms).unwrap_or_default();
+        let suppressed = SuppressedCreator {
+            creator: creator.to_string(),
+            launches: count,
+            suppressed_at: suppressed_at.to_rfc3339(),
+            expires_at: expires_at.to_rfc3339(),
+            expires_ms,
+        };
+        self.suppressed.insert(creator.to_string(), suppressed.clone());
+        CreatorLaunch::NewlySuppressed(suppressed)
+    }
+
+    /// Drop launches outside the window, creators with none left, and expired suppressions
+    fn prune(&mut self, now: i64) {
+        let cutoff = now - self.window_ms;
+        self.launches.retain(|_, window| {
+            while window.front().is_some_and(|&ts| ts < cutoff) {
+                window.pop_front();
+            }
+            !window.is_empty()
+        });
+        self.suppressed.retain(|_, creator| creator.expires_ms > now);
+    }
+
+    fn suppressed(&mut self) -> Vec<SuppressedCreator> {
+        self.prune(chrono::Utc::now().timestamp_millis());
+        let mut creators: Vec<SuppressedCreator> = self.suppressed.values().cloned().collect();
+        creators.sort_by(|a, b| b.suppressed_at.cmp(&a.suppressed_at));
+        creators
+    }
+}
+
+/// Rolling one-minute count of detected tokens, for throughput and burst detection
+struct LaunchRateTracker {
+    timestamps: VecDeque<i64>,
+    burst_threshold: u32,
+    in_burst: bool,
+}
+
+impl LaunchRateTracker {
+    fn new(burst_threshold: u32) -> Self {
+        Self {
+            timestamps: VecDeque::with_capacity(100),
+            burst_threshold,
+            in_burst: false,
+        }
+    }
+
+    fn prune(&mut self, now: i64) {
+        let one_minute_ago = now - 60;
+        while let Some(&ts) = self.timestamps.front() {
+            if ts < one_minute_ago {
+                self.timestamps.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Record a detection. Returns the current rate when it newly crosses the
+    /// burst threshold; the burst re-arms once the rate falls back below it.
+    fn record(&mut self) -> Option<usize> {
+        let now = chrono::Utc::now().timestamp();
+        self.prune(now);
+        self.timestamps.push_back(now);
+
+        let rate = self.timestamps.len();
+        if self.burst_threshold == 0 || rate < self.burst_threshold as usize {
+            self.in_burst = false;
+            return None;
+        }
+        if self.in_burst {
+            return None;
+        }
+        self.in_burst = true;
+        Some(rate)
+    }
+
+    fn rate(&mut self) -> usize {
+        self.prune(chrono::Utc::now().timestamp());
+        self.timestamps.len()
+    }
+}
+
+/// Completed minutes needed before the launch baseline is trusted
+const BASELINE_MIN_MINUTES: usize = 10;
+
+/// A minute whose launch count is far above the rolling baseline
+#[derive(Debug, Clone, Copy)]
+struct LaunchSurge {
+    rate: u32,
+    mean: f64,
+    std_dev: f64,
+    z_score: f64,
+}
+
+/// Per-minute launch counts over a rolling window. A minute is a surge when its count is
+/// `z_threshold` standard deviations above the window's mean, so the bar rises and falls
+/// with how busy launches normally are.
+struct LaunchBaseline {
+    /// Completed minutes, oldest first
+    minutes: VecDeque<u32>,
+    current_minute: Option<i64>,
+    current_count: u32,
+    window: usize,
+    z_threshold: f64,
+    alerted_minute: Option<i64>,
+}
+
+impl LaunchBaseline {
+    fn new(window_minutes: usize, z_threshold: f64) -> Self {
+        Self {
+            minutes: VecDeque::with_capacity(window_minutes),
+            current_minute: None,
+            current_count: 0,
+            window: window_minutes.max(BASELINE_MIN_MINUTES),
+            z_threshold,
+            alerted_minute: None,
+        }
+    }
+
+    /// Close the current minute once `minute` has moved past it; minutes without any
+    /// launch count as zero
+    fn roll(&mut self, minute: i64) {
+        let Some(current) = self.current_minute else {
+            self.current_minute = Some(minute);
+            return;
+        };
+        if minute <= current {
+            return;
+        }
+        self.minutes.push_back(self.current_count);
+        let idle = ((minute - current - 1) as usize).min(self.window);
+        self.minutes.extend(std::iter::repeat_n(0, idle));
+        while self.minutes.len() > self.window {
+            self.minutes.pop_front();
+        }
+        self.current_minute = Some(minute);
+        self.current_count = 0;
+    }
+
+    /// Mean and standard deviation of the completed minutes
+    fn stats(&self) -> Option<(f64, f64)> {
+        if self.minutes.len() < BASELINE_MIN_MINUTES {
+            return None;
+        }
+        let n = self.minutes.len() as f64;
+        let mean = self.minutes.iter().map(|&count| count as f64).sum::<f64>() / n;
+        let variance = self
+            .minutes
+            .iter()
+            .map(|&count| (count as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        Some((mean, variance.sqrt()))
+    }
+
+    /// Count a launch in `minute` (Unix minutes). Returns the surge the first time that
+    /// minute's count crosses the threshold.
+    fn record(&mut self, minute: i64) -> Option<LaunchSurge> {
+        self.roll(minute);
+        self.current_count += 1;
+        if self.z_threshold <= 0.0 || self.alerted_minute == Some(minute) {
+            return None;
+        }
+
+        let (mean, std_dev) = self.stats()?;
+        // On a perfectly flat baseline a single extra launch would be infinitely unusual
+        let z_score = (self.current_count as f64 - mean) / std_dev.max(1.0);
+        if z_score < self.z_threshold {
+            return None;
+        }
+        self.alerted_minute = Some(minute);
+        Some(LaunchSurge {
+            rate: self.current_count,
+            mean,
+            std_dev,
+            z_score,
+        })
+    }
+}
+
+/// Under launch spikes, fetch only a sample of create transactions. Creates whose
+/// initial buy already meets the alert liquidity floor are always fetched.
+struct LaunchSampler {
+    threshold: u32,
+    keep_rate: f64,
+    decisions: VecDeque<(i64, bool)>, // (timestamp, dropped) over the last minute
+}
+
+impl LaunchSampler {
+    fn new(threshold: u32, keep_rate: f64) -> Self {
+        Self {
+            threshold,
+            keep_rate,
+            decisions: VecDeque::with_capacity(100),
+        }
+    }
+
+    fn prune(&mut self, now: i64) {
+        let one_minute_ago = now - 60;
+        while let Some(&(ts, _)) = self.decisions.front() {
+            if ts < one_minute_ago {
+                self.decisions.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn is_active(&self, rate: usize) -> bool {
+        self.threshold > 0 && rate >= self.threshold as usize
+    }
+
+    fn should_fetch(&mut self, rate: usize, priority: bool) -> bool {
+        let now = chrono::Utc::now().timestamp();
+        self.prune(now);
+
+        let fetch = !self.is_active(rate) || priority || rand::random::<f64>() < self.keep_rate;
+        self.decisions.push_back((now, !fetch));
+        fetch
+    }
+
+    /// Share of create events skipped in the last minute
+    fn drop_fraction(&mut self) -> f64 {
+        self.prune(chrono::Utc::now().timestamp());
+        if self.decisions.is_empty() {
+            return 0.0;
+        }
+        let dropped = self.decisions.iter().filter(|(_, dropped)| *dropped).count();
+        dropped as f64 / self.decisions.len() as f64
+    }
+}
+
+/// Posts every parsed launch to an integrator's endpoint, independent of filters and alerts
+#[derive(Clone)]
+struct NewTokenWebhook {
+    client: reqwest::Client,
+    url: String,
+    instance: Option<String>,
+    failures: Arc<AtomicU64>,
+}
+
+impl NewTokenWebhook {
+    fn from_config(config: &Config) -> Option<Self> {
+        let url = config.new_token_webhook_url.clone()?;
+        let timeout = std::time::Duration::from_millis(config.new_token_webhook_timeout_ms);
+        let client = reqwest::Client::builder()
+            .connect_timeout(timeout)
+            .timeout(timeout)
+            .build()
+            .ok()?;
+        Some(Self {
+            client,
+            url,
+            instance: config.instance_name.clone(),
+            failures: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    /// Fire-and-forget; a slow or failing endpoint never delays detection
+    fn send(&self, token: &DetectedToken) {
+        let request = self.client.post(&self.url).json(&InstanceTagged {
+            payload: token,
+            instance: self.instance.as_deref(),
+        });
+        let failures = Arc::clone(&self.failures);
+        tokio::spawn(async move {
+            let failed = match request.send().await {
+                Ok(response) => !response.status().is_success(),
+                Err(_) => true,
+            };
+            if failed {
+                failures.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+    }
+}
+
+/// Anchor discriminator of pump.fun's `TradeEvent` (first 8 bytes of sha256("event:TradeEvent"))
+/// Placeholders used when the creation logs carry no name or symbol
+const UNKNOWN_NAME: &str = "Unknown";
+const UNKNOWN_SYMBOL: &str = "UNK";
+
+/// Metadata resolution attempts, and the wait between them, when complete metadata is required
+const METADATA_RESOLVE_ATTEMPTS: u32 = 3;
+const METADATA_RETRY_DELAY_MS: u64 = 1000;
+
+const TRADE_EVENT_DISCRIMINATOR: [u8; 8] = [189, 219, 127, 211, 78, 230, 97, 238];
+
 /// Token Monitor module
 pub struct TokenMonitor {
     config: Config,
@@ -128,16 +592,26 @@ pub struct TokenMonitor {
     alerts: Arc<AlertService>,
     database: Arc<DatabaseService>,
 
-    is_running: Arc<AtomicBool>,
+    is_running: RunFlag,
     detected_tokens: Arc<DashMap<String, DetectedToken>>,
     filters: Arc<RwLock<TokenFilters>>,
     rate_limiter: Arc<RwLock<AlertRateLimiter>>,
+    launch_rate: Arc<RwLock<LaunchRateTracker>>,
+    launch_baseline: Arc<RwLock<LaunchBaseline>>,
+    creator_limiter: Arc<RwLock<CreatorRateLimiter>>,
+    sampler: Arc<RwLock<LaunchSampler>>,
+    webhook: Option<NewTokenWebhook>,
 
     tokens_detected: Arc<AtomicU64>,
     alerts_sent: Arc<AtomicU64>,
     alerts_skipped: Arc<AtomicU64>,
+    tx_loss: TxLossStats,
+    parse_rejected: Arc<AtomicU64>,
+    tokens_sampled_out: Arc<AtomicU64>,
+    analysis_queue: QueueStats,
 
     new_token_sender: broadcast::Sender<DetectedToken>,
+    launch_holding_sender: broadcast::Sender<LaunchHolding>,
 }
 
 impl TokenMonitor {
@@ -149,8 +623,26 @@ impl TokenMonitor {
         database: Arc<DatabaseService>,
     ) -> Self {
         let (new_token_sender, _) = broadcast::channel(10000);
-        let filters = TokenFilters::from_config(&config);
+        let (launch_holding_sender, _) = broadcast::channel(10000);
+        let mut filters = TokenFilters::from_config(&config);
+        match database.get_blacklisted_creators() {
+            Ok(saved) => filters.blacklisted_creators.extend(saved),
+            Err(e) => warn!(target: "TOKEN_MONITOR", "Failed to load blacklisted creators: {}", e),
+        }
         let rate_limiter = AlertRateLimiter::new(config.max_alerts_per_minute);
+        let launch_rate = LaunchRateTracker::new(config.launch_burst_threshold);
+        let launch_baseline =
+            LaunchBaseline::new(config.launch_surge_baseline_minutes, config.launch_surge_zscore);
+        let creator_limiter = CreatorRateLimiter::new(
+            config.creator_launch_limit,
+            config.creator_launch_window_secs,
+            config.creator_suppress_secs,
+        );
+        let sampler = LaunchSampler::new(config.sampling_threshold, config.sampling_rate);
+        let webhook = NewTokenWebhook::from_config(&config);
+        if config.new_token_webhook_url.is_some() && webhook.is_none() {
+            warn!(target: "TOKEN_MONITOR", "New-token webhook client could not be built, webhook disabled");
+        }
 
         info!(
             target: "TOKEN_MONITOR",
@@ -165,14 +657,24 @@ impl TokenMonitor {
             solana,
             alerts,
             database,
-            is_running: Arc::new(AtomicBool::new(false)),
+            is_running: RunFlag::default(),
             detected_tokens: Arc::new(DashMap::new()),
             filters: Arc::new(RwLock::new(filters)),
             rate_limiter: Arc::new(RwLock::new(rate_limiter)),
+            launch_rate: Arc::new(RwLock::new(launch_rate)),
+            launch_baseline: Arc::new(RwLock::new(launch_baseline)),
+            creator_limiter: Arc::new(RwLock::new(creator_limiter)),
+            sampler: Arc::new(RwLock::new(sampler)),
+            webhook,
             tokens_detected: Arc::new(AtomicU64::new(0)),
             alerts_sent: Arc::new(AtomicU64::new(0)),
             alerts_skipped: Arc::new(AtomicU64::new(0)),
+            tx_loss: TxLossStats::default(),
+            parse_rejected: Arc::new(AtomicU64::new(0)),
+            tokens_sampled_out: Arc::new(AtomicU64::new(0)),
+            analysis_queue: QueueStats::default(),
             new_token_sender,
+            launch_holding_sender,
         }
     }
 
@@ -181,64 +683,78 @@ impl TokenMonitor {
         self.new_token_sender.subscribe()
     }
 
+    /// Subscribe to launch holder lookups, sent shortly after the matching new token event
+    pub fn subscribe_launch_holdings(&self) -> broadcast::Receiver<LaunchHolding> {
+        self.launch_holding_sender.subscribe()
+    }
+
     /// Start the token monitor
     pub async fn start(&self) -> Result<()> {
-        if self.is_running.load(Ordering::SeqCst) {
+        let Some(run) = self.is_running.start() else {
             warn!(target: "TOKEN_MONITOR", "Already running");
             return Ok(());
-        }
-
-        self.is_running.store(true, Ordering::SeqCst);
+        };
         info!(target: "TOKEN_MONITOR", "🆕 Starting Token Monitor...");
 
         // Subscribe to Solana logs
         let mut log_receiver = self.solana.subscribe_logs();
 
-        let is_running = Arc::clone(&self.is_running);
-        let solana = Arc::clone(&self.solana);
         let alerts = Arc::clone(&self.alerts);
-        let database = Arc::clone(&self.database);
-        let detected_tokens = Arc::clone(&self.detected_tokens);
         let filters = Arc::clone(&self.filters);
-        let rate_limiter = Arc::clone(&self.rate_limiter);
+        let launch_rate = Arc::clone(&self.launch_rate);
+        let launch_baseline = Arc::clone(&self.launch_baseline);
+        let sampler = Arc::clone(&self.sampler);
+        let tokens_sampled_out = Arc::clone(&self.tokens_sampled_out);
         let tokens_detected = Arc::clone(&self.tokens_detected);
-        let alerts_sent = Arc::clone(&self.alerts_sent);
-        let alerts_skipped = Arc::clone(&self.alerts_skipped);
-        let new_token_sender = self.new_token_sender.clone();
+
+        let launch_instruction = self.config.launch_instruction;
+
+        // Token fetches run on a worker pool so slow RPC calls don't hold up log reception
+        let monitor = Arc::new(self.clone());
+        let queue = WorkQueue::spawn(
+            "token_monitor",
+            self.config.analysis_queue_size,
+            self.config.analysis_workers,
+            self.analysis_queue.clone(),
+            move |job: LaunchJob| {
+                let monitor = Arc::clone(&monitor);
+                async move {
+                    match job {
+                        LaunchJob::Launch(signature) => monitor.process_log_job(&signature).await,
+                        LaunchJob::Enrich(signature) => monitor.enrich_liquidity(&signature).await,
+                    }
+                }
+            },
+        );
 
         tokio::spawn(async move {
             info!(target: "TOKEN_MONITOR", "Token Monitor active - watching for new token launches");
 
-            while is_running.load(Ordering::SeqCst) {
+            while run.is_current() {
                 match log_receiver.recv().await {
                     Ok(log_event) => {
-                        // Check for token creation
-                        let is_create = log_event.logs.iter().any(|log| {
-                            log.contains("Program log: Instruction: Create")
-                                || log.contains("Program log: Instruction: Initialize")
-                        });
-
-                        if is_create {
-                            // Throttle: small delay between processing
-                            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-                            
-                            if let Err(e) = Self::handle_new_token(
-                                &solana,
-                                &alerts,
-                                &database,
-                                &detected_tokens,
-                                &filters,
-                                &rate_limiter,
-                                &tokens_detected,
-                                &alerts_sent,
-                                &alerts_skipped,
-                                &new_token_sender,
-                                &log_event.signature,
-                            )
-                            .await
-                            {
-                                error!(target: "TOKEN_MONITOR", "Error handling new token: {}", e);
+                        let signature = log_event.signature;
+                        match LaunchJob::for_logs(launch_instruction, &log_event.logs, signature) {
+                            Some(LaunchJob::Launch(signature)) => {
+                                // Under a launch spike, skip fetching some low-value creates
+                                // but keep counting them
+                                let rate = launch_rate.write().rate();
+                                let priority = Self::initial_buy_sol(&log_event.logs)
+                                    .is_some_and(|sol| sol >= filters.read().min_liquidity_sol);
+                                if !sampler.write().should_fetch(rate, priority) {
+                                    tokens_detected.fetch_add(1, Ordering::SeqCst);
+                                    tokens_sampled_out.fetch_add(1, Ordering::SeqCst);
+                                    Self::record_launch(&alerts, &launch_rate, &launch_baseline)
+                                        .await;
+                                    continue;
+                                }
+
+                                queue.push(LaunchJob::Launch(signature));
                             }
+                            Some(enrich) => {
+                                queue.push(enrich);
+                            }
+                            None => {}
                         }
                     }
                     Err(broadcast::error::RecvError::Lagged(n)) => {
@@ -256,12 +772,94 @@ impl TokenMonitor {
         Ok(())
     }
 
+    /// Fetch and handle one create transaction on a worker
+    async fn process_log_job(&self, signature: &str) {
+        // Throttle: small delay between processing
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+        if let Err(e) = Self::handle_new_token(
+            &self.solana,
+            &self.alerts,
+            &self.database,
+            &self.detected_tokens,
+            &self.filters,
+            &self.rate_limiter,
+            &self.launch_rate,
+            &self.launch_baseline,
+            &self.creator_limiter,
+            &self.tokens_detected,
+            &self.alerts_sent,
+            &self.alerts_skipped,
+            &self.tx_loss,
+            &self.parse_rejected,
+            &self.new_token_sender,
+            &self.launch_holding_sender,
+            self.webhook.as_ref(),
+            self.config.copycat_max_distance,
+            self.config.new_token_confirm_delay_ms,
+            self.config.launch_holder_alert_percent,
+            self.config.require_complete_metadata,
+            self.config.alert_incomplete_metadata,
+            self.config.creator_auto_blacklist,
+            self.config.reject_unparsed_tokens,
+            signature,
+        )
+        .await
+        {
+            error!(target: "TOKEN_MONITOR", "Error handling new token: {}", e);
+        }
+    }
+
+    /// Take the liquidity of a transaction carrying only the non-launch instruction,
+    /// when its mint was already detected with less
+    async fn enrich_liquidity(&self, signature: &str) {
+        let tx = match self.solana.get_transaction(signature).await {
+            Ok(Some(tx)) => tx,
+            Ok(None) => {
+                self.tx_loss.record_fetch_none();
+                return;
+            }
+            Err(e) => {
+                debug!(target: "TOKEN_MONITOR", "Enrichment fetch failed for {}: {}", signature, e);
+                return;
+            }
+        };
+        let Some(parsed) = Self::parse_token_creation(&tx) else {
+            return;
+        };
+
+        let Some(mut token) = self.detected_tokens.get_mut(&parsed.mint) else {
+            debug!(
+                target: "TOKEN_MONITOR",
+                "{} initialized before its launch was detected; not enriched",
+                SolanaService::shorten_address(&parsed.mint, 4)
+            );
+            return;
+        };
+        if parsed.initial_liquidity <= token.initial_liquidity {
+            return;
+        }
+        debug!(
+            target: "TOKEN_MONITOR",
+            "{} liquidity enriched: {:.4} -> {:.4} SOL",
+            token.symbol,
+            token.initial_liquidity,
+            parsed.initial_liquidity
+        );
+        token.initial_liquidity = parsed.initial_liquidity;
+        self.database.write(PendingWrite::InitialLiquidity {
+            mint: parsed.mint,
+            liquidity: parsed.initial_liquidity,
+        });
+    }
+
     /// Stop the token monitor
     pub fn stop(&self) {
-        self.is_running.store(false, Ordering::SeqCst);
+        self.is_running.stop();
         info!(target: "TOKEN_MONITOR", "Token Monitor stopping...");
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn handle_new_token(
         solana: &Arc<SolanaService>,
         alerts: &Arc<AlertService>,
@@ -269,10 +867,24 @@ impl TokenMonitor {
         detected_tokens: &Arc<DashMap<String, DetectedToken>>,
         filters: &Arc<RwLock<TokenFilters>>,
         rate_limiter: &Arc<RwLock<AlertRateLimiter>>,
+        launch_rate: &Arc<RwLock<LaunchRateTracker>>,
+        launch_baseline: &Arc<RwLock<LaunchBaseline>>,
+        creator_limiter: &Arc<RwLock<CreatorRateLimiter>>,
         tokens_detected: &Arc<AtomicU64>,
         alerts_sent: &Arc<AtomicU64>,
         alerts_skipped: &Arc<AtomicU64>,
+        tx_loss: &TxLossStats,
+        parse_rejected: &Arc<AtomicU64>,
         new_token_sender: &broadcast::Sender<DetectedToken>,
+        launch_holding_sender: &broadcast::Sender<LaunchHolding>,
+        webhook: Option<&NewTokenWebhook>,
+        copycat_max_distance: usize,
+        confirm_delay_ms: u64,
+        launch_holder_alert_percent: f64,
+        require_complete_metadata: bool,
+        alert_incomplete_metadata: bool,
+        creator_auto_blacklist: bool,
+        reject_unparsed_tokens: bool,
         signature: &str,
     ) -> Result<()> {
         // Small delay to ensure transaction is confirmed
@@ -280,12 +892,18 @@ impl TokenMonitor {
 
         let tx = match solana.get_transaction(signature).await? {
             Some(tx) => tx,
-            None => return Ok(()),
+            None => {
+                tx_loss.record_fetch_none();
+                return Ok(());
+            }
         };
 
-        let token_info = match Self::parse_token_creation(&tx) {
+        let mut token_info = match Self::parse_token_creation(&tx) {
             Some(info) => info,
-            None => return Ok(()),
+            None => {
+                tx_loss.record_parse_none();
+                return Ok(());
+            }
         };
 
         // Check if we already have this token (duplicate detection)
@@ -293,33 +911,120 @@ impl TokenMonitor {
             return Ok(());
         }
 
-        // Check filters and get alert settings
-        let (should_alert, alert_enabled) = {
-            let filters = filters.read();
+        // Integrators doing their own filtering get the raw launch before any of ours
+        if let Some(webhook) = webhook {
+            webhook.send(&token_info);
+        }
 
-            // Check blacklist
-            if filters.blacklisted_creators.contains(&token_info.creator) {
-                return Ok(());
+        // The creation tx's SOL delta misses dev buys that land right after it,
+        // so optionally wait and read the settled bonding curve balance instead
+        if confirm_delay_ms > 0 {
+            tokio::time::sleep(tokio::time::Duration::from_millis(confirm_delay_ms)).await;
+            if let Ok(mint) = Pubkey::from_str(&token_info.mint) {
+                let bonding_curve = solana.derive_bonding_curve(&mint);
+                match solana.get_balance(&bonding_curve.to_string()).await {
+                    Ok(balance) => token_info.initial_liquidity = balance,
+                    Err(e) => {
+                        warn!(target: "TOKEN_MONITOR", "Liquidity re-read failed for {}: {}", token_info.symbol, e);
+                    }
+                }
             }
+        }
 
-            // Check whitelist
-            if !filters.whitelisted_creators.is_empty()
-                && !filters.whitelisted_creators.contains(&token_info.creator)
-            {
-                return Ok(());
-            }
+        if require_complete_metadata && !Self::resolve_metadata(solana, &mut token_info).await {
+            warn!(
+                target: "TOKEN_MONITOR",
+                "Metadata still incomplete for {} after {} attempts",
+                SolanaService::shorten_address(&token_info.mint, 4),
+                METADATA_RESOLVE_ATTEMPTS
+            );
+            token_info.incomplete_metadata = true;
+        }
 
-            // Check liquidity bounds
-            let meets_liquidity = token_info.initial_liquidity >= filters.min_liquidity_sol
-                && token_info.initial_liquidity <= filters.max_liquidity_sol;
+        // Checked after the liquidity re-read and metadata lookups, which can still rescue it
+        if reject_unparsed_tokens && token_info.is_parse_garbage() {
+            parse_rejected.fetch_add(1, Ordering::SeqCst);
+            debug!(
+                target: "TOKEN_MONITOR",
+                "Rejected unparsed detection {} from {}",
+                SolanaService::shorten_address(&token_info.mint, 4),
+                signature
+            );
+            return Ok(());
+        }
+
+        // Spam creators are handled as a group: one spam_creator alert, then their launches
+        // are still recorded but not alerted until the suppression expires
+        let creator_launch = creator_limiter.write().record(&token_info.creator);
+        if let CreatorLaunch::NewlySuppressed(suppressed) = &creator_launch {
+            Self::suppress_spam_creator(alerts, filters, creator_limiter, suppressed, creator_auto_blacklist)
+                .await;
+        }
 
-            (meets_liquidity, filters.alert_new_tokens)
+        // Check filters; the first one that fails is recorded on the token
+        let filter_skip = {
+            let filters = filters.read();
+
+            if let Some(skip) = Self::creator_filter(&filters, &token_info.creator) {
+                Some(skip)
+            } else if !matches!(creator_launch, CreatorLaunch::Allowed) {
+                Some(AlertSkipReason::SpamCreator)
+            } else if token_info.incomplete_metadata && !alert_incomplete_metadata {
+                Some(AlertSkipReason::IncompleteMetadata)
+            } else if token_info.initial_liquidity < filters.min_liquidity_sol {
+                Some(AlertSkipReason::BelowMinLiquidity)
+            } else if token_info.initial_liquidity > filters.max_liquidity_sol {
+                Some(AlertSkipReason::AboveMaxLiquidity)
+            } else if !filters.alert_new_tokens {
+                Some(AlertSkipReason::AlertsDisabled)
+            } else {
+                None
+            }
         };
+        // Creators filtered out by the black/whitelist are not handed to the rug detector either
+        let ignored_creator = matches!(
+            filter_skip,
+            Some(AlertSkipReason::BlacklistedCreator | AlertSkipReason::NotWhitelisted)
+        );
 
         tokens_detected.fetch_add(1, Ordering::SeqCst);
 
+        // Scammers often relaunch a rugged token under a near-identical name. Placeholder
+        // names would match every other unresolved token that rugged, so they're skipped.
+        let placeholder = token_info.name == UNKNOWN_NAME || token_info.symbol == UNKNOWN_SYMBOL;
+        let copycat_of = if placeholder {
+            None
+        } else {
+            database
+                .find_similar_rugged(&token_info.name, &token_info.symbol, copycat_max_distance)
+                .ok()
+                .flatten()
+        };
+        if let Some(original) = copycat_of {
+            warn!(
+                target: "TOKEN_MONITOR",
+                "{} looks like a copycat of rugged {} ({})",
+                token_info.symbol,
+                original.symbol,
+                SolanaService::shorten_address(&original.mint, 4)
+            );
+            token_info.possible_copycat = Some(CopycatMatch {
+                mint: original.mint,
+                name: original.name,
+                symbol: original.symbol,
+                rug_reason: original.rug_reason,
+            });
+        }
+
+        Self::record_launch(alerts, launch_rate, launch_baseline).await;
+
+        // Rate limiting is checked last so filtered tokens don't use up the budget
+        token_info.alert_skip_reason = filter_skip.or_else(|| {
+            (!rate_limiter.write().can_send()).then_some(AlertSkipReason::RateLimited)
+        });
+
         // Save to database (always save, regardless of filters)
-        let _ = database.save_token(&TokenRecord {
+        database.write(PendingWrite::Token(TokenRecord {
             mint: token_info.mint.clone(),
             name: token_info.name.clone(),
             symbol: token_info.symbol.clone(),
@@ -331,7 +1036,9 @@ impl TokenMonitor {
             is_rugged: false,
             rug_reason: None,
             last_updated: Utc::now().to_rfc3339(),
-        });
+            is_muted: false,
+            is_graduated: false,
+        }));
 
         // Store in memory
         detected_tokens.insert(token_info.mint.clone(), token_info.clone());
@@ -344,17 +1051,12 @@ impl TokenMonitor {
         }
 
         // Broadcast new token event (for rug detector linking)
-        let _ = new_token_sender.send(token_info.clone());
-
-        // Only send alert if it passes filters and alerts are enabled
-        if should_alert && alert_enabled {
-            // Check rate limiter
-            let can_send = {
-                let mut limiter = rate_limiter.write();
-                limiter.can_send()
-            };
+        if !ignored_creator {
+            let _ = new_token_sender.send(token_info.clone());
+        }
 
-            if can_send {
+        match token_info.alert_skip_reason {
+            None => {
                 alerts_sent.fetch_add(1, Ordering::SeqCst);
 
                 info!(
@@ -368,22 +1070,213 @@ impl TokenMonitor {
                 );
 
                 let _ = alerts
-                    .alert_new_token(&TokenAlertInfo {
-                        mint: token_info.mint.clone(),
-                        name: token_info.name.clone(),
-                        symbol: token_info.symbol.clone(),
-                        creator: token_info.creator.clone(),
-                        initial_liquidity: Some(token_info.initial_liquidity),
-                    })
+                    .alert_new_token(
+                        &TokenAlertInfo {
+                            mint: token_info.mint.clone(),
+                            name: token_info.name.clone(),
+                            symbol: token_info.symbol.clone(),
+                            creator: token_info.creator.clone(),
+                            initial_liquidity: Some(token_info.initial_liquidity),
+                        },
+                        token_info.possible_copycat.as_ref(),
+                        token_info.detection_lag_ms,
+                        token_info.incomplete_metadata,
+                    )
                     .await;
-            } else {
+            }
+            Some(AlertSkipReason::RateLimited) => {
                 alerts_skipped.fetch_add(1, Ordering::SeqCst);
             }
+            Some(_) => {}
+        }
+
+        // A dev already sitting on a big share of supply is a rug setup before trading starts;
+        // the rug detector alerts on it once the lookup comes back
+        if launch_holder_alert_percent > 0.0 && !ignored_creator {
+            tokio::spawn({
+                let solana = Arc::clone(solana);
+                let detected_tokens = Arc::clone(detected_tokens);
+                let launch_holding_sender = launch_holding_sender.clone();
+                let mint = token_info.mint.clone();
+                let symbol = token_info.symbol.clone();
+
+                async move {
+                    let percent = match solana.get_top_holders(&mint, 1).await {
+                        Ok(holders) => holders.first().map_or(0.0, |h| h.percent),
+                        Err(e) => {
+                            warn!(target: "TOKEN_MONITOR", "Top holder lookup failed for {}: {}", symbol, e);
+                            return;
+                        }
+                    };
+                    if let Some(mut token) = detected_tokens.get_mut(&mint) {
+                        token.top_holder_percent = Some(percent);
+                    }
+                    let holding = LaunchHolding { mint, top_holder_percent: percent };
+                    let _ = launch_holding_sender.send(holding);
+                }
+            });
         }
 
         Ok(())
     }
 
+    /// Fill in what the creation tx left out: name/symbol from Metaplex metadata and a zero
+    /// liquidity from the bonding curve, retrying while the accounts settle.
+    /// Returns whether the token ended up complete.
+    async fn resolve_metadata(solana: &Arc<SolanaService>, token: &mut DetectedToken) -> bool {
+        for attempt in 0..METADATA_RESOLVE_ATTEMPTS {
+            if token.has_complete_metadata() {
+                return true;
+            }
+            if attempt > 0 {
+                tokio::time::sleep(tokio::time::Duration::from_millis(METADATA_RETRY_DELAY_MS)).await;
+            }
+
+            if token.name == UNKNOWN_NAME || token.symbol == UNKNOWN_SYMBOL {
+                match solana.get_token_metadata(&token.mint).await {
+                    Ok(Some((name, symbol))) => {
+                        if !name.is_empty() {
+                            token.name = name;
+                        }
+                        if !symbol.is_empty() {
+                            token.symbol = symbol;
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        warn!(target: "TOKEN_MONITOR", "Metadata lookup failed for {}: {}", token.mint, e);
+                    }
+                }
+            }
+
+            if token.initial_liquidity <= 0.0 {
+                if let Ok(mint) = Pubkey::from_str(&token.mint) {
+                    let bonding_curve = solana.derive_bonding_curve(&mint);
+                    if let Ok(balance) = solana.get_balance(&bonding_curve.to_string()).await {
+                        token.initial_liquidity = balance;
+                    }
+                }
+            }
+        }
+        token.has_complete_metadata()
+    }
+
+    /// Alert once for a creator that just crossed the launch limit, optionally blacklisting it
+    async fn suppress_spam_creator(
+        alerts: &Arc<AlertService>,
+        filters: &Arc<RwLock<TokenFilters>>,
+        creator_limiter: &Arc<RwLock<CreatorRateLimiter>>,
+        suppressed: &SuppressedCreator,
+        auto_blacklist: bool,
+    ) {
+        let window_secs = creator_limiter.read().window_ms / 1000;
+        warn!(
+            target: "TOKEN_MONITOR",
+            "Spam creator {}: {} launches in {}s, suppressing alerts until {}",
+            SolanaService::shorten_address(&suppressed.creator, 4),
+            suppressed.launches,
+            window_secs,
+            suppressed.expires_at
+        );
+        if auto_blacklist {
+            filters.write().blacklisted_creators.insert(suppressed.creator.clone());
+        }
+
+        let mut message = format!(
+            "Creator `{}` launched {} tokens in {}s\nAlerts suppressed until {}",
+            suppressed.creator, suppressed.launches, window_secs, suppressed.expires_at
+        );
+        if auto_blacklist {
+            message.push_str("\nCreator blacklisted");
+        }
+        let _ = alerts
+            .send_alert(
+                "spam_creator",
+                "Spam Creator",
+                &message,
+                serde_json::json!({
+                    "creator": suppressed.creator,
+                    "launches": suppressed.launches,
+                    "window_secs": window_secs,
+                    "expires_at": suppressed.expires_at,
+                    "blacklisted": auto_blacklist,
+                }),
+            )
+            .await;
+    }
+
+    /// Creators currently suppressed for launching too fast, newest first
+    pub fn get_suppressed_creators(&self) -> Vec<SuppressedCreator> {
+        self.creator_limiter.write().suppressed()
+    }
+
+    /// Count a launch towards the per-minute rate and alert when it starts a burst
+    /// or surges above the rolling baseline
+    async fn record_launch(
+        alerts: &Arc<AlertService>,
+        launch_rate: &Arc<RwLock<LaunchRateTracker>>,
+        launch_baseline: &Arc<RwLock<LaunchBaseline>>,
+    ) {
+        // A sudden jump in launches usually means a coordinated spam wave
+        let burst = launch_rate.write().record();
+        if let Some(rate) = burst {
+            warn!(target: "TOKEN_MONITOR", "Launch burst: {} tokens in the last minute", rate);
+            let _ = alerts
+                .send_alert(
+                    "launch_burst",
+                    "Launch Burst",
+                    &format!("{} tokens launched in the last minute", rate),
+                    serde_json::json!({ "tokens_per_minute": rate }),
+                )
+                .await;
+        }
+
+        let minute = chrono::Utc::now().timestamp().div_euclid(60);
+        let surge = launch_baseline.write().record(minute);
+        if let Some(surge) = surge {
+            warn!(
+                target: "TOKEN_MONITOR",
+                "Launch surge: {} tokens this minute vs baseline {:.1} ± {:.1} (z = {:.1})",
+                surge.rate,
+                surge.mean,
+                surge.std_dev,
+                surge.z_score
+            );
+            let _ = alerts
+                .send_alert(
+                    "launch_surge",
+                    "Launch Surge",
+                    &format!(
+                        "{} tokens launched this minute, {:.1} standard deviations above the \
+                         usual {:.1}/min",
+                        surge.rate, surge.z_score, surge.mean
+                    ),
+                    serde_json::json!({
+                        "tokens_this_minute": surge.rate,
+                        "baseline_mean": surge.mean,
+                        "baseline_std_dev": surge.std_dev,
+                        "z_score": surge.z_score,
+                    }),
+                )
+                .await;
+        }
+    }
+
+    /// SOL spent by the creator's initial buy, read from the `TradeEvent` in the logs
+    fn initial_buy_sol(logs: &[String]) -> Option<f64> {
+        use base64::Engine;
+
+        logs.iter()
+            .filter_map(|log| log.strip_prefix("Program data: "))
+            .filter_map(|payload| base64::engine::general_purpose::STANDARD.decode(payload.trim()).ok())
+            .find(|bytes| bytes.len() >= 48 && bytes[..8] == TRADE_EVENT_DISCRIMINATOR)
+            .map(|bytes| {
+                // discriminator (8) + mint (32), then sol_amount as u64 lamports
+                let lamports = u64::from_le_bytes(bytes[40..48].try_into().unwrap());
+                lamports as f64 / 1_000_000_000.0
+            })
+    }
+
     fn parse_token_creation(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Option<DetectedToken> {
         let meta = tx.transaction.meta.as_ref()?;
 
@@ -394,24 +1287,11 @@ impl TokenMonitor {
         }?;
 
         // Get creator from first signer (fee payer)
-        let creator = match &tx.transaction.transaction {
-            EncodedTransaction::Json(ui_tx) => match &ui_tx.message {
-                UiMessage::Parsed(msg) => msg
-                    .account_keys
-                    .first()
-                    .map(|k| k.pubkey.clone()),
-                UiMessage::Raw(msg) => msg.account_keys.first().cloned(),
-            },
-            EncodedTransaction::LegacyBinary(_) | EncodedTransaction::Binary(_, _) => {
-                // Binary encoded transactions - skip
-                None
-            }
-            _ => None,
-        }?;
+        let creator = SolanaService::account_keys(tx).into_iter().next()?;
 
         // Parse name/symbol from logs
-        let mut name = "Unknown".to_string();
-        let mut symbol = "UNK".to_string();
+        let mut name = UNKNOWN_NAME.to_string();
+        let mut symbol = UNKNOWN_SYMBOL.to_string();
 
         if let OptionSerializer::Some(logs) = &meta.log_messages {
             for log in logs {
@@ -436,20 +1316,28 @@ impl TokenMonitor {
             }
         };
 
-        let signature = match &tx.transaction.transaction {
-            EncodedTransaction::Json(ui_tx) => ui_tx.signatures.first().cloned().unwrap_or_default(),
-            _ => String::new(),
-        };
+        let signature = SolanaService::signature_of(tx);
+
+        // Block time is in seconds; without it the creation time falls back to detection time
+        let now = Utc::now();
+        let detected_at = now.timestamp_millis();
+        let created = tx.block_time.and_then(|t| chrono::DateTime::from_timestamp(t, 0));
+        let detection_lag_ms = tx.block_time.map(|t| (detected_at - t * 1000).max(0));
 
         Some(DetectedToken {
             mint,
             name,
             symbol,
             creator,
-            created_at: Utc::now().to_rfc3339(),
+            created_at: created.unwrap_or(now).to_rfc3339(),
             signature,
             initial_liquidity,
-            detected_at: Utc::now().timestamp_millis(),
+            detected_at,
+            detection_lag_ms,
+            possible_copycat: None,
+            top_holder_percent: None,
+            alert_skip_reason: None,
+            incomplete_metadata: false,
         })
     }
 
@@ -464,29 +1352,145 @@ impl TokenMonitor {
         info!(target: "TOKEN_MONITOR", "Filter updated: {} = {}", key, value);
     }
 
-    /// Blacklist a creator address
-    pub fn blacklist_creator(&self, address: &str) {
-        let mut filters = self.filters.write();
-        filters.blacklisted_creators.insert(address.to_string());
-        info!(target: "TOKEN_MONITOR", "Creator blacklisted: {}", address);
+    /// Blacklist a creator address; the entry is saved and survives restarts
+    pub fn blacklist_creator(&self, address: &str, reason: &str) {
+        self.filters.write().blacklisted_creators.insert(address.to_string());
+        if let Err(e) = self.database.save_blacklisted_creator(address, reason) {
+            warn!(target: "TOKEN_MONITOR", "Failed to save blacklisted creator {}: {}", address, e);
+        }
+        info!(target: "TOKEN_MONITOR", "Creator blacklisted: {} ({})", address, reason);
+    }
+
+    /// Blacklist the creator of a confirmed rug unless it is trusted or already listed.
+    /// Returns whether the creator was added.
+    pub fn blacklist_rugger(&self, rug: &ConfirmedRug) -> bool {
+        if rug.trusted || rug.creator.is_empty() {
+            return false;
+        }
+        if self.filters.read().blacklisted_creators.contains(&rug.creator) {
+            return false;
+        }
+        warn!(
+            target: "TOKEN_MONITOR",
+            "Auto-blacklisting creator {} after rug of {}: {}",
+            rug.creator, rug.symbol, rug.reason
+        );
+        self.blacklist_creator(&rug.creator, &format!("rugged {}", rug.mint));
+        true
     }
 
-    /// Whitelist a creator address
-    pub fn whitelist_creator(&self, address: &str) {
+    /// Blacklist the creator of every rug received on `rugs`, until the sender closes
+    pub fn link_rug_blacklist(&self, mut rugs: broadcast::Receiver<ConfirmedRug>) {
+        let token_monitor = self.clone();
+
+        tokio::spawn(async move {
+            loop {
+                match rugs.recv().await {
+                    Ok(rug) => {
+                        token_monitor.blacklist_rugger(&rug);
+                    }
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!(target: "TOKEN_MONITOR", "Rug blacklist link lagged {} messages", n);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    /// Why the creator black/whitelist keeps launches by `creator` from alerting, if it does
+    #[cfg(test)]
+    pub fn creator_skip_reason(&self, creator: &str) -> Option<AlertSkipReason> {
+        Self::creator_filter(&self.filters.read(), creator)
+    }
+
+    fn creator_filter(filters: &TokenFilters, creator: &str) -> Option<AlertSkipReason> {
+        if filters.blacklisted_creators.contains(creator) {
+            Some(AlertSkipReason::BlacklistedCreator)
+        } else if !filters.whitelisted_creators.is_empty()
+            && !filters.whitelisted_creators.contains(creator)
+        {
+            Some(AlertSkipReason::NotWhitelisted)
+        } else {
+            None
+        }
+    }
+
+    /// Take a creator off the blacklist, including a saved entry; false if it wasn't listed
+    pub fn unblacklist_creator(&self, address: &str) -> PumpResult<bool> {
+        let in_memory = self.filters.write().blacklisted_creators.remove(address);
+        let saved = self.database.remove_blacklisted_creator(address)?;
+        if in_memory || saved {
+            info!(target: "TOKEN_MONITOR", "Creator removed from blacklist: {}", address);
+        }
+        Ok(in_memory || saved)
+    }
+
+    /// Get a copy of the current filters
+    pub fn get_filters(&self) -> TokenFilters {
+        self.filters.read().clone()
+    }
+
+    /// Merge imported filters: limits are taken from the import, creator lists are unioned
+    pub fn merge_filters(&self, imported: TokenFilters) {
         let mut filters = self.filters.write();
-        filters.whitelisted_creators.insert(address.to_string());
-        info!(target: "TOKEN_MONITOR", "Creator whitelisted: {}", address);
+        filters.min_liquidity_sol = imported.min_liquidity_sol;
+        filters.max_liquidity_sol = imported.max_liquidity_sol;
+        filters.max_alerts_per_minute = imported.max_alerts_per_minute;
+        filters.alert_new_tokens = imported.alert_new_tokens;
+        filters.blacklisted_creators.extend(imported.blacklisted_creators);
+        filters.whitelisted_creators.extend(imported.whitelisted_creators);
+        info!(target: "TOKEN_MONITOR", "Filters merged from imported state");
     }
 
     /// Get monitor statistics
     pub fn get_stats(&self) -> TokenMonitorStats {
+        let tokens_per_minute = self.launch_rate.write().rate();
+        let (sampling_active, sampling_drop_fraction) = {
+            let mut sampler = self.sampler.write();
+            (sampler.is_active(tokens_per_minute), sampler.drop_fraction())
+        };
+        let baseline = {
+            let mut baseline = self.launch_baseline.write();
+            baseline.roll(chrono::Utc::now().timestamp().div_euclid(60));
+            baseline.stats()
+        };
         TokenMonitorStats {
             tokens_detected: self.tokens_detected.load(Ordering::SeqCst),
             alerts_sent: self.alerts_sent.load(Ordering::SeqCst),
             alerts_skipped: self.alerts_skipped.load(Ordering::SeqCst),
             tokens_tracked: self.detected_tokens.len(),
-            is_running: self.is_running.load(Ordering::SeqCst),
+            tokens_per_minute,
+            launch_baseline_mean: baseline.map(|(mean, _)| mean),
+            launch_baseline_std_dev: baseline.map(|(_, std_dev)| std_dev),
+            avg_detection_lag_ms: self.avg_detection_lag_ms(),
+            sampling_active,
+            sampling_drop_fraction,
+            tokens_sampled_out: self.tokens_sampled_out.load(Ordering::SeqCst),
+            webhook_failures: self
+                .webhook
+                .as_ref()
+                .map_or(0, |webhook| webhook.failures.load(Ordering::SeqCst)),
+            tx_fetch_none: self.tx_loss.fetch_none(),
+            tx_parse_none: self.tx_loss.parse_none(),
+            parse_rejected: self.parse_rejected.load(Ordering::SeqCst),
+            queue_depth: self.analysis_queue.depth(),
+            queue_dropped: self.analysis_queue.dropped(),
+            is_running: self.is_running.is_running(),
+        }
+    }
+
+    /// Mean detection lag over the tokens currently held in memory
+    pub fn avg_detection_lag_ms(&self) -> Option<f64> {
+        let lags: Vec<i64> = self
+            .detected_tokens
+            .iter()
+            .filter_map(|e| e.detection_lag_ms)
+            .collect();
+        if lags.is_empty() {
+            return None;
         }
+        Some(lags.iter().sum::<i64>() as f64 / lags.len() as f64)
     }
 
     /// Get recent tokens
@@ -496,19 +1500,19 @@ impl TokenMonitor {
             .iter()
             .map(|e| e.value().clone())
             .collect();
-        tokens.sort_by(|a, b| b.detected_at.cmp(&a.detected_at));
+        tokens.sort_by_key(|t| std::cmp::Reverse(t.detected_at));
         tokens.truncate(limit);
         tokens
     }
 
-    /// Get a specific token by mint
-    pub fn get_token(&self, mint: &str) -> Option<DetectedToken> {
-        self.detected_tokens.get(mint).map(|e| e.value().clone())
-    }
-
     /// Check if running
     pub fn is_running(&self) -> bool {
-        self.is_running.load(Ordering::SeqCst)
+        self.is_running.is_running()
+    }
+
+    /// Queued and in-progress analysis jobs, which still finish after `stop`
+    pub fn pending_work(&self) -> u64 {
+        self.analysis_queue.pending()
     }
 
     /// Get detected tokens map (for rug detector linking)
@@ -524,14 +1528,232 @@ impl Clone for TokenMonitor {
             solana: Arc::clone(&self.solana),
             alerts: Arc::clone(&self.alerts),
             database: Arc::clone(&self.database),
-            is_running: Arc::clone(&self.is_running),
+            is_running: self.is_running.clone(),
             detected_tokens: Arc::clone(&self.detected_tokens),
             filters: Arc::clone(&self.filters),
             rate_limiter: Arc::clone(&self.rate_limiter),
+            launch_rate: Arc::clone(&self.launch_rate),
+            launch_baseline: Arc::clone(&self.launch_baseline),
+            creator_limiter: Arc::clone(&self.creator_limiter),
+            sampler: Arc::clone(&self.sampler),
+            webhook: self.webhook.clone(),
             tokens_detected: Arc::clone(&self.tokens_detected),
             alerts_sent: Arc::clone(&self.alerts_sent),
             alerts_skipped: Arc::clone(&self.alerts_skipped),
+            tx_loss: self.tx_loss.clone(),
+            parse_rejected: Arc::clone(&self.parse_rejected),
+            tokens_sampled_out: Arc::clone(&self.tokens_sampled_out),
+            analysis_queue: self.analysis_queue.clone(),
             new_token_sender: self.new_token_sender.clone(),
+            launch_holding_sender: self.launch_holding_sender.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logs(instructions: &[&str]) -> Vec<String> {
+        instructions
+            .iter()
+            .map(|instruction| format!("Program log: Instruction: {}", instruction))
+            .collect()
+    }
+
+    fn job(instruction: LaunchInstruction, instructions: &[&str]) -> Option<&'static str> {
+        match LaunchJob::for_logs(instruction, &logs(instructions), "sig".to_string()) {
+            Some(LaunchJob::Launch(_)) => Some("launch"),
+            Some(LaunchJob::Enrich(_)) => Some("enrich"),
+            None => None,
+        }
+    }
+
+    #[test]
+    fn launch_in_one_transaction_is_detected_once() {
+        for instruction in [LaunchInstruction::Create, LaunchInstruction::Initialize] {
+            assert_eq!(job(instruction, &["Create", "Initialize", "Buy"]), Some("launch"));
+        }
+        assert_eq!(job(LaunchInstruction::Create, &["Buy"]), None);
+    }
+
+    #[test]
+    fn split_launch_detects_on_the_configured_instruction_only() {
+        assert_eq!(job(LaunchInstruction::Create, &["Create"]), Some("launch"));
+        assert_eq!(job(LaunchInstruction::Create, &["InitializeMint2"]), Some("enrich"));
+
+        assert_eq!(job(LaunchInstruction::Initialize, &["Initialize"]), Some("launch"));
+        assert_eq!(job(LaunchInstruction::Initialize, &["Create"]), Some("enrich"));
+    }
+
+    #[test]
+    fn launch_surge_fires_only_on_the_spike() {
+        let mut baseline = LaunchBaseline::new(10, 3.0);
+        // Per-minute launch counts: a quiet stretch, one spike, then back to normal
+        let series = [3, 5, 4, 4, 3, 5, 4, 3, 5, 4, 5, 4, 30, 4, 3, 5];
+        let mut surges = Vec::new();
+        for (minute, &count) in series.iter().enumerate() {
+            for _ in 0..count {
+                if let Some(surge) = baseline.record(minute as i64) {
+                    surges.push((minute, surge));
+                }
+            }
+        }
+
+        assert_eq!(surges.len(), 1, "{:?}", surges);
+        let (minute, surge) = surges[0];
+        assert_eq!(minute, 12);
+        assert!(surge.z_score >= 3.0);
+        assert!((surge.mean - 4.1).abs() < 0.01, "{}", surge.mean);
+    }
+
+    #[test]
+    fn launch_baseline_waits_for_history_and_counts_idle_minutes() {
+        let mut baseline = LaunchBaseline::new(10, 3.0);
+        // Nine minutes of history is not enough to judge a spike
+        for minute in 0..9 {
+            baseline.record(minute);
+        }
+        assert!((0..50).all(|_| baseline.record(9).is_none()));
+
+        // Jumping to minute 15 closes the busy minute and adds five idle ones; the window
+        // keeps the last ten: four quiet minutes, the busy one and the idle stretch
+        baseline.record(15);
+        let (mean, _) = baseline.stats().unwrap();
+        assert!((mean - 5.4).abs() < 0.01, "{}", mean);
+    }
+
+    #[test]
+    fn zero_liquidity_placeholder_detections_are_parse_garbage() {
+        let detected = |name: &str, symbol: &str, initial_liquidity: f64| DetectedToken {
+            mint: "mint".to_string(),
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            creator: "creator".to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            signature: "sig".to_string(),
+            initial_liquidity,
+            detected_at: 0,
+            detection_lag_ms: None,
+            possible_copycat: None,
+            top_holder_percent: None,
+            alert_skip_reason: None,
+            incomplete_metadata: false,
+        };
+
+        assert!(detected(UNKNOWN_NAME, UNKNOWN_SYMBOL, 0.0).is_parse_garbage());
+        // A real launch, or one with any real field, is kept
+        assert!(!detected("Real Token", "REAL", 1.5).is_parse_garbage());
+        assert!(!detected(UNKNOWN_NAME, UNKNOWN_SYMBOL, 0.2).is_parse_garbage());
+        assert!(!detected("Real Token", UNKNOWN_SYMBOL, 0.0).is_parse_garbage());
+        assert!(!detected(UNKNOWN_NAME, "REAL", 0.0).is_parse_garbage());
+    }
+
+    const RUGGER: &str = "Dev1111111111111111111111111111111111111111";
+    const RELAUNCH_MINT: &str = "So11111111111111111111111111111111111111112";
+
+    /// JSON-RPC endpoint answering `getTransaction` with `RUGGER` launching `RELAUNCH_MINT`
+    /// for 1 SOL and every other method besides the client's version probe with an empty value
+    async fn relaunch_rpc() -> String {
+        use axum::{routing::post, Json, Router};
+        use serde_json::{json, Value};
+
+        let launch = json!({
+            "slot": 1,
+            "blockTime": 1_700_000_000,
+            "transaction": {
+                "signatures": [solana_sdk::signature::Signature::default().to_string()],
+                "message": {
+                    "accountKeys": [
+                        { "pubkey": RUGGER, "writable": true, "signer": true },
+                        { "pubkey": RELAUNCH_MINT, "writable": true, "signer": true },
+                    ],
+                    "recentBlockhash": "11111111111111111111111111111111",
+                    "instructions": [],
+                },
+            },
+            "meta": {
+                "err": null,
+                "status": { "Ok": null },
+                "fee": 5000,
+                "preBalances": [2_000_000_000u64, 0u64],
+                "postBalances": [1_000_000_000u64, 0u64],
+                "logMessages": ["Program log: name: Relaunch", "Program log: symbol: RUG2"],
+                "postTokenBalances": [{
+                    "accountIndex": 1,
+                    "mint": RELAUNCH_MINT,
+                    "owner": RUGGER,
+                    "uiTokenAmount": {
+                        "uiAmount": 1000.0,
+                        "decimals": 6,
+                        "amount": "1000000000",
+                        "uiAmountString": "1000",
+                    },
+                }],
+            },
+        });
+        let app = Router::new().route(
+            "/",
+            post(move |Json(request): Json<Value>| async move {
+                let result = match request["method"].as_str() {
+                    Some("getVersion") => json!({ "solana-core": "1.18.0" }),
+                    Some("getTransaction") => launch,
+                    _ => json!({ "context": { "slot": 1 }, "value": null }),
+                };
+                Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        url
+    }
+
+    #[tokio::test]
+    async fn rug_alert_blacklists_the_creator_and_skips_their_next_launch() {
+        use crate::modules::RugDetector;
+
+        let rpc_url = relaunch_rpc().await;
+        let mut config = Config::for_tests();
+        config.rpc_url = rpc_url.clone();
+        config.rpc_urls = vec![rpc_url];
+        config.new_token_confirm_delay_ms = 0;
+        config.require_complete_metadata = false;
+        config.launch_holder_alert_percent = 0.0;
+        let solana = Arc::new(SolanaService::new(config.clone()));
+        let alerts = Arc::new(AlertService::new(config.clone()));
+        let database = Arc::new(DatabaseService::new(":memory:").unwrap());
+        let token_monitor = TokenMonitor::new(
+            config.clone(),
+            Arc::clone(&solana),
+            Arc::clone(&alerts),
+            Arc::clone(&database),
+        );
+        let rug_detector =
+            RugDetector::new(config, solana, Arc::clone(&alerts), Arc::clone(&database));
+        token_monitor.link_rug_blacklist(rug_detector.subscribe_rugs());
+
+        rug_detector.watch_token("RuggedMint", "Rugged", "RUG", RUGGER, 10.0);
+        rug_detector.flag_token("RuggedMint", true, "LP pulled").await.unwrap();
+        // The link blacklists on its own task
+        for _ in 0..100 {
+            if token_monitor.creator_skip_reason(RUGGER).is_some() {
+                break;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
         }
+        assert_eq!(database.get_blacklisted_creators().unwrap(), [RUGGER]);
+
+        let mut new_tokens = token_monitor.subscribe_new_tokens();
+        let mut sent = alerts.subscribe();
+        token_monitor
+            .process_log_job(&solana_sdk::signature::Signature::default().to_string())
+            .await;
+
+        let relaunch = token_monitor.detected_tokens().get(RELAUNCH_MINT).unwrap().clone();
+        assert_eq!(relaunch.creator, RUGGER);
+        assert_eq!(relaunch.alert_skip_reason, Some(AlertSkipReason::BlacklistedCreator));
+        assert!(new_tokens.try_recv().is_err());
+        assert!(sent.try_recv().is_err());
     }
 }
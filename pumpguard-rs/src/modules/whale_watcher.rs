@@ -5,20 +5,28 @@ use chrono::Utc;
 use dashmap::DashMap;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
 use solana_transaction_status::{
-    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiMessage,
-    option_serializer::OptionSerializer,
+    EncodedConfirmedTransactionWithStatusMeta,
+    UiTransactionStatusMeta, option_serializer::OptionSerializer,
 };
-use std::collections::{HashSet, VecDeque};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::broadcast;
 use tokio::time::{interval, Duration};
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 
 use crate::config::Config;
-use crate::utils::alerts::TokenAlertInfo;
-use crate::utils::database::{TransactionRecord, WalletRecord};
+use crate::utils::alerts::{
+    AccumulationWindow, SandwichLeg, SuppressedActivity, TokenAlertInfo, WalletHistory,
+};
+use crate::utils::database::{PendingWrite, TransactionRecord, WalletRecord, WalletTradeSummary};
+use crate::utils::solana::{TxLossStats, PUMP_TOKEN_DECIMALS};
+use crate::utils::tx_kind::{instruction_kinds, TxKind};
+use crate::utils::run_flag::RunFlag;
+use crate::utils::work_queue::{QueueStats, WorkQueue};
 use crate::utils::{AlertService, DatabaseService, SolanaService};
 
 /// Transaction info for whale tracking
@@ -30,6 +38,35 @@ pub struct TxInfo {
     pub tx_type: String,
     pub amount_sol: f64,
     pub amount_tokens: f64,
+    #[serde(default = "default_token_decimals")]
+    pub token_decimals: u8,
+    pub timestamp: i64,
+    /// Slot the transaction landed in (0 = unknown)
+    #[serde(default)]
+    pub slot: u64,
+}
+
+fn default_token_decimals() -> u8 {
+    PUMP_TOKEN_DECIMALS
+}
+
+/// Whale trade made by the creator of the traded token
+#[derive(Debug, Clone)]
+pub struct CreatorActivity {
+    pub mint: String,
+    pub tx_type: String,
+    pub amount_sol: f64,
+}
+
+/// Distinct wallets buying one token within a few slots of each other, the footprint of
+/// a bundled launch
+#[derive(Debug, Clone)]
+pub struct CoordinatedBuy {
+    pub mint: String,
+    pub wallets: Vec<String>,
+    pub total_sol: f64,
+    pub first_slot: u64,
+    /// Millis of the earliest buy in the cluster
     pub timestamp: i64,
 }
 
@@ -53,16 +90,170 @@ pub struct TokenMovement {
     pub net_flow: f64,
     pub unique_buyers: HashSet<String>,
     pub unique_sellers: HashSet<String>,
+    /// Millis of the latest trade, used to pick eviction victims
+    pub last_activity: i64,
+    /// Multi-window accumulation score as of the latest trade or pattern pass
+    pub accumulation_score: f64,
+    /// An accumulation alert went out and the score hasn't dropped below the threshold since
+    pub accumulation_alerted: bool,
+    /// A coordinated buy was already reported for this token
+    pub coordinated_buy_reported: bool,
 }
 
 /// Whale watcher thresholds
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WhaleThresholds {
     pub whale_threshold_sol: f64,
     pub alert_on_accumulation: bool,
     pub alert_on_dump: bool,
     pub accumulation_window_ms: i64,
     pub min_transactions_for_pattern: usize,
+    /// Net SOL flow over the window a pattern also needs, so offsetting trades don't count
+    #[serde(default)]
+    pub min_pattern_net_flow_sol: f64,
+    pub whale_alert_cooldown_secs: u64,
+    /// Most mints tracked for movers at once; the least recently traded are evicted (0 = unlimited)
+    pub max_tracked_tokens: usize,
+    pub alert_on_creator_activity: bool,
+    /// Alert when a wallet sells one token and buys another within the rotation window
+    #[serde(default)]
+    pub alert_on_rotation: bool,
+    #[serde(default)]
+    pub rotation_window_secs: u64,
+    #[serde(default)]
+    pub rotation_min_sell_sol: f64,
+    #[serde(default)]
+    pub rotation_min_buy_sol: f64,
+    /// Attach the wallet's trading history to whale alerts
+    #[serde(default)]
+    pub alert_with_history: bool,
+    /// Windows averaged into a token's accumulation score
+    #[serde(default)]
+    pub accumulation_windows_secs: Vec<u64>,
+    #[serde(default)]
+    pub accumulation_buy_weight: f64,
+    #[serde(default)]
+    pub accumulation_flow_weight: f64,
+    #[serde(default)]
+    pub accumulation_buyer_weight: f64,
+    /// Score at which an accumulation alert fires (0 = off)
+    #[serde(default)]
+    pub accumulation_alert_score: f64,
+    /// Alert when a wallet trades just before and after another wallet's trade of the same token
+    #[serde(default)]
+    pub alert_on_sandwich: bool,
+    #[serde(default)]
+    pub sandwich_window_slots: u64,
+    #[serde(default)]
+    pub sandwich_min_victim_sol: f64,
+    /// Distinct wallets buying within `coordinated_buy_window_slots` that make a coordinated
+    /// buy (0 = off)
+    #[serde(default)]
+    pub coordinated_buy_min_wallets: usize,
+    #[serde(default)]
+    pub coordinated_buy_window_slots: u64,
+    #[serde(default)]
+    pub coordinated_buy_min_sol: f64,
+}
+
+impl WhaleThresholds {
+    /// How long trades are kept per token: the pattern window or the longest scoring window
+    fn movement_retention_ms(&self) -> i64 {
+        let longest = self.accumulation_windows_secs.iter().max().copied().unwrap_or(0);
+        self.accumulation_window_ms.max(longest as i64 * 1000)
+    }
+}
+
+/// Front-run, victim trade and back-run: the attacker's first trade matches the victim's
+/// direction and the second reverses it
+#[derive(Debug, Clone)]
+struct Sandwich {
+    front_run: TxInfo,
+    victim: TxInfo,
+    back_run: TxInfo,
+}
+
+impl Sandwich {
+    /// SOL the attacker came out ahead, before priority fees and tips
+    fn extracted_sol(&self) -> f64 {
+        if self.front_run.tx_type == "buy" {
+            self.back_run.amount_sol - self.front_run.amount_sol
+        } else {
+            self.front_run.amount_sol - self.back_run.amount_sol
+        }
+    }
+}
+
+fn sandwich_leg(tx: &TxInfo) -> SandwichLeg {
+    SandwichLeg {
+        signature: tx.signature.clone(),
+        slot: tx.slot,
+        amount_sol: tx.amount_sol,
+    }
+}
+
+/// Most wallet history summaries cached before expired ones are dropped
+const MAX_CACHED_HISTORIES: usize = 10_000;
+
+/// Stored-transaction summaries per wallet, reused for `ttl_ms` so alert bursts from one
+/// wallet don't query the database each time
+#[derive(Debug)]
+struct WalletHistoryCache {
+    ttl_ms: i64,
+    /// Wallet -> (millis fetched, summary)
+    entries: DashMap<String, (i64, WalletTradeSummary)>,
+}
+
+impl WalletHistoryCache {
+    fn new(ttl_secs: u64) -> Self {
+        Self {
+            ttl_ms: ttl_secs as i64 * 1000,
+            entries: DashMap::new(),
+        }
+    }
+
+    /// History for an alert on `wallet`, whose in-memory volume is already up to date
+    fn history(&self, database: &DatabaseService, wallet: &WatchedWallet) -> Option<WalletHistory> {
+        let now = Utc::now().timestamp_millis();
+        let cached = self
+            .entries
+            .get(&wallet.address)
+            .filter(|entry| now - entry.0 < self.ttl_ms)
+            .map(|entry| entry.1.clone());
+
+        let summary = match cached {
+            Some(summary) => summary,
+            None => {
+                let summary = match database.get_wallet_summary(&wallet.address) {
+                    Ok(summary) => summary,
+                    Err(e) => {
+                        debug!(target: "WHALE_WATCHER", "Wallet history lookup failed: {}", e);
+                        return None;
+                    }
+                };
+                if self.entries.len() >= MAX_CACHED_HISTORIES {
+                    self.entries.retain(|_, entry| now - entry.0 < self.ttl_ms);
+                }
+                self.entries.insert(wallet.address.clone(), (now, summary.clone()));
+                summary
+            }
+        };
+
+        Some(WalletHistory {
+            total_volume_sol: wallet.total_volume,
+            trades: summary.trades,
+            tokens_traded: summary.tokens_traded,
+            net_sol_flow: summary.sell_sol - summary.buy_sol,
+        })
+    }
+}
+
+/// Per-wallet alert cooldown state
+#[derive(Debug, Clone, Default)]
+struct WhaleCooldown {
+    last_alert_at: i64,
+    suppressed_trades: u32,
+    suppressed_net_sol: f64,
 }
 
 /// Whale watcher statistics
@@ -73,18 +264,87 @@ pub struct WhaleWatcherStats {
     pub whales_identified: u64,
     pub accumulation_alerts: u64,
     pub dump_alerts: u64,
+    pub creator_activity_alerts: u64,
+    #[serde(default)]
+    pub rotation_alerts: u64,
+    #[serde(default)]
+    pub sandwich_alerts: u64,
+    #[serde(default)]
+    pub coordinated_buys: u64,
+    pub failed_txs_skipped: u64,
+    pub tx_fetch_none: u64,
+    pub tx_parse_none: u64,
+    pub movements_evicted: u64,
     pub total_volume_tracked: f64,
+    pub queue_depth: u64,
+    pub queue_dropped: u64,
     pub watched_wallets: usize,
     pub tokens_tracked: usize,
     pub is_running: bool,
 }
 
+/// Outcome of a CSV wallet import
+#[derive(Debug, Clone, Serialize)]
+pub struct WalletCsvImport {
+    pub imported: usize,
+    pub duplicates: usize,
+    pub errors: Vec<CsvRowError>,
+}
+
+/// A CSV row that could not be imported (1-based line number)
+#[derive(Debug, Clone, Serialize)]
+pub struct CsvRowError {
+    pub row: usize,
+    pub error: String,
+}
+
+/// Split an `address,label` line; the label may be double-quoted with `""` escapes
+fn parse_csv_row(line: &str) -> (&str, String) {
+    let (address, label) = line.split_once(',').unwrap_or((line, ""));
+    let label = label.trim();
+    let label = match label.strip_prefix('"').and_then(|l| l.strip_suffix('"')) {
+        Some(quoted) => quoted.replace("\"\"", "\""),
+        None => label.to_string(),
+    };
+    (address.trim(), label)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 /// Top mover info
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TopMover {
     pub mint: String,
     pub net_flow: f64,
     pub volume: f64,
+    /// Share of volume that was buys, 0.0-1.0
+    #[serde(default)]
+    pub buy_pressure: f64,
+}
+
+/// Token ranked by accumulation score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccumulatingToken {
+    pub mint: String,
+    pub score: f64,
+    pub windows: Vec<AccumulationWindow>,
+}
+
+/// Ranking used for top movers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoverSort {
+    /// Largest absolute net flow, in either direction
+    #[default]
+    NetFlow,
+    Volume,
+    BuyPressure,
 }
 
 /// Whale Watcher module
@@ -94,16 +354,28 @@ pub struct WhaleWatcher {
     alerts: Arc<AlertService>,
     database: Arc<DatabaseService>,
 
-    is_running: Arc<AtomicBool>,
+    is_running: RunFlag,
     watched_wallets: Arc<DashMap<String, WatchedWallet>>,
     token_movements: Arc<DashMap<String, TokenMovement>>,
     thresholds: Arc<RwLock<WhaleThresholds>>,
+    alert_cooldowns: Arc<DashMap<String, WhaleCooldown>>,
+    wallet_histories: Arc<WalletHistoryCache>,
 
     wallets_tracked: Arc<AtomicU64>,
     whales_identified: Arc<AtomicU64>,
     accumulation_alerts: Arc<AtomicU64>,
     dump_alerts: Arc<AtomicU64>,
+    creator_activity_alerts: Arc<AtomicU64>,
+    rotation_alerts: Arc<AtomicU64>,
+    sandwich_alerts: Arc<AtomicU64>,
+    coordinated_buys: Arc<AtomicU64>,
+    failed_txs_skipped: Arc<AtomicU64>,
+    tx_loss: TxLossStats,
+    movements_evicted: Arc<AtomicU64>,
     total_volume_tracked: Arc<RwLock<f64>>,
+    analysis_queue: QueueStats,
+    creator_activity_sender: broadcast::Sender<CreatorActivity>,
+    coordinated_buy_sender: broadcast::Sender<CoordinatedBuy>,
 }
 
 impl WhaleWatcher {
@@ -120,25 +392,70 @@ impl WhaleWatcher {
             alert_on_dump: config.alert_on_dump,
             accumulation_window_ms: 3600000, // 1 hour
             min_transactions_for_pattern: 3,
+            min_pattern_net_flow_sol: config.min_pattern_net_flow_sol,
+            whale_alert_cooldown_secs: config.whale_alert_cooldown_secs,
+            max_tracked_tokens: config.max_tracked_tokens,
+            alert_on_creator_activity: config.alert_on_creator_activity,
+            alert_on_rotation: config.alert_on_rotation,
+            rotation_window_secs: config.rotation_window_secs,
+            rotation_min_sell_sol: config.rotation_min_sell_sol,
+            rotation_min_buy_sol: config.rotation_min_buy_sol,
+            alert_with_history: config.whale_alert_history,
+            accumulation_windows_secs: config.accumulation_windows_secs.clone(),
+            accumulation_buy_weight: config.accumulation_buy_weight,
+            accumulation_flow_weight: config.accumulation_flow_weight,
+            accumulation_buyer_weight: config.accumulation_buyer_weight,
+            accumulation_alert_score: config.accumulation_alert_score,
+            alert_on_sandwich: config.alert_on_sandwich,
+            sandwich_window_slots: config.sandwich_window_slots,
+            sandwich_min_victim_sol: config.sandwich_min_victim_sol,
+            coordinated_buy_min_wallets: config.coordinated_buy_min_wallets,
+            coordinated_buy_window_slots: config.coordinated_buy_window_slots,
+            coordinated_buy_min_sol: config.coordinated_buy_min_sol,
         };
+        let (creator_activity_sender, _) = broadcast::channel(1000);
+        let (coordinated_buy_sender, _) = broadcast::channel(1000);
+        let wallet_histories = WalletHistoryCache::new(config.whale_history_cache_secs);
 
         Self {
             config,
             solana,
             alerts,
             database,
-            is_running: Arc::new(AtomicBool::new(false)),
+            is_running: RunFlag::default(),
             watched_wallets: Arc::new(DashMap::new()),
             token_movements: Arc::new(DashMap::new()),
             thresholds: Arc::new(RwLock::new(thresholds)),
+            alert_cooldowns: Arc::new(DashMap::new()),
+            wallet_histories: Arc::new(wallet_histories),
             wallets_tracked: Arc::new(AtomicU64::new(0)),
             whales_identified: Arc::new(AtomicU64::new(0)),
             accumulation_alerts: Arc::new(AtomicU64::new(0)),
             dump_alerts: Arc::new(AtomicU64::new(0)),
+            creator_activity_alerts: Arc::new(AtomicU64::new(0)),
+            rotation_alerts: Arc::new(AtomicU64::new(0)),
+            sandwich_alerts: Arc::new(AtomicU64::new(0)),
+            coordinated_buys: Arc::new(AtomicU64::new(0)),
+            failed_txs_skipped: Arc::new(AtomicU64::new(0)),
+            tx_loss: TxLossStats::default(),
+            movements_evicted: Arc::new(AtomicU64::new(0)),
             total_volume_tracked: Arc::new(RwLock::new(0.0)),
+            analysis_queue: QueueStats::default(),
+            creator_activity_sender,
+            coordinated_buy_sender,
         }
     }
 
+    /// Get a receiver for whale trades made by a token's creator (for rug detector scoring)
+    pub fn subscribe_creator_activity(&self) -> broadcast::Receiver<CreatorActivity> {
+        self.creator_activity_sender.subscribe()
+    }
+
+    /// Get a receiver for coordinated buys (for rug detector bundle tracking)
+    pub fn subscribe_coordinated_buys(&self) -> broadcast::Receiver<CoordinatedBuy> {
+        self.coordinated_buy_sender.subscribe()
+    }
+
     /// Watch a wallet
     pub fn watch_wallet(&self, address: &str, label: &str) {
         if self.watched_wallets.contains_key(address) {
@@ -161,13 +478,13 @@ impl WhaleWatcher {
 
         self.watched_wallets.insert(address.to_string(), wallet);
 
-        let _ = self.database.save_wallet(&WalletRecord {
+        self.database.write(PendingWrite::Wallet(WalletRecord {
             address: address.to_string(),
             label: label.to_string(),
             total_volume_sol: 0.0,
             last_activity: None,
             is_whale: false,
-        });
+        }));
 
         self.wallets_tracked.fetch_add(1, Ordering::SeqCst);
 
@@ -182,16 +499,6 @@ impl WhaleWatcher {
         );
     }
 
-    /// Unwatch a wallet
-    pub fn unwatch_wallet(&self, address: &str) {
-        self.watched_wallets.remove(address);
-        info!(
-            target: "WHALE_WATCHER",
-            "Stopped watching: {}",
-            SolanaService::shorten_address(address, 4)
-        );
-    }
-
     /// Load known whales from database
     async fn load_known_whales(&self) -> Result<()> {
         let whales = self.database.get_whales()?;
@@ -218,12 +525,10 @@ impl WhaleWatcher {
 
     /// Start the whale watcher
     pub async fn start(&self) -> Result<()> {
-        if self.is_running.load(Ordering::SeqCst) {
+        let Some(run) = self.is_running.start() else {
             warn!(target: "WHALE_WATCHER", "Already running");
             return Ok(());
-        }
-
-        self.is_running.store(true, Ordering::SeqCst);
+        };
         info!(target: "WHALE_WATCHER", "🐋 Starting Whale Watcher...");
 
         // Load known whales from database
@@ -232,58 +537,35 @@ impl WhaleWatcher {
         // Subscribe to Solana logs
         let mut log_receiver = self.solana.subscribe_logs();
 
-        let is_running = Arc::clone(&self.is_running);
-        let solana = Arc::clone(&self.solana);
-        let alerts = Arc::clone(&self.alerts);
-        let database = Arc::clone(&self.database);
-        let watched_wallets = Arc::clone(&self.watched_wallets);
-        let token_movements = Arc::clone(&self.token_movements);
-        let thresholds = Arc::clone(&self.thresholds);
-        let whales_identified = Arc::clone(&self.whales_identified);
-        let accumulation_alerts = Arc::clone(&self.accumulation_alerts);
-        let dump_alerts = Arc::clone(&self.dump_alerts);
-        let total_volume_tracked = Arc::clone(&self.total_volume_tracked);
+
+        // Analysis runs on a worker pool so slow RPC fetches don't hold up log reception
+        let watcher = Arc::new(self.clone());
+        let queue = WorkQueue::spawn(
+            "whale_watcher",
+            self.config.analysis_queue_size,
+            self.config.analysis_workers,
+            self.analysis_queue.clone(),
+            move |(signature, tx_type): (String, &'static str)| {
+                let watcher = Arc::clone(&watcher);
+                async move { watcher.process_log_job(&signature, tx_type).await }
+            },
+        );
 
         // Log handler task
         tokio::spawn({
-            let is_running = Arc::clone(&is_running);
+            let run = run.clone();
             async move {
-                while is_running.load(Ordering::SeqCst) {
+                while run.is_current() {
                     match log_receiver.recv().await {
                         Ok(log_event) => {
                             // Check for buy/sell events
-                            let is_buy = log_event
-                                .logs
-                                .iter()
-                                .any(|log| log.contains("Program log: Instruction: Buy"));
-                            let is_sell = log_event
-                                .logs
-                                .iter()
-                                .any(|log| log.contains("Program log: Instruction: Sell"));
+                            let kinds = instruction_kinds(&log_event.logs);
+                            let is_buy = kinds.contains(&TxKind::Buy);
+                            let is_sell = kinds.contains(&TxKind::Sell);
 
                             if is_buy || is_sell {
-                                // Throttle processing
-                                tokio::time::sleep(Duration::from_millis(100)).await;
-                                
                                 let tx_type = if is_buy { "buy" } else { "sell" };
-                                if let Err(e) = Self::analyze_transaction(
-                                    &solana,
-                                    &alerts,
-                                    &database,
-                                    &watched_wallets,
-                                    &token_movements,
-                                    &thresholds,
-                                    &whales_identified,
-                                    &accumulation_alerts,
-                                    &dump_alerts,
-                                    &total_volume_tracked,
-                                    &log_event.signature,
-                                    tx_type,
-                                )
-                                .await
-                                {
-                                    error!(target: "WHALE_WATCHER", "Error analyzing transaction: {}", e);
-                                }
+                                queue.push((log_event.signature, tx_type));
                             }
                         }
                         Err(broadcast::error::RecvError::Lagged(n)) => {
@@ -299,17 +581,26 @@ impl WhaleWatcher {
 
         // Pattern analysis task
         tokio::spawn({
-            let is_running = Arc::clone(&is_running);
+            let run = run.clone();
+            let alerts = Arc::clone(&self.alerts);
             let token_movements = Arc::clone(&self.token_movements);
             let thresholds = Arc::clone(&self.thresholds);
             let database = Arc::clone(&self.database);
+            let alert_cooldowns = Arc::clone(&self.alert_cooldowns);
 
             async move {
                 let mut interval = interval(Duration::from_secs(60));
 
-                while is_running.load(Ordering::SeqCst) {
+                while run.is_current() {
                     interval.tick().await;
-                    Self::analyze_patterns(&token_movements, &thresholds, &database).await;
+                    Self::analyze_patterns(&alerts, &token_movements, &thresholds, &database)
+                        .await;
+                    let cooldown_secs = thresholds.read().whale_alert_cooldown_secs;
+                    Self::prune_cooldowns(
+                        &alert_cooldowns,
+                        cooldown_secs,
+                        Utc::now().timestamp_millis(),
+                    );
                 }
             }
         });
@@ -318,12 +609,49 @@ impl WhaleWatcher {
         Ok(())
     }
 
+    /// Analyze one matched buy/sell on a worker
+    async fn process_log_job(&self, signature: &str, tx_type: &str) {
+        // Throttle processing
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        if let Err(e) = Self::analyze_transaction(
+            &self.solana,
+            &self.alerts,
+            &self.database,
+            &self.watched_wallets,
+            &self.token_movements,
+            &self.thresholds,
+            &self.alert_cooldowns,
+            &self.wallet_histories,
+            &self.whales_identified,
+            &self.accumulation_alerts,
+            &self.dump_alerts,
+            &self.creator_activity_alerts,
+            &self.rotation_alerts,
+            &self.sandwich_alerts,
+            &self.coordinated_buys,
+            &self.failed_txs_skipped,
+            &self.tx_loss,
+            &self.movements_evicted,
+            &self.total_volume_tracked,
+            &self.creator_activity_sender,
+            &self.coordinated_buy_sender,
+            signature,
+            tx_type,
+        )
+        .await
+        {
+            error!(target: "WHALE_WATCHER", "Error analyzing transaction: {}", e);
+        }
+    }
+
     /// Stop the whale watcher
     pub fn stop(&self) {
-        self.is_running.store(false, Ordering::SeqCst);
+        self.is_running.stop();
         info!(target: "WHALE_WATCHER", "🐋 Whale Watcher stopping...");
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn analyze_transaction(
         solana: &Arc<SolanaService>,
         alerts: &Arc<AlertService>,
@@ -331,10 +659,21 @@ impl WhaleWatcher {
         watched_wallets: &Arc<DashMap<String, WatchedWallet>>,
         token_movements: &Arc<DashMap<String, TokenMovement>>,
         thresholds: &Arc<RwLock<WhaleThresholds>>,
+        alert_cooldowns: &Arc<DashMap<String, WhaleCooldown>>,
+        wallet_histories: &Arc<WalletHistoryCache>,
         whales_identified: &Arc<AtomicU64>,
         accumulation_alerts: &Arc<AtomicU64>,
         dump_alerts: &Arc<AtomicU64>,
+        creator_activity_alerts: &Arc<AtomicU64>,
+        rotation_alerts: &Arc<AtomicU64>,
+        sandwich_alerts: &Arc<AtomicU64>,
+        coordinated_buys: &Arc<AtomicU64>,
+        failed_txs_skipped: &Arc<AtomicU64>,
+        tx_loss: &TxLossStats,
+        movements_evicted: &Arc<AtomicU64>,
         total_volume_tracked: &Arc<RwLock<f64>>,
+        creator_activity_sender: &broadcast::Sender<CreatorActivity>,
+        coordinated_buy_sender: &broadcast::Sender<CoordinatedBuy>,
         signature: &str,
         tx_type: &str,
     ) -> Result<()> {
@@ -342,16 +681,56 @@ impl WhaleWatcher {
 
         let tx = match solana.get_transaction(signature).await? {
             Some(tx) => tx,
-            None => return Ok(()),
+            None => {
+                tx_loss.record_fetch_none();
+                return Ok(());
+            }
         };
 
+        // A failed buy/sell moved no tokens; only its fee left the wallet
+        if SolanaService::transaction_failed(&tx) {
+            failed_txs_skipped.fetch_add(1, Ordering::SeqCst);
+            return Ok(());
+        }
+
         let tx_info = match Self::parse_transaction(&tx, tx_type) {
             Some(info) => info,
-            None => return Ok(()),
+            None => {
+                tx_loss.record_parse_none();
+                return Ok(());
+            }
         };
 
         let thresholds_val = thresholds.read().clone();
 
+        // Checked before this trade joins the wallet's history
+        if let Some(sell) = Self::find_rotation(watched_wallets, &thresholds_val, &tx_info) {
+            let from_token = Self::token_alert_info(database, &sell.mint);
+            let to_token = Self::token_alert_info(database, &tx_info.mint);
+            let elapsed_secs = ((tx_info.timestamp - sell.timestamp).max(0) / 1000) as u64;
+            info!(
+                target: "WHALE_WATCHER",
+                "🔄 Whale rotation: {} sold {:.2} SOL of {} and bought {:.2} SOL of {} within {}s",
+                SolanaService::shorten_address(&tx_info.wallet, 4),
+                sell.amount_sol,
+                from_token.symbol,
+                tx_info.amount_sol,
+                to_token.symbol,
+                elapsed_secs
+            );
+            rotation_alerts.fetch_add(1, Ordering::SeqCst);
+            let _ = alerts
+                .alert_whale_rotation(
+                    &tx_info.wallet,
+                    &from_token,
+                    sell.amount_sol,
+                    &to_token,
+                    tx_info.amount_sol,
+                    elapsed_secs,
+                )
+                .await;
+        }
+
         // Check if this is a whale transaction
         if tx_info.amount_sol >= thresholds_val.whale_threshold_sol {
             Self::handle_whale_transaction(
@@ -359,10 +738,14 @@ impl WhaleWatcher {
                 database,
                 watched_wallets,
                 thresholds,
+                alert_cooldowns,
+                wallet_histories,
                 whales_identified,
                 accumulation_alerts,
                 dump_alerts,
+                creator_activity_alerts,
                 total_volume_tracked,
+                creator_activity_sender,
                 &tx_info,
             )
             .await?;
@@ -372,7 +755,59 @@ impl WhaleWatcher {
         Self::track_wallet_activity(watched_wallets, &thresholds_val, whales_identified, &tx_info);
 
         // Track token movement
-        Self::track_token_movement(token_movements, &thresholds_val, &tx_info);
+        Self::track_token_movement(token_movements, &thresholds_val, movements_evicted, &tx_info);
+
+        // Trades are analyzed out of order, so whichever leg arrives last completes the match
+        let sandwich = token_movements
+            .get(&tx_info.mint)
+            .and_then(|movement| Self::find_sandwich(&movement, &thresholds_val, &tx_info));
+        if let Some(sandwich) = sandwich {
+            let token = Self::token_alert_info(database, &tx_info.mint);
+            let extracted_sol = sandwich.extracted_sol();
+            info!(
+                target: "WHALE_WATCHER",
+                "🥪 Possible sandwich on {}: {} bracketed {}'s {:.2} SOL {}, ~{:.4} SOL extracted",
+                token.symbol,
+                SolanaService::shorten_address(&sandwich.front_run.wallet, 4),
+                SolanaService::shorten_address(&sandwich.victim.wallet, 4),
+                sandwich.victim.amount_sol,
+                sandwich.victim.tx_type,
+                extracted_sol
+            );
+            sandwich_alerts.fetch_add(1, Ordering::SeqCst);
+            let _ = alerts
+                .alert_sandwich(
+                    &token,
+                    &sandwich.front_run.wallet,
+                    &sandwich.victim.wallet,
+                    &sandwich.victim.tx_type,
+                    &sandwich_leg(&sandwich.front_run),
+                    &sandwich_leg(&sandwich.victim),
+                    &sandwich_leg(&sandwich.back_run),
+                    extracted_sol,
+                )
+                .await;
+        }
+
+        // Reported once per token; the rug detector decides whether it was a launch bundle
+        let coordinated = token_movements.get_mut(&tx_info.mint).and_then(|mut movement| {
+            let buy = Self::find_coordinated_buy(&movement, &thresholds_val, &tx_info)?;
+            movement.coordinated_buy_reported = true;
+            Some(buy)
+        });
+        if let Some(buy) = coordinated {
+            let token = Self::token_alert_info(database, &buy.mint);
+            info!(
+                target: "WHALE_WATCHER",
+                "🤝 Coordinated buying on {}: {} wallets bought {:.2} SOL around slot {}",
+                token.symbol,
+                buy.wallets.len(),
+                buy.total_sol,
+                buy.first_slot
+            );
+            coordinated_buys.fetch_add(1, Ordering::SeqCst);
+            let _ = coordinated_buy_sender.send(buy);
+        }
 
         Ok(())
     }
@@ -380,14 +815,12 @@ impl WhaleWatcher {
     fn parse_transaction(tx: &EncodedConfirmedTransactionWithStatusMeta, tx_type: &str) -> Option<TxInfo> {
         let meta = tx.transaction.meta.as_ref()?;
 
-        // Get wallet from first signer
-        let wallet = match &tx.transaction.transaction {
-            EncodedTransaction::Json(ui_tx) => match &ui_tx.message {
-                UiMessage::Parsed(msg) => msg.account_keys.first().map(|k| k.pubkey.clone()),
-                UiMessage::Raw(msg) => msg.account_keys.first().cloned(),
-            },
-            _ => None,
-        }?;
+        let mut account_keys = SolanaService::account_keys(tx);
+        // Balance indices of v0 transactions run on into the lookup-table addresses
+        if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+            account_keys.extend(loaded.writable.iter().cloned());
+            account_keys.extend(loaded.readonly.iter().cloned());
+        }
 
         // Get mint from token balances
         let balances = if tx_type == "buy" {
@@ -403,22 +836,20 @@ impl WhaleWatcher {
         };
         let mint = balances.and_then(|b| b.first()).map(|b| b.mint.clone())?;
 
+        // The fee payer may be a relayer, so prefer the account that actually traded
+        let actor = Self::trade_actor(&account_keys, meta, &mint, tx_type).unwrap_or(0);
+        let wallet = account_keys.get(actor)?.clone();
+
         // Calculate SOL amount
-        let amount_sol = {
-            let pre = &meta.pre_balances;
-            let post = &meta.post_balances;
-            if !pre.is_empty() && !post.is_empty() {
-                let change = (post[0] as i64 - pre[0] as i64).abs();
-                change as f64 / 1_000_000_000.0
-            } else {
-                0.0
-            }
+        let amount_sol = match (meta.pre_balances.get(actor), meta.post_balances.get(actor)) {
+            (Some(&pre), Some(&post)) => (post as i64 - pre as i64).abs() as f64 / 1_000_000_000.0,
+            _ => 0.0,
         };
 
-        let signature = match &tx.transaction.transaction {
-            EncodedTransaction::Json(ui_tx) => ui_tx.signatures.first().cloned().unwrap_or_default(),
-            _ => String::new(),
-        };
+        let (amount_tokens, token_decimals) =
+            Self::token_change(meta, &mint, &wallet).unwrap_or((0.0, PUMP_TOKEN_DECIMALS));
+
+        let signature = SolanaService::signature_of(tx);
 
         Some(TxInfo {
             signature,
@@ -426,20 +857,94 @@ impl WhaleWatcher {
             mint,
             tx_type: tx_type.to_string(),
             amount_sol,
-            amount_tokens: 0.0, // Would need more parsing
+            amount_tokens,
+            token_decimals,
             timestamp: Utc::now().timestamp_millis(),
+            slot: tx.slot,
         })
     }
 
+    /// Index of the one account whose SOL and `mint` balances both moved the way a
+    /// trade of `tx_type` implies (SOL out and tokens in for a buy, the reverse for a
+    /// sell). `None` when no account or more than one fits.
+    fn trade_actor(
+        account_keys: &[String],
+        meta: &UiTransactionStatusMeta,
+        mint: &str,
+        tx_type: &str,
+    ) -> Option<usize> {
+        let (pre_tokens, post_tokens) = match (&meta.pre_token_balances, &meta.post_token_balances) {
+            (OptionSerializer::Some(pre), OptionSerializer::Some(post)) => (pre, post),
+            _ => return None,
+        };
+
+        // Net token change per owner; owners without a pre balance opened their account here
+        let mut token_deltas: HashMap<&str, i128> = HashMap::new();
+        for (balances, sign) in [(post_tokens, 1), (pre_tokens, -1)] {
+            for balance in balances.iter().filter(|b| b.mint == mint) {
+                if let (OptionSerializer::Some(owner), Ok(amount)) =
+                    (&balance.owner, balance.ui_token_amount.amount.parse::<i128>())
+                {
+                    *token_deltas.entry(owner.as_str()).or_default() += sign * amount;
+                }
+            }
+        }
+
+        let buy = tx_type == "buy";
+        let mut actors = token_deltas
+            .into_iter()
+            .filter(|&(_, delta)| if buy { delta > 0 } else { delta < 0 })
+            .filter_map(|(owner, _)| account_keys.iter().position(|key| key == owner))
+            .filter(|&index| {
+                match (meta.pre_balances.get(index), meta.post_balances.get(index)) {
+                    (Some(&pre), Some(&post)) if buy => post < pre,
+                    (Some(&pre), Some(&post)) => post > pre,
+                    _ => false,
+                }
+            });
+
+        match (actors.next(), actors.next()) {
+            (Some(index), None) => Some(index),
+            _ => None,
+        }
+    }
+
+    /// How many `mint` tokens (UI units) `owner` gained or lost, with the mint's decimals
+    fn token_change(meta: &UiTransactionStatusMeta, mint: &str, owner: &str) -> Option<(f64, u8)> {
+        let mut raw_delta: i128 = 0;
+        let mut decimals = None;
+        for (balances, sign) in [(&meta.post_token_balances, 1), (&meta.pre_token_balances, -1)] {
+            let OptionSerializer::Some(balances) = balances else {
+                continue;
+            };
+            for balance in balances.iter().filter(|b| b.mint == mint) {
+                if !matches!(&balance.owner, OptionSerializer::Some(o) if o == owner) {
+                    continue;
+                }
+                if let Ok(amount) = balance.ui_token_amount.amount.parse::<i128>() {
+                    raw_delta += sign * amount;
+                    decimals = Some(balance.ui_token_amount.decimals);
+                }
+            }
+        }
+        let decimals = decimals?;
+        Some((raw_delta.unsigned_abs() as f64 / 10f64.powi(decimals as i32), decimals))
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn handle_whale_transaction(
         alerts: &Arc<AlertService>,
         database: &Arc<DatabaseService>,
         watched_wallets: &Arc<DashMap<String, WatchedWallet>>,
         thresholds: &Arc<RwLock<WhaleThresholds>>,
+        alert_cooldowns: &Arc<DashMap<String, WhaleCooldown>>,
+        wallet_histories: &Arc<WalletHistoryCache>,
         whales_identified: &Arc<AtomicU64>,
         accumulation_alerts: &Arc<AtomicU64>,
         dump_alerts: &Arc<AtomicU64>,
+        creator_activity_alerts: &Arc<AtomicU64>,
         total_volume_tracked: &Arc<RwLock<f64>>,
+        creator_activity_sender: &broadcast::Sender<CreatorActivity>,
         tx_info: &TxInfo,
     ) -> Result<()> {
         let thresholds_val = thresholds.read().clone();
@@ -482,15 +987,15 @@ impl WhaleWatcher {
         }
 
         // Save to database
-        let _ = database.save_wallet(&WalletRecord {
+        database.write(PendingWrite::Wallet(WalletRecord {
             address: tx_info.wallet.clone(),
             label: wallet_data.label.clone(),
             total_volume_sol: wallet_data.total_volume,
             last_activity: wallet_data.last_activity.clone(),
             is_whale: true,
-        });
+        }));
 
-        let _ = database.save_transaction(&TransactionRecord {
+        database.write(PendingWrite::Transaction(TransactionRecord {
             signature: tx_info.signature.clone(),
             mint: tx_info.mint.clone(),
             wallet: tx_info.wallet.clone(),
@@ -498,11 +1003,132 @@ impl WhaleWatcher {
             amount_sol: tx_info.amount_sol,
             amount_tokens: tx_info.amount_tokens,
             timestamp: Utc::now().to_rfc3339(),
-        });
+        }));
+
+        let token_info = Self::token_alert_info(database, &tx_info.mint);
+
+        // Log and alert
+        let is_buy = tx_info.tx_type == "buy";
+        info!(
+            target: "WHALE_WATCHER",
+            "🐋 Whale {}: {:.2} SOL of {} - wallet: {}",
+            if is_buy { "BUYING" } else { "SELLING" },
+            tx_info.amount_sol,
+            token_info.symbol,
+            SolanaService::shorten_address(&tx_info.wallet, 4)
+        );
+
+        // A creator trading their own token in size is a manipulation signal on its own,
+        // so it gets an elevated alert that skips the cooldown instead of the plain one
+        let creator_trade = thresholds_val.alert_on_creator_activity
+            && !token_info.creator.is_empty()
+            && token_info.creator == tx_info.wallet;
+        if creator_trade {
+            warn!(
+                target: "WHALE_WATCHER",
+                "🚩 Creator {} traded own token {}: {} {:.2} SOL",
+                SolanaService::shorten_address(&tx_info.wallet, 4),
+                token_info.symbol,
+                tx_info.tx_type,
+                tx_info.amount_sol
+            );
+            creator_activity_alerts.fetch_add(1, Ordering::SeqCst);
+            let _ = creator_activity_sender.send(CreatorActivity {
+                mint: tx_info.mint.clone(),
+                tx_type: tx_info.tx_type.clone(),
+                amount_sol: tx_info.amount_sol,
+            });
+            let _ = alerts
+                .alert_creator_whale_activity(
+                    &tx_info.tx_type,
+                    &tx_info.wallet,
+                    &token_info,
+                    tx_info.amount_sol,
+                    tx_info.amount_tokens,
+                    tx_info.token_decimals,
+                )
+                .await;
+        }
 
-        // Get token info
-        let token_info = database
-            .get_token(&tx_info.mint)
+        let alert_enabled = if is_buy {
+            thresholds_val.alert_on_accumulation
+        } else {
+            thresholds_val.alert_on_dump
+        };
+
+        if alert_enabled && !creator_trade {
+            // Per-wallet cooldown: suppressed trades are aggregated into the next alert
+            let signed_amount = if is_buy { tx_info.amount_sol } else { -tx_info.amount_sol };
+            let now = Utc::now().timestamp_millis();
+            let cooldown_ms = thresholds_val.whale_alert_cooldown_secs as i64 * 1000;
+
+            let pending_alert = {
+                let mut cooldown = alert_cooldowns.entry(tx_info.wallet.clone()).or_default();
+                if cooldown.last_alert_at > 0 && now - cooldown.last_alert_at < cooldown_ms {
+                    cooldown.suppressed_trades += 1;
+                    cooldown.suppressed_net_sol += signed_amount;
+                    None
+                } else {
+                    let summary = (cooldown.suppressed_trades > 0).then(|| SuppressedActivity {
+                        trades: cooldown.suppressed_trades,
+                        net_sol: cooldown.suppressed_net_sol,
+                    });
+                    *cooldown = WhaleCooldown {
+                        last_alert_at: now,
+                        ..Default::default()
+                    };
+                    Some(summary)
+                }
+            };
+
+            match pending_alert {
+                Some(summary) => {
+                    if is_buy {
+                        accumulation_alerts.fetch_add(1, Ordering::SeqCst);
+                    } else {
+                        dump_alerts.fetch_add(1, Ordering::SeqCst);
+                    }
+                    let history = thresholds_val
+                        .alert_with_history
+                        .then(|| wallet_histories.history(database, &wallet_data))
+                        .flatten();
+                    let _ = alerts
+                        .alert_whale(
+                            &tx_info.tx_type,
+                            &tx_info.wallet,
+                            &token_info,
+                            tx_info.amount_sol,
+                            tx_info.amount_tokens,
+                            tx_info.token_decimals,
+                            summary.as_ref(),
+                            history.as_ref(),
+                        )
+                        .await;
+                }
+                None => {
+                    debug!(
+                        target: "WHALE_WATCHER",
+                        "Whale alert for {} suppressed (cooldown)",
+                        SolanaService::shorten_address(&tx_info.wallet, 4)
+                    );
+                }
+            }
+        }
+
+        {
+            let mut volume = total_volume_tracked.write();
+            *volume += tx_info.amount_sol;
+        }
+
+        watched_wallets.insert(tx_info.wallet.clone(), wallet_data);
+
+        Ok(())
+    }
+
+    /// Token info for an alert (served from the database's token cache after the first lookup)
+    fn token_alert_info(database: &DatabaseService, mint: &str) -> TokenAlertInfo {
+        database
+            .get_token(mint)
             .ok()
             .flatten()
             .map(|t| TokenAlertInfo {
@@ -513,66 +1139,136 @@ impl WhaleWatcher {
                 initial_liquidity: Some(t.initial_liquidity),
             })
             .unwrap_or_else(|| TokenAlertInfo {
-                mint: tx_info.mint.clone(),
+                mint: mint.to_string(),
                 name: "UNKNOWN".to_string(),
                 symbol: "UNK".to_string(),
                 creator: String::new(),
                 initial_liquidity: None,
-            });
+            })
+    }
 
-        // Log and alert
-        if tx_info.tx_type == "buy" {
-            info!(
-                target: "WHALE_WATCHER",
-                "🐋 Whale BUYING: {:.2} SOL of {} - wallet: {}",
-                tx_info.amount_sol,
-                token_info.symbol,
-                SolanaService::shorten_address(&tx_info.wallet, 4)
-            );
+    /// For a large enough buy, the wallet's latest large enough sell of a different token
+    /// within the rotation window
+    fn find_rotation(
+        watched_wallets: &DashMap<String, WatchedWallet>,
+        thresholds: &WhaleThresholds,
+        tx_info: &TxInfo,
+    ) -> Option<TxInfo> {
+        if !thresholds.alert_on_rotation
+            || tx_info.tx_type != "buy"
+            || tx_info.amount_sol < thresholds.rotation_min_buy_sol
+        {
+            return None;
+        }
+        let window_ms = thresholds.rotation_window_secs as i64 * 1000;
+        let wallet = watched_wallets.get(&tx_info.wallet)?;
+        wallet
+            .transactions
+            .iter()
+            .rev()
+            .take_while(|tx| tx_info.timestamp - tx.timestamp <= window_ms)
+            .find(|tx| {
+                tx.tx_type == "sell"
+                    && tx.mint != tx_info.mint
+                    && tx.amount_sol >= thresholds.rotation_min_sell_sol
+            })
+            .cloned()
+    }
 
-            if thresholds_val.alert_on_accumulation {
-                accumulation_alerts.fetch_add(1, Ordering::SeqCst);
-                let _ = alerts
-                    .alert_whale(
-                        "buy",
-                        &tx_info.wallet,
-                        &token_info,
-                        tx_info.amount_sol,
-                        tx_info.amount_tokens,
-                    )
-                    .await;
-            }
-        } else {
-            info!(
-                target: "WHALE_WATCHER",
-                "🐋 Whale SELLING: {:.2} SOL of {} - wallet: {}",
-                tx_info.amount_sol,
-                token_info.symbol,
-                SolanaService::shorten_address(&tx_info.wallet, 4)
-            );
+    /// A profitable sandwich within the slot window that `tx_info` is one leg of. Trades in
+    /// the same slot can't be ordered, so a match there is only a possible sandwich.
+    fn find_sandwich(
+        movement: &TokenMovement,
+        thresholds: &WhaleThresholds,
+        tx_info: &TxInfo,
+    ) -> Option<Sandwich> {
+        if !thresholds.alert_on_sandwich || tx_info.slot == 0 {
+            return None;
+        }
+        let window = thresholds.sandwich_window_slots;
+        let nearby: Vec<&TxInfo> = movement
+            .buys
+            .iter()
+            .chain(movement.sells.iter())
+            .filter(|tx| tx.slot != 0 && tx.slot.abs_diff(tx_info.slot) <= window)
+            .collect();
 
-            if thresholds_val.alert_on_dump {
-                dump_alerts.fetch_add(1, Ordering::SeqCst);
-                let _ = alerts
-                    .alert_whale(
-                        "sell",
-                        &tx_info.wallet,
-                        &token_info,
-                        tx_info.amount_sol,
-                        tx_info.amount_tokens,
-                    )
-                    .await;
+        let mut best: Option<Sandwich> = None;
+        for front_run in &nearby {
+            for back_run in nearby.iter().filter(|tx| {
+                tx.wallet == front_run.wallet
+                    && tx.tx_type != front_run.tx_type
+                    && tx.slot >= front_run.slot
+                    && tx.slot - front_run.slot <= window
+            }) {
+                for victim in nearby.iter().filter(|tx| {
+                    tx.wallet != front_run.wallet
+                        && tx.tx_type == front_run.tx_type
+                        && tx.amount_sol >= thresholds.sandwich_min_victim_sol
+                        && (front_run.slot..=back_run.slot).contains(&tx.slot)
+                }) {
+                    if ![front_run, back_run, victim]
+                        .iter()
+                        .any(|tx| tx.signature == tx_info.signature)
+                    {
+                        continue;
+                    }
+                    let candidate = Sandwich {
+                        front_run: (*front_run).clone(),
+                        victim: (*victim).clone(),
+                        back_run: (*back_run).clone(),
+                    };
+                    let extracted = candidate.extracted_sol();
+                    if extracted > 0.0
+                        && best.as_ref().is_none_or(|b| extracted > b.extracted_sol())
+                    {
+                        best = Some(candidate);
+                    }
+                }
             }
         }
+        best
+    }
 
+    /// Distinct wallets buying within the slot window of `tx_info`, when there are enough of
+    /// them and they spent enough together
+    fn find_coordinated_buy(
+        movement: &TokenMovement,
+        thresholds: &WhaleThresholds,
+        tx_info: &TxInfo,
+    ) -> Option<CoordinatedBuy> {
+        let min_wallets = thresholds.coordinated_buy_min_wallets;
+        if min_wallets == 0
+            || movement.coordinated_buy_reported
+            || tx_info.tx_type != "buy"
+            || tx_info.slot == 0
         {
-            let mut volume = total_volume_tracked.write();
-            *volume += tx_info.amount_sol;
+            return None;
         }
+        let window = thresholds.coordinated_buy_window_slots;
+        let cluster: Vec<&TxInfo> = movement
+            .buys
+            .iter()
+            .filter(|tx| tx.slot != 0 && tx.slot.abs_diff(tx_info.slot) <= window)
+            .collect();
 
-        watched_wallets.insert(tx_info.wallet.clone(), wallet_data);
-
-        Ok(())
+        let mut wallets: Vec<String> = Vec::new();
+        for tx in &cluster {
+            if !wallets.contains(&tx.wallet) {
+                wallets.push(tx.wallet.clone());
+            }
+        }
+        let total_sol: f64 = cluster.iter().map(|tx| tx.amount_sol).sum();
+        if wallets.len() < min_wallets || total_sol < thresholds.coordinated_buy_min_sol {
+            return None;
+        }
+        Some(CoordinatedBuy {
+            mint: tx_info.mint.clone(),
+            wallets,
+            total_sol,
+            first_slot: cluster.iter().map(|tx| tx.slot).min().unwrap_or(tx_info.slot),
+            timestamp: cluster.iter().map(|tx| tx.timestamp).min().unwrap_or(tx_info.timestamp),
+        })
     }
 
     fn track_wallet_activity(
@@ -582,9 +1278,8 @@ impl WhaleWatcher {
         tx_info: &TxInfo,
     ) {
         let mut wallet_data = watched_wallets
-            .get(&tx_info.wallet)
-            .map(|e| e.value().clone())
-            .unwrap_or_else(|| WatchedWallet {
+            .entry(tx_info.wallet.clone())
+            .or_insert_with(|| WatchedWallet {
                 address: tx_info.wallet.clone(),
                 label: String::new(),
                 total_volume: 0.0,
@@ -609,26 +1304,31 @@ impl WhaleWatcher {
                 wallet_data.total_volume
             );
         }
-
-        watched_wallets.insert(tx_info.wallet.clone(), wallet_data);
     }
 
     fn track_token_movement(
         token_movements: &Arc<DashMap<String, TokenMovement>>,
         thresholds: &WhaleThresholds,
+        movements_evicted: &Arc<AtomicU64>,
         tx_info: &TxInfo,
     ) {
+        // Updated in place under the entry's shard lock, so workers handling trades of the
+        // same mint can't overwrite each other's history
         let mut token_data = token_movements
-            .get(&tx_info.mint)
-            .map(|e| e.value().clone())
-            .unwrap_or_else(|| TokenMovement {
+            .entry(tx_info.mint.clone())
+            .or_insert_with(|| TokenMovement {
                 mint: tx_info.mint.clone(),
                 buys: VecDeque::new(),
                 sells: VecDeque::new(),
                 net_flow: 0.0,
                 unique_buyers: HashSet::new(),
                 unique_sellers: HashSet::new(),
+                last_activity: 0,
+                accumulation_score: 0.0,
+                accumulation_alerted: false,
+                coordinated_buy_reported: false,
             });
+        token_data.last_activity = tx_info.timestamp;
 
         if tx_info.tx_type == "buy" {
             token_data.buys.push_back(tx_info.clone());
@@ -641,57 +1341,85 @@ impl WhaleWatcher {
         }
 
         // Keep only recent data
-        let cutoff = Utc::now().timestamp_millis() - thresholds.accumulation_window_ms;
+        let now = Utc::now().timestamp_millis();
+        let cutoff = now - thresholds.movement_retention_ms();
         token_data.buys.retain(|t| t.timestamp > cutoff);
         token_data.sells.retain(|t| t.timestamp > cutoff);
+        token_data.accumulation_score =
+            Self::accumulation_score(&Self::accumulation_windows(&token_data, thresholds, now));
+        drop(token_data);
+
+        let cap = thresholds.max_tracked_tokens;
+        if cap > 0 && token_movements.len() > cap {
+            let evicted = Self::evict_token_movements(token_movements, cap);
+            movements_evicted.fetch_add(evicted as u64, Ordering::SeqCst);
+        }
+    }
 
-        token_movements.insert(tx_info.mint.clone(), token_data);
+    /// Drop the least recently traded mints down to 90% of `cap`, so a steady stream
+    /// of new mints doesn't rescan the map on every trade. Returns how many were dropped.
+    fn evict_token_movements(token_movements: &DashMap<String, TokenMovement>, cap: usize) -> usize {
+        let target = cap - cap / 10;
+        let excess = token_movements.len().saturating_sub(target);
+        if excess == 0 {
+            return 0;
+        }
+
+        let mut by_activity: Vec<(i64, String)> = token_movements
+            .iter()
+            .map(|e| (e.last_activity, e.key().clone()))
+            .collect();
+        by_activity.select_nth_unstable_by_key(excess - 1, |(last_activity, _)| *last_activity);
+
+        for (_, mint) in &by_activity[..excess] {
+            token_movements.remove(mint);
+        }
+        debug!(target: "WHALE_WATCHER", "Evicted {} idle token movements (cap {})", excess, cap);
+        excess
+    }
+
+    /// Drop per-wallet cooldowns that have run out, so wallets seen once don't stay forever
+    fn prune_cooldowns(
+        alert_cooldowns: &DashMap<String, WhaleCooldown>,
+        cooldown_secs: u64,
+        now: i64,
+    ) {
+        // An expired cooldown behaves like no entry at all; its suppressed trades are stale
+        let cooldown_ms = cooldown_secs as i64 * 1000;
+        alert_cooldowns.retain(|_, cooldown| now - cooldown.last_alert_at < cooldown_ms);
     }
 
     async fn analyze_patterns(
+        alerts: &Arc<AlertService>,
         token_movements: &Arc<DashMap<String, TokenMovement>>,
         thresholds: &Arc<RwLock<WhaleThresholds>>,
         database: &Arc<DatabaseService>,
     ) {
         let thresholds_val = thresholds.read().clone();
-
-        for entry in token_movements.iter() {
-            let data = entry.value();
-
-            // Check for whale accumulation pattern
-            let whale_buys: Vec<_> = data
-                .buys
-                .iter()
-                .filter(|b| b.amount_sol >= thresholds_val.whale_threshold_sol)
-                .collect();
-
-            if whale_buys.len() >= thresholds_val.min_transactions_for_pattern {
-                let total_accumulation: f64 = whale_buys.iter().map(|b| b.amount_sol).sum();
-                let token_info = database
-                    .get_token(&data.mint)
-                    .ok()
-                    .flatten()
-                    .map(|t| t.symbol)
-                    .unwrap_or_else(|| "UNKNOWN".to_string());
-
-                info!(
-                    target: "WHALE_WATCHER",
-                    "🐋 Accumulation pattern detected for {}: {} whale buys totaling {:.2} SOL",
-                    token_info,
-                    whale_buys.len(),
-                    total_accumulation
-                );
+        let now = Utc::now().timestamp_millis();
+        let alert_score = thresholds_val.accumulation_alert_score;
+        let mut crossed = Vec::new();
+
+        for mut entry in token_movements.iter_mut() {
+            // Re-scored here too, so the score decays as trades age out of the windows
+            let windows = Self::accumulation_windows(entry.value(), &thresholds_val, now);
+            let score = Self::accumulation_score(&windows);
+            let data = entry.value_mut();
+            data.accumulation_score = score;
+            if alert_score > 0.0 && score >= alert_score {
+                if !data.accumulation_alerted {
+                    data.accumulation_alerted = true;
+                    crossed.push((data.mint.clone(), score, windows));
+                }
+            } else {
+                data.accumulation_alerted = false;
             }
+            let data = entry.value();
 
             // Check for coordinated selling
-            let whale_sells: Vec<_> = data
-                .sells
-                .iter()
-                .filter(|s| s.amount_sol >= thresholds_val.whale_threshold_sol)
-                .collect();
-
-            if whale_sells.len() >= thresholds_val.min_transactions_for_pattern {
-                let total_dump: f64 = whale_sells.iter().map(|s| s.amount_sol).sum();
+            if let Some((whale_sells, total_dump, window_net_flow)) =
+                Self::dump_pattern(data, &thresholds_val)
+            {
                 let token_info = database
                     .get_token(&data.mint)
                     .ok()
@@ -701,16 +1429,148 @@ impl WhaleWatcher {
 
                 warn!(
                     target: "WHALE_WATCHER",
-                    "⚠️ Dump pattern detected for {}: {} whale sells totaling {:.2} SOL",
+                    "⚠️ Dump pattern detected for {}: {} whale sells totaling {:.2} SOL, net flow {:+.2} SOL",
                     token_info,
-                    whale_sells.len(),
-                    total_dump
+                    whale_sells,
+                    total_dump,
+                    window_net_flow
                 );
             }
         }
 
         // Clean up old data
         token_movements.retain(|_, data| !data.buys.is_empty() || !data.sells.is_empty());
+
+        // Sent once the map is no longer borrowed
+        for (mint, score, windows) in crossed {
+            let token_info = Self::token_alert_info(database, &mint);
+            info!(
+                target: "WHALE_WATCHER",
+                "🐋 Accumulation score for {} reached {:.1} (threshold {:.1})",
+                token_info.symbol,
+                score,
+                alert_score
+            );
+            let _ = alerts.alert_accumulation(&token_info, score, alert_score, &windows).await;
+        }
+    }
+
+    /// Whale sells as (count, SOL sold, net flow) when they form a dump pattern: at least
+    /// `min_transactions_for_pattern` of them and a net outflow of `min_pattern_net_flow_sol`
+    fn dump_pattern(
+        data: &TokenMovement,
+        thresholds: &WhaleThresholds,
+    ) -> Option<(usize, f64, f64)> {
+        // `net_flow` is lifetime; patterns only look at the trades still in the window
+        let window_net_flow = data.buys.iter().map(|b| b.amount_sol).sum::<f64>()
+            - data.sells.iter().map(|s| s.amount_sol).sum::<f64>();
+
+        let whale_sells: Vec<f64> = data
+            .sells
+            .iter()
+            .map(|s| s.amount_sol)
+            .filter(|&amount| amount >= thresholds.whale_threshold_sol)
+            .collect();
+
+        (whale_sells.len() >= thresholds.min_transactions_for_pattern
+            && -window_net_flow >= thresholds.min_pattern_net_flow_sol)
+            .then(|| (whale_sells.len(), whale_sells.iter().sum(), window_net_flow))
+    }
+
+    /// Whale buys, net flow and unique buyers in each scoring window ending at `now`, each
+    /// weighted into a window score. Net flow is counted in multiples of the whale threshold
+    /// so it weighs on the same scale as a whale buy.
+    fn accumulation_windows(
+        data: &TokenMovement,
+        thresholds: &WhaleThresholds,
+        now: i64,
+    ) -> Vec<AccumulationWindow> {
+        let flow_unit = thresholds.whale_threshold_sol.max(1.0);
+        thresholds
+            .accumulation_windows_secs
+            .iter()
+            .map(|&window_secs| {
+                let cutoff = now - window_secs as i64 * 1000;
+                let buys: Vec<&TxInfo> = data.buys.iter().filter(|b| b.timestamp > cutoff).collect();
+                let sold: f64 = data
+                    .sells
+                    .iter()
+                    .filter(|s| s.timestamp > cutoff)
+                    .map(|s| s.amount_sol)
+                    .sum();
+
+                let whale_buys = buys
+                    .iter()
+                    .filter(|b| b.amount_sol >= thresholds.whale_threshold_sol)
+                    .count();
+                let net_flow = buys.iter().map(|b| b.amount_sol).sum::<f64>() - sold;
+                let unique_buyers = buys
+                    .iter()
+                    .map(|b| b.wallet.as_str())
+                    .collect::<HashSet<_>>()
+                    .len();
+                let score = thresholds.accumulation_buy_weight * whale_buys as f64
+                    + thresholds.accumulation_flow_weight * net_flow / flow_unit
+                    + thresholds.accumulation_buyer_weight * unique_buyers as f64;
+
+                AccumulationWindow {
+                    window_secs,
+                    whale_buys,
+                    net_flow,
+                    unique_buyers,
+                    score,
+                }
+            })
+            .collect()
+    }
+
+    /// Mean of the window scores, floored at 0; activity in the shortest window counts in
+    /// every longer one, so recent buying weighs the most
+    fn accumulation_score(windows: &[AccumulationWindow]) -> f64 {
+        if windows.is_empty() {
+            return 0.0;
+        }
+        let total: f64 = windows.iter().map(|w| w.score).sum();
+        (total / windows.len() as f64).max(0.0)
+    }
+
+    /// Get current thresholds
+    pub fn get_thresholds(&self) -> WhaleThresholds {
+        self.thresholds.read().clone()
+    }
+
+    /// Set a threshold value
+    pub fn set_threshold(&self, key: &str, value: f64) -> bool {
+        let mut thresholds = self.thresholds.write();
+        match key {
+            "whale_threshold_sol" => thresholds.whale_threshold_sol = value,
+            "accumulation_window_ms" => thresholds.accumulation_window_ms = value as i64,
+            "min_transactions_for_pattern" => thresholds.min_transactions_for_pattern = value as usize,
+            "min_pattern_net_flow_sol" => thresholds.min_pattern_net_flow_sol = value,
+            "whale_alert_cooldown_secs" => thresholds.whale_alert_cooldown_secs = value as u64,
+            "max_tracked_tokens" => thresholds.max_tracked_tokens = value as usize,
+            "alert_on_accumulation" => thresholds.alert_on_accumulation = value != 0.0,
+            "alert_on_dump" => thresholds.alert_on_dump = value != 0.0,
+            "alert_on_creator_activity" => thresholds.alert_on_creator_activity = value != 0.0,
+            "alert_on_rotation" => thresholds.alert_on_rotation = value != 0.0,
+            "rotation_window_secs" => thresholds.rotation_window_secs = value as u64,
+            "rotation_min_sell_sol" => thresholds.rotation_min_sell_sol = value,
+            "rotation_min_buy_sol" => thresholds.rotation_min_buy_sol = value,
+            "alert_with_history" => thresholds.alert_with_history = value != 0.0,
+            "accumulation_buy_weight" => thresholds.accumulation_buy_weight = value,
+            "accumulation_flow_weight" => thresholds.accumulation_flow_weight = value,
+            "accumulation_buyer_weight" => thresholds.accumulation_buyer_weight = value,
+            "accumulation_alert_score" => thresholds.accumulation_alert_score = value,
+            "alert_on_sandwich" => thresholds.alert_on_sandwich = value != 0.0,
+            "sandwich_window_slots" => thresholds.sandwich_window_slots = value as u64,
+            "sandwich_min_victim_sol" => thresholds.sandwich_min_victim_sol = value,
+            "coordinated_buy_min_wallets" => thresholds.coordinated_buy_min_wallets = value as usize,
+            "coordinated_buy_window_slots" => thresholds.coordinated_buy_window_slots = value as u64,
+            "coordinated_buy_min_sol" => thresholds.coordinated_buy_min_sol = value,
+            _ => return false,
+        }
+        info!(target: "WHALE_WATCHER", "Threshold updated: {} = {}", key, value);
+        true
     }
 
     /// Get watcher statistics
@@ -720,10 +1580,20 @@ impl WhaleWatcher {
             whales_identified: self.whales_identified.load(Ordering::SeqCst),
             accumulation_alerts: self.accumulation_alerts.load(Ordering::SeqCst),
             dump_alerts: self.dump_alerts.load(Ordering::SeqCst),
+            creator_activity_alerts: self.creator_activity_alerts.load(Ordering::SeqCst),
+            rotation_alerts: self.rotation_alerts.load(Ordering::SeqCst),
+            sandwich_alerts: self.sandwich_alerts.load(Ordering::SeqCst),
+            coordinated_buys: self.coordinated_buys.load(Ordering::SeqCst),
+            failed_txs_skipped: self.failed_txs_skipped.load(Ordering::SeqCst),
+            tx_fetch_none: self.tx_loss.fetch_none(),
+            tx_parse_none: self.tx_loss.parse_none(),
+            movements_evicted: self.movements_evicted.load(Ordering::SeqCst),
             total_volume_tracked: *self.total_volume_tracked.read(),
+            queue_depth: self.analysis_queue.depth(),
+            queue_dropped: self.analysis_queue.dropped(),
             watched_wallets: self.watched_wallets.len(),
             tokens_tracked: self.token_movements.len(),
-            is_running: self.is_running.load(Ordering::SeqCst),
+            is_running: self.is_running.is_running(),
         }
     }
 
@@ -743,41 +1613,172 @@ impl WhaleWatcher {
             .collect()
     }
 
+    /// Export watched wallets with transactions bounded to the most recent `max_transactions`
+    pub fn export_wallets(&self, max_transactions: usize) -> Vec<WatchedWallet> {
+        self.watched_wallets
+            .iter()
+            .map(|e| {
+                let mut wallet = e.value().clone();
+                while wallet.transactions.len() > max_transactions {
+                    wallet.transactions.pop_front();
+                }
+                wallet
+            })
+            .collect()
+    }
+
+    /// Merge imported wallets, keeping any already being watched.
+    /// Returns the number of wallets added.
+    pub fn import_wallets(&self, wallets: Vec<WatchedWallet>) -> usize {
+        let mut added = 0;
+        for wallet in wallets {
+            if self.watched_wallets.contains_key(&wallet.address) {
+                continue;
+            }
+
+            self.database.write(PendingWrite::Wallet(WalletRecord {
+                address: wallet.address.clone(),
+                label: wallet.label.clone(),
+                total_volume_sol: wallet.total_volume,
+                last_activity: wallet.last_activity.clone(),
+                is_whale: wallet.is_whale,
+            }));
+
+            self.watched_wallets.insert(wallet.address.clone(), wallet);
+            self.wallets_tracked.fetch_add(1, Ordering::SeqCst);
+            added += 1;
+        }
+        info!(target: "WHALE_WATCHER", "Imported {} watched wallets", added);
+        added
+    }
+
     /// Get wallet activity
     pub fn get_wallet_activity(&self, address: &str) -> Option<WatchedWallet> {
         self.watched_wallets.get(address).map(|e| e.value().clone())
     }
 
-    /// Get top token movers
-    pub fn get_top_movers(&self, limit: usize) -> Vec<TopMover> {
+    /// Get top token movers, skipping tokens whose total volume is below `min_volume`
+    /// so a single small trade in a quiet window can't top the list
+    pub fn get_top_movers(&self, limit: usize, min_volume: f64, sort: MoverSort) -> Vec<TopMover> {
         let mut movers: Vec<_> = self
             .token_movements
             .iter()
-            .map(|e| {
+            .filter_map(|e| {
                 let data = e.value();
                 let buy_vol: f64 = data.buys.iter().map(|b| b.amount_sol).sum();
                 let sell_vol: f64 = data.sells.iter().map(|s| s.amount_sol).sum();
-                TopMover {
+                let volume = buy_vol + sell_vol;
+                (volume >= min_volume).then(|| TopMover {
                     mint: data.mint.clone(),
                     net_flow: data.net_flow,
-                    volume: buy_vol + sell_vol,
-                }
+                    volume,
+                    buy_pressure: if volume > 0.0 { buy_vol / volume } else { 0.0 },
+                })
             })
             .collect();
 
-        movers.sort_by(|a, b| {
-            b.net_flow
-                .abs()
-                .partial_cmp(&a.net_flow.abs())
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+        let key = |mover: &TopMover| match sort {
+            MoverSort::NetFlow => mover.net_flow.abs(),
+            MoverSort::Volume => mover.volume,
+            MoverSort::BuyPressure => mover.buy_pressure,
+        };
+        movers.sort_by(|a, b| key(b).total_cmp(&key(a)));
         movers.truncate(limit);
         movers
     }
 
+    /// Tokens with a positive accumulation score, highest first, with their window breakdown
+    pub fn get_accumulating(&self, limit: usize) -> Vec<AccumulatingToken> {
+        let thresholds = self.thresholds.read().clone();
+        let now = Utc::now().timestamp_millis();
+        let mut tokens: Vec<_> = self
+            .token_movements
+            .iter()
+            .filter_map(|e| {
+                let windows = Self::accumulation_windows(e.value(), &thresholds, now);
+                let score = Self::accumulation_score(&windows);
+                (score > 0.0).then(|| AccumulatingToken {
+                    mint: e.key().clone(),
+                    score,
+                    windows,
+                })
+            })
+            .collect();
+
+        tokens.sort_by(|a, b| b.score.total_cmp(&a.score));
+        tokens.truncate(limit);
+        tokens
+    }
+
+    /// Watch every `address,label` row of a CSV, skipping a header row and wallets
+    /// already watched. Invalid rows are reported rather than failing the import.
+    pub fn import_wallets_csv(&self, csv: &str) -> WalletCsvImport {
+        let mut report = WalletCsvImport {
+            imported: 0,
+            duplicates: 0,
+            errors: Vec::new(),
+        };
+
+        for (index, line) in csv.lines().enumerate() {
+            let row = index + 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (address, label) = parse_csv_row(line);
+            if row == 1 && address.eq_ignore_ascii_case("address") {
+                continue;
+            }
+            if let Err(e) = Pubkey::from_str(address) {
+                report.errors.push(CsvRowError {
+                    row,
+                    error: format!("invalid address {:?}: {}", address, e),
+                });
+                continue;
+            }
+            if self.watched_wallets.contains_key(address) {
+                report.duplicates += 1;
+                continue;
+            }
+
+            self.watch_wallet(address, &label);
+            report.imported += 1;
+        }
+
+        info!(
+            target: "WHALE_WATCHER",
+            "CSV import: {} added, {} already watched, {} invalid rows",
+            report.imported,
+            report.duplicates,
+            report.errors.len()
+        );
+        report
+    }
+
+    /// All watched wallets as `address,label` CSV with a header row
+    pub fn export_wallets_csv(&self) -> String {
+        let mut rows: Vec<(String, String)> = self
+            .watched_wallets
+            .iter()
+            .map(|e| (e.address.clone(), e.label.clone()))
+            .collect();
+        rows.sort();
+
+        let mut csv = String::from("address,label\n");
+        for (address, label) in rows {
+            csv.push_str(&format!("{},{}\n", address, csv_field(&label)));
+        }
+        csv
+    }
+
     /// Check if running
     pub fn is_running(&self) -> bool {
-        self.is_running.load(Ordering::SeqCst)
+        self.is_running.is_running()
+    }
+
+    /// Queued and in-progress analysis jobs, which still finish after `stop`
+    pub fn pending_work(&self) -> u64 {
+        self.analysis_queue.pending()
     }
 }
 
@@ -788,15 +1789,469 @@ impl Clone for WhaleWatcher {
             solana: Arc::clone(&self.solana),
             alerts: Arc::clone(&self.alerts),
             database: Arc::clone(&self.database),
-            is_running: Arc::clone(&self.is_running),
+            is_running: self.is_running.clone(),
             watched_wallets: Arc::clone(&self.watched_wallets),
             token_movements: Arc::clone(&self.token_movements),
             thresholds: Arc::clone(&self.thresholds),
+            alert_cooldowns: Arc::clone(&self.alert_cooldowns),
+            wallet_histories: Arc::clone(&self.wallet_histories),
             wallets_tracked: Arc::clone(&self.wallets_tracked),
             whales_identified: Arc::clone(&self.whales_identified),
             accumulation_alerts: Arc::clone(&self.accumulation_alerts),
             dump_alerts: Arc::clone(&self.dump_alerts),
+            creator_activity_alerts: Arc::clone(&self.creator_activity_alerts),
+            rotation_alerts: Arc::clone(&self.rotation_alerts),
+            sandwich_alerts: Arc::clone(&self.sandwich_alerts),
+            coordinated_buys: Arc::clone(&self.coordinated_buys),
+            failed_txs_skipped: Arc::clone(&self.failed_txs_skipped),
+            tx_loss: self.tx_loss.clone(),
+            movements_evicted: Arc::clone(&self.movements_evicted),
             total_volume_tracked: Arc::clone(&self.total_volume_tracked),
+            analysis_queue: self.analysis_queue.clone(),
+            creator_activity_sender: self.creator_activity_sender.clone(),
+            coordinated_buy_sender: self.coordinated_buy_sender.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expired_cooldowns_are_pruned() {
+        let cooldowns = DashMap::new();
+        let now = 1_000_000;
+        for (wallet, age_ms) in [("fresh", 30_000), ("expired", 120_000)] {
+            let cooldown = WhaleCooldown {
+                last_alert_at: now - age_ms,
+                suppressed_trades: 2,
+                ..Default::default()
+            };
+            cooldowns.insert(wallet.to_string(), cooldown);
         }
+
+        WhaleWatcher::prune_cooldowns(&cooldowns, 60, now);
+
+        assert!(cooldowns.contains_key("fresh"));
+        assert!(!cooldowns.contains_key("expired"));
+    }
+
+    /// Watcher over an in-memory database and an unreachable RPC endpoint
+    fn watcher() -> WhaleWatcher {
+        let mut config = Config::for_tests();
+        config.rpc_url = "http://127.0.0.1:1".to_string();
+        config.rpc_urls = vec![config.rpc_url.clone()];
+        config.telegram_bot_token = None;
+        config.alert_file_path = None;
+        WhaleWatcher::new(
+            config.clone(),
+            Arc::new(SolanaService::new(config.clone())),
+            Arc::new(AlertService::new(config)),
+            Arc::new(DatabaseService::new(":memory:").unwrap()),
+        )
+    }
+
+    fn whale_trade(wallet: &str, mint: &str, tx_type: &str, amount_sol: f64) -> TxInfo {
+        TxInfo {
+            signature: format!("sig-{}-{}", wallet, mint),
+            wallet: wallet.to_string(),
+            mint: mint.to_string(),
+            tx_type: tx_type.to_string(),
+            amount_sol,
+            amount_tokens: 1000.0,
+            token_decimals: PUMP_TOKEN_DECIMALS,
+            timestamp: Utc::now().timestamp_millis(),
+            slot: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn creator_whale_trades_raise_the_elevated_alert() {
+        let watcher = watcher();
+        watcher
+            .database
+            .save_token(&crate::utils::database::TokenRecord {
+                mint: "mint".to_string(),
+                name: "Test".to_string(),
+                symbol: "TST".to_string(),
+                creator: "creator".to_string(),
+                created_at: Utc::now().to_rfc3339(),
+                initial_liquidity: 1.0,
+                current_liquidity: 1.0,
+                holder_count: 0,
+                is_rugged: false,
+                rug_reason: None,
+                last_updated: Utc::now().to_rfc3339(),
+                is_muted: false,
+                is_graduated: false,
+            })
+            .unwrap();
+        let mut creator_activity = watcher.subscribe_creator_activity();
+        let handle = |tx_info: TxInfo| {
+            let watcher = &watcher;
+            async move {
+                WhaleWatcher::handle_whale_transaction(
+                    &watcher.alerts,
+                    &watcher.database,
+                    &watcher.watched_wallets,
+                    &watcher.thresholds,
+                    &watcher.alert_cooldowns,
+                    &watcher.wallet_histories,
+                    &watcher.whales_identified,
+                    &watcher.accumulation_alerts,
+                    &watcher.dump_alerts,
+                    &watcher.creator_activity_alerts,
+                    &watcher.total_volume_tracked,
+                    &watcher.creator_activity_sender,
+                    &tx_info,
+                )
+                .await
+                .unwrap();
+            }
+        };
+
+        handle(whale_trade("whale", "mint", "sell", 50.0)).await;
+        assert_eq!(watcher.creator_activity_alerts.load(Ordering::SeqCst), 0);
+        assert!(creator_activity.try_recv().is_err());
+
+        handle(whale_trade("creator", "mint", "sell", 50.0)).await;
+        assert_eq!(watcher.creator_activity_alerts.load(Ordering::SeqCst), 1);
+        let activity = creator_activity.try_recv().unwrap();
+        assert_eq!(activity.mint, "mint");
+        assert_eq!((activity.tx_type.as_str(), activity.amount_sol), ("sell", 50.0));
+        // The creator's sell goes out as the elevated alert instead of a plain dump alert
+        assert_eq!(watcher.dump_alerts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn movements_over_the_cap_evict_the_least_recently_traded() {
+        let watcher = watcher();
+        let thresholds = WhaleThresholds { max_tracked_tokens: 10, ..watcher.get_thresholds() };
+
+        let now = Utc::now().timestamp_millis();
+        let trade = |mint: String, seconds_ago: i64| {
+            let tx_info = TxInfo {
+                timestamp: now - seconds_ago * 1000,
+                ..whale_trade("wallet", &mint, "buy", 1.0)
+            };
+            WhaleWatcher::track_token_movement(
+                &watcher.token_movements,
+                &thresholds,
+                &watcher.movements_evicted,
+                &tx_info,
+            );
+        };
+        for i in 0..10 {
+            trade(format!("mint{}", i), 60 - i);
+        }
+        // Trading again makes mint0 the most recent
+        trade("mint0".to_string(), 1);
+        assert_eq!(watcher.token_movements.len(), 10);
+
+        // The 11th mint trims the map to 90% of the cap, dropping the two idlest
+        trade("mint10".to_string(), 0);
+
+        assert_eq!(watcher.token_movements.len(), 9);
+        assert_eq!(watcher.movements_evicted.load(Ordering::SeqCst), 2);
+        assert!(!watcher.token_movements.contains_key("mint1"));
+        assert!(!watcher.token_movements.contains_key("mint2"));
+        assert!(watcher.token_movements.contains_key("mint0"));
+        assert!(watcher.token_movements.contains_key("mint10"));
+    }
+
+    #[test]
+    fn dump_pattern_needs_a_net_outflow() {
+        let watcher = watcher();
+        let thresholds = WhaleThresholds {
+            whale_threshold_sol: 10.0,
+            min_transactions_for_pattern: 3,
+            min_pattern_net_flow_sol: 25.0,
+            ..watcher.get_thresholds()
+        };
+        let trade = |mint: &str, tx_type: &str| {
+            WhaleWatcher::track_token_movement(
+                &watcher.token_movements,
+                &thresholds,
+                &watcher.movements_evicted,
+                &whale_trade("whale", mint, tx_type, 20.0),
+            );
+        };
+        for _ in 0..3 {
+            trade("churn", "sell");
+            trade("churn", "buy");
+            trade("dump", "sell");
+        }
+        let pattern = |mint| {
+            WhaleWatcher::dump_pattern(&watcher.token_movements.get(mint).unwrap(), &thresholds)
+        };
+
+        // Three whale sells offset by as many buys are churn, not a dump
+        assert_eq!(pattern("churn"), None);
+        assert_eq!(pattern("dump"), Some((3, 60.0, -60.0)));
+    }
+
+    const MINT: &str = "So11111111111111111111111111111111111111112";
+    const TRADER: &str = "Trader1111111111111111111111111111111111111";
+    const RELAYER: &str = "Re1ayer111111111111111111111111111111111111";
+    const CURVE: &str = "Curve11111111111111111111111111111111111111";
+
+    /// A buy of 1,000 tokens from `CURVE` by each of `buyers`, over `keys` with the given
+    /// lamport balances before and after
+    fn buy(
+        keys: [&str; 3],
+        pre_sol: [u64; 3],
+        post_sol: [u64; 3],
+        buyers: &[&str],
+    ) -> EncodedConfirmedTransactionWithStatusMeta {
+        let index = |owner: &str| keys.iter().position(|key| *key == owner).unwrap();
+        let balance = |owner: &str, amount: u64| {
+            serde_json::json!({
+                "accountIndex": index(owner),
+                "mint": MINT,
+                "owner": owner,
+                "uiTokenAmount": {
+                    "uiAmount": amount as f64 / 1e6,
+                    "decimals": 6,
+                    "amount": amount.to_string(),
+                    "uiAmountString": (amount as f64 / 1e6).to_string(),
+                },
+            })
+        };
+        let sold = 1_000_000_000 * buyers.len() as u64;
+        let mut post_tokens = vec![balance(CURVE, 0)];
+        post_tokens.extend(buyers.iter().map(|buyer| balance(buyer, 1_000_000_000)));
+
+        serde_json::from_value(serde_json::json!({
+            "slot": 7,
+            "transaction": {
+                "signatures": ["sig"],
+                "message": {
+                    "header": {
+                        "numRequiredSignatures": 2,
+                        "numReadonlySignedAccounts": 0,
+                        "numReadonlyUnsignedAccounts": 0,
+                    },
+                    "accountKeys": keys,
+                    "recentBlockhash": "11111111111111111111111111111111",
+                    "instructions": [],
+                },
+            },
+            "meta": {
+                "err": null,
+                "status": { "Ok": null },
+                "fee": 5000,
+                "preBalances": pre_sol,
+                "postBalances": post_sol,
+                "preTokenBalances": [balance(CURVE, sold)],
+                "postTokenBalances": post_tokens,
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn direct_buy_is_attributed_to_the_fee_payer() {
+        let tx = buy(
+            [TRADER, RELAYER, CURVE],
+            [10_000_000_000, 1_000_000_000, 5_000_000_000],
+            [7_999_995_000, 1_000_000_000, 7_000_000_000],
+            &[TRADER],
+        );
+
+        let info = WhaleWatcher::parse_transaction(&tx, "buy").unwrap();
+        assert_eq!(info.wallet, TRADER);
+        assert_eq!(info.mint, MINT);
+        assert_eq!(info.amount_sol, 2.000005);
+        assert_eq!(info.amount_tokens, 1000.0);
+        assert_eq!(info.slot, 7);
+    }
+
+    #[test]
+    fn relayed_buy_is_attributed_to_the_trader() {
+        let tx = buy(
+            [RELAYER, TRADER, CURVE],
+            [1_000_000_000, 10_000_000_000, 5_000_000_000],
+            [999_995_000, 8_000_000_000, 7_000_000_000],
+            &[TRADER],
+        );
+
+        let info = WhaleWatcher::parse_transaction(&tx, "buy").unwrap();
+        assert_eq!(info.wallet, TRADER);
+        assert_eq!(info.amount_sol, 2.0);
+        assert_eq!(info.amount_tokens, 1000.0);
+    }
+
+    #[test]
+    fn ambiguous_buy_falls_back_to_the_fee_payer() {
+        // Both signers paid SOL and received tokens
+        let tx = buy(
+            [RELAYER, TRADER, CURVE],
+            [10_000_000_000, 10_000_000_000, 5_000_000_000],
+            [8_999_995_000, 9_000_000_000, 7_000_000_000],
+            &[RELAYER, TRADER],
+        );
+
+        let info = WhaleWatcher::parse_transaction(&tx, "buy").unwrap();
+        assert_eq!(info.wallet, RELAYER);
+    }
+
+    #[test]
+    fn sell_then_buy_of_another_token_is_a_rotation() {
+        let watcher = watcher();
+        let thresholds = WhaleThresholds {
+            alert_on_rotation: true,
+            rotation_window_secs: 300,
+            rotation_min_sell_sol: 10.0,
+            rotation_min_buy_sol: 5.0,
+            ..watcher.get_thresholds()
+        };
+        let now = Utc::now().timestamp_millis();
+        let trade = |mint: &str, tx_type: &str, amount_sol: f64, seconds_ago: i64| TxInfo {
+            timestamp: now - seconds_ago * 1000,
+            ..whale_trade("whale", mint, tx_type, amount_sol)
+        };
+        let record = |tx_info: &TxInfo| {
+            WhaleWatcher::track_wallet_activity(
+                &watcher.watched_wallets,
+                &thresholds,
+                &watcher.whales_identified,
+                tx_info,
+            );
+        };
+        let rotation = |tx_info: &TxInfo| {
+            WhaleWatcher::find_rotation(&watcher.watched_wallets, &thresholds, tx_info)
+        };
+
+        record(&trade("old", "sell", 3.0, 400));
+        record(&trade("from", "sell", 20.0, 120));
+        record(&trade("small", "sell", 2.0, 60));
+
+        let found = rotation(&trade("to", "buy", 8.0, 0)).unwrap();
+        assert_eq!((found.mint.as_str(), found.amount_sol), ("from", 20.0));
+
+        // Too small a buy, a sell, or buying back the same token isn't a rotation
+        assert!(rotation(&trade("to", "buy", 1.0, 0)).is_none());
+        assert!(rotation(&trade("to", "sell", 8.0, 0)).is_none());
+        assert!(rotation(&trade("from", "buy", 8.0, 0)).is_none());
+        // Nor is a buy after the sell has left the window
+        assert!(rotation(&trade("to", "buy", 8.0, -200)).is_none());
+    }
+
+    #[test]
+    fn accumulation_score_ranks_sustained_broad_buying_first() {
+        let watcher = watcher();
+        let thresholds = WhaleThresholds {
+            whale_threshold_sol: 10.0,
+            max_tracked_tokens: 0,
+            accumulation_windows_secs: vec![300, 3600],
+            accumulation_buy_weight: 3.0,
+            accumulation_flow_weight: 1.0,
+            accumulation_buyer_weight: 1.0,
+            ..watcher.get_thresholds()
+        };
+        *watcher.thresholds.write() = thresholds.clone();
+
+        let now = Utc::now().timestamp_millis();
+        let trade = |mint: &str, wallet: String, tx_type: &str, amount_sol: f64, secs_ago: i64| {
+            let tx_info = TxInfo {
+                timestamp: now - secs_ago * 1000,
+                ..whale_trade(&wallet, mint, tx_type, amount_sol)
+            };
+            WhaleWatcher::track_token_movement(
+                &watcher.token_movements,
+                &thresholds,
+                &watcher.movements_evicted,
+                &tx_info,
+            );
+        };
+        // Four whales buying now scores 12 for buys, 4 for flow and 4 for buyers in both windows
+        for i in 0..4 {
+            trade("steady", format!("whale{}", i), "buy", 10.0, 60);
+        }
+        // Many small buyers: no whale buys, but 10 buyers and 1 unit of flow
+        for i in 0..10 {
+            trade("crowd", format!("small{}", i), "buy", 1.0, 60);
+        }
+        // The same whale buying, but only within the longer window
+        for i in 0..4 {
+            trade("stale", format!("whale{}", i), "buy", 10.0, 1800);
+        }
+        trade("single", "whale0".to_string(), "buy", 10.0, 60);
+        // Bought then dumped past zero
+        trade("dumped", "whale0".to_string(), "buy", 10.0, 60);
+        trade("dumped", "whale1".to_string(), "buy", 10.0, 60);
+        trade("dumped", "whale0".to_string(), "sell", 120.0, 30);
+
+        let ranked: Vec<(String, f64)> = watcher
+            .get_accumulating(10)
+            .into_iter()
+            .map(|token| (token.mint, token.score))
+            .collect();
+        let expected = [("steady", 20.0), ("crowd", 11.0), ("stale", 10.0), ("single", 5.0)];
+        assert_eq!(ranked.len(), expected.len(), "{:?}", ranked);
+        for ((mint, score), (expected_mint, expected_score)) in ranked.iter().zip(expected) {
+            assert_eq!(mint, expected_mint);
+            assert!((score - expected_score).abs() < 1e-9, "{}: {}", mint, score);
+        }
+        assert_eq!(watcher.token_movements.get("steady").unwrap().accumulation_score, 20.0);
+    }
+
+    #[test]
+    fn bracketing_trades_by_one_wallet_are_a_possible_sandwich() {
+        let watcher = watcher();
+        let thresholds = WhaleThresholds {
+            max_tracked_tokens: 0,
+            alert_on_sandwich: true,
+            sandwich_window_slots: 2,
+            sandwich_min_victim_sol: 1.0,
+            ..watcher.get_thresholds()
+        };
+        // Records a trade and returns the sandwich it completes, if any
+        let trade = |mint: &str, wallet: &str, tx_type: &str, amount_sol: f64, slot: u64| {
+            let tx_info = TxInfo {
+                signature: format!("sig-{}-{}-{}-{}", mint, wallet, tx_type, slot),
+                slot,
+                ..whale_trade(wallet, mint, tx_type, amount_sol)
+            };
+            WhaleWatcher::track_token_movement(
+                &watcher.token_movements,
+                &thresholds,
+                &watcher.movements_evicted,
+                &tx_info,
+            );
+            let movement = watcher.token_movements.get(mint).unwrap();
+            WhaleWatcher::find_sandwich(&movement, &thresholds, &tx_info)
+        };
+
+        assert!(trade("mint", "attacker", "buy", 5.0, 100).is_none());
+        assert!(trade("mint", "victim", "buy", 3.0, 100).is_none());
+        let sandwich = trade("mint", "attacker", "sell", 5.4, 101).unwrap();
+        assert_eq!(sandwich.front_run.slot, 100);
+        assert_eq!(sandwich.victim.wallet, "victim");
+        assert_eq!(sandwich.back_run.slot, 101);
+        assert!((sandwich.extracted_sol() - 0.4).abs() < 1e-9);
+
+        // Selling back at a loss extracts nothing
+        trade("loss", "attacker", "buy", 5.0, 100);
+        trade("loss", "victim", "buy", 3.0, 100);
+        assert!(trade("loss", "attacker", "sell", 4.5, 101).is_none());
+
+        // A back-run outside the slot window doesn't bracket the victim
+        trade("late", "attacker", "buy", 5.0, 100);
+        trade("late", "victim", "buy", 3.0, 101);
+        assert!(trade("late", "attacker", "sell", 5.4, 110).is_none());
+
+        // Nor does one around a trade below the victim floor
+        trade("dust", "attacker", "buy", 5.0, 100);
+        trade("dust", "victim", "buy", 0.5, 100);
+        assert!(trade("dust", "attacker", "sell", 5.4, 101).is_none());
+
+        // A victim trade analyzed after both attacker legs still completes the match
+        trade("late_victim", "attacker", "sell", 5.0, 200);
+        trade("late_victim", "attacker", "buy", 4.6, 201);
+        let sandwich = trade("late_victim", "victim", "sell", 2.0, 200).unwrap();
+        assert_eq!(sandwich.front_run.tx_type, "sell");
+        assert!((sandwich.extracted_sol() - 0.4).abs() < 1e-9);
     }
 }
@@ -1,15 +1,42 @@
 //! Alert service for Telegram and WebSocket notifications
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use chrono::Utc;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::broadcast;
-use tracing::{error, info};
+use std::time::Duration;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::sync::{broadcast, Mutex};
+use tracing::{debug, error, info, warn};
 
-use crate::config::Config;
+use crate::config::{Cluster, Config};
+use crate::utils::database::{DatabaseService, PendingWrite};
+use crate::utils::error::{PumpError, PumpResult};
+use crate::utils::format::{format_sol, format_sol_signed, format_tokens, format_usd};
+use crate::utils::work_queue::{QueueStats, WorkQueue};
+
+/// Telegram `reply_markup` with one row of link buttons for `mint`; `None` on clusters
+/// these sites don't index
+fn telegram_link_buttons(mint: &str, cluster: Cluster) -> Option<serde_json::Value> {
+    let solscan = match cluster {
+        Cluster::Mainnet => format!("https://solscan.io/token/{}", mint),
+        Cluster::Devnet => format!("https://solscan.io/token/{}?cluster=devnet", mint),
+        Cluster::Localnet | Cluster::Custom => return None,
+    };
+    let mut row = vec![serde_json::json!({ "text": "View on Solscan", "url": solscan })];
+    // pump.fun only runs on mainnet
+    if cluster == Cluster::Mainnet {
+        row.push(serde_json::json!({
+            "text": "View on pump.fun",
+            "url": format!("https://pump.fun/coin/{}", mint),
+        }));
+    }
+    Some(serde_json::json!({ "inline_keyboard": [row] }))
+}
 
 /// Alert data structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +48,378 @@ pub struct Alert {
     pub message: String,
     pub data: serde_json::Value,
     pub timestamp: String,
+    /// `INSTANCE_NAME` of the PumpGuard that raised it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+}
+
+impl Alert {
+    /// Decode `data` into its typed payload based on the alert type
+    // The decoding side is for WS and webhook consumers; nothing in the binary reads it back
+    #[allow(dead_code)]
+    pub fn payload(&self) -> Result<AlertPayload> {
+        let data = self.data.clone();
+        let payload = match self.alert_type.as_str() {
+            "new_token" => {
+                let new_token: NewTokenPayload = serde_json::from_value(data)?;
+                AlertPayload::NewToken {
+                    token: new_token.token,
+                    possible_copycat: new_token.possible_copycat,
+                    detection_lag_ms: new_token.detection_lag_ms,
+                    incomplete_metadata: new_token.incomplete_metadata,
+                }
+            }
+            "rug" => {
+                let rug: RugPayload = serde_json::from_value(data)?;
+                AlertPayload::Rug {
+                    token: rug.token,
+                    reason: rug.reason,
+                    severity: rug.severity,
+                }
+            }
+            "whale_buy" | "whale_sell" => {
+                let whale: WhalePayload = serde_json::from_value(data)?;
+                AlertPayload::Whale {
+                    wallet: whale.wallet,
+                    token: whale.token,
+                    amount_sol: whale.amount_sol,
+                    amount_tokens: whale.amount_tokens,
+                    tx_type: whale.tx_type,
+                    since_last_alert: whale.since_last_alert,
+                    wallet_history: whale.wallet_history,
+                }
+            }
+            "creator_whale_activity" => {
+                let creator: CreatorWhalePayload = serde_json::from_value(data)?;
+                AlertPayload::CreatorWhale {
+                    wallet: creator.wallet,
+                    token: creator.token,
+                    amount_sol: creator.amount_sol,
+                    amount_tokens: creator.amount_tokens,
+                    tx_type: creator.tx_type,
+                    severity: creator.severity,
+                }
+            }
+            "whale_rotation" => {
+                let rotation: WhaleRotationPayload = serde_json::from_value(data)?;
+                AlertPayload::WhaleRotation {
+                    wallet: rotation.wallet,
+                    from_token: rotation.from_token,
+                    to_token: rotation.to_token,
+                    sold_sol: rotation.sold_sol,
+                    bought_sol: rotation.bought_sol,
+                    elapsed_secs: rotation.elapsed_secs,
+                }
+            }
+            "accumulation" => {
+                let accumulation: AccumulationPayload = serde_json::from_value(data)?;
+                AlertPayload::Accumulation {
+                    token: accumulation.token,
+                    score: accumulation.score,
+                    threshold: accumulation.threshold,
+                    windows: accumulation.windows,
+                }
+            }
+            "possible_sandwich" => {
+                let sandwich: SandwichPayload = serde_json::from_value(data)?;
+                AlertPayload::Sandwich {
+                    token: sandwich.token,
+                    attacker: sandwich.attacker,
+                    victim: sandwich.victim,
+                    victim_type: sandwich.victim_type,
+                    front_run: sandwich.front_run,
+                    victim_trade: sandwich.victim_trade,
+                    back_run: sandwich.back_run,
+                    extracted_sol: sandwich.extracted_sol,
+                    slot_span: sandwich.slot_span,
+                }
+            }
+            "abandoned" => {
+                let abandoned: AbandonedPayload = serde_json::from_value(data)?;
+                AlertPayload::Abandoned {
+                    token: abandoned.token,
+                    idle_secs: abandoned.idle_secs,
+                }
+            }
+            "holder_growth" => {
+                let growth: HolderGrowthPayload = serde_json::from_value(data)?;
+                AlertPayload::HolderGrowth {
+                    token: growth.token,
+                    holder_count: growth.holder_count,
+                    milestone: growth.milestone,
+                }
+            }
+            "mcap_milestone" => {
+                let mcap: MarketCapPayload = serde_json::from_value(data)?;
+                AlertPayload::MarketCap {
+                    token: mcap.token,
+                    market_cap_usd: mcap.market_cap_usd,
+                    milestone_usd: mcap.milestone_usd,
+                }
+            }
+            "liquidity_added" => {
+                let added: LiquidityAddedPayload = serde_json::from_value(data)?;
+                AlertPayload::LiquidityAdded {
+                    token: added.token,
+                    before_sol: added.before_sol,
+                    after_sol: added.after_sol,
+                    percent: added.percent,
+                }
+            }
+            "liquidity_oscillation" => {
+                let oscillation: LiquidityOscillationPayload = serde_json::from_value(data)?;
+                AlertPayload::LiquidityOscillation {
+                    token: oscillation.token,
+                    flips: oscillation.flips,
+                    amplitude_sol: oscillation.amplitude_sol,
+                    window_secs: oscillation.window_secs,
+                }
+            }
+            "safety_downgrade" => {
+                let downgrade: SafetyDowngradePayload = serde_json::from_value(data)?;
+                AlertPayload::SafetyDowngrade {
+                    token: downgrade.token,
+                    from: downgrade.from,
+                    to: downgrade.to,
+                    risk_score: downgrade.risk_score,
+                }
+            }
+            "suspicious" => {
+                let suspicious: SuspiciousPayload = serde_json::from_value(data)?;
+                AlertPayload::Suspicious {
+                    token: suspicious.token,
+                    reason: suspicious.reason,
+                }
+            }
+            other => bail!("Alert type '{}' has no typed payload", other),
+        };
+        Ok(payload)
+    }
+}
+
+/// Typed alert payload. Serializes untagged so `Alert.data` keeps its wire shape;
+/// decode with `Alert::payload`, which picks the variant from the alert type.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum AlertPayload {
+    NewToken {
+        #[serde(flatten)]
+        token: TokenAlertInfo,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        possible_copycat: Option<CopycatMatch>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        detection_lag_ms: Option<i64>,
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        incomplete_metadata: bool,
+    },
+    Rug {
+        token: TokenAlertInfo,
+        reason: String,
+        severity: String,
+    },
+    Whale {
+        wallet: String,
+        token: TokenAlertInfo,
+        amount_sol: f64,
+        amount_tokens: f64,
+        #[serde(rename = "type")]
+        tx_type: String,
+        since_last_alert: Option<SuppressedActivity>,
+        wallet_history: Option<WalletHistory>,
+    },
+    CreatorWhale {
+        wallet: String,
+        token: TokenAlertInfo,
+        amount_sol: f64,
+        amount_tokens: f64,
+        #[serde(rename = "type")]
+        tx_type: String,
+        severity: String,
+    },
+    WhaleRotation {
+        wallet: String,
+        from_token: TokenAlertInfo,
+        to_token: TokenAlertInfo,
+        sold_sol: f64,
+        bought_sol: f64,
+        elapsed_secs: u64,
+    },
+    Accumulation {
+        token: TokenAlertInfo,
+        score: f64,
+        threshold: f64,
+        windows: Vec<AccumulationWindow>,
+    },
+    Sandwich {
+        token: TokenAlertInfo,
+        attacker: String,
+        victim: String,
+        victim_type: String,
+        front_run: SandwichLeg,
+        victim_trade: SandwichLeg,
+        back_run: SandwichLeg,
+        extracted_sol: f64,
+        slot_span: u64,
+    },
+    Suspicious {
+        token: TokenAlertInfo,
+        reason: String,
+    },
+    Abandoned {
+        token: TokenAlertInfo,
+        idle_secs: u64,
+    },
+    HolderGrowth {
+        token: TokenAlertInfo,
+        holder_count: u64,
+        milestone: u64,
+    },
+    MarketCap {
+        token: TokenAlertInfo,
+        market_cap_usd: f64,
+        milestone_usd: f64,
+    },
+    SafetyDowngrade {
+        token: TokenAlertInfo,
+        from: String,
+        to: String,
+        risk_score: i32,
+    },
+    LiquidityAdded {
+        token: TokenAlertInfo,
+        before_sol: f64,
+        after_sol: f64,
+        percent: f64,
+    },
+    LiquidityOscillation {
+        token: TokenAlertInfo,
+        flips: u32,
+        amplitude_sol: f64,
+        window_secs: u64,
+    },
+}
+
+#[derive(Deserialize)]
+struct NewTokenPayload {
+    #[serde(flatten)]
+    token: TokenAlertInfo,
+    possible_copycat: Option<CopycatMatch>,
+    #[serde(default)]
+    detection_lag_ms: Option<i64>,
+    #[serde(default)]
+    incomplete_metadata: bool,
+}
+
+#[derive(Deserialize)]
+struct RugPayload {
+    token: TokenAlertInfo,
+    reason: String,
+    severity: String,
+}
+
+#[derive(Deserialize)]
+struct WhalePayload {
+    wallet: String,
+    token: TokenAlertInfo,
+    amount_sol: f64,
+    amount_tokens: f64,
+    #[serde(rename = "type")]
+    tx_type: String,
+    since_last_alert: Option<SuppressedActivity>,
+    #[serde(default)]
+    wallet_history: Option<WalletHistory>,
+}
+
+#[derive(Deserialize)]
+struct CreatorWhalePayload {
+    wallet: String,
+    token: TokenAlertInfo,
+    amount_sol: f64,
+    amount_tokens: f64,
+    #[serde(rename = "type")]
+    tx_type: String,
+    severity: String,
+}
+
+#[derive(Deserialize)]
+struct SuspiciousPayload {
+    token: TokenAlertInfo,
+    reason: String,
+}
+
+#[derive(Deserialize)]
+struct AbandonedPayload {
+    token: TokenAlertInfo,
+    idle_secs: u64,
+}
+
+#[derive(Deserialize)]
+struct HolderGrowthPayload {
+    token: TokenAlertInfo,
+    holder_count: u64,
+    milestone: u64,
+}
+
+#[derive(Deserialize)]
+struct MarketCapPayload {
+    token: TokenAlertInfo,
+    market_cap_usd: f64,
+    milestone_usd: f64,
+}
+
+#[derive(Deserialize)]
+struct LiquidityAddedPayload {
+    token: TokenAlertInfo,
+    before_sol: f64,
+    after_sol: f64,
+    percent: f64,
+}
+
+#[derive(Deserialize)]
+struct WhaleRotationPayload {
+    wallet: String,
+    from_token: TokenAlertInfo,
+    to_token: TokenAlertInfo,
+    sold_sol: f64,
+    bought_sol: f64,
+    elapsed_secs: u64,
+}
+
+#[derive(Deserialize)]
+struct SandwichPayload {
+    token: TokenAlertInfo,
+    attacker: String,
+    victim: String,
+    victim_type: String,
+    front_run: SandwichLeg,
+    victim_trade: SandwichLeg,
+    back_run: SandwichLeg,
+    extracted_sol: f64,
+    slot_span: u64,
+}
+
+#[derive(Deserialize)]
+struct AccumulationPayload {
+    token: TokenAlertInfo,
+    score: f64,
+    threshold: f64,
+    windows: Vec<AccumulationWindow>,
+}
+
+#[derive(Deserialize)]
+struct LiquidityOscillationPayload {
+    token: TokenAlertInfo,
+    flips: u32,
+    amplitude_sol: f64,
+    window_secs: u64,
+}
+
+#[derive(Deserialize)]
+struct SafetyDowngradePayload {
+    token: TokenAlertInfo,
+    from: String,
+    to: String,
+    risk_score: i32,
 }
 
 /// Token info for alerts
@@ -33,20 +432,236 @@ pub struct TokenAlertInfo {
     pub initial_liquidity: Option<f64>,
 }
 
+/// Previously rugged token a new launch closely resembles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopycatMatch {
+    pub mint: String,
+    pub name: String,
+    pub symbol: String,
+    pub rug_reason: Option<String>,
+}
+
+/// Whale activity suppressed by the per-wallet cooldown since the previous alert
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuppressedActivity {
+    pub trades: u32,
+    pub net_sol: f64,
+}
+
+/// Buying activity in a token over one accumulation scoring window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccumulationWindow {
+    pub window_secs: u64,
+    pub whale_buys: usize,
+    pub net_flow: f64,
+    pub unique_buyers: usize,
+    pub score: f64,
+}
+
+/// One trade of a possible sandwich
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SandwichLeg {
+    pub signature: String,
+    pub slot: u64,
+    pub amount_sol: f64,
+}
+
+/// What is known about a whale's past trading, so recipients can tell a known wallet from
+/// a one-off; net flow is SOL received from sells minus SOL spent on buys
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletHistory {
+    pub total_volume_sol: f64,
+    pub trades: i64,
+    pub tokens_traded: i64,
+    pub net_sol_flow: f64,
+}
+
+/// How often buffered alert file lines are flushed to disk
+const ALERT_FILE_FLUSH_SECS: u64 = 1;
+
+/// Appends alerts to a file as NDJSON, rotating to `<path>.1` by size. Lines are buffered
+/// and reach the file on the periodic flush, a rotation or shutdown.
+struct AlertFileSink {
+    path: PathBuf,
+    max_bytes: u64,
+    writer: Option<BufWriter<File>>,
+    written: u64,
+}
+
+impl AlertFileSink {
+    fn new(path: &str, max_bytes: u64) -> Self {
+        Self {
+            path: PathBuf::from(path),
+            max_bytes,
+            writer: None,
+            written: 0,
+        }
+    }
+
+    async fn open(&mut self) -> Result<()> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        self.written = file.metadata().await?.len();
+        self.writer = Some(BufWriter::new(file));
+        Ok(())
+    }
+
+    async fn rotate(&mut self) -> Result<()> {
+        if let Some(mut writer) = self.writer.take() {
+            writer.flush().await?;
+        }
+        let mut rotated = self.path.clone().into_os_string();
+        rotated.push(".1");
+        tokio::fs::rename(&self.path, rotated).await?;
+        self.open().await
+    }
+
+    async fn write(&mut self, alert: &Alert) -> Result<()> {
+        let mut line = serde_json::to_vec(alert)?;
+        line.push(b'\n');
+
+        if self.writer.is_none() {
+            self.open().await?;
+        }
+        if self.max_bytes > 0 && self.written > 0 && self.written + line.len() as u64 > self.max_bytes {
+            self.rotate().await?;
+        }
+
+        if let Some(writer) = self.writer.as_mut() {
+            writer.write_all(&line).await?;
+            self.written += line.len() as u64;
+        }
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        if let Some(writer) = self.writer.as_mut() {
+            writer.flush().await?;
+        }
+        Ok(())
+    }
+}
+
+/// Minimal payload for the rug webhook, kept small so automations can act fast
+#[derive(Debug, Clone, Serialize)]
+pub struct RugWebhookPayload {
+    pub mint: String,
+    pub reason: String,
+    pub severity: String,
+    /// Unix milliseconds
+    pub timestamp: i64,
+    /// Liquidity in SOL when the rug was confirmed
+    pub liquidity: f64,
+}
+
+/// A webhook body with the sending instance's `INSTANCE_NAME` alongside the payload fields
+#[derive(Serialize)]
+pub struct InstanceTagged<'a, T> {
+    #[serde(flatten)]
+    pub payload: &'a T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<&'a str>,
+}
+
+/// How often expired alert markers are deleted
+const MARKER_PRUNE_INTERVAL_SECS: u64 = 3600;
+
+/// Persisted "last alerted" markers. Only markers left by an earlier run are consulted,
+/// so repeats within a run stay up to each module.
+struct RestartDedup {
+    database: Arc<DatabaseService>,
+    window_secs: u64,
+    started_at: String,
+}
+
+impl RestartDedup {
+    /// Which token or wallet an alert is about, read from its data
+    fn subject(data: &serde_json::Value) -> Option<String> {
+        let mint = data
+            .get("mint")
+            .or_else(|| data.pointer("/token/mint"))
+            .and_then(|v| v.as_str());
+        let wallet = data.get("wallet").and_then(|v| v.as_str());
+        match (wallet, mint) {
+            (Some(wallet), Some(mint)) => Some(format!("{}:{}", wallet, mint)),
+            (Some(subject), None) | (None, Some(subject)) => Some(subject.to_string()),
+            (None, None) => None,
+        }
+    }
+
+    /// Whether the same alert already went out before this restart, within the window
+    fn already_sent(&self, alert_type: &str, subject: &str) -> bool {
+        let cutoff = (Utc::now() - chrono::Duration::seconds(self.window_secs as i64)).to_rfc3339();
+        match self.database.last_alerted(alert_type, subject) {
+            Ok(Some(sent_at)) => sent_at < self.started_at && sent_at >= cutoff,
+            Ok(None) => false,
+            Err(e) => {
+                warn!(target: "ALERTS", "Alert marker lookup failed: {}", e);
+                false
+            }
+        }
+    }
+}
+
+/// Every alert type the modules emit; each can be muted with `ALERT_<TYPE>=false`
+pub const ALERT_TYPES: &[&str] = &[
+    "rug",
+    "whale_buy",
+    "whale_sell",
+    "creator_whale_activity",
+    "whale_rotation",
+    "accumulation",
+    "new_token",
+    "suspicious",
+    "concentrated_launch",
+    "launch_burst",
+    "launch_surge",
+    "spam_creator",
+    "abandoned",
+    "holder_growth",
+    "safety_downgrade",
+    "liquidity_added",
+    "liquidity_oscillation",
+    "mcap_milestone",
+    "possible_sandwich",
+    "digest",
+    "custom_watch",
+];
+
 /// Alert service for sending notifications
 pub struct AlertService {
     config: Config,
     telegram_client: Option<reqwest::Client>,
+    /// Sends waiting for one of `TELEGRAM_SEND_WORKERS`; `None` without a bot token
+    telegram_queue: Option<Arc<WorkQueue<reqwest::RequestBuilder>>>,
+    rug_webhook_client: Option<reqwest::Client>,
+    file_sink: Option<Arc<Mutex<AlertFileSink>>>,
     alert_history: Arc<RwLock<VecDeque<Alert>>>,
     alert_sender: broadcast::Sender<Alert>,
     next_id: Arc<RwLock<i64>>,
+    restart_dedup: Option<Arc<RestartDedup>>,
+    muted_types: Arc<RwLock<HashSet<String>>>,
 }
 
 impl AlertService {
     /// Create a new alert service
     pub fn new(config: Config) -> Self {
         let telegram_client = if config.telegram_bot_token.is_some() {
-            Some(reqwest::Client::new())
+            // One pooled client for every send; timeouts keep a hung API from piling up tasks
+            let client = reqwest::Client::builder()
+                .connect_timeout(Duration::from_secs(config.telegram_connect_timeout_secs))
+                .timeout(Duration::from_secs(config.telegram_timeout_secs))
+                .pool_idle_timeout(Duration::from_secs(90))
+                .pool_max_idle_per_host(4)
+                .build()
+                .unwrap_or_else(|e| {
+                    warn!(target: "ALERTS", "Telegram client config rejected ({}), using defaults", e);
+                    reqwest::Client::new()
+                });
+            Some(client)
         } else {
             None
         };
@@ -54,15 +669,121 @@ impl AlertService {
         if telegram_client.is_some() {
             info!(target: "ALERTS", "Telegram bot initialized");
         }
+        let telegram_queue = telegram_client.as_ref().map(|_| {
+            let timeout_secs = config.telegram_timeout_secs;
+            Arc::new(WorkQueue::spawn(
+                "telegram",
+                config.telegram_queue_size,
+                config.telegram_send_workers,
+                QueueStats::default(),
+                move |request: reqwest::RequestBuilder| async move {
+                    match request.send().await {
+                        Ok(_) => {}
+                        Err(e) if e.is_timeout() => warn!(
+                            target: "ALERTS",
+                            "Telegram send timed out after {}s",
+                            timeout_secs
+                        ),
+                        Err(e) => error!(target: "ALERTS", "Telegram send failed: {}", e),
+                    }
+                },
+            ))
+        });
+
+        let rug_webhook_client = config.rug_webhook_url.as_ref().and_then(|_| {
+            let timeout = Duration::from_millis(config.rug_webhook_timeout_ms);
+            match reqwest::Client::builder().connect_timeout(timeout).timeout(timeout).build() {
+                Ok(client) => Some(client),
+                Err(e) => {
+                    warn!(target: "ALERTS", "Rug webhook disabled, client config rejected: {}", e);
+                    None
+                }
+            }
+        });
+
+        let file_sink = config.alert_file_path.as_deref().map(|path| {
+            info!(target: "ALERTS", "Writing alerts to {}", path);
+            Arc::new(Mutex::new(AlertFileSink::new(path, config.alert_file_max_bytes)))
+        });
 
         let (alert_sender, _) = broadcast::channel(10000);
+        let muted_types: HashSet<String> = config.muted_alert_types.iter().cloned().collect();
+        if !muted_types.is_empty() {
+            info!(target: "ALERTS", "Muted alert types: {}", config.muted_alert_types.join(", "));
+        }
 
         Self {
             config,
             telegram_client,
+            telegram_queue,
+            rug_webhook_client,
+            file_sink,
             alert_history: Arc::new(RwLock::new(VecDeque::with_capacity(1000))),
             alert_sender,
             next_id: Arc::new(RwLock::new(1)),
+            restart_dedup: None,
+            muted_types: Arc::new(RwLock::new(muted_types)),
+        }
+    }
+
+    /// Remember what was alerted in the database and skip alerts that already went out
+    /// before a restart within `window_secs` (0 = off)
+    pub fn with_restart_dedup(mut self, database: Arc<DatabaseService>, window_secs: u64) -> Self {
+        self.restart_dedup = (window_secs > 0).then(|| {
+            Arc::new(RestartDedup {
+                database,
+                window_secs,
+                started_at: Utc::now().to_rfc3339(),
+            })
+        });
+        self
+    }
+
+    /// Prune alert markers older than the dedup window every hour
+    pub fn start_marker_prune(&self) {
+        let Some(dedup) = self.restart_dedup.clone() else {
+            return;
+        };
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(MARKER_PRUNE_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                match dedup.database.prune_alert_markers(dedup.window_secs) {
+                    Ok(removed) if removed > 0 => {
+                        info!(target: "ALERTS", "Pruned {} expired alert markers", removed);
+                    }
+                    Ok(_) => {}
+                    Err(e) => warn!(target: "ALERTS", "Alert marker prune failed: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Flush the alert file every `ALERT_FILE_FLUSH_SECS`
+    pub fn start_file_flush(&self) {
+        if self.file_sink.is_none() {
+            return;
+        }
+        let alerts = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(ALERT_FILE_FLUSH_SECS));
+            loop {
+                interval.tick().await;
+                alerts.flush_file().await;
+            }
+        });
+    }
+
+    /// Write buffered alert file lines to disk
+    pub async fn flush_file(&self) {
+        let Some(sink) = &self.file_sink else {
+            return;
+        };
+        let mut sink = sink.lock().await;
+        if let Err(e) = sink.flush().await {
+            sink.writer = None;
+            error!(target: "ALERTS", "Alert file flush failed: {}", e);
         }
     }
 
@@ -71,7 +792,39 @@ impl AlertService {
         self.alert_sender.subscribe()
     }
 
-    /// Send an alert
+    /// Whether each alert type is delivered; muted types are still recorded
+    pub fn alert_settings(&self) -> BTreeMap<String, bool> {
+        let muted = self.muted_types.read();
+        ALERT_TYPES
+            .iter()
+            .map(|alert_type| (alert_type.to_string(), !muted.contains(*alert_type)))
+            .collect()
+    }
+
+    /// Enable or mute alert types; unknown types are rejected before anything changes
+    pub fn set_alert_settings(
+        &self,
+        changes: &HashMap<String, bool>,
+    ) -> PumpResult<BTreeMap<String, bool>> {
+        if let Some(unknown) = changes.keys().find(|t| !ALERT_TYPES.contains(&t.as_str())) {
+            return Err(PumpError::InvalidInput(format!("Unknown alert type: {}", unknown)));
+        }
+        {
+            let mut muted = self.muted_types.write();
+            for (alert_type, enabled) in changes {
+                if *enabled {
+                    muted.remove(alert_type);
+                } else {
+                    muted.insert(alert_type.clone());
+                }
+            }
+        }
+        info!(target: "ALERTS", "Alert settings updated: {:?}", changes);
+        Ok(self.alert_settings())
+    }
+
+    /// Send an alert. Muted types are kept in the history and the alert file but are not
+    /// broadcast or sent to Telegram.
     pub async fn send_alert(
         &self,
         alert_type: &str,
@@ -79,6 +832,27 @@ impl AlertService {
         message: &str,
         data: serde_json::Value,
     ) -> Result<Alert> {
+        let subject = self.restart_dedup.as_ref().and_then(|_| RestartDedup::subject(&data));
+        if let (Some(dedup), Some(subject)) = (&self.restart_dedup, &subject) {
+            if dedup.already_sent(alert_type, subject) {
+                debug!(
+                    target: "ALERTS",
+                    "Skipping {} alert for {}: already sent before restart",
+                    alert_type,
+                    subject
+                );
+                bail!("{} alert for {} already sent before restart", alert_type, subject);
+            }
+        }
+        let muted = self.muted_types.read().contains(alert_type);
+        // A muted alert never went out, so it must not block the same alert after a restart
+        if let (Some(dedup), Some(subject), false) = (&self.restart_dedup, subject, muted) {
+            dedup.database.write(PendingWrite::AlertMarker {
+                alert_type: alert_type.to_string(),
+                subject,
+            });
+        }
+
         let id = {
             let mut next_id = self.next_id.write();
             let id = *next_id;
@@ -93,6 +867,7 @@ impl AlertService {
             message: message.to_string(),
             data,
             timestamp: Utc::now().to_rfc3339(),
+            instance: self.config.instance_name.clone(),
         };
 
         // Add to history
@@ -105,40 +880,106 @@ impl AlertService {
         }
 
         // Broadcast to subscribers
-        let _ = self.alert_sender.send(alert.clone());
+        if !muted {
+            let _ = self.alert_sender.send(alert.clone());
+        }
+
+        // Append to file
+        if let Some(sink) = &self.file_sink {
+            let mut sink = sink.lock().await;
+            if let Err(e) = sink.write(&alert).await {
+                // Drop the handle so the next alert retries opening the file
+                sink.writer = None;
+                error!(target: "ALERTS", "Alert file write failed: {}", e);
+            }
+        }
+
+        if muted {
+            debug!(target: "ALERTS", "Recorded muted {} alert: {}", alert_type, title);
+            return Ok(alert);
+        }
 
         // Send to Telegram
-        if let (Some(client), Some(token), Some(chat_id)) = (
+        if let (Some(client), Some(queue), Some(token), Some(chat_id)) = (
             &self.telegram_client,
+            &self.telegram_queue,
             &self.config.telegram_bot_token,
             &self.config.telegram_chat_id,
         ) {
             let emoji = self.get_emoji(alert_type);
-            let telegram_message = format!("{} *{}*\n\n{}", emoji, title, message);
+            // A code span keeps underscores in the name from breaking the Markdown
+            let prefix = alert
+                .instance
+                .as_ref()
+                .map(|name| format!("`{}` ", name.replace('`', "'")))
+                .unwrap_or_default();
+            let telegram_message = format!("{}{} *{}*\n\n{}", prefix, emoji, title, message);
 
             let url = format!("https://api.telegram.org/bot{}/sendMessage", token);
-            let params = serde_json::json!({
+            let mut params = serde_json::json!({
                 "chat_id": chat_id,
                 "text": telegram_message,
                 "parse_mode": "Markdown",
                 "disable_web_page_preview": true,
             });
-
-            if let Err(e) = client.post(&url).json(&params).send().await {
-                error!(target: "ALERTS", "Telegram send failed: {}", e);
+            if self.config.telegram_buttons {
+                if let Some(markup) = Self::alert_mint(&alert.data)
+                    .and_then(|mint| telegram_link_buttons(mint, self.config.cluster))
+                {
+                    params["reply_markup"] = markup;
+                }
             }
+
+            // Queued for a send worker so a slow Telegram API never holds up the caller
+            // and a burst of alerts can't pile up unbounded in-flight requests
+            queue.push(client.post(&url).json(&params));
         }
 
         Ok(alert)
     }
 
+    /// Mint an alert is about: `token.mint` in typed payloads, `mint` in ad-hoc ones
+    fn alert_mint(data: &serde_json::Value) -> Option<&str> {
+        data.pointer("/token/mint")
+            .or_else(|| data.get("mint"))
+            .and_then(|mint| mint.as_str())
+            .filter(|mint| !mint.is_empty())
+    }
+
+    /// Send an alert whose data is a typed payload
+    async fn send_payload(
+        &self,
+        alert_type: &str,
+        title: &str,
+        message: &str,
+        payload: AlertPayload,
+    ) -> Result<Alert> {
+        self.send_alert(alert_type, title, message, serde_json::to_value(payload)?)
+            .await
+    }
+
     fn get_emoji(&self, alert_type: &str) -> &'static str {
         match alert_type {
             "rug" => "🚨",
             "whale_buy" => "🐋📈",
             "whale_sell" => "🐋📉",
+            "creator_whale_activity" => "🐋🚩",
+            "whale_rotation" => "🐋🔄",
+            "accumulation" => "🐋📥",
+            "possible_sandwich" => "🥪",
             "new_token" => "🆕",
             "suspicious" => "⚠️",
+            "concentrated_launch" => "🎯",
+            "launch_burst" => "🌊",
+            "launch_surge" => "📊",
+            "spam_creator" => "🚫",
+            "abandoned" => "💤",
+            "holder_growth" => "📈",
+            "safety_downgrade" => "🔻",
+            "liquidity_added" => "💧",
+            "liquidity_oscillation" => "🔁",
+            "digest" => "📰",
+            "custom_watch" => "👀",
             "success" => "✅",
             "error" => "❌",
             _ => "📢",
@@ -146,6 +987,11 @@ impl AlertService {
     }
 
     /// Get recent alerts
+    /// SOL amount at the configured precision
+    fn sol(&self, amount: f64) -> String {
+        format_sol(amount, self.config.alert_sol_digits)
+    }
+
     pub fn get_recent_alerts(&self, limit: usize) -> Vec<Alert> {
         let history = self.alert_history.read();
         history.iter().take(limit).cloned().collect()
@@ -155,26 +1001,137 @@ impl AlertService {
     // SPECIFIC ALERT METHODS
     // ============================================
 
-    pub async fn alert_new_token(&self, token: &TokenAlertInfo) -> Result<Alert> {
+    pub async fn alert_new_token(
+        &self,
+        token: &TokenAlertInfo,
+        possible_copycat: Option<&CopycatMatch>,
+        detection_lag_ms: Option<i64>,
+        incomplete_metadata: bool,
+    ) -> Result<Alert> {
         let liquidity = token
             .initial_liquidity
-            .map(|l| format!("{:.2} SOL", l))
+            .map(|l| self.sol(l))
             .unwrap_or_else(|| "Unknown".to_string());
 
-        let message = format!(
-            "Token: {} ({})\nMint: `{}`\nCreator: `{}`\nLiquidity: {}",
-            token.name, token.symbol, token.mint, token.creator, liquidity
+        let mut message = format!(
+            "Token: {} ({})\nMint: `{}`\nCreator: `{}`\nLiquidity: {}\n[Explorer]({})",
+            token.name,
+            token.symbol,
+            token.mint,
+            token.creator,
+            liquidity,
+            self.config.explorer_link(&format!("address/{}", token.mint))
         );
+        if let Some(original) = possible_copycat {
+            message.push_str(&format!(
+                "\nPossible copycat of rugged {} ({}) `{}`",
+                original.name, original.symbol, original.mint
+            ));
+        }
+        // A large lag means the monitor is falling behind or this is an old token resurfacing
+        let warn_ms = self.config.detection_lag_warn_ms;
+        if let Some(lag) = detection_lag_ms.filter(|&lag| warn_ms > 0 && lag > warn_ms) {
+            message.push_str(&format!("\n⚠️ Detected {:.1}s after creation", lag as f64 / 1000.0));
+        }
+        if incomplete_metadata {
+            message.push_str("\n⚠️ Name, symbol or liquidity could not be resolved");
+        }
 
-        self.send_alert(
+        self.send_payload(
             "new_token",
             "New Token Detected",
             &message,
-            serde_json::to_value(token)?,
+            AlertPayload::NewToken {
+                token: token.clone(),
+                possible_copycat: possible_copycat.cloned(),
+                detection_lag_ms,
+                incomplete_metadata,
+            },
         )
         .await
     }
 
+    /// Verify the bot token with Telegram `getMe` and return the bot's username.
+    /// `None` when Telegram isn't configured.
+    pub async fn check_telegram(&self) -> Option<Result<String>> {
+        let (client, token) = match (&self.telegram_client, &self.config.telegram_bot_token) {
+            (Some(client), Some(token)) => (client, token),
+            _ => return None,
+        };
+
+        let result = async {
+            if self.config.telegram_chat_id.is_none() {
+                bail!("TELEGRAM_CHAT_ID is not set");
+            }
+            let url = format!("https://api.telegram.org/bot{}/getMe", token);
+            // Errors would otherwise print the URL, and with it the bot token
+            let response = client.get(&url).send().await.map_err(|e| e.without_url())?;
+            let body: serde_json::Value = response.json().await.map_err(|e| e.without_url())?;
+            if body["ok"].as_bool() != Some(true) {
+                bail!("{}", body["description"].as_str().unwrap_or("getMe rejected the bot token"));
+            }
+            Ok(format!("@{}", body["result"]["username"].as_str().unwrap_or("unknown")))
+        };
+        Some(result.await)
+    }
+
+    /// Post a confirmed rug to `RUG_WEBHOOK_URL` in the background; returns immediately
+    pub fn send_rug_webhook(&self, payload: &RugWebhookPayload) {
+        let (client, url) = match (&self.rug_webhook_client, &self.config.rug_webhook_url) {
+            (Some(client), Some(url)) => (client, url),
+            _ => return,
+        };
+        let tagged = InstanceTagged {
+            payload,
+            instance: self.config.instance_name.as_deref(),
+        };
+        let body = match serde_json::to_vec(&tagged) {
+            Ok(body) => body,
+            Err(e) => {
+                error!(target: "ALERTS", "Rug webhook payload failed to serialize: {}", e);
+                return;
+            }
+        };
+
+        let mut request = client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(secret) = &self.config.rug_webhook_secret {
+            request = request.header("X-PumpGuard-Signature", Self::sign(secret, &body));
+        }
+        let request = request.body(body);
+
+        let mint = payload.mint.clone();
+        tokio::spawn(async move {
+            match request.send().await {
+                Ok(response) if response.status().is_success() => {}
+                Ok(response) => {
+                    warn!(target: "ALERTS", "Rug webhook for {} returned {}", mint, response.status());
+                }
+                Err(e) if e.is_timeout() => warn!(target: "ALERTS", "Rug webhook for {} timed out", mint),
+                Err(e) => error!(target: "ALERTS", "Rug webhook for {} failed: {}", mint, e),
+            }
+        });
+    }
+
+    /// `sha256=<hex>` HMAC-SHA256 of `body`, for receivers to verify the sender
+    fn sign(secret: &str, body: &[u8]) -> String {
+        use hmac::{Hmac, Mac};
+        use std::fmt::Write;
+
+        let mut mac = Hmac::<sha2::Sha256>::new_from_slice(secret.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(body);
+        let digest = mac.finalize().into_bytes();
+
+        let mut signature = String::with_capacity(7 + digest.len() * 2);
+        signature.push_str("sha256=");
+        for byte in digest {
+            let _ = write!(signature, "{:02x}", byte);
+        }
+        signature
+    }
+
     pub async fn alert_rug_pull(
         &self,
         token: &TokenAlertInfo,
@@ -182,23 +1139,27 @@ impl AlertService {
         severity: &str,
     ) -> Result<Alert> {
         let message = format!(
-            "Token: {}\nMint: `{}`\nReason: {}",
-            token.symbol, token.mint, reason
+            "Token: {}\nMint: `{}`\nReason: {}\n[Explorer]({})",
+            token.symbol,
+            token.mint,
+            reason,
+            self.config.explorer_link(&format!("address/{}", token.mint))
         );
 
-        self.send_alert(
+        self.send_payload(
             "rug",
             &format!("RUG PULL DETECTED - {}", severity.to_uppercase()),
             &message,
-            serde_json::json!({
-                "token": token,
-                "reason": reason,
-                "severity": severity,
-            }),
+            AlertPayload::Rug {
+                token: token.clone(),
+                reason: reason.to_string(),
+                severity: severity.to_string(),
+            },
         )
         .await
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn alert_whale(
         &self,
         tx_type: &str,
@@ -206,6 +1167,9 @@ impl AlertService {
         token: &TokenAlertInfo,
         amount_sol: f64,
         amount_tokens: f64,
+        token_decimals: u8,
+        since_last_alert: Option<&SuppressedActivity>,
+        wallet_history: Option<&WalletHistory>,
     ) -> Result<Alert> {
         let action = if tx_type == "buy" {
             "ACCUMULATING"
@@ -213,22 +1177,82 @@ impl AlertService {
             "DUMPING"
         };
 
-        let message = format!(
-            "Wallet: `{}`\nToken: {}\nAmount: {:.2} SOL ({} tokens)",
-            wallet, token.symbol, amount_sol, amount_tokens as i64
+        let mut message = format!(
+            "Wallet: `{}`\nToken: {}\nAmount: {} ({} tokens)",
+            wallet,
+            token.symbol,
+            self.sol(amount_sol),
+            format_tokens(amount_tokens, token_decimals)
         );
+        if let Some(activity) = since_last_alert {
+            message.push_str(&format!(
+                "\nSince last alert: net {} across {} trades",
+                format_sol_signed(activity.net_sol, self.config.alert_sol_digits),
+                activity.trades
+            ));
+        }
+        if let Some(history) = wallet_history {
+            message.push_str(&format!(
+                "\nHistory: {} volume, {} trades across {} tokens, net {}",
+                self.sol(history.total_volume_sol),
+                history.trades,
+                history.tokens_traded,
+                format_sol_signed(history.net_sol_flow, self.config.alert_sol_digits)
+            ));
+        }
 
-        self.send_alert(
+        self.send_payload(
             &format!("whale_{}", tx_type),
             &format!("Whale {}", action),
             &message,
-            serde_json::json!({
-                "wallet": wallet,
-                "token": token,
-                "amount_sol": amount_sol,
-                "amount_tokens": amount_tokens,
-                "type": tx_type,
-            }),
+            AlertPayload::Whale {
+                wallet: wallet.to_string(),
+                token: token.clone(),
+                amount_sol,
+                amount_tokens,
+                tx_type: tx_type.to_string(),
+                since_last_alert: since_last_alert.cloned(),
+                wallet_history: wallet_history.cloned(),
+            },
+        )
+        .await
+    }
+
+    /// Whale trade by the token's own creator; sells are critical, buys high
+    pub async fn alert_creator_whale_activity(
+        &self,
+        tx_type: &str,
+        wallet: &str,
+        token: &TokenAlertInfo,
+        amount_sol: f64,
+        amount_tokens: f64,
+        token_decimals: u8,
+    ) -> Result<Alert> {
+        let severity = if tx_type == "sell" { "critical" } else { "high" };
+        let action = if tx_type == "sell" { "sold" } else { "bought" };
+        let message = format!(
+            "Creator `{}` {} {} ({} tokens) of their own token {}\nMint: `{}`\nSeverity: {}",
+            wallet,
+            action,
+            self.sol(amount_sol),
+            format_tokens(amount_tokens, token_decimals),
+            token.symbol,
+            token.mint,
+            severity
+        );
+
+        self.send_payload(
+            "creator_whale_activity",
+            "Creator Whale Activity",
+            &message,
+            AlertPayload::CreatorWhale {
+                wallet: wallet.to_string(),
+                token: token.clone(),
+                amount_sol,
+                amount_tokens,
+                tx_type: tx_type.to_string(),
+                severity: severity.to_string(),
+            },
         )
         .await
     }
@@ -239,14 +1263,294 @@ impl AlertService {
             token.symbol, token.mint, reason
         );
 
-        self.send_alert(
+        self.send_payload(
             "suspicious",
             "Suspicious Activity",
             &message,
-            serde_json::json!({
-                "token": token,
-                "reason": reason,
-            }),
+            AlertPayload::Suspicious {
+                token: token.clone(),
+                reason: reason.to_string(),
+            },
+        )
+        .await
+    }
+
+    pub async fn alert_abandoned(&self, token: &TokenAlertInfo, idle_secs: u64) -> Result<Alert> {
+        let message = format!(
+            "Token: {}\nMint: `{}`\nNo trades for {} min",
+            token.symbol,
+            token.mint,
+            idle_secs / 60
+        );
+
+        self.send_payload(
+            "abandoned",
+            "Token Abandoned",
+            &message,
+            AlertPayload::Abandoned {
+                token: token.clone(),
+                idle_secs,
+            },
+        )
+        .await
+    }
+
+    pub async fn alert_liquidity_added(
+        &self,
+        token: &TokenAlertInfo,
+        before_sol: f64,
+        after_sol: f64,
+    ) -> Result<Alert> {
+        let percent = (after_sol - before_sol) / before_sol * 100.0;
+        let message = format!(
+            "Token: {}\nMint: `{}`\nLiquidity: {} -> {} (+{:.1}%)",
+            token.symbol,
+            token.mint,
+            self.sol(before_sol),
+            self.sol(after_sol),
+            percent
+        );
+
+        self.send_payload(
+            "liquidity_added",
+            "Liquidity Added",
+            &message,
+            AlertPayload::LiquidityAdded {
+                token: token.clone(),
+                before_sol,
+                after_sol,
+                percent,
+            },
+        )
+        .await
+    }
+
+    /// Whale that sold one token and bought another shortly after
+    pub async fn alert_whale_rotation(
+        &self,
+        wallet: &str,
+        from_token: &TokenAlertInfo,
+        sold_sol: f64,
+        to_token: &TokenAlertInfo,
+        bought_sol: f64,
+        elapsed_secs: u64,
+    ) -> Result<Alert> {
+        let message = format!(
+            "Wallet: `{}`\nSold: {} of {} (`{}`)\nBought: {} of {} (`{}`)\nWithin: {}s",
+            wallet,
+            self.sol(sold_sol),
+            from_token.symbol,
+            from_token.mint,
+            self.sol(bought_sol),
+            to_token.symbol,
+            to_token.mint,
+            elapsed_secs
+        );
+
+        self.send_payload(
+            "whale_rotation",
+            "Whale Rotation",
+            &message,
+            AlertPayload::WhaleRotation {
+                wallet: wallet.to_string(),
+                from_token: from_token.clone(),
+                to_token: to_token.clone(),
+                sold_sol,
+                bought_sol,
+                elapsed_secs,
+            },
+        )
+        .await
+    }
+
+    /// Token whose multi-window accumulation score crossed the alert threshold
+    pub async fn alert_accumulation(
+        &self,
+        token: &TokenAlertInfo,
+        score: f64,
+        threshold: f64,
+        windows: &[AccumulationWindow],
+    ) -> Result<Alert> {
+        let mut message = format!(
+            "Token: {} (`{}`)\nScore: {:.1} (threshold {:.1})",
+            token.symbol, token.mint, score, threshold
+        );
+        for window in windows {
+            message.push_str(&format!(
+                "\n{}s: {} whale buys, net {}, {} buyers",
+                window.window_secs,
+                window.whale_buys,
+                format_sol_signed(window.net_flow, self.config.alert_sol_digits),
+                window.unique_buyers
+            ));
+        }
+
+        self.send_payload(
+            "accumulation",
+            "Whale Accumulation",
+            &message,
+            AlertPayload::Accumulation {
+                token: token.clone(),
+                score,
+                threshold,
+                windows: windows.to_vec(),
+            },
+        )
+        .await
+    }
+
+    /// Wallet that traded right before and after another wallet's trade of the same token
+    #[allow(clippy::too_many_arguments)]
+    pub async fn alert_sandwich(
+        &self,
+        token: &TokenAlertInfo,
+        attacker: &str,
+        victim: &str,
+        victim_type: &str,
+        front_run: &SandwichLeg,
+        victim_trade: &SandwichLeg,
+        back_run: &SandwichLeg,
+        extracted_sol: f64,
+    ) -> Result<Alert> {
+        let slot_span = back_run.slot.saturating_sub(front_run.slot);
+        let message = format!(
+            "Token: {} (`{}`)\nAttacker: `{}`\nVictim: `{}` ({} {})\n\
+             Front-run: {} / Back-run: {}\nExtracted: ~{} over {} slot(s)\nVictim tx: `{}`",
+            token.symbol,
+            token.mint,
+            attacker,
+            victim,
+            victim_type,
+            self.sol(victim_trade.amount_sol),
+            self.sol(front_run.amount_sol),
+            self.sol(back_run.amount_sol),
+            self.sol(extracted_sol),
+            slot_span,
+            victim_trade.signature
+        );
+
+        self.send_payload(
+            "possible_sandwich",
+            "Possible Sandwich",
+            &message,
+            AlertPayload::Sandwich {
+                token: token.clone(),
+                attacker: attacker.to_string(),
+                victim: victim.to_string(),
+                victim_type: victim_type.to_string(),
+                front_run: front_run.clone(),
+                victim_trade: victim_trade.clone(),
+                back_run: back_run.clone(),
+                extracted_sol,
+                slot_span,
+            },
+        )
+        .await
+    }
+
+    pub async fn alert_liquidity_oscillation(
+        &self,
+        token: &TokenAlertInfo,
+        flips: u32,
+        amplitude_sol: f64,
+        window_secs: u64,
+    ) -> Result<Alert> {
+        let message = format!(
+            "Token: {}\nMint: `{}`\nLiquidity reversed {} times in {} min\nAmplitude: {}",
+            token.symbol,
+            token.mint,
+            flips,
+            window_secs / 60,
+            self.sol(amplitude_sol)
+        );
+
+        self.send_payload(
+            "liquidity_oscillation",
+            "Liquidity Oscillation",
+            &message,
+            AlertPayload::LiquidityOscillation {
+                token: token.clone(),
+                flips,
+                amplitude_sol,
+                window_secs,
+            },
+        )
+        .await
+    }
+
+    pub async fn alert_safety_downgrade(
+        &self,
+        token: &TokenAlertInfo,
+        from: &str,
+        to: &str,
+        risk_score: i32,
+    ) -> Result<Alert> {
+        let message = format!(
+            "Token: {}\nMint: `{}`\nSafety: {} -> {} (risk score {})",
+            token.symbol, token.mint, from, to, risk_score
+        );
+
+        self.send_payload(
+            "safety_downgrade",
+            "Safety Downgrade",
+            &message,
+            AlertPayload::SafetyDowngrade {
+                token: token.clone(),
+                from: from.to_string(),
+                to: to.to_string(),
+                risk_score,
+            },
+        )
+        .await
+    }
+
+    pub async fn alert_holder_growth(
+        &self,
+        token: &TokenAlertInfo,
+        holder_count: u64,
+        milestone: u64,
+    ) -> Result<Alert> {
+        let message = format!(
+            "Token: {}\nMint: `{}`\nHolders: {} (passed {})",
+            token.symbol, token.mint, holder_count, milestone
+        );
+
+        self.send_payload(
+            "holder_growth",
+            "Holder Milestone",
+            &message,
+            AlertPayload::HolderGrowth {
+                token: token.clone(),
+                holder_count,
+                milestone,
+            },
+        )
+        .await
+    }
+
+    pub async fn alert_mcap_milestone(
+        &self,
+        token: &TokenAlertInfo,
+        market_cap_usd: f64,
+        milestone_usd: f64,
+    ) -> Result<Alert> {
+        let message = format!(
+            "Token: {}\nMint: `{}`\nImplied market cap: {} (passed {})",
+            token.symbol,
+            token.mint,
+            format_usd(market_cap_usd),
+            format_usd(milestone_usd)
+        );
+
+        self.send_payload(
+            "mcap_milestone",
+            "Market Cap Milestone",
+            &message,
+            AlertPayload::MarketCap {
+                token: token.clone(),
+                market_cap_usd,
+                milestone_usd,
+            },
         )
         .await
     }
@@ -257,10 +1561,102 @@ impl Clone for AlertService {
         Self {
             config: self.config.clone(),
             telegram_client: self.telegram_client.clone(),
+            telegram_queue: self.telegram_queue.clone(),
+            rug_webhook_client: self.rug_webhook_client.clone(),
+            file_sink: self.file_sink.clone(),
             alert_history: Arc::clone(&self.alert_history),
             alert_sender: self.alert_sender.clone(),
             next_id: Arc::clone(&self.next_id),
+            restart_dedup: self.restart_dedup.clone(),
+            muted_types: Arc::clone(&self.muted_types),
         }
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn telegram_buttons_link_the_alerted_mint() {
+        assert_eq!(
+            telegram_link_buttons("Mint111", Cluster::Mainnet),
+            Some(serde_json::json!({
+                "inline_keyboard": [[
+                    { "text": "View on Solscan", "url": "https://solscan.io/token/Mint111" },
+                    { "text": "View on pump.fun", "url": "https://pump.fun/coin/Mint111" },
+                ]],
+            }))
+        );
+        assert_eq!(
+            telegram_link_buttons("Mint111", Cluster::Devnet),
+            Some(serde_json::json!({
+                "inline_keyboard": [[{
+                    "text": "View on Solscan",
+                    "url": "https://solscan.io/token/Mint111?cluster=devnet",
+                }]],
+            }))
+        );
+        assert_eq!(telegram_link_buttons("Mint111", Cluster::Localnet), None);
+
+        let mint = |data| AlertService::alert_mint(&data).map(String::from);
+        let typed = serde_json::json!({ "token": { "mint": "Typed111" } });
+        assert_eq!(mint(typed).unwrap(), "Typed111");
+        assert_eq!(mint(serde_json::json!({ "mint": "Adhoc111" })).unwrap(), "Adhoc111");
+        assert_eq!(mint(serde_json::json!({ "mint": "" })), None);
+    }
+
+    #[tokio::test]
+    async fn alert_file_lines_land_on_flush() {
+        let file = format!("pumpguard-alerts-{}.ndjson", std::process::id());
+        let path = std::env::temp_dir().join(file);
+        let _ = std::fs::remove_file(&path);
+        let mut config = Config::for_tests();
+        config.telegram_bot_token = None;
+        config.alert_file_path = Some(path.to_string_lossy().into_owned());
+        let alerts = AlertService::new(config);
+
+        alerts.send_alert("test", "Buffered", "first", serde_json::json!({})).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+
+        alerts.flush_file().await;
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(written.lines().count(), 1);
+        assert!(written.contains("\"title\":\"Buffered\""));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn new_token_alert_is_not_repeated_after_a_restart() {
+        let database = Arc::new(DatabaseService::new(":memory:").unwrap());
+        let mut config = Config::for_tests();
+        config.telegram_bot_token = None;
+        config.alert_file_path = None;
+        let start = || AlertService::new(config.clone()).with_restart_dedup(database.clone(), 3600);
+        let token = TokenAlertInfo {
+            mint: "Mint111".to_string(),
+            name: "Test".to_string(),
+            symbol: "TST".to_string(),
+            creator: "creator".to_string(),
+            initial_liquidity: Some(2.0),
+        };
+
+        let first_run = start();
+        first_run.alert_new_token(&token, None, None, false).await.unwrap();
+        // Repeats within a run are left to the modules
+        first_run.alert_new_token(&token, None, None, false).await.unwrap();
+        assert!(database.last_alerted("new_token", "Mint111").unwrap().is_some());
+
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let restarted = start();
+        assert!(restarted.alert_new_token(&token, None, None, false).await.is_err());
+        assert!(restarted.get_recent_alerts(10).is_empty());
+
+        // Another mint still alerts, as does the same one with dedup off
+        let other = TokenAlertInfo { mint: "Mint222".to_string(), ..token.clone() };
+        restarted.alert_new_token(&other, None, None, false).await.unwrap();
+        let no_window = AlertService::new(config.clone()).with_restart_dedup(database.clone(), 0);
+        no_window.alert_new_token(&token, None, None, false).await.unwrap();
+    }
+}
@@ -1,13 +1,18 @@
 //! SQLite database service for PumpGuard
 
-use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::Utc;
 use parking_lot::Mutex;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tracing::info;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tracing::{error, info, warn};
+
+use crate::utils::error::PumpResult;
 
 /// Token information stored in database
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +28,38 @@ pub struct TokenRecord {
     pub is_rugged: bool,
     pub rug_reason: Option<String>,
     pub last_updated: String,
+    #[serde(default)]
+    pub is_muted: bool,
+    #[serde(default)]
+    pub is_graduated: bool,
+}
+
+/// Status filters for token listings, combined with AND; `None` matches anything
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenQuery {
+    pub rugged: Option<bool>,
+    pub graduated: Option<bool>,
+    pub min_liquidity: Option<f64>,
+}
+
+/// Edit distance between two strings, counted in chars
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
 }
 
 /// Transaction record
@@ -37,6 +74,17 @@ pub struct TransactionRecord {
     pub timestamp: String,
 }
 
+/// Raw program logs kept for a transaction that raised an alert
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxLogRecord {
+    pub signature: String,
+    pub mint: String,
+    pub reason: String,
+    pub logs: Vec<String>,
+    pub created_at: String,
+}
+
 /// Wallet record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WalletRecord {
@@ -47,6 +95,15 @@ pub struct WalletRecord {
     pub is_whale: bool,
 }
 
+/// Totals over the transactions stored for one wallet
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WalletTradeSummary {
+    pub trades: i64,
+    pub tokens_traded: i64,
+    pub buy_sol: f64,
+    pub sell_sol: f64,
+}
+
 /// Alert record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlertRecord {
@@ -56,25 +113,254 @@ pub struct AlertRecord {
     pub message: String,
     pub data: String,
     pub created_at: String,
+    /// Recorded for a muted token and never sent out
+    #[serde(default)]
+    pub muted: bool,
+}
+
+/// Point-in-time holder count for a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HolderSnapshot {
+    pub holder_count: i64,
+    pub taken_at: String,
+}
+
+/// SOL volume traded by one wallet over a window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletVolume {
+    pub wallet: String,
+    pub volume_sol: f64,
+    pub trades: i64,
+}
+
+/// SOL volume and net flow for one token over a window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenVolume {
+    pub mint: String,
+    pub symbol: Option<String>,
+    pub volume_sol: f64,
+    pub net_flow_sol: f64,
+}
+
+/// Tokens whose age since creation falls in one bucket, and how many of them rugged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenCohort {
+    pub label: String,
+    pub min_age_secs: u64,
+    /// Exclusive upper bound; `None` for the oldest bucket
+    pub max_age_secs: Option<u64>,
+    pub tokens: i64,
+    pub rugged: i64,
+    /// Share of the bucket's tokens that rugged, 0.0-1.0
+    pub rug_rate: f64,
 }
 
-/// Database statistics
+/// Compact age for cohort labels: `90s`, `10m`, `1h`
+fn age_label(secs: u64) -> String {
+    match secs {
+        s if s % 3600 == 0 => format!("{}h", s / 3600),
+        s if s % 60 == 0 => format!("{}m", s / 60),
+        s => format!("{}s", s),
+    }
+}
+
+/// A background write that can be retried and, if it keeps failing, dead-lettered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PendingWrite {
+    Token(TokenRecord),
+    Transaction(TransactionRecord),
+    Wallet(WalletRecord),
+    HolderSnapshot { mint: String, holder_count: i64 },
+    Rugged { mint: String, reason: String },
+    Graduated { mint: String },
+    InitialLiquidity { mint: String, liquidity: f64 },
+    TxLogs(TxLogRecord),
+    AlertMarker { alert_type: String, subject: String },
+    Alert { alert_type: String, title: String, message: String, data: String, muted: bool },
+}
+
+/// A write that still failed after every retry
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DbStats {
-    pub total_tokens: i64,
-    pub rugged_tokens: i64,
-    pub whales: i64,
-    pub alerts: i64,
+#[serde(rename_all = "camelCase")]
+pub struct DeadLetter {
+    pub write: PendingWrite,
+    pub error: String,
+    pub failed_at: String,
+}
+
+/// Rows per multi-row INSERT when flushing buffered transactions; 7 parameters each
+/// stays well under SQLite's bound-parameter limit
+const TX_INSERT_CHUNK: usize = 500;
+
+/// Buffered transaction inserts, for metrics
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TxBatchStats {
+    pub buffered: usize,
+    pub flushes: u64,
+    pub last_flush_ms: f64,
+}
+
+/// Rows deleted from each table per size-limit eviction round
+const EVICTION_BATCH: i64 = 500;
+
+/// How often the size limit is checked
+const SIZE_CHECK_INTERVAL_SECS: u64 = 60;
+
+/// Rows removed by a size-limit eviction round or after an archive export
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct EvictedRows {
+    pub transactions: usize,
+    pub alerts: usize,
+    pub tokens: usize,
+}
+
+impl EvictedRows {
+    fn total(&self) -> usize {
+        self.transactions + self.alerts + self.tokens
+    }
+}
+
+/// Rows read per query while writing an archive, so the connection is never held for
+/// the whole export
+const ARCHIVE_PAGE_SIZE: i64 = 500;
+
+/// First line of an archive bundle
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveManifest {
+    pub before: String,
+    pub exported_at: String,
+    pub tokens: usize,
+    pub transactions: usize,
+    pub alerts: usize,
+    /// Oldest and newest timestamp across the archived rows
+    pub oldest: Option<String>,
+    pub newest: Option<String>,
+}
+
+/// One NDJSON line of an archive bundle
+#[derive(Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+enum ArchiveLine<'a> {
+    Manifest(&'a ArchiveManifest),
+    Token(&'a TokenRecord),
+    Transaction(&'a TransactionRecord),
+    Alert(&'a AlertRecord),
+}
+
+/// Writes kept for replay; the oldest is discarded beyond this
+const DEAD_LETTER_CAPACITY: usize = 1000;
+
+/// Token lookups cached in memory, including misses
+const TOKEN_CACHE_CAPACITY: usize = 2048;
+
+/// Most recently rugged tokens kept in memory for copycat matching
+const COPYCAT_CANDIDATES: usize = 500;
+
+/// Prefix match on name or symbol; the pattern never starts with a wildcard, so SQLite can
+/// use the NOCASE indexes instead of scanning the table
+const TOKEN_SEARCH_SQL: &str = r#"
+    SELECT * FROM tokens
+    WHERE name LIKE ?1 ESCAPE '\' OR symbol LIKE ?1 ESCAPE '\'
+    ORDER BY (symbol = ?2 COLLATE NOCASE) DESC, created_at DESC
+    LIMIT ?3
+"#;
+
+/// Hit/miss counters for the token record cache
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct TokenCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+/// Least-recently-used cache of `get_token` results. Entries are filled and invalidated
+/// while the connection lock is held, so a lookup can't cache a row a write just replaced.
+struct TokenCache {
+    capacity: usize,
+    entries: HashMap<String, (Option<TokenRecord>, u64)>,
+    /// Last-use tick -> mint, oldest first
+    order: BTreeMap<u64, String>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl TokenCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    fn get(&mut self, mint: &str) -> Option<Option<TokenRecord>> {
+        self.tick += 1;
+        let tick = self.tick;
+        match self.entries.get_mut(mint) {
+            Some((record, last_used)) => {
+                self.order.remove(last_used);
+                self.order.insert(tick, mint.to_string());
+                *last_used = tick;
+                self.hits += 1;
+                Some(record.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn insert(&mut self, mint: &str, record: Option<TokenRecord>) {
+        self.invalidate(mint);
+        while self.entries.len() >= self.capacity {
+            match self.order.pop_first() {
+                Some((_, oldest)) => {
+                    self.entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+        self.tick += 1;
+        self.order.insert(self.tick, mint.to_string());
+        self.entries.insert(mint.to_string(), (record, self.tick));
+    }
+
+    fn invalidate(&mut self, mint: &str) {
+        if let Some((_, last_used)) = self.entries.remove(mint) {
+            self.order.remove(&last_used);
+        }
+    }
 }
 
 /// SQLite database service
 pub struct DatabaseService {
     conn: Arc<Mutex<Connection>>,
+    token_cache: Arc<Mutex<TokenCache>>,
+    write_retries: u32,
+    write_backoff_ms: u64,
+    dead_letters: Arc<Mutex<VecDeque<DeadLetter>>>,
+    write_failures: Arc<AtomicU64>,
+    /// Flush buffered transactions at this many records; 0 writes each one directly
+    tx_batch_size: usize,
+    tx_buffer: Arc<Mutex<Vec<TransactionRecord>>>,
+    tx_batch_stats: Arc<Mutex<TxBatchStats>>,
+    /// Wakes the flusher early once the buffer reaches `tx_batch_size`
+    tx_flush_wanted: Arc<Notify>,
+    /// Newest rugged tokens first, loaded on the first copycat check and kept current by
+    /// rug updates, so launches never scan the table under the connection lock
+    rugged_tokens: Arc<Mutex<Option<VecDeque<TokenRecord>>>>,
 }
 
 impl DatabaseService {
     /// Create a new database service
-    pub fn new<P: AsRef<Path>>(db_path: P) -> Result<Self> {
+    pub fn new<P: AsRef<Path>>(db_path: P) -> PumpResult<Self> {
         // Ensure parent directory exists
         if let Some(parent) = db_path.as_ref().parent() {
             std::fs::create_dir_all(parent)?;
@@ -83,12 +369,390 @@ impl DatabaseService {
         let conn = Connection::open(db_path)?;
         let service = Self {
             conn: Arc::new(Mutex::new(conn)),
+            token_cache: Arc::new(Mutex::new(TokenCache::new(TOKEN_CACHE_CAPACITY))),
+            write_retries: 3,
+            write_backoff_ms: 100,
+            dead_letters: Arc::new(Mutex::new(VecDeque::new())),
+            write_failures: Arc::new(AtomicU64::new(0)),
+            tx_batch_size: 0,
+            tx_buffer: Arc::new(Mutex::new(Vec::new())),
+            tx_batch_stats: Arc::new(Mutex::new(TxBatchStats::default())),
+            tx_flush_wanted: Arc::new(Notify::new()),
+            rugged_tokens: Arc::new(Mutex::new(None)),
         };
         service.initialize()?;
         Ok(service)
     }
 
-    fn initialize(&self) -> Result<()> {
+    /// Retry failed background writes `retries` times, doubling the delay from `backoff_ms`
+    pub fn with_write_retry(mut self, retries: u32, backoff_ms: u64) -> Self {
+        self.write_retries = retries;
+        self.write_backoff_ms = backoff_ms;
+        self
+    }
+
+    /// Buffer transaction inserts and flush them `batch_size` at a time; 0 disables.
+    /// Flushes run on `start_transaction_flusher`, so transaction reads (wallet history,
+    /// whale summaries, the digest) don't see buffered records until the next flush.
+    pub fn with_transaction_batching(mut self, batch_size: usize) -> Self {
+        self.tx_batch_size = batch_size;
+        self
+    }
+
+    // ============================================
+    // RETRIED WRITES
+    // ============================================
+
+    fn apply(&self, write: &PendingWrite) -> PumpResult<()> {
+        match write {
+            PendingWrite::Token(token) => self.save_token(token),
+            PendingWrite::Transaction(tx) => self.save_transaction(tx),
+            PendingWrite::Wallet(wallet) => self.save_wallet(wallet),
+            PendingWrite::HolderSnapshot { mint, holder_count } => {
+                self.save_holder_snapshot(mint, *holder_count)
+            }
+            PendingWrite::Rugged { mint, reason } => self.mark_as_rugged(mint, reason),
+            PendingWrite::Graduated { mint } => self.mark_graduated(mint),
+            PendingWrite::InitialLiquidity { mint, liquidity } => {
+                self.set_initial_liquidity(mint, *liquidity)
+            }
+            PendingWrite::TxLogs(record) => self.save_tx_logs(record),
+            PendingWrite::AlertMarker { alert_type, subject } => {
+                self.mark_alerted(alert_type, subject)
+            }
+            PendingWrite::Alert { alert_type, title, message, data, muted } => {
+                self.save_alert(alert_type, title, message, data, *muted)
+            }
+        }
+    }
+
+    /// Write without blocking the caller on failure: the first attempt runs inline and
+    /// retries continue in the background. A write that never succeeds is dead-lettered.
+    /// With batching on, transactions are buffered instead and land on the next flush;
+    /// a full buffer wakes the flusher rather than flushing on the caller's task.
+    pub fn write(&self, write: PendingWrite) {
+        match write {
+            PendingWrite::Transaction(tx) if self.tx_batch_size > 0 => {
+                let full = {
+                    let mut buffer = self.tx_buffer.lock();
+                    buffer.push(tx);
+                    buffer.len() >= self.tx_batch_size
+                };
+                if full {
+                    self.tx_flush_wanted.notify_one();
+                }
+            }
+            write => self.write_direct(write),
+        }
+    }
+
+    fn write_direct(&self, write: PendingWrite) {
+        let first_error = match self.apply(&write) {
+            Ok(()) => return,
+            Err(e) => e,
+        };
+        if self.write_retries == 0 {
+            self.dead_letter(write, first_error.to_string());
+            return;
+        }
+
+        warn!(target: "DATABASE", "Write failed, retrying: {}", first_error);
+        let database = self.clone();
+        tokio::spawn(async move {
+            let mut delay_ms = database.write_backoff_ms;
+            let mut last_error = first_error.to_string();
+            for _ in 0..database.write_retries {
+                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+                match database.apply(&write) {
+                    Ok(()) => return,
+                    Err(e) => last_error = e.to_string(),
+                }
+                delay_ms = delay_ms.saturating_mul(2);
+            }
+            database.dead_letter(write, last_error);
+        });
+    }
+
+    /// Insert every buffered transaction in one SQLite transaction. If that fails the
+    /// records fall back to individual retried writes, so none are lost.
+    /// Returns how many were flushed.
+    pub fn flush_transactions(&self) -> usize {
+        let records = std::mem::take(&mut *self.tx_buffer.lock());
+        if records.is_empty() {
+            return 0;
+        }
+
+        let started = std::time::Instant::now();
+        match self.insert_transactions(&records) {
+            Ok(()) => {
+                let mut stats = self.tx_batch_stats.lock();
+                stats.flushes += 1;
+                stats.last_flush_ms = started.elapsed().as_secs_f64() * 1000.0;
+            }
+            Err(e) => {
+                warn!(
+                    target: "DATABASE",
+                    "Batched insert of {} transactions failed, writing individually: {}",
+                    records.len(),
+                    e
+                );
+                for tx in records.iter().cloned() {
+                    self.write_direct(PendingWrite::Transaction(tx));
+                }
+            }
+        }
+        records.len()
+    }
+
+    fn insert_transactions(&self, records: &[TransactionRecord]) -> PumpResult<()> {
+        let mut conn = self.conn.lock();
+        let db_tx = conn.transaction()?;
+        for chunk in records.chunks(TX_INSERT_CHUNK) {
+            let placeholders = vec!["(?, ?, ?, ?, ?, ?, ?)"; chunk.len()].join(", ");
+            let sql = format!(
+                "INSERT OR IGNORE INTO transactions \
+                 (signature, mint, wallet, type, amount_sol, amount_tokens, timestamp) VALUES {}",
+                placeholders
+            );
+            let values: Vec<&dyn rusqlite::ToSql> = chunk
+                .iter()
+                .flat_map(|tx| {
+                    [
+                        &tx.signature as &dyn rusqlite::ToSql,
+                        &tx.mint,
+                        &tx.wallet,
+                        &tx.tx_type,
+                        &tx.amount_sol,
+                        &tx.amount_tokens,
+                        &tx.timestamp,
+                    ]
+                })
+                .collect();
+            db_tx.execute(&sql, values.as_slice())?;
+        }
+        db_tx.commit()?;
+        Ok(())
+    }
+
+    /// Flush buffered transactions every `interval_ms`, and early whenever the buffer fills
+    pub fn start_transaction_flusher(&self, interval_ms: u64) {
+        if self.tx_batch_size == 0 {
+            return;
+        }
+        let database = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(interval_ms.max(1)));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = database.tx_flush_wanted.notified() => {}
+                }
+                database.flush_transactions();
+            }
+        });
+    }
+
+    pub fn tx_batch_stats(&self) -> TxBatchStats {
+        TxBatchStats {
+            buffered: self.tx_buffer.lock().len(),
+            ..*self.tx_batch_stats.lock()
+        }
+    }
+
+    fn dead_letter(&self, write: PendingWrite, error: String) {
+        self.write_failures.fetch_add(1, Ordering::SeqCst);
+        error!(target: "DATABASE", "Write failed after retries, dead-lettered: {}", error);
+
+        let mut dead_letters = self.dead_letters.lock();
+        if dead_letters.len() >= DEAD_LETTER_CAPACITY {
+            dead_letters.pop_front();
+        }
+        dead_letters.push_back(DeadLetter {
+            write,
+            error,
+            failed_at: Utc::now().to_rfc3339(),
+        });
+    }
+
+    /// Replay dead-lettered writes once; those that fail again stay queued.
+    /// Returns how many were written.
+    pub fn drain_dead_letters(&self) -> usize {
+        let pending: Vec<DeadLetter> = self.dead_letters.lock().drain(..).collect();
+        let mut written = 0;
+        let mut still_failing = Vec::new();
+        for mut letter in pending {
+            match self.apply(&letter.write) {
+                Ok(()) => written += 1,
+                Err(e) => {
+                    letter.error = e.to_string();
+                    still_failing.push(letter);
+                }
+            }
+        }
+
+        if !still_failing.is_empty() {
+            // Put them back ahead of anything dead-lettered while we were replaying
+            let mut dead_letters = self.dead_letters.lock();
+            for letter in still_failing.into_iter().rev() {
+                dead_letters.push_front(letter);
+            }
+            while dead_letters.len() > DEAD_LETTER_CAPACITY {
+                dead_letters.pop_front();
+            }
+        }
+        if written > 0 {
+            info!(target: "DATABASE", "Replayed {} dead-lettered writes", written);
+        }
+        written
+    }
+
+    /// Replay dead-lettered writes every `interval_secs` (0 = never)
+    pub fn start_dead_letter_drain(&self, interval_secs: u64) {
+        if interval_secs == 0 {
+            return;
+        }
+        let database = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                if database.dead_letter_count() > 0 {
+                    database.drain_dead_letters();
+                }
+            }
+        });
+    }
+
+    /// Database size in bytes: every page, including free pages not yet reclaimed by VACUUM
+    pub fn size_bytes(&self) -> PumpResult<u64> {
+        let conn = self.conn.lock();
+        let page_count: u64 = conn.query_row("PRAGMA page_count", [], |row| row.get(0))?;
+        let page_size: u64 = conn.query_row("PRAGMA page_size", [], |row| row.get(0))?;
+        Ok(page_count * page_size)
+    }
+
+    /// Bytes in pages holding data, which shrinks as soon as rows are deleted
+    fn used_bytes(&self) -> PumpResult<u64> {
+        let conn = self.conn.lock();
+        let page_count: u64 = conn.query_row("PRAGMA page_count", [], |row| row.get(0))?;
+        let free_pages: u64 = conn.query_row("PRAGMA freelist_count", [], |row| row.get(0))?;
+        let page_size: u64 = conn.query_row("PRAGMA page_size", [], |row| row.get(0))?;
+        Ok(page_count.saturating_sub(free_pages) * page_size)
+    }
+
+    /// Delete the oldest `batch` transactions, alerts and non-rugged tokens.
+    /// Rugged tokens are kept: they are what copycat matching and post-mortems need.
+    fn evict_oldest(&self, batch: i64) -> PumpResult<EvictedRows> {
+        let conn = self.conn.lock();
+        let transactions = conn.execute(
+            r#"
+            DELETE FROM transactions WHERE signature IN
+                (SELECT signature FROM transactions ORDER BY timestamp LIMIT ?)
+            "#,
+            params![batch],
+        )?;
+        let alerts = conn.execute(
+            "DELETE FROM alerts WHERE id IN (SELECT id FROM alerts ORDER BY id LIMIT ?)",
+            params![batch],
+        )?;
+
+        // A token goes once its transactions have; they reference it by foreign key
+        let mut stmt = conn.prepare(
+            r#"
+            DELETE FROM tokens WHERE mint IN
+                (SELECT mint FROM tokens WHERE is_rugged = 0
+                 AND NOT EXISTS (SELECT 1 FROM transactions WHERE transactions.mint = tokens.mint)
+                 ORDER BY created_at LIMIT ?)
+            RETURNING mint
+            "#,
+        )?;
+        let mints = stmt
+            .query_map(params![batch], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut cache = self.token_cache.lock();
+        for mint in &mints {
+            cache.invalidate(mint);
+        }
+
+        Ok(EvictedRows {
+            transactions,
+            alerts,
+            tokens: mints.len(),
+        })
+    }
+
+    /// Evict the oldest data in rounds until the used size is under `max_bytes`, then VACUUM
+    /// to give the space back. Returns the rows removed, or `None` when already under the cap.
+    pub fn enforce_size_limit(&self, max_bytes: u64) -> PumpResult<Option<EvictedRows>> {
+        let mut size = self.used_bytes()?;
+        if size <= max_bytes {
+            return Ok(None);
+        }
+
+        let mut evicted = EvictedRows::default();
+        while size > max_bytes {
+            let round = self.evict_oldest(EVICTION_BATCH)?;
+            if round.total() == 0 {
+                warn!(
+                    target: "DATABASE",
+                    "Still {:.1} MB used with nothing left to evict (rugged tokens are kept)",
+                    size as f64 / 1_048_576.0
+                );
+                break;
+            }
+            size = self.used_bytes()?;
+            info!(
+                target: "DATABASE",
+                "Size limit eviction: {} transactions, {} alerts, {} tokens removed ({:.1} MB used)",
+                round.transactions,
+                round.alerts,
+                round.tokens,
+                size as f64 / 1_048_576.0
+            );
+            evicted.transactions += round.transactions;
+            evicted.alerts += round.alerts;
+            evicted.tokens += round.tokens;
+        }
+
+        self.conn.lock().execute_batch("VACUUM")?;
+        Ok(Some(evicted))
+    }
+
+    /// Keep the database under `max_mb` megabytes with a periodic check (0 = no limit)
+    pub fn start_size_limit(&self, max_mb: u64) {
+        if max_mb == 0 {
+            return;
+        }
+        let max_bytes = max_mb * 1_048_576;
+        let database = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(SIZE_CHECK_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                if let Err(e) = database.enforce_size_limit(max_bytes) {
+                    error!(target: "DATABASE", "Size limit check failed: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Writes that failed after every retry, including ones since replayed
+    pub fn write_failures(&self) -> u64 {
+        self.write_failures.load(Ordering::SeqCst)
+    }
+
+    /// Writes currently waiting in the dead-letter queue
+    pub fn dead_letter_count(&self) -> usize {
+        self.dead_letters.lock().len()
+    }
+
+    /// Snapshot of the dead-letter queue, oldest first
+    pub fn dead_letters(&self) -> Vec<DeadLetter> {
+        self.dead_letters.lock().iter().cloned().collect()
+    }
+
+    fn initialize(&self) -> PumpResult<()> {
         let conn = self.conn.lock();
 
         // Tokens table
@@ -105,7 +769,8 @@ impl DatabaseService {
                 holder_count INTEGER DEFAULT 0,
                 is_rugged INTEGER DEFAULT 0,
                 rug_reason TEXT,
-                last_updated TEXT
+                last_updated TEXT,
+                is_muted INTEGER DEFAULT 0
             )
             "#,
             [],
@@ -157,6 +822,104 @@ impl DatabaseService {
             [],
         )?;
 
+        // Holder count history
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS holder_snapshots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                mint TEXT,
+                holder_count INTEGER,
+                taken_at TEXT
+            )
+            "#,
+            [],
+        )?;
+
+        // Leader leases for instances sharing this database
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS instance_locks (
+                name TEXT PRIMARY KEY,
+                holder TEXT NOT NULL,
+                heartbeat INTEGER NOT NULL
+            )
+            "#,
+            [],
+        )?;
+
+        // Creators treated leniently by the rug detector
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS trusted_creators (
+                address TEXT PRIMARY KEY,
+                added_at TEXT
+            )
+            "#,
+            [],
+        )?;
+
+        // Creators blacklisted through the API or for rugging, restored on startup
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS blacklisted_creators (
+                address TEXT PRIMARY KEY,
+                reason TEXT,
+                added_at TEXT
+            )
+            "#,
+            [],
+        )?;
+
+        // Raw program logs of alerted transactions, when STORE_RAW_LOGS is on
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS tx_logs (
+                signature TEXT PRIMARY KEY,
+                mint TEXT NOT NULL,
+                reason TEXT,
+                logs TEXT NOT NULL,
+                created_at TEXT
+            )
+            "#,
+            [],
+        )?;
+
+        // When each (alert type, subject) was last alerted, for dedup across restarts
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS alert_markers (
+                alert_type TEXT NOT NULL,
+                subject TEXT NOT NULL,
+                sent_at TEXT NOT NULL,
+                PRIMARY KEY (alert_type, subject)
+            )
+            "#,
+            [],
+        )?;
+
+        // Cumulative Prometheus counters, restored on startup
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS metric_counters (
+                name TEXT PRIMARY KEY,
+                value REAL NOT NULL,
+                updated_at TEXT
+            )
+            "#,
+            [],
+        )?;
+
+        // Columns added after the initial schema
+        Self::add_column_if_missing(&conn, "tokens", "is_muted", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(&conn, "tokens", "is_graduated", "INTEGER DEFAULT 0")?;
+        Self::add_column_if_missing(&conn, "tokens", "rugged_at", "TEXT")?;
+        Self::add_column_if_missing(&conn, "alerts", "muted", "INTEGER NOT NULL DEFAULT 0")?;
+        // Rugs recorded before `rugged_at` existed fall back to their last update
+        conn.execute(
+            "UPDATE tokens SET rugged_at = last_updated WHERE is_rugged = 1 AND rugged_at IS NULL",
+            [],
+        )?;
+
         // Create indexes
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_tokens_created ON tokens(created_at)",
@@ -170,22 +933,78 @@ impl DatabaseService {
             "CREATE INDEX IF NOT EXISTS idx_tx_wallet ON transactions(wallet)",
             [],
         )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_tx_timestamp ON transactions(timestamp)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_holder_snapshots_mint ON holder_snapshots(mint, taken_at)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_tokens_symbol ON tokens(symbol COLLATE NOCASE)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_tokens_name ON tokens(name COLLATE NOCASE)",
+            [],
+        )?;
 
         info!(target: "DATABASE", "Initialized successfully");
         Ok(())
     }
 
+    /// Add a column to an existing table (no-op when already present)
+    fn add_column_if_missing(conn: &Connection, table: &str, column: &str, decl: &str) -> PumpResult<()> {
+        let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+        let exists = stmt
+            .query_map([], |row| row.get::<_, String>(1))?
+            .filter_map(|r| r.ok())
+            .any(|name| name == column);
+
+        if !exists {
+            conn.execute(
+                &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, decl),
+                [],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Take the write lock and make a schema change inside a rolled-back savepoint,
+    /// so a read-only or locked database is reported without changing anything
+    pub fn check_writable(&self) -> PumpResult<()> {
+        let conn = self.conn.lock();
+        conn.execute_batch("SAVEPOINT preflight")?;
+        let probe = conn.execute_batch("CREATE TABLE preflight_probe (id INTEGER)");
+        // Always unwind, or later writes would land inside the open savepoint
+        conn.execute_batch("ROLLBACK TO preflight; RELEASE preflight")?;
+        probe?;
+        Ok(())
+    }
+
     // ============================================
     // TOKEN METHODS
     // ============================================
 
-    pub fn save_token(&self, token: &TokenRecord) -> Result<()> {
+    /// Insert a detected token or refresh the detection fields of an existing row. The rug,
+    /// mute and graduation flags and the holder count belong to their own updates, so a
+    /// retried or replayed write landing after them leaves them alone.
+    pub fn save_token(&self, token: &TokenRecord) -> PumpResult<()> {
         let conn = self.conn.lock();
         conn.execute(
             r#"
-            INSERT OR REPLACE INTO tokens 
+            INSERT INTO tokens
             (mint, name, symbol, creator, created_at, initial_liquidity, current_liquidity, holder_count, last_updated)
             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            ON CONFLICT(mint) DO UPDATE SET
+                name = excluded.name,
+                symbol = excluded.symbol,
+                creator = excluded.creator,
+                created_at = excluded.created_at,
+                initial_liquidity = MAX(tokens.initial_liquidity, excluded.initial_liquidity),
+                current_liquidity = excluded.current_liquidity,
+                last_updated = excluded.last_updated
             "#,
             params![
                 token.mint,
@@ -199,53 +1018,66 @@ impl DatabaseService {
                 Utc::now().to_rfc3339(),
             ],
         )?;
+        self.token_cache.lock().invalidate(&token.mint);
         Ok(())
     }
 
-    pub fn get_token(&self, mint: &str) -> Result<Option<TokenRecord>> {
+    /// Look up a token, served from the in-memory cache when possible
+    pub fn get_token(&self, mint: &str) -> PumpResult<Option<TokenRecord>> {
+        if let Some(cached) = self.token_cache.lock().get(mint) {
+            return Ok(cached);
+        }
+
         let conn = self.conn.lock();
         let mut stmt = conn.prepare("SELECT * FROM tokens WHERE mint = ?")?;
         let mut rows = stmt.query(params![mint])?;
 
-        if let Some(row) = rows.next()? {
-            Ok(Some(TokenRecord {
-                mint: row.get(0)?,
-                name: row.get(1)?,
-                symbol: row.get(2)?,
-                creator: row.get(3)?,
-                created_at: row.get(4)?,
-                initial_liquidity: row.get(5)?,
-                current_liquidity: row.get(6)?,
-                holder_count: row.get(7)?,
-                is_rugged: row.get::<_, i32>(8)? != 0,
-                rug_reason: row.get(9)?,
-                last_updated: row.get(10)?,
-            }))
-        } else {
-            Ok(None)
+        let token = match rows.next()? {
+            Some(row) => Some(Self::token_from_row(row)?),
+            None => None,
+        };
+        self.token_cache.lock().insert(mint, token.clone());
+        Ok(token)
+    }
+
+    /// Token cache hit/miss counts since startup
+    pub fn token_cache_stats(&self) -> TokenCacheStats {
+        let cache = self.token_cache.lock();
+        TokenCacheStats {
+            hits: cache.hits,
+            misses: cache.misses,
+            entries: cache.entries.len(),
         }
     }
 
-    pub fn get_recent_tokens(&self, limit: i64) -> Result<Vec<TokenRecord>> {
+    pub fn get_recent_tokens(&self, limit: i64, query: &TokenQuery) -> PumpResult<Vec<TokenRecord>> {
+        let mut conditions = Vec::new();
+        let mut values: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        if let Some(rugged) = query.rugged {
+            conditions.push("is_rugged = ?");
+            values.push(Box::new(rugged as i32));
+        }
+        if let Some(graduated) = query.graduated {
+            conditions.push("COALESCE(is_graduated, 0) = ?");
+            values.push(Box::new(graduated as i32));
+        }
+        if let Some(min_liquidity) = query.min_liquidity {
+            conditions.push("current_liquidity >= ?");
+            values.push(Box::new(min_liquidity));
+        }
+        values.push(Box::new(limit));
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
         let conn = self.conn.lock();
-        let mut stmt = conn.prepare(
-            "SELECT * FROM tokens ORDER BY created_at DESC LIMIT ?",
-        )?;
-        let rows = stmt.query_map(params![limit], |row| {
-            Ok(TokenRecord {
-                mint: row.get(0)?,
-                name: row.get(1)?,
-                symbol: row.get(2)?,
-                creator: row.get(3)?,
-                created_at: row.get(4)?,
-                initial_liquidity: row.get(5)?,
-                current_liquidity: row.get(6)?,
-                holder_count: row.get(7)?,
-                is_rugged: row.get::<_, i32>(8)? != 0,
-                rug_reason: row.get(9)?,
-                last_updated: row.get(10)?,
-            })
-        })?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT * FROM tokens {} ORDER BY created_at DESC LIMIT ?",
+            where_clause
+        ))?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(values), Self::token_from_row)?;
 
         let mut tokens = Vec::new();
         for row in rows {
@@ -254,12 +1086,171 @@ impl DatabaseService {
         Ok(tokens)
     }
 
-    pub fn mark_as_rugged(&self, mint: &str, reason: &str) -> Result<()> {
+    /// Case-insensitive substring match on name or symbol; exact symbol matches sort first
+    pub fn search_tokens(&self, query: &str, limit: i64) -> PumpResult<Vec<TokenRecord>> {
+        let escaped = query
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        let pattern = format!("{}%", escaped);
+
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(TOKEN_SEARCH_SQL)?;
+        let rows = stmt.query_map(params![pattern, query, limit], Self::token_from_row)?;
+
+        let mut tokens = Vec::new();
+        for row in rows {
+            tokens.push(row?);
+        }
+        Ok(tokens)
+    }
+
+    fn token_from_row(row: &rusqlite::Row) -> rusqlite::Result<TokenRecord> {
+        Ok(TokenRecord {
+            mint: row.get(0)?,
+            name: row.get(1)?,
+            symbol: row.get(2)?,
+            creator: row.get(3)?,
+            created_at: row.get(4)?,
+            initial_liquidity: row.get(5)?,
+            current_liquidity: row.get(6)?,
+            holder_count: row.get(7)?,
+            is_rugged: row.get::<_, i32>(8)? != 0,
+            rug_reason: row.get(9)?,
+            last_updated: row.get(10)?,
+            is_muted: row.get::<_, Option<i32>>(11)?.unwrap_or(0) != 0,
+            is_graduated: row.get::<_, Option<i32>>(12)?.unwrap_or(0) != 0,
+        })
+    }
+
+    /// Find a recently rugged token whose name or symbol is within `max_distance`
+    /// edits of the given one (case-insensitive), closest match first
+    pub fn find_similar_rugged(
+        &self,
+        name: &str,
+        symbol: &str,
+        max_distance: usize,
+    ) -> PumpResult<Option<TokenRecord>> {
+        let name = name.trim().to_lowercase();
+        let symbol = symbol.trim().to_lowercase();
+
+        let mut rugged_tokens = self.rugged_tokens.lock();
+        if rugged_tokens.is_none() {
+            *rugged_tokens = Some(self.load_rugged_tokens()?);
+        }
+
+        let mut best: Option<(usize, &TokenRecord)> = None;
+        for token in rugged_tokens.iter().flatten() {
+            let distance = [
+                (&name, token.name.trim().to_lowercase()),
+                (&symbol, token.symbol.trim().to_lowercase()),
+            ]
+            .into_iter()
+            .filter(|(candidate, rugged)| !candidate.is_empty() && !rugged.is_empty())
+            .map(|(candidate, rugged)| levenshtein(candidate, &rugged))
+            .min();
+
+            if let Some(distance) = distance {
+                if distance <= max_distance && best.as_ref().is_none_or(|(d, _)| distance < *d) {
+                    best = Some((distance, token));
+                }
+            }
+        }
+
+        Ok(best.map(|(_, token)| token.clone()))
+    }
+
+    fn load_rugged_tokens(&self) -> PumpResult<VecDeque<TokenRecord>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM tokens WHERE is_rugged = 1 ORDER BY last_updated DESC LIMIT ?",
+        )?;
+        let rows = stmt.query_map(params![COPYCAT_CANDIDATES as i64], Self::token_from_row)?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Keep the copycat candidates in step with a token's rug flag, once they are loaded
+    fn update_rugged_tokens(&self, mint: &str) -> PumpResult<()> {
+        let token = self.get_token(mint)?;
+        if let Some(rugged_tokens) = self.rugged_tokens.lock().as_mut() {
+            rugged_tokens.retain(|rugged| rugged.mint != mint);
+            if let Some(token) = token.filter(|token| token.is_rugged) {
+                rugged_tokens.push_front(token);
+                rugged_tokens.truncate(COPYCAT_CANDIDATES);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn mark_as_rugged(&self, mint: &str, reason: &str) -> PumpResult<()> {
+        let conn = self.conn.lock();
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "UPDATE tokens SET is_rugged = 1, rug_reason = ?, rugged_at = ?, last_updated = ? \
+             WHERE mint = ?",
+            params![reason, now, now, mint],
+        )?;
+        drop(conn);
+        self.token_cache.lock().invalidate(mint);
+        self.update_rugged_tokens(mint)
+    }
+
+    pub fn clear_rugged(&self, mint: &str) -> PumpResult<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "UPDATE tokens SET is_rugged = 0, rug_reason = NULL, rugged_at = NULL, last_updated = ? \
+             WHERE mint = ?",
+            params![Utc::now().to_rfc3339(), mint],
+        )?;
+        drop(conn);
+        self.token_cache.lock().invalidate(mint);
+        self.update_rugged_tokens(mint)
+    }
+
+    pub fn mark_graduated(&self, mint: &str) -> PumpResult<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "UPDATE tokens SET is_graduated = 1, last_updated = ? WHERE mint = ?",
+            params![Utc::now().to_rfc3339(), mint],
+        )?;
+        self.token_cache.lock().invalidate(mint);
+        Ok(())
+    }
+
+    pub fn set_initial_liquidity(&self, mint: &str, liquidity: f64) -> PumpResult<()> {
         let conn = self.conn.lock();
         conn.execute(
-            "UPDATE tokens SET is_rugged = 1, rug_reason = ? WHERE mint = ?",
-            params![reason, mint],
+            "UPDATE tokens SET initial_liquidity = ?, last_updated = ? WHERE mint = ?",
+            params![liquidity, Utc::now().to_rfc3339(), mint],
+        )?;
+        self.token_cache.lock().invalidate(mint);
+        Ok(())
+    }
+
+    /// Persist `token.is_muted`, inserting the token when it has no row yet (a watch added
+    /// by hand, or a launch whose save is still queued)
+    pub fn set_token_muted(&self, token: &TokenRecord) -> PumpResult<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            r#"
+            INSERT INTO tokens
+            (mint, name, symbol, creator, created_at, initial_liquidity, current_liquidity, last_updated, is_muted)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+            ON CONFLICT(mint) DO UPDATE SET is_muted = excluded.is_muted
+            "#,
+            params![
+                token.mint,
+                token.name,
+                token.symbol,
+                token.creator,
+                token.created_at,
+                token.initial_liquidity,
+                token.current_liquidity,
+                Utc::now().to_rfc3339(),
+                token.is_muted as i32,
+            ],
         )?;
+        self.token_cache.lock().invalidate(&token.mint);
         Ok(())
     }
 
@@ -267,7 +1258,7 @@ impl DatabaseService {
     // TRANSACTION METHODS
     // ============================================
 
-    pub fn save_transaction(&self, tx: &TransactionRecord) -> Result<()> {
+    pub fn save_transaction(&self, tx: &TransactionRecord) -> PumpResult<()> {
         let conn = self.conn.lock();
         conn.execute(
             r#"
@@ -288,22 +1279,13 @@ impl DatabaseService {
         Ok(())
     }
 
-    pub fn get_transactions_for_token(&self, mint: &str, limit: i64) -> Result<Vec<TransactionRecord>> {
+    #[cfg(test)]
+    pub fn get_transactions_for_token(&self, mint: &str, limit: i64) -> PumpResult<Vec<TransactionRecord>> {
         let conn = self.conn.lock();
         let mut stmt = conn.prepare(
             "SELECT * FROM transactions WHERE mint = ? ORDER BY timestamp DESC LIMIT ?",
         )?;
-        let rows = stmt.query_map(params![mint, limit], |row| {
-            Ok(TransactionRecord {
-                signature: row.get(0)?,
-                mint: row.get(1)?,
-                wallet: row.get(2)?,
-                tx_type: row.get(3)?,
-                amount_sol: row.get(4)?,
-                amount_tokens: row.get(5)?,
-                timestamp: row.get(6)?,
-            })
-        })?;
+        let rows = stmt.query_map(params![mint, limit], Self::transaction_from_row)?;
 
         let mut txs = Vec::new();
         for row in rows {
@@ -312,11 +1294,159 @@ impl DatabaseService {
         Ok(txs)
     }
 
+    /// A wallet's recorded transactions, newest first, paged by `limit`/`offset`
+    pub fn get_transactions_for_wallet(
+        &self,
+        address: &str,
+        limit: i64,
+        offset: i64,
+    ) -> PumpResult<Vec<TransactionRecord>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM transactions WHERE wallet = ? ORDER BY timestamp DESC LIMIT ? OFFSET ?",
+        )?;
+        let rows = stmt.query_map(params![address, limit, offset], Self::transaction_from_row)?;
+
+        let mut txs = Vec::new();
+        for row in rows {
+            txs.push(row?);
+        }
+        Ok(txs)
+    }
+
+    /// Trade count, distinct mints and SOL bought/sold across a wallet's stored transactions
+    pub fn get_wallet_summary(&self, address: &str) -> PumpResult<WalletTradeSummary> {
+        let conn = self.conn.lock();
+        let summary = conn.query_row(
+            r#"
+            SELECT COUNT(*), COUNT(DISTINCT mint),
+                   COALESCE(SUM(CASE WHEN type = 'buy' THEN amount_sol END), 0),
+                   COALESCE(SUM(CASE WHEN type = 'sell' THEN amount_sol END), 0)
+            FROM transactions WHERE wallet = ?
+            "#,
+            params![address],
+            |row| {
+                Ok(WalletTradeSummary {
+                    trades: row.get(0)?,
+                    tokens_traded: row.get(1)?,
+                    buy_sol: row.get(2)?,
+                    sell_sol: row.get(3)?,
+                })
+            },
+        )?;
+        Ok(summary)
+    }
+
+    /// Keep a transaction's program logs; a later alert on the same signature replaces them
+    pub fn save_tx_logs(&self, record: &TxLogRecord) -> PumpResult<()> {
+        let logs = serde_json::to_string(&record.logs)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let conn = self.conn.lock();
+        conn.execute(
+            r#"
+            INSERT OR REPLACE INTO tx_logs (signature, mint, reason, logs, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+            params![record.signature, record.mint, record.reason, logs, record.created_at],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_tx_logs(&self, mint: &str, signature: &str) -> PumpResult<Option<TxLogRecord>> {
+        let conn = self.conn.lock();
+        let record = conn
+            .query_row(
+                "SELECT reason, logs, created_at FROM tx_logs WHERE signature = ? AND mint = ?",
+                params![signature, mint],
+                |row| {
+                    let logs: String = row.get(1)?;
+                    Ok(TxLogRecord {
+                        signature: signature.to_string(),
+                        mint: mint.to_string(),
+                        reason: row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+                        logs: serde_json::from_str(&logs).map_err(|e| {
+                            rusqlite::Error::FromSqlConversionFailure(
+                                1,
+                                rusqlite::types::Type::Text,
+                                Box::new(e),
+                            )
+                        })?,
+                        created_at: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                    })
+                },
+            )
+            .optional()?;
+        Ok(record)
+    }
+
+    fn transaction_from_row(row: &rusqlite::Row) -> rusqlite::Result<TransactionRecord> {
+        Ok(TransactionRecord {
+            signature: row.get(0)?,
+            mint: row.get(1)?,
+            wallet: row.get(2)?,
+            tx_type: row.get(3)?,
+            amount_sol: row.get(4)?,
+            amount_tokens: row.get(5)?,
+            timestamp: row.get(6)?,
+        })
+    }
+
+    // ============================================
+    // HOLDER SNAPSHOT METHODS
+    // ============================================
+
+    /// Record a holder count snapshot and keep the token's current count in sync
+    pub fn save_holder_snapshot(&self, mint: &str, holder_count: i64) -> PumpResult<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT INTO holder_snapshots (mint, holder_count, taken_at) VALUES (?, ?, ?)",
+            params![mint, holder_count, Utc::now().to_rfc3339()],
+        )?;
+        conn.execute(
+            "UPDATE tokens SET holder_count = ? WHERE mint = ?",
+            params![holder_count, mint],
+        )?;
+        self.token_cache.lock().invalidate(mint);
+        Ok(())
+    }
+
+    /// The most recent `limit` snapshots for a token, oldest first
+    pub fn get_holder_snapshots(&self, mint: &str, limit: i64) -> PumpResult<Vec<HolderSnapshot>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT holder_count, taken_at FROM holder_snapshots WHERE mint = ? ORDER BY taken_at DESC LIMIT ?",
+        )?;
+        let rows = stmt.query_map(params![mint, limit], |row| {
+            Ok(HolderSnapshot {
+                holder_count: row.get(0)?,
+                taken_at: row.get(1)?,
+            })
+        })?;
+
+        let mut snapshots = Vec::new();
+        for row in rows {
+            snapshots.push(row?);
+        }
+        snapshots.reverse();
+        Ok(snapshots)
+    }
+
+    /// Delete snapshots older than `max_age_hours`, returning how many were removed
+    pub fn prune_holder_snapshots(&self, max_age_hours: u64) -> PumpResult<usize> {
+        let cutoff = Utc::now() - chrono::Duration::hours(max_age_hours as i64);
+        let conn = self.conn.lock();
+        let removed = conn.execute(
+            "DELETE FROM holder_snapshots WHERE taken_at < ?",
+            params![cutoff.to_rfc3339()],
+        )?;
+        Ok(removed)
+    }
+
     // ============================================
     // WALLET METHODS
     // ============================================
 
-    pub fn save_wallet(&self, wallet: &WalletRecord) -> Result<()> {
+    pub fn save_wallet(&self, wallet: &WalletRecord) -> PumpResult<()> {
         let conn = self.conn.lock();
         conn.execute(
             r#"
@@ -335,7 +1465,7 @@ impl DatabaseService {
         Ok(())
     }
 
-    pub fn get_whales(&self) -> Result<Vec<WalletRecord>> {
+    pub fn get_whales(&self) -> PumpResult<Vec<WalletRecord>> {
         let conn = self.conn.lock();
         let mut stmt = conn.prepare("SELECT * FROM watched_wallets WHERE is_whale = 1")?;
         let rows = stmt.query_map([], |row| {
@@ -355,37 +1485,87 @@ impl DatabaseService {
         Ok(wallets)
     }
 
+    /// Replace the saved trusted creator list
+    pub fn save_trusted_creators(&self, creators: &[String]) -> PumpResult<()> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        let now = Utc::now().to_rfc3339();
+        tx.execute("DELETE FROM trusted_creators", [])?;
+        for address in creators {
+            tx.execute(
+                "INSERT OR IGNORE INTO trusted_creators (address, added_at) VALUES (?, ?)",
+                params![address, now],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn get_trusted_creators(&self) -> PumpResult<Vec<String>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare("SELECT address FROM trusted_creators ORDER BY address")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        let creators = rows.collect::<Result<Vec<String>, _>>()?;
+        Ok(creators)
+    }
+
+    pub fn save_blacklisted_creator(&self, address: &str, reason: &str) -> PumpResult<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT OR REPLACE INTO blacklisted_creators (address, reason, added_at) VALUES (?, ?, ?)",
+            params![address, reason, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Whether the creator was on the saved blacklist
+    pub fn remove_blacklisted_creator(&self, address: &str) -> PumpResult<bool> {
+        let conn = self.conn.lock();
+        let removed = conn.execute(
+            "DELETE FROM blacklisted_creators WHERE address = ?",
+            params![address],
+        )?;
+        Ok(removed > 0)
+    }
+
+    pub fn get_blacklisted_creators(&self) -> PumpResult<Vec<String>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare("SELECT address FROM blacklisted_creators ORDER BY address")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        let creators = rows.collect::<Result<Vec<String>, _>>()?;
+        Ok(creators)
+    }
+
     // ============================================
     // ALERT METHODS
     // ============================================
 
-    pub fn save_alert(&self, alert_type: &str, title: &str, message: &str, data: &str) -> Result<()> {
+    pub fn save_alert(
+        &self,
+        alert_type: &str,
+        title: &str,
+        message: &str,
+        data: &str,
+        muted: bool,
+    ) -> PumpResult<()> {
         let conn = self.conn.lock();
         conn.execute(
             r#"
-            INSERT INTO alerts (type, title, message, data, created_at)
-            VALUES (?1, ?2, ?3, ?4, ?5)
+            INSERT INTO alerts (type, title, message, data, created_at, muted)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
             "#,
-            params![alert_type, title, message, data, Utc::now().to_rfc3339()],
+            params![alert_type, title, message, data, Utc::now().to_rfc3339(), muted],
         )?;
         Ok(())
     }
 
-    pub fn get_recent_alerts(&self, limit: i64) -> Result<Vec<AlertRecord>> {
+    #[cfg(test)]
+    pub fn get_recent_alerts(&self, limit: i64) -> PumpResult<Vec<AlertRecord>> {
         let conn = self.conn.lock();
         let mut stmt = conn.prepare(
             "SELECT * FROM alerts ORDER BY created_at DESC LIMIT ?",
         )?;
-        let rows = stmt.query_map(params![limit], |row| {
-            Ok(AlertRecord {
-                id: row.get(0)?,
-                alert_type: row.get(1)?,
-                title: row.get(2)?,
-                message: row.get(3)?,
-                data: row.get(4)?,
-                created_at: row.get(5)?,
-            })
-        })?;
+        let rows = stmt.query_map(params![limit], Self::alert_from_row)?;
 
         let mut alerts = Vec::new();
         for row in rows {
@@ -394,43 +1574,436 @@ impl DatabaseService {
         Ok(alerts)
     }
 
+    fn alert_from_row(row: &rusqlite::Row) -> rusqlite::Result<AlertRecord> {
+        Ok(AlertRecord {
+            id: row.get(0)?,
+            alert_type: row.get(1)?,
+            title: row.get(2)?,
+            message: row.get(3)?,
+            data: row.get(4)?,
+            created_at: row.get(5)?,
+            muted: row.get(6)?,
+        })
+    }
+
+    // ============================================
+    // ALERT MARKER METHODS
+    // ============================================
+
+    pub fn mark_alerted(&self, alert_type: &str, subject: &str) -> PumpResult<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "INSERT OR REPLACE INTO alert_markers (alert_type, subject, sent_at) VALUES (?1, ?2, ?3)",
+            params![alert_type, subject, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// When an alert of this type was last sent for `subject`
+    pub fn last_alerted(&self, alert_type: &str, subject: &str) -> PumpResult<Option<String>> {
+        let conn = self.conn.lock();
+        let sent_at = conn
+            .query_row(
+                "SELECT sent_at FROM alert_markers WHERE alert_type = ? AND subject = ?",
+                params![alert_type, subject],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(sent_at)
+    }
+
+    pub fn prune_alert_markers(&self, max_age_secs: u64) -> PumpResult<usize> {
+        let cutoff = Utc::now() - chrono::Duration::seconds(max_age_secs as i64);
+        let conn = self.conn.lock();
+        let removed = conn.execute(
+            "DELETE FROM alert_markers WHERE sent_at < ?",
+            params![cutoff.to_rfc3339()],
+        )?;
+        Ok(removed)
+    }
+
+    // ============================================
+    // ARCHIVE METHODS
+    // ============================================
+
+    /// Counts and date range of the tokens, transactions and alerts older than `before`
+    /// (RFC 3339, compared as stored)
+    pub fn archive_manifest(&self, before: &str) -> PumpResult<ArchiveManifest> {
+        let conn = self.conn.lock();
+        let manifest = conn.query_row(
+            r#"
+            WITH archived(ts) AS (
+                SELECT created_at FROM tokens WHERE created_at < ?1
+                UNION ALL SELECT timestamp FROM transactions WHERE timestamp < ?1
+                UNION ALL SELECT created_at FROM alerts WHERE created_at < ?1
+            )
+            SELECT
+                (SELECT COUNT(*) FROM tokens WHERE created_at < ?1),
+                (SELECT COUNT(*) FROM transactions WHERE timestamp < ?1),
+                (SELECT COUNT(*) FROM alerts WHERE created_at < ?1),
+                MIN(ts),
+                MAX(ts)
+            FROM archived
+            "#,
+            params![before],
+            |row| {
+                Ok(ArchiveManifest {
+                    before: before.to_string(),
+                    exported_at: Utc::now().to_rfc3339(),
+                    tokens: row.get::<_, i64>(0)? as usize,
+                    transactions: row.get::<_, i64>(1)? as usize,
+                    alerts: row.get::<_, i64>(2)? as usize,
+                    oldest: row.get(3)?,
+                    newest: row.get(4)?,
+                })
+            },
+        )?;
+        Ok(manifest)
+    }
+
+    /// Write everything older than `before` to `out` as NDJSON: the manifest, then tokens,
+    /// transactions and alerts. Rows are read a page at a time, so live writes continue
+    /// while a large archive streams out.
+    pub fn write_archive(
+        &self,
+        before: &str,
+        out: &mut impl std::io::Write,
+    ) -> PumpResult<ArchiveManifest> {
+        self.flush_transactions();
+        let manifest = self.archive_manifest(before)?;
+        Self::write_archive_line(out, &ArchiveLine::Manifest(&manifest))?;
+
+        let mut after = String::new();
+        loop {
+            let page = self.archive_page(
+                "SELECT * FROM tokens WHERE created_at < ?1 AND mint > ?2 ORDER BY mint LIMIT ?3",
+                before,
+                &after,
+                Self::token_from_row,
+            )?;
+            for token in &page {
+                Self::write_archive_line(out, &ArchiveLine::Token(token))?;
+            }
+            let Some(last) = page.last() else { break };
+            after = last.mint.clone();
+        }
+
+        let mut after = String::new();
+        loop {
+            let page = self.archive_page(
+                "SELECT * FROM transactions WHERE timestamp < ?1 AND signature > ?2 \
+                 ORDER BY signature LIMIT ?3",
+                before,
+                &after,
+                Self::transaction_from_row,
+            )?;
+            for tx in &page {
+                Self::write_archive_line(out, &ArchiveLine::Transaction(tx))?;
+            }
+            let Some(last) = page.last() else { break };
+            after = last.signature.clone();
+        }
+
+        let mut after = 0i64;
+        loop {
+            let page = self.archive_page(
+                "SELECT * FROM alerts WHERE created_at < ?1 AND id > ?2 ORDER BY id LIMIT ?3",
+                before,
+                &after,
+                Self::alert_from_row,
+            )?;
+            for alert in &page {
+                Self::write_archive_line(out, &ArchiveLine::Alert(alert))?;
+            }
+            let Some(last) = page.last() else { break };
+            after = last.id;
+        }
+
+        out.flush()?;
+        Ok(manifest)
+    }
+
+    fn archive_page<T>(
+        &self,
+        sql: &str,
+        before: &str,
+        after: &dyn rusqlite::ToSql,
+        from_row: fn(&rusqlite::Row) -> rusqlite::Result<T>,
+    ) -> PumpResult<Vec<T>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt
+            .query_map(params![before, after, ARCHIVE_PAGE_SIZE], from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    fn write_archive_line(out: &mut impl std::io::Write, line: &ArchiveLine) -> PumpResult<()> {
+        serde_json::to_writer(&mut *out, line).map_err(std::io::Error::from)?;
+        out.write_all(b"\n")?;
+        Ok(())
+    }
+
+    /// Delete the tokens, transactions and alerts older than `before` in one transaction,
+    /// once they have been archived. A token still traded since `before` stays, since its
+    /// newer transactions reference it.
+    pub fn delete_archived(&self, before: &str) -> PumpResult<EvictedRows> {
+        self.flush_transactions();
+        let mut conn = self.conn.lock();
+        let db_tx = conn.transaction()?;
+        let transactions =
+            db_tx.execute("DELETE FROM transactions WHERE timestamp < ?", params![before])?;
+        let alerts = db_tx.execute("DELETE FROM alerts WHERE created_at < ?", params![before])?;
+        let mints = db_tx
+            .prepare(
+                "DELETE FROM tokens WHERE created_at < ? AND NOT EXISTS \
+                 (SELECT 1 FROM transactions WHERE transactions.mint = tokens.mint) RETURNING mint",
+            )?
+            .query_map(params![before], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        db_tx.commit()?;
+
+        let mut cache = self.token_cache.lock();
+        for mint in &mints {
+            cache.invalidate(mint);
+        }
+        Ok(EvictedRows {
+            transactions,
+            alerts,
+            tokens: mints.len(),
+        })
+    }
+
+    // ============================================
+    // INSTANCE LOCK METHODS
+    // ============================================
+
+    /// Take or renew the named lease; succeeds when it is free, expired or already ours
+    pub fn try_acquire_lease(&self, name: &str, holder: &str, ttl_secs: u64) -> PumpResult<bool> {
+        let conn = self.conn.lock();
+        let now = Utc::now().timestamp();
+        let changed = conn.execute(
+            r#"
+            INSERT INTO instance_locks (name, holder, heartbeat)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT(name) DO UPDATE SET holder = excluded.holder, heartbeat = excluded.heartbeat
+            WHERE instance_locks.holder = excluded.holder OR instance_locks.heartbeat < ?4
+            "#,
+            params![name, holder, now, now - ttl_secs as i64],
+        )?;
+        Ok(changed > 0)
+    }
+
+    /// Give up the named lease if we hold it
+    pub fn release_lease(&self, name: &str, holder: &str) -> PumpResult<()> {
+        let conn = self.conn.lock();
+        conn.execute(
+            "DELETE FROM instance_locks WHERE name = ? AND holder = ?",
+            params![name, holder],
+        )?;
+        Ok(())
+    }
+
+    /// Current holder of the named lease and its last heartbeat (unix seconds)
+    pub fn get_lease(&self, name: &str) -> PumpResult<Option<(String, i64)>> {
+        let conn = self.conn.lock();
+        let lease = conn
+            .query_row(
+                "SELECT holder, heartbeat FROM instance_locks WHERE name = ?",
+                params![name],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        Ok(lease)
+    }
+
+    /// Overwrite the saved counter totals
+    pub fn save_metric_counters(&self, counters: &[(&str, f64)]) -> PumpResult<()> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        let now = Utc::now().to_rfc3339();
+        for (name, value) in counters {
+            tx.execute(
+                "INSERT OR REPLACE INTO metric_counters (name, value, updated_at) VALUES (?, ?, ?)",
+                params![name, value, now],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Saved counter totals by name
+    pub fn load_metric_counters(&self) -> PumpResult<HashMap<String, f64>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare("SELECT name, value FROM metric_counters")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        let counters = rows.collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(counters)
+    }
+
     // ============================================
     // STATS
     // ============================================
 
-    pub fn get_stats(&self) -> Result<DbStats> {
+    /// Tokens first seen at or after `since` (RFC 3339)
+    pub fn count_tokens_since(&self, since: &str) -> PumpResult<i64> {
         let conn = self.conn.lock();
-        
-        let total_tokens: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM tokens",
-            [],
+        let count = conn.query_row(
+            "SELECT COUNT(*) FROM tokens WHERE created_at >= ?",
+            params![since],
             |row| row.get(0),
         )?;
+        Ok(count)
+    }
 
-        let rugged_tokens: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM tokens WHERE is_rugged = 1",
-            [],
+    /// Number of tokens marked rugged at or after `since`
+    pub fn count_rugged_since(&self, since: &str) -> PumpResult<i64> {
+        let conn = self.conn.lock();
+        let count = conn.query_row(
+            "SELECT COUNT(*) FROM tokens WHERE is_rugged = 1 AND rugged_at >= ?",
+            params![since],
             |row| row.get(0),
         )?;
+        Ok(count)
+    }
 
-        let whales: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM watched_wallets WHERE is_whale = 1",
-            [],
-            |row| row.get(0),
+    /// Tokens marked rugged at or after `since`, most recent first
+    pub fn get_rugged_since(&self, since: &str, limit: i64) -> PumpResult<Vec<TokenRecord>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM tokens WHERE is_rugged = 1 AND rugged_at >= ? ORDER BY rugged_at DESC LIMIT ?",
         )?;
+        let rows = stmt.query_map(params![since, limit], Self::token_from_row)?;
 
-        let alerts: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM alerts",
-            [],
-            |row| row.get(0),
+        let mut tokens = Vec::new();
+        for row in rows {
+            tokens.push(row?);
+        }
+        Ok(tokens)
+    }
+
+    /// Wallets with the most SOL traded at or after `since`
+    pub fn get_top_wallets_since(&self, since: &str, limit: i64) -> PumpResult<Vec<WalletVolume>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT wallet, SUM(amount_sol) AS volume, COUNT(*)
+            FROM transactions
+            WHERE timestamp >= ?
+            GROUP BY wallet
+            ORDER BY volume DESC
+            LIMIT ?
+            "#,
         )?;
+        let rows = stmt.query_map(params![since, limit], |row| {
+            Ok(WalletVolume {
+                wallet: row.get(0)?,
+                volume_sol: row.get(1)?,
+                trades: row.get(2)?,
+            })
+        })?;
 
-        Ok(DbStats {
-            total_tokens,
-            rugged_tokens,
-            whales,
-            alerts,
-        })
+        let mut wallets = Vec::new();
+        for row in rows {
+            wallets.push(row?);
+        }
+        Ok(wallets)
+    }
+
+    /// Tokens with the most SOL traded at or after `since`
+    pub fn get_top_tokens_since(&self, since: &str, limit: i64) -> PumpResult<Vec<TokenVolume>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT t.mint, k.symbol, SUM(t.amount_sol) AS volume,
+                   SUM(CASE WHEN t.type = 'buy' THEN t.amount_sol ELSE -t.amount_sol END)
+            FROM transactions t
+            LEFT JOIN tokens k ON k.mint = t.mint
+            WHERE t.timestamp >= ?
+            GROUP BY t.mint
+            ORDER BY volume DESC
+            LIMIT ?
+            "#,
+        )?;
+        let rows = stmt.query_map(params![since, limit], |row| {
+            Ok(TokenVolume {
+                mint: row.get(0)?,
+                symbol: row.get(1)?,
+                volume_sol: row.get(2)?,
+                net_flow_sol: row.get(3)?,
+            })
+        })?;
+
+        let mut tokens = Vec::new();
+        for row in rows {
+            tokens.push(row?);
+        }
+        Ok(tokens)
+    }
+
+    /// Token counts and rug rates by age since creation, split at `boundaries_secs`;
+    /// `[60, 600]` gives `<1m`, `1m-10m` and `>10m`. Every bucket is listed, even when empty.
+    pub fn get_token_cohorts(&self, boundaries_secs: &[u64]) -> PumpResult<Vec<TokenCohort>> {
+        let mut boundaries: Vec<u64> = boundaries_secs.iter().copied().filter(|&b| b > 0).collect();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        // Boundaries are integers, so they can go into the CASE as literals
+        let bucket = if boundaries.is_empty() {
+            "0".to_string()
+        } else {
+            let when: String = boundaries
+                .iter()
+                .enumerate()
+                .map(|(bucket, boundary)| format!("WHEN age < {} THEN {} ", boundary, bucket))
+                .collect();
+            format!("CASE {}ELSE {} END", when, boundaries.len())
+        };
+        let sql = format!(
+            "SELECT {} AS bucket, COUNT(*), COALESCE(SUM(is_rugged), 0) \
+             FROM (SELECT (julianday('now') - julianday(created_at)) * 86400.0 AS age, is_rugged \
+                   FROM tokens WHERE julianday(created_at) IS NOT NULL) \
+             GROUP BY bucket",
+            bucket
+        );
+
+        let mut counts = vec![(0i64, 0i64); boundaries.len() + 1];
+        {
+            let conn = self.conn.lock();
+            let mut stmt = conn.prepare(&sql)?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?))
+            })?;
+            for row in rows {
+                let (bucket, tokens, rugged) = row?;
+                if let Some(count) = counts.get_mut(bucket as usize) {
+                    *count = (tokens, rugged);
+                }
+            }
+        }
+
+        Ok(counts
+            .into_iter()
+            .enumerate()
+            .map(|(bucket, (tokens, rugged))| {
+                let min_age_secs = bucket.checked_sub(1).map_or(0, |i| boundaries[i]);
+                let max_age_secs = boundaries.get(bucket).copied();
+                let label = match (min_age_secs, max_age_secs) {
+                    (0, None) => "all".to_string(),
+                    (0, Some(max)) => format!("<{}", age_label(max)),
+                    (min, Some(max)) => format!("{}-{}", age_label(min), age_label(max)),
+                    (min, None) => format!(">{}", age_label(min)),
+                };
+                TokenCohort {
+                    label,
+                    min_age_secs,
+                    max_age_secs,
+                    tokens,
+                    rugged,
+                    rug_rate: if tokens > 0 { rugged as f64 / tokens as f64 } else { 0.0 },
+                }
+            })
+            .collect())
     }
 }
 
@@ -438,8 +2011,312 @@ impl Clone for DatabaseService {
     fn clone(&self) -> Self {
         Self {
             conn: Arc::clone(&self.conn),
+            token_cache: Arc::clone(&self.token_cache),
+            write_retries: self.write_retries,
+            write_backoff_ms: self.write_backoff_ms,
+            dead_letters: Arc::clone(&self.dead_letters),
+            write_failures: Arc::clone(&self.write_failures),
+            tx_batch_size: self.tx_batch_size,
+            tx_buffer: Arc::clone(&self.tx_buffer),
+            tx_batch_stats: Arc::clone(&self.tx_batch_stats),
+            tx_flush_wanted: Arc::clone(&self.tx_flush_wanted),
+            rugged_tokens: Arc::clone(&self.rugged_tokens),
         }
     }
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(mint: &str) -> TokenRecord {
+        TokenRecord {
+            mint: mint.to_string(),
+            name: "Test".to_string(),
+            symbol: "TST".to_string(),
+            creator: "creator".to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            initial_liquidity: 1.0,
+            current_liquidity: 1.0,
+            holder_count: 0,
+            is_rugged: false,
+            rug_reason: None,
+            last_updated: Utc::now().to_rfc3339(),
+            is_muted: false,
+            is_graduated: false,
+        }
+    }
+
+    #[test]
+    fn replayed_token_save_keeps_status_flags() {
+        let database = DatabaseService::new(":memory:").unwrap();
+        database.save_token(&token("mint")).unwrap();
+        database.mark_as_rugged("mint", "LP removed").unwrap();
+        database.mark_graduated("mint").unwrap();
+        database.set_token_muted(&TokenRecord { is_muted: true, ..token("mint") }).unwrap();
+
+        database.save_token(&token("mint")).unwrap();
+
+        let saved = database.get_token("mint").unwrap().unwrap();
+        assert!(saved.is_rugged);
+        assert_eq!(saved.rug_reason.as_deref(), Some("LP removed"));
+        assert!(saved.is_graduated);
+        assert!(saved.is_muted);
+    }
+
+    #[test]
+    fn token_cache_is_invalidated_by_writes() {
+        let database = DatabaseService::new(":memory:").unwrap();
+        // An unknown mint is cached as absent until it is saved
+        assert!(database.get_token("mint").unwrap().is_none());
+        assert!(database.get_token("mint").unwrap().is_none());
+        database.save_token(&token("mint")).unwrap();
+        assert!(database.get_token("mint").unwrap().is_some());
+
+        database.set_token_muted(&TokenRecord { is_muted: true, ..token("mint") }).unwrap();
+        assert!(database.get_token("mint").unwrap().unwrap().is_muted);
+
+        database.mark_as_rugged("mint", "LP removed").unwrap();
+        let cached = database.get_token("mint").unwrap().unwrap();
+        assert!(cached.is_rugged);
+        assert_eq!(cached.rug_reason.as_deref(), Some("LP removed"));
+
+        database.clear_rugged("mint").unwrap();
+        assert!(!database.get_token("mint").unwrap().unwrap().is_rugged);
+
+        // A read with no write since the last one is served from the cache
+        let before = database.token_cache_stats();
+        assert!(!database.get_token("mint").unwrap().unwrap().is_rugged);
+        let after = database.token_cache_stats();
+        assert_eq!((after.hits, after.misses), (before.hits + 1, before.misses));
+        assert_eq!(after.entries, 1);
+    }
+
+    #[test]
+    fn token_cache_evicts_the_least_recently_used() {
+        let mut cache = TokenCache::new(2);
+        cache.insert("a", Some(token("a")));
+        cache.insert("b", None);
+        assert!(cache.get("a").is_some());
+
+        cache.insert("c", Some(token("c")));
+
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("a").is_some());
+        assert!(cache.get("c").is_some());
+    }
+
+    #[test]
+    fn size_limit_evicts_the_oldest_data_and_keeps_rugged_tokens() {
+        let database = DatabaseService::new(":memory:").unwrap();
+        for mint in ["old", "new", "rugged"] {
+            database.save_token(&token(mint)).unwrap();
+        }
+        database.mark_as_rugged("rugged", "LP removed").unwrap();
+        let start = Utc::now() - chrono::Duration::hours(1);
+        // 799 trades of the rugged token, then the newest one of `new`
+        for i in 0..800 {
+            database
+                .save_transaction(&TransactionRecord {
+                    signature: format!("sig{:03}", i),
+                    mint: if i < 799 { "rugged" } else { "new" }.to_string(),
+                    wallet: "wallet".to_string(),
+                    tx_type: "buy".to_string(),
+                    amount_sol: 1.0,
+                    amount_tokens: 1000.0,
+                    timestamp: (start + chrono::Duration::seconds(i)).to_rfc3339(),
+                })
+                .unwrap();
+        }
+        for i in 0..10 {
+            database.save_alert("test", "Alert", &format!("alert {}", i), "{}", false).unwrap();
+        }
+
+        assert!(database.enforce_size_limit(u64::MAX).unwrap().is_none());
+
+        // Oldest first: one row of each
+        let round = database.evict_oldest(1).unwrap();
+        assert_eq!((round.transactions, round.alerts, round.tokens), (1, 1, 1));
+        assert!(database.get_token("old").unwrap().is_none());
+        let remaining = database.get_transactions_for_token("rugged", 1000).unwrap();
+        assert_eq!(remaining.len(), 798);
+        assert!(remaining.iter().all(|tx| tx.signature != "sig000"));
+
+        // A cap nothing fits under empties every table but the rugged tokens; `new` goes
+        // in the round after its transaction
+        let evicted = database.enforce_size_limit(1).unwrap().unwrap();
+        assert_eq!((evicted.transactions, evicted.alerts, evicted.tokens), (799, 9, 1));
+        assert!(database.get_token("new").unwrap().is_none());
+        assert!(database.get_token("rugged").unwrap().is_some());
+        assert!(database.get_transactions_for_token("rugged", 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn deleting_archived_rows_keeps_tokens_traded_since() {
+        let database = DatabaseService::new(":memory:").unwrap();
+        let before = Utc::now() - chrono::Duration::hours(1);
+        let old = (before - chrono::Duration::hours(1)).to_rfc3339();
+        for mint in ["quiet", "traded"] {
+            database.save_token(&TokenRecord { created_at: old.clone(), ..token(mint) }).unwrap();
+        }
+        for (signature, timestamp) in [("old", old.clone()), ("recent", Utc::now().to_rfc3339())] {
+            database
+                .save_transaction(&TransactionRecord {
+                    signature: signature.to_string(),
+                    mint: "traded".to_string(),
+                    wallet: "wallet".to_string(),
+                    tx_type: "buy".to_string(),
+                    amount_sol: 1.0,
+                    amount_tokens: 1000.0,
+                    timestamp,
+                })
+                .unwrap();
+        }
+
+        let deleted = database.delete_archived(&before.to_rfc3339()).unwrap();
+
+        assert_eq!((deleted.transactions, deleted.tokens), (1, 1));
+        assert!(database.get_token("quiet").unwrap().is_none());
+        assert!(database.get_token("traded").unwrap().is_some());
+    }
+
+    #[test]
+    fn muting_an_unsaved_token_inserts_it() {
+        let database = DatabaseService::new(":memory:").unwrap();
+        database.set_token_muted(&TokenRecord { is_muted: true, ..token("mint") }).unwrap();
+        assert!(database.get_token("mint").unwrap().unwrap().is_muted);
+
+        database.set_token_muted(&token("mint")).unwrap();
+        assert!(!database.get_token("mint").unwrap().unwrap().is_muted);
+    }
+
+    #[test]
+    fn copycat_candidates_follow_rug_updates() {
+        let database = DatabaseService::new(":memory:").unwrap();
+        database.save_token(&token("first")).unwrap();
+        database.mark_as_rugged("first", "LP removed").unwrap();
+        let found = database.find_similar_rugged("Tesst", "XYZ", 1).unwrap();
+        assert_eq!(found.map(|t| t.mint).as_deref(), Some("first"));
+
+        // Candidates are loaded now; later updates must reach them
+        let mut second = token("second");
+        second.name = "Other".to_string();
+        second.symbol = "OTH".to_string();
+        database.save_token(&second).unwrap();
+        database.mark_as_rugged("second", "Dev dump").unwrap();
+        database.clear_rugged("first").unwrap();
+
+        assert!(database.find_similar_rugged("Tesst", "XYZ", 1).unwrap().is_none());
+        let found = database.find_similar_rugged("Otter", "OTH", 1).unwrap();
+        assert_eq!(found.map(|t| t.mint).as_deref(), Some("second"));
+    }
+
+    #[test]
+    fn rugged_since_counts_rugs_by_rug_time() {
+        let database = DatabaseService::new(":memory:").unwrap();
+        let since = (Utc::now() - chrono::Duration::hours(24)).to_rfc3339();
+        for i in 0..7 {
+            let mint = format!("mint{}", i);
+            database.save_token(&token(&mint)).unwrap();
+            database.mark_as_rugged(&mint, "LP removed").unwrap();
+        }
+        // Rugged two days ago; a later update must not pull it back into the window
+        let old = (Utc::now() - chrono::Duration::hours(48)).to_rfc3339();
+        database
+            .conn
+            .lock()
+            .execute("UPDATE tokens SET rugged_at = ? WHERE mint = 'mint0'", params![old])
+            .unwrap();
+        database.save_token(&token("mint0")).unwrap();
+
+        assert_eq!(database.count_rugged_since(&since).unwrap(), 6);
+        let recent = database.get_rugged_since(&since, 5).unwrap();
+        assert_eq!(recent.len(), 5);
+        assert!(recent.iter().all(|t| t.mint != "mint0"));
+    }
+
+    #[test]
+    fn token_search_matches_prefixes_through_the_indexes() {
+        let database = DatabaseService::new(":memory:").unwrap();
+        for (mint, name, symbol) in [("a", "Pepe Coin", "PEPE"), ("b", "Dog Pepe", "DPEPE")] {
+            let record = TokenRecord {
+                name: name.to_string(),
+                symbol: symbol.to_string(),
+                ..token(mint)
+            };
+            database.save_token(&record).unwrap();
+        }
+
+        let found = database.search_tokens("pepe", 10).unwrap();
+        assert_eq!(found.iter().map(|t| t.mint.as_str()).collect::<Vec<_>>(), vec!["a"]);
+
+        let conn = database.conn.lock();
+        let mut stmt = conn.prepare(&format!("EXPLAIN QUERY PLAN {}", TOKEN_SEARCH_SQL)).unwrap();
+        let plan: Vec<String> = stmt
+            .query_map(params!["pepe%", "pepe", 10], |row| row.get(3))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(plan.iter().any(|step| step.contains("idx_tokens_symbol")), "{:?}", plan);
+        assert!(plan.iter().any(|step| step.contains("idx_tokens_name")), "{:?}", plan);
+    }
+
+    fn buffered_transaction(signature: &str, mint: &str) -> PendingWrite {
+        PendingWrite::Transaction(TransactionRecord {
+            signature: signature.to_string(),
+            mint: mint.to_string(),
+            wallet: "wallet".to_string(),
+            tx_type: "buy".to_string(),
+            amount_sol: 1.0,
+            amount_tokens: 1000.0,
+            timestamp: Utc::now().to_rfc3339(),
+        })
+    }
+
+    #[tokio::test]
+    async fn full_transaction_buffer_wakes_the_flusher() {
+        let database = DatabaseService::new(":memory:").unwrap().with_transaction_batching(3);
+        database.save_token(&token("mint")).unwrap();
+        database.start_transaction_flusher(60_000);
+        // Let the interval's immediate first tick pass
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        database.write(buffered_transaction("a", "mint"));
+        database.write(buffered_transaction("b", "mint"));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(database.tx_batch_stats().buffered, 2);
+        assert!(database.get_transactions_for_token("mint", 10).unwrap().is_empty());
+
+        database.write(buffered_transaction("c", "mint"));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let stats = database.tx_batch_stats();
+        assert_eq!((stats.buffered, stats.flushes), (0, 1));
+        assert_eq!(database.get_transactions_for_token("mint", 10).unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn failed_batch_falls_back_to_individual_writes() {
+        let database = DatabaseService::new(":memory:")
+            .unwrap()
+            .with_write_retry(0, 0)
+            .with_transaction_batching(10);
+        database.save_token(&token("mint")).unwrap();
+        database.write(buffered_transaction("a", "mint"));
+        // No such token, so the foreign key fails the whole batch
+        database.write(buffered_transaction("b", "unknown"));
+        database.write(buffered_transaction("c", "mint"));
+
+        assert_eq!(database.flush_transactions(), 3);
+        assert_eq!(database.tx_batch_stats().flushes, 0);
+        let saved = database.get_transactions_for_token("mint", 10).unwrap();
+        assert_eq!(saved.len(), 2);
+        let dead_letters = database.dead_letters();
+        assert_eq!(dead_letters.len(), 1);
+        assert!(matches!(
+            &dead_letters[0].write,
+            PendingWrite::Transaction(tx) if tx.signature == "b"
+        ));
+    }
+}
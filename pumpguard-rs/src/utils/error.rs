@@ -0,0 +1,52 @@
+//! Typed errors for the Solana and database service boundaries
+
+use solana_client::client_error::ClientError;
+use thiserror::Error;
+
+/// Error kinds callers can branch on (retry, HTTP status, ...)
+#[derive(Debug, Error)]
+pub enum PumpError {
+    #[error("RPC unavailable: {0}")]
+    RpcUnavailable(String),
+    #[error("RPC rate limited")]
+    RateLimited,
+    #[error("{0} not found")]
+    NotFound(String),
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("Storage error: {0}")]
+    Storage(#[from] rusqlite::Error),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type PumpResult<T> = std::result::Result<T, PumpError>;
+
+impl PumpError {
+    /// Transient failures worth retrying later
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::RpcUnavailable(_) | Self::RateLimited) || self.is_storage_busy()
+    }
+
+    /// SQLite reported the database as busy or locked
+    pub fn is_storage_busy(&self) -> bool {
+        match self {
+            Self::Storage(rusqlite::Error::SqliteFailure(e, _)) => matches!(
+                e.code,
+                rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked
+            ),
+            _ => false,
+        }
+    }
+}
+
+impl From<ClientError> for PumpError {
+    fn from(e: ClientError) -> Self {
+        let message = e.to_string();
+        if message.contains("429") {
+            Self::RateLimited
+        } else {
+            Self::RpcUnavailable(message)
+        }
+    }
+}
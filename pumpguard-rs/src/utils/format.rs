@@ -0,0 +1,122 @@
+//! Format - Human-readable SOL, token and USD amounts for alert messages
+
+/// A lamport is the smallest SOL amount, so no SOL value needs more decimals
+const SOL_DECIMALS: usize = 9;
+
+/// f64 holds about 15 significant digits; decimals past that are noise
+const F64_DIGITS: usize = 15;
+
+/// SOL amount with two decimals from 1 SOL up, and below that enough decimals to
+/// keep `significant_digits` digits, e.g. `0.000123 SOL` rather than `0.00 SOL`
+pub fn format_sol(amount: f64, significant_digits: u32) -> String {
+    let magnitude = amount.abs();
+    let decimals = if magnitude >= 1.0 || magnitude == 0.0 || !magnitude.is_finite() {
+        2
+    } else {
+        // Zeros between the point and the first significant digit
+        let leading_zeros = ((-magnitude.log10()).ceil() as usize).saturating_sub(1);
+        (leading_zeros + significant_digits.max(1) as usize).min(SOL_DECIMALS)
+    };
+    format!("{} SOL", group_thousands(&format!("{:.*}", decimals, amount)))
+}
+
+/// [`format_sol`] with an explicit sign, for net flows
+pub fn format_sol_signed(amount: f64, significant_digits: u32) -> String {
+    let formatted = format_sol(amount, significant_digits);
+    if amount > 0.0 {
+        format!("+{}", formatted)
+    } else {
+        formatted
+    }
+}
+
+/// Token amount (UI units) with thousands separators and at most the mint's
+/// `decimals`, trailing zeros trimmed: `1,234,567.5`
+pub fn format_tokens(amount: f64, decimals: u8) -> String {
+    if !amount.is_finite() {
+        return amount.to_string();
+    }
+    let integer_digits = (amount.abs().max(1.0).log10().floor() as usize) + 1;
+    let decimals = (decimals as usize).min(F64_DIGITS.saturating_sub(integer_digits));
+    let fixed = format!("{:.*}", decimals, amount);
+    let trimmed = if fixed.contains('.') {
+        fixed.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &fixed
+    };
+    group_thousands(trimmed)
+}
+
+/// Whole US dollars with thousands separators: `$1,250,000`
+pub fn format_usd(amount: f64) -> String {
+    let fixed = format!("{:.0}", amount);
+    match fixed.strip_prefix('-') {
+        Some(unsigned) => format!("-${}", group_thousands(unsigned)),
+        None => format!("${}", group_thousands(&fixed)),
+    }
+}
+
+/// Insert `,` every three digits of the integer part of a formatted number
+fn group_thousands(number: &str) -> String {
+    let (sign, unsigned) = match number.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", number),
+    };
+    let (integer, fraction) = match unsigned.find('.') {
+        Some(dot) => unsigned.split_at(dot),
+        None => (unsigned, ""),
+    };
+
+    let mut grouped = String::with_capacity(number.len() + integer.len() / 3);
+    grouped.push_str(sign);
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped.push_str(fraction);
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sol_keeps_significant_digits_below_one_sol() {
+        assert_eq!(format_sol(0.5, 3), "0.500 SOL");
+        assert_eq!(format_sol(0.000123, 3), "0.000123 SOL");
+        // Dust stops at lamport precision
+        assert_eq!(format_sol(0.000000001, 3), "0.000000001 SOL");
+        assert_eq!(format_sol(0.0, 3), "0.00 SOL");
+    }
+
+    #[test]
+    fn sol_uses_two_decimals_and_separators_from_one_sol() {
+        assert_eq!(format_sol(1.0, 3), "1.00 SOL");
+        assert_eq!(format_sol(1_234_567.891, 3), "1,234,567.89 SOL");
+        assert_eq!(format_sol(-1_500.0, 3), "-1,500.00 SOL");
+        assert_eq!(format_sol_signed(2.5, 3), "+2.50 SOL");
+        assert_eq!(format_sol_signed(-0.25, 2), "-0.25 SOL");
+        assert_eq!(format_sol_signed(0.0, 2), "0.00 SOL");
+    }
+
+    #[test]
+    fn tokens_respect_mint_decimals_and_group_thousands() {
+        assert_eq!(format_tokens(1_234_567.5, 6), "1,234,567.5");
+        assert_eq!(format_tokens(1_000_000_000.0, 6), "1,000,000,000");
+        assert_eq!(format_tokens(12_345_678_901.5, 9), "12,345,678,901.5");
+        assert_eq!(format_tokens(0.000001, 6), "0.000001");
+        // Below the mint's smallest unit rounds away entirely
+        assert_eq!(format_tokens(0.0000001, 6), "0");
+        assert_eq!(format_tokens(999.999, 0), "1,000");
+    }
+
+    #[test]
+    fn usd_is_whole_dollars_with_separators() {
+        assert_eq!(format_usd(0.0), "$0");
+        assert_eq!(format_usd(1_250_000.4), "$1,250,000");
+        assert_eq!(format_usd(-1_234.6), "-$1,235");
+    }
+}
@@ -0,0 +1,146 @@
+//! Leader Election - Lease row in the shared database so only one instance runs detection
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::utils::DatabaseService;
+
+/// Name of the lease row guarding the detection modules
+const LEADER_LEASE: &str = "detection";
+
+/// Role of this instance as reported by `/health`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceRole {
+    Leader,
+    Follower,
+}
+
+/// Leader election over the `instance_locks` table
+pub struct LeaderElection {
+    instance_id: String,
+    lease_ttl: Duration,
+    database: Arc<DatabaseService>,
+    is_leader: Arc<AtomicBool>,
+}
+
+impl LeaderElection {
+    /// Create an election for this instance
+    pub fn new(instance_id: String, lease_ttl_secs: u64, database: Arc<DatabaseService>) -> Self {
+        Self {
+            instance_id,
+            lease_ttl: Duration::from_secs(lease_ttl_secs.max(3)),
+            database,
+            is_leader: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Heartbeats are sent well inside the TTL so one slow write doesn't lose the lease
+    fn heartbeat_interval(&self) -> Duration {
+        self.lease_ttl / 3
+    }
+
+    fn try_acquire(&self) -> bool {
+        match self.database.try_acquire_lease(
+            LEADER_LEASE,
+            &self.instance_id,
+            self.lease_ttl.as_secs(),
+        ) {
+            Ok(acquired) => acquired,
+            Err(e) => {
+                warn!(target: "LEADER", "Lease update failed: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Wait as a follower until this instance holds the lease
+    pub async fn acquire(&self) {
+        let mut announced = false;
+        loop {
+            if self.try_acquire() {
+                self.is_leader.store(true, Ordering::SeqCst);
+                info!(target: "LEADER", "Instance {} is now the leader", self.instance_id);
+                return;
+            }
+            if !announced {
+                let holder = self.database.get_lease(LEADER_LEASE).ok().flatten();
+                info!(
+                    target: "LEADER",
+                    "Instance {} running as follower (leader: {})",
+                    self.instance_id,
+                    holder.map(|(holder, _)| holder).unwrap_or_else(|| "unknown".to_string())
+                );
+                announced = true;
+            }
+            tokio::time::sleep(self.heartbeat_interval()).await;
+        }
+    }
+
+    /// Keep renewing the lease; returns an error once it is lost or lapses
+    pub async fn hold(&self) -> Result<()> {
+        let mut last_renewed = Instant::now();
+        loop {
+            tokio::time::sleep(self.heartbeat_interval()).await;
+
+            match self.database.try_acquire_lease(
+                LEADER_LEASE,
+                &self.instance_id,
+                self.lease_ttl.as_secs(),
+            ) {
+                Ok(true) => last_renewed = Instant::now(),
+                Ok(false) => {
+                    self.is_leader.store(false, Ordering::SeqCst);
+                    bail!("leader lease taken over by another instance");
+                }
+                Err(e) => {
+                    warn!(target: "LEADER", "Lease heartbeat failed: {}", e);
+                    if last_renewed.elapsed() >= self.lease_ttl {
+                        self.is_leader.store(false, Ordering::SeqCst);
+                        bail!("leader lease lapsed without a heartbeat");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Hand the lease back so a follower can take over immediately
+    pub fn release(&self) {
+        if !self.is_leader.swap(false, Ordering::SeqCst) {
+            return;
+        }
+        match self.database.release_lease(LEADER_LEASE, &self.instance_id) {
+            Ok(()) => info!(target: "LEADER", "Instance {} released the leader lease", self.instance_id),
+            Err(e) => warn!(target: "LEADER", "Lease release failed: {}", e),
+        }
+    }
+
+    /// Current role of this instance
+    pub fn role(&self) -> InstanceRole {
+        if self.is_leader.load(Ordering::SeqCst) {
+            InstanceRole::Leader
+        } else {
+            InstanceRole::Follower
+        }
+    }
+
+    /// Identifier this instance uses for the lease
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+}
+
+impl Clone for LeaderElection {
+    fn clone(&self) -> Self {
+        Self {
+            instance_id: self.instance_id.clone(),
+            lease_ttl: self.lease_ttl,
+            database: Arc::clone(&self.database),
+            is_leader: Arc::clone(&self.is_leader),
+        }
+    }
+}
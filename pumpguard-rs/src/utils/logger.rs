@@ -1,11 +1,130 @@
 //! Colored logging module for PumpGuard
 
-use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use parking_lot::Mutex;
+use std::collections::BTreeMap;
+use tracing_subscriber::{
+    filter::LevelFilter, fmt, layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter,
+    Registry,
+};
 
-/// Initialize the tracing logger with colored output
-pub fn init_logger() {
-    let filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new("info,pumpguard=debug"));
+use crate::utils::error::{PumpError, PumpResult};
+
+/// Filter used when `RUST_LOG` is unset or doesn't parse
+const DEFAULT_FILTER: &str = "info,pumpguard=debug";
+
+/// Handle for changing log levels per target while running. Overrides are layered over
+/// the startup filter and last until restart.
+pub struct LogFilter {
+    handle: reload::Handle<EnvFilter, Registry>,
+    /// Directives the process started with
+    base: String,
+    overrides: Mutex<BTreeMap<String, LevelFilter>>,
+}
+
+impl LogFilter {
+    /// The startup filter from `RUST_LOG`, or the default when it's missing or invalid
+    fn startup() -> (String, EnvFilter) {
+        std::env::var(EnvFilter::DEFAULT_ENV)
+            .ok()
+            .and_then(|directives| {
+                let filter = EnvFilter::try_new(&directives).ok()?;
+                Some((directives, filter))
+            })
+            .unwrap_or_else(|| (DEFAULT_FILTER.to_string(), EnvFilter::new(DEFAULT_FILTER)))
+    }
+
+    /// A filter layer for `Registry` and the handle that reloads it
+    pub fn reloadable() -> (reload::Layer<EnvFilter, Registry>, Self) {
+        let (base, filter) = Self::startup();
+        let (layer, handle) = reload::Layer::new(filter);
+        let log_filter = Self {
+            handle,
+            base,
+            overrides: Mutex::new(BTreeMap::new()),
+        };
+        (layer, log_filter)
+    }
+
+    /// Effective filter directives
+    pub fn current(&self) -> String {
+        self.handle
+            .with_current(|filter| filter.to_string())
+            .unwrap_or_else(|_| self.directives(&self.overrides.lock()))
+    }
+
+    /// Levels set at runtime, by target
+    pub fn overrides(&self) -> BTreeMap<String, String> {
+        self.overrides
+            .lock()
+            .iter()
+            .map(|(target, level)| (target.clone(), level.to_string()))
+            .collect()
+    }
+
+    /// Log `target` (e.g. `RUG_DETECTOR`) at `level`, or back at its startup level with
+    /// `reset`. Returns the effective filter.
+    pub fn set_level(&self, target: &str, level: &str) -> PumpResult<String> {
+        let target = target.trim();
+        let valid_target = !target.is_empty()
+            && target.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | ':'));
+        if !valid_target {
+            return Err(PumpError::InvalidInput(format!(
+                "target must be a log target such as RUG_DETECTOR, got {:?}",
+                target
+            )));
+        }
+        let level = level.trim().to_ascii_lowercase();
+        let level = match level.as_str() {
+            "reset" => None,
+            "trace" | "debug" | "info" | "warn" | "error" | "off" => {
+                level.parse::<LevelFilter>().ok()
+            }
+            _ => {
+                return Err(PumpError::InvalidInput(format!(
+                    "level must be trace, debug, info, warn, error, off or reset, got {}",
+                    level
+                )))
+            }
+        };
+
+        let mut overrides = self.overrides.lock();
+        match level {
+            Some(level) => overrides.insert(target.to_string(), level),
+            None => overrides.remove(target),
+        };
+        let directives = self.directives(&overrides);
+        let filter = EnvFilter::try_new(&directives)
+            .map_err(|e| PumpError::InvalidInput(format!("{}: {}", directives, e)))?;
+        let effective = filter.to_string();
+        self.handle
+            .reload(filter)
+            .map_err(|e| PumpError::InvalidInput(format!("Log filter reload failed: {}", e)))?;
+        tracing::info!(target: "PUMPGUARD", "Log filter now {}", effective);
+        Ok(effective)
+    }
+
+    /// Startup directives without the overridden targets, followed by the overrides
+    fn directives(&self, overrides: &BTreeMap<String, LevelFilter>) -> String {
+        let mut directives: Vec<String> = self
+            .base
+            .split(',')
+            .map(str::trim)
+            .filter(|directive| !directive.is_empty())
+            .filter(|directive| {
+                let target = directive.split(['=', '[']).next().unwrap_or_default();
+                !overrides.contains_key(target)
+            })
+            .map(String::from)
+            .collect();
+        directives.extend(overrides.iter().map(|(target, level)| format!("{}={}", target, level)));
+        directives.join(",")
+    }
+}
+
+/// Initialize the tracing logger with colored output; the returned handle changes
+/// levels at runtime
+pub fn init_logger() -> LogFilter {
+    let (filter, log_filter) = LogFilter::reloadable();
 
     tracing_subscriber::registry()
         .with(filter)
@@ -18,6 +137,7 @@ pub fn init_logger() {
                 .with_ansi(true),
         )
         .init();
+    log_filter
 }
 
 /// Log macros with module prefixes and emojis
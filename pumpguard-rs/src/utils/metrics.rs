@@ -1,28 +1,56 @@
 //! Prometheus metrics service for PumpGuard
 
 use prometheus::{
-    Counter, CounterVec, Encoder, Gauge, GaugeVec, Histogram, HistogramOpts, HistogramVec, Opts,
-    Registry, TextEncoder,
+    Counter, CounterVec, Encoder, Gauge, GaugeVec, HistogramOpts, HistogramVec, IntCounter,
+    IntCounterVec, Opts, Registry, TextEncoder,
 };
+use parking_lot::RwLock;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 use tracing::info;
 
+/// Cumulative module counts mirrored into the persisted Prometheus counters
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CounterTotals {
+    pub tokens_detected: u64,
+    pub token_alerts: u64,
+    pub rugs_detected: u64,
+    pub accumulation_alerts: u64,
+    pub dump_alerts: u64,
+}
+
+/// Raise a counter that mirrors a cumulative module count up to `total`
+pub fn advance_counter(counter: &IntCounter, total: u64) {
+    let current = counter.get();
+    if total > current {
+        counter.inc_by(total - current);
+    }
+}
+
 /// Metrics service for Prometheus
 pub struct MetricsService {
     registry: Registry,
     start_time: Instant,
+    /// Counter values restored from the database, added to the live module counts
+    restored: Arc<RwLock<HashMap<&'static str, f64>>>,
 
     // Token Monitor metrics
     pub tokens_detected: Counter,
     pub token_alerts: Counter,
     pub pending_tokens: Gauge,
+    pub tokens_per_minute: Gauge,
 
     // Rug Detector metrics
     pub tokens_watched: Gauge,
     pub rugs_detected: CounterVec,
     pub suspicious_activity: CounterVec,
     pub suspicion_score: GaugeVec,
+    pub rpc_fetches_avoided: Gauge,
+    pub tokens_auto_unwatched: Gauge,
+    pub token_movements_evicted: Gauge,
+    pub safety_downgrades: IntCounter,
+    pub health_check_interval: Gauge,
 
     // Whale Watcher metrics
     pub whales_tracked: Gauge,
@@ -36,7 +64,30 @@ pub struct MetricsService {
     pub rpc_requests: CounterVec,
     pub rpc_latency: HistogramVec,
     pub websocket_connected: Gauge,
+    pub ws_parse_errors: IntCounter,
+    pub curve_verification_failures: Gauge,
+    pub rpc_encoding_fallbacks: Gauge,
+    pub token_cache_hits: IntCounter,
+    pub token_cache_misses: IntCounter,
+    pub avg_detection_lag: Gauge,
+    pub broadcast_channel_len: Gauge,
+    pub broadcast_receiver_count: Gauge,
+    pub dashboard_ws_clients: Gauge,
+    pub dashboard_ws_missed_alerts: Gauge,
+    pub rpc_endpoint_latency: GaugeVec,
+    pub rpc_endpoint_healthy: GaugeVec,
+    pub ws_connection_up: GaugeVec,
+    pub ws_connection_messages: IntCounterVec,
+    pub ws_connection_reconnects: IntCounterVec,
+    pub ws_duplicate_logs: Gauge,
     pub module_status: GaugeVec,
+    pub db_write_failures: Gauge,
+    pub db_dead_letters: Gauge,
+    pub db_size_bytes: Gauge,
+    pub db_tx_buffer_depth: Gauge,
+    pub db_tx_flush_seconds: Gauge,
+    pub analysis_queue_depth: GaugeVec,
+    pub tx_lost: IntCounterVec,
     pub uptime: Gauge,
 }
 
@@ -52,6 +103,11 @@ impl MetricsService {
             .unwrap();
         let pending_tokens = Gauge::new("pumpguard_tokens_tracked", "Tokens being tracked")
             .unwrap();
+        let tokens_per_minute = Gauge::new(
+            "pumpguard_tokens_per_minute",
+            "Tokens detected over the last minute",
+        )
+        .unwrap();
 
         // Rug Detector metrics
         let tokens_watched = Gauge::new("pumpguard_tokens_watched", "Tokens watched for rugs")
@@ -71,6 +127,31 @@ impl MetricsService {
             &["mint", "symbol"],
         )
         .unwrap();
+        let tokens_auto_unwatched = Gauge::new(
+            "pumpguard_tokens_auto_unwatched",
+            "Tokens dropped from the watch list after graduating or going inactive",
+        )
+        .unwrap();
+        let token_movements_evicted = Gauge::new(
+            "pumpguard_token_movements_evicted",
+            "Idle mints evicted from whale watcher movement tracking",
+        )
+        .unwrap();
+        let safety_downgrades = IntCounter::new(
+            "pumpguard_safety_downgrades_total",
+            "Safety downgrade alerts raised for tokens previously classified safe",
+        )
+        .unwrap();
+        let health_check_interval = Gauge::new(
+            "pumpguard_health_check_interval_seconds",
+            "Average adaptive health check interval across watched tokens",
+        )
+        .unwrap();
+        let rpc_fetches_avoided = Gauge::new(
+            "pumpguard_rpc_fetches_avoided",
+            "Transaction fetches skipped by the log pre-filter",
+        )
+        .unwrap();
 
         // Whale Watcher metrics
         let whales_tracked = Gauge::new("pumpguard_whales_tracked", "Whale wallets tracked")
@@ -115,21 +196,148 @@ impl MetricsService {
             "WebSocket connection status",
         )
         .unwrap();
+        let ws_parse_errors = IntCounter::new(
+            "pumpguard_ws_parse_errors_total",
+            "RPC WebSocket messages dropped as malformed or oversized",
+        )
+        .unwrap();
+        let curve_verification_failures = Gauge::new(
+            "pumpguard_curve_verification_failures",
+            "Bonding curve reads skipped as missing or not owned by the pump program",
+        )
+        .unwrap();
+        let rpc_encoding_fallbacks = Gauge::new(
+            "pumpguard_rpc_encoding_fallbacks",
+            "Transactions re-fetched in the fallback encoding after the RPC rejected the preferred one",
+        )
+        .unwrap();
+        let token_cache_hits = IntCounter::new(
+            "pumpguard_token_cache_hits_total",
+            "Token record lookups served from memory",
+        )
+        .unwrap();
+        let token_cache_misses = IntCounter::new(
+            "pumpguard_token_cache_misses_total",
+            "Token record lookups that went to SQLite",
+        )
+        .unwrap();
+        let avg_detection_lag = Gauge::new(
+            "pumpguard_avg_detection_lag_ms",
+            "Mean time between a token's creation block and its detection",
+        )
+        .unwrap();
+        let broadcast_channel_len = Gauge::new(
+            "pumpguard_broadcast_channel_len",
+            "Log events queued in the broadcast channel",
+        )
+        .unwrap();
+        let broadcast_receiver_count = Gauge::new(
+            "pumpguard_broadcast_receiver_count",
+            "Active log event subscribers",
+        )
+        .unwrap();
+        let dashboard_ws_clients = Gauge::new(
+            "pumpguard_dashboard_ws_clients",
+            "Connected dashboard WebSocket clients",
+        )
+        .unwrap();
+        let dashboard_ws_missed_alerts = Gauge::new(
+            "pumpguard_dashboard_ws_missed_alerts",
+            "Alerts dashboard WebSocket clients missed by lagging the alert channel",
+        )
+        .unwrap();
+        let rpc_endpoint_latency = GaugeVec::new(
+            Opts::new("pumpguard_rpc_endpoint_latency_ms", "RPC endpoint latency EMA"),
+            &["endpoint"],
+        )
+        .unwrap();
+        let rpc_endpoint_healthy = GaugeVec::new(
+            Opts::new("pumpguard_rpc_endpoint_healthy", "RPC endpoint health (1 = in rotation)"),
+            &["endpoint"],
+        )
+        .unwrap();
+        let ws_connection_up = GaugeVec::new(
+            Opts::new("pumpguard_ws_connection_up", "Log subscription connection state (1 = connected)"),
+            &["connection", "endpoint"],
+        )
+        .unwrap();
+        let ws_connection_messages = IntCounterVec::new(
+            Opts::new("pumpguard_ws_connection_messages_total", "Log notifications received per subscription connection"),
+            &["connection", "endpoint"],
+        )
+        .unwrap();
+        let ws_connection_reconnects = IntCounterVec::new(
+            Opts::new("pumpguard_ws_connection_reconnects_total", "Reconnects per log subscription connection"),
+            &["connection", "endpoint"],
+        )
+        .unwrap();
+        let ws_duplicate_logs = Gauge::new(
+            "pumpguard_ws_duplicate_logs",
+            "Log notifications dropped as already seen by signature",
+        )
+        .unwrap();
         let module_status = GaugeVec::new(
             Opts::new("pumpguard_module_running", "Module status"),
             &["module"],
         )
         .unwrap();
+        let db_write_failures = Gauge::new(
+            "pumpguard_db_write_failures",
+            "Database writes that failed after every retry",
+        )
+        .unwrap();
+        let db_dead_letters = Gauge::new(
+            "pumpguard_db_dead_letters",
+            "Failed database writes waiting to be replayed",
+        )
+        .unwrap();
+        let db_size_bytes = Gauge::new(
+            "pumpguard_db_size_bytes",
+            "Database size in bytes (page count x page size)",
+        )
+        .unwrap();
+        let db_tx_buffer_depth = Gauge::new(
+            "pumpguard_db_tx_buffer_depth",
+            "Transactions buffered for the next batched insert",
+        )
+        .unwrap();
+        let db_tx_flush_seconds = Gauge::new(
+            "pumpguard_db_tx_flush_seconds",
+            "Duration of the last batched transaction insert",
+        )
+        .unwrap();
+        let analysis_queue_depth = GaugeVec::new(
+            Opts::new(
+                "pumpguard_analysis_queue_depth",
+                "Matched transactions waiting for an analysis worker",
+            ),
+            &["module"],
+        )
+        .unwrap();
+        let tx_lost = IntCounterVec::new(
+            Opts::new(
+                "pumpguard_tx_lost_total",
+                "Matched transactions dropped because the fetch returned nothing or the parse failed",
+            ),
+            &["module", "reason"],
+        )
+        .unwrap();
         let uptime = Gauge::new("pumpguard_uptime_seconds", "Application uptime").unwrap();
 
         // Register all metrics
         registry.register(Box::new(tokens_detected.clone())).unwrap();
         registry.register(Box::new(token_alerts.clone())).unwrap();
         registry.register(Box::new(pending_tokens.clone())).unwrap();
+        registry.register(Box::new(tokens_per_minute.clone())).unwrap();
         registry.register(Box::new(tokens_watched.clone())).unwrap();
         registry.register(Box::new(rugs_detected.clone())).unwrap();
         registry.register(Box::new(suspicious_activity.clone())).unwrap();
         registry.register(Box::new(suspicion_score.clone())).unwrap();
+        registry.register(Box::new(rpc_fetches_avoided.clone())).unwrap();
+        registry.register(Box::new(tokens_auto_unwatched.clone())).unwrap();
+        registry.register(Box::new(token_movements_evicted.clone())).unwrap();
+        registry.register(Box::new(safety_downgrades.clone())).unwrap();
+        registry.register(Box::new(health_check_interval.clone())).unwrap();
         registry.register(Box::new(whales_tracked.clone())).unwrap();
         registry.register(Box::new(whale_transactions.clone())).unwrap();
         registry.register(Box::new(whale_volume.clone())).unwrap();
@@ -139,7 +347,30 @@ impl MetricsService {
         registry.register(Box::new(rpc_requests.clone())).unwrap();
         registry.register(Box::new(rpc_latency.clone())).unwrap();
         registry.register(Box::new(websocket_connected.clone())).unwrap();
+        registry.register(Box::new(ws_parse_errors.clone())).unwrap();
+        registry.register(Box::new(curve_verification_failures.clone())).unwrap();
+        registry.register(Box::new(rpc_encoding_fallbacks.clone())).unwrap();
+        registry.register(Box::new(token_cache_hits.clone())).unwrap();
+        registry.register(Box::new(token_cache_misses.clone())).unwrap();
+        registry.register(Box::new(avg_detection_lag.clone())).unwrap();
+        registry.register(Box::new(broadcast_channel_len.clone())).unwrap();
+        registry.register(Box::new(broadcast_receiver_count.clone())).unwrap();
+        registry.register(Box::new(dashboard_ws_clients.clone())).unwrap();
+        registry.register(Box::new(dashboard_ws_missed_alerts.clone())).unwrap();
+        registry.register(Box::new(rpc_endpoint_latency.clone())).unwrap();
+        registry.register(Box::new(rpc_endpoint_healthy.clone())).unwrap();
+        registry.register(Box::new(ws_connection_up.clone())).unwrap();
+        registry.register(Box::new(ws_connection_messages.clone())).unwrap();
+        registry.register(Box::new(ws_connection_reconnects.clone())).unwrap();
+        registry.register(Box::new(ws_duplicate_logs.clone())).unwrap();
         registry.register(Box::new(module_status.clone())).unwrap();
+        registry.register(Box::new(db_write_failures.clone())).unwrap();
+        registry.register(Box::new(db_dead_letters.clone())).unwrap();
+        registry.register(Box::new(db_size_bytes.clone())).unwrap();
+        registry.register(Box::new(db_tx_buffer_depth.clone())).unwrap();
+        registry.register(Box::new(db_tx_flush_seconds.clone())).unwrap();
+        registry.register(Box::new(analysis_queue_depth.clone())).unwrap();
+        registry.register(Box::new(tx_lost.clone())).unwrap();
         registry.register(Box::new(uptime.clone())).unwrap();
 
         info!(target: "METRICS", "Prometheus metrics initialized");
@@ -147,13 +378,20 @@ impl MetricsService {
         Self {
             registry,
             start_time: Instant::now(),
+            restored: Arc::new(RwLock::new(HashMap::new())),
             tokens_detected,
             token_alerts,
             pending_tokens,
+            tokens_per_minute,
             tokens_watched,
             rugs_detected,
             suspicious_activity,
             suspicion_score,
+            rpc_fetches_avoided,
+            tokens_auto_unwatched,
+            token_movements_evicted,
+            safety_downgrades,
+            health_check_interval,
             whales_tracked,
             whale_transactions,
             whale_volume,
@@ -163,27 +401,83 @@ impl MetricsService {
             rpc_requests,
             rpc_latency,
             websocket_connected,
+            ws_parse_errors,
+            curve_verification_failures,
+            rpc_encoding_fallbacks,
+            token_cache_hits,
+            token_cache_misses,
+            avg_detection_lag,
+            broadcast_channel_len,
+            broadcast_receiver_count,
+            dashboard_ws_clients,
+            dashboard_ws_missed_alerts,
+            rpc_endpoint_latency,
+            rpc_endpoint_healthy,
+            ws_connection_up,
+            ws_connection_messages,
+            ws_connection_reconnects,
+            ws_duplicate_logs,
             module_status,
+            db_write_failures,
+            db_dead_letters,
+            db_size_bytes,
+            db_tx_buffer_depth,
+            db_tx_flush_seconds,
+            analysis_queue_depth,
+            tx_lost,
             uptime,
         }
     }
 
-    /// Record token detected
-    pub fn record_token_detected(&self) {
-        self.tokens_detected.inc();
+    /// Counters that survive restarts, by the name they are saved under
+    fn persisted_counters(&self) -> [(&'static str, Counter); 5] {
+        [
+            ("tokens_detected", self.tokens_detected.clone()),
+            ("token_alerts", self.token_alerts.clone()),
+            // Every confirmed rug is alerted as critical
+            ("rugs_detected", self.rugs_detected.with_label_values(&["critical"])),
+            ("accumulation_alerts", self.accumulation_alerts.clone()),
+            ("dump_alerts", self.dump_alerts.clone()),
+        ]
     }
 
-    /// Record rug detected
-    pub fn record_rug_detected(&self, severity: &str) {
-        self.rugs_detected.with_label_values(&[severity]).inc();
+    /// Add saved totals to the counters; call once, before the first `sync_totals`
+    pub fn restore_counters(&self, saved: &HashMap<String, f64>) {
+        let mut restored = self.restored.write();
+        for (name, counter) in self.persisted_counters() {
+            if let Some(&value) = saved.get(name).filter(|v| **v > 0.0) {
+                counter.inc_by(value);
+                restored.insert(name, value);
+            }
+        }
+        info!(target: "METRICS", "Restored {} persisted counter(s)", restored.len());
+    }
+
+    /// Advance the persisted counters to the restored totals plus the live module counts
+    pub fn sync_totals(&self, totals: &CounterTotals) {
+        let restored = self.restored.read();
+        let live = [
+            totals.tokens_detected,
+            totals.token_alerts,
+            totals.rugs_detected,
+            totals.accumulation_alerts,
+            totals.dump_alerts,
+        ];
+        for ((name, counter), live) in self.persisted_counters().into_iter().zip(live) {
+            let target = restored.get(name).copied().unwrap_or(0.0) + live as f64;
+            let delta = target - counter.get();
+            if delta > 0.0 {
+                counter.inc_by(delta);
+            }
+        }
     }
 
-    /// Record whale transaction
-    pub fn record_whale_transaction(&self, tx_type: &str, volume_sol: f64) {
-        self.whale_transactions.with_label_values(&[tx_type]).inc();
-        self.whale_volume
-            .with_label_values(&[tx_type])
-            .inc_by(volume_sol);
+    /// Current values of the persisted counters, for saving
+    pub fn counter_values(&self) -> Vec<(&'static str, f64)> {
+        self.persisted_counters()
+            .into_iter()
+            .map(|(name, counter)| (name, counter.get()))
+            .collect()
     }
 
     /// Set module status
@@ -219,13 +513,20 @@ impl Clone for MetricsService {
         Self {
             registry: self.registry.clone(),
             start_time: self.start_time,
+            restored: Arc::clone(&self.restored),
             tokens_detected: self.tokens_detected.clone(),
             token_alerts: self.token_alerts.clone(),
             pending_tokens: self.pending_tokens.clone(),
+            tokens_per_minute: self.tokens_per_minute.clone(),
             tokens_watched: self.tokens_watched.clone(),
             rugs_detected: self.rugs_detected.clone(),
             suspicious_activity: self.suspicious_activity.clone(),
             suspicion_score: self.suspicion_score.clone(),
+            rpc_fetches_avoided: self.rpc_fetches_avoided.clone(),
+            tokens_auto_unwatched: self.tokens_auto_unwatched.clone(),
+            token_movements_evicted: self.token_movements_evicted.clone(),
+            safety_downgrades: self.safety_downgrades.clone(),
+            health_check_interval: self.health_check_interval.clone(),
             whales_tracked: self.whales_tracked.clone(),
             whale_transactions: self.whale_transactions.clone(),
             whale_volume: self.whale_volume.clone(),
@@ -235,10 +536,48 @@ impl Clone for MetricsService {
             rpc_requests: self.rpc_requests.clone(),
             rpc_latency: self.rpc_latency.clone(),
             websocket_connected: self.websocket_connected.clone(),
+            ws_parse_errors: self.ws_parse_errors.clone(),
+            curve_verification_failures: self.curve_verification_failures.clone(),
+            rpc_encoding_fallbacks: self.rpc_encoding_fallbacks.clone(),
+            token_cache_hits: self.token_cache_hits.clone(),
+            token_cache_misses: self.token_cache_misses.clone(),
+            avg_detection_lag: self.avg_detection_lag.clone(),
+            broadcast_channel_len: self.broadcast_channel_len.clone(),
+            broadcast_receiver_count: self.broadcast_receiver_count.clone(),
+            dashboard_ws_clients: self.dashboard_ws_clients.clone(),
+            dashboard_ws_missed_alerts: self.dashboard_ws_missed_alerts.clone(),
+            rpc_endpoint_latency: self.rpc_endpoint_latency.clone(),
+            rpc_endpoint_healthy: self.rpc_endpoint_healthy.clone(),
+            ws_connection_up: self.ws_connection_up.clone(),
+            ws_connection_messages: self.ws_connection_messages.clone(),
+            ws_connection_reconnects: self.ws_connection_reconnects.clone(),
+            ws_duplicate_logs: self.ws_duplicate_logs.clone(),
             module_status: self.module_status.clone(),
+            db_write_failures: self.db_write_failures.clone(),
+            db_dead_letters: self.db_dead_letters.clone(),
+            db_size_bytes: self.db_size_bytes.clone(),
+            db_tx_buffer_depth: self.db_tx_buffer_depth.clone(),
+            db_tx_flush_seconds: self.db_tx_flush_seconds.clone(),
+            analysis_queue_depth: self.analysis_queue_depth.clone(),
+            tx_lost: self.tx_lost.clone(),
             uptime: self.uptime.clone(),
         }
     }
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mirrored_counters_never_go_backwards() {
+        let counter = IntCounter::new("test_total", "test").unwrap();
+        advance_counter(&counter, 5);
+        advance_counter(&counter, 3);
+        assert_eq!(counter.get(), 5);
+        advance_counter(&counter, 8);
+        assert_eq!(counter.get(), 8);
+    }
+}
@@ -2,12 +2,21 @@
 
 pub mod alerts;
 pub mod database;
+pub mod error;
+pub mod format;
+pub mod leader;
 pub mod logger;
 pub mod metrics;
+pub mod preflight;
+pub mod run_flag;
 pub mod solana;
+pub mod tx_kind;
+pub mod work_queue;
 
 pub use alerts::AlertService;
 pub use database::DatabaseService;
+pub use error::PumpError;
+pub use leader::LeaderElection;
 pub use logger::init_logger;
 pub use metrics::MetricsService;
 pub use solana::SolanaService;
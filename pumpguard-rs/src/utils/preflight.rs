@@ -0,0 +1,171 @@
+//! Preflight - Startup self-check of configuration and connectivity
+
+use chrono::Utc;
+use serde::Serialize;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::config::{redact_url, Config};
+use crate::utils::{AlertService, DatabaseService, SolanaService};
+use crate::utils::solana::SubscriptionMode;
+
+/// Upper bound on each network check
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    /// Not configured, so nothing to check
+    Skipped,
+}
+
+/// One row of the preflight report
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    pub duration_ms: u64,
+}
+
+/// Result of a preflight run, also served at `/api/preflight`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightReport {
+    pub passed: bool,
+    pub checks: Vec<PreflightCheck>,
+    pub ran_at: String,
+}
+
+impl PreflightReport {
+    /// Log the report as a pass/fail table
+    pub fn log(&self) {
+        info!(target: "PREFLIGHT", "{:<10} {:<8} {:>7}  detail", "check", "status", "ms");
+        for check in &self.checks {
+            let status = match check.status {
+                CheckStatus::Pass => "PASS",
+                CheckStatus::Fail => "FAIL",
+                CheckStatus::Skipped => "SKIP",
+            };
+            let line = format!(
+                "{:<10} {:<8} {:>7}  {}",
+                check.name, status, check.duration_ms, check.detail
+            );
+            if check.status == CheckStatus::Fail {
+                warn!(target: "PREFLIGHT", "{}", line);
+            } else {
+                info!(target: "PREFLIGHT", "{}", line);
+            }
+        }
+        if self.passed {
+            info!(target: "PREFLIGHT", "✅ Preflight passed");
+        } else {
+            warn!(target: "PREFLIGHT", "❌ Preflight failed; fix the checks above");
+        }
+    }
+}
+
+/// Client errors echo the (normalized) request URL, query-string API key included,
+/// so blank out everything from a `?` up to the end of the URL
+fn scrub_query_strings(message: &str) -> String {
+    let mut scrubbed = String::with_capacity(message.len());
+    let mut in_query = false;
+    for c in message.chars() {
+        if in_query {
+            if c.is_whitespace() || c == ')' {
+                in_query = false;
+                scrubbed.push(c);
+            }
+        } else if c == '?' {
+            in_query = true;
+            scrubbed.push_str("?***");
+        } else {
+            scrubbed.push(c);
+        }
+    }
+    scrubbed
+}
+
+/// Run a check under `CHECK_TIMEOUT`; `Ok(None)` marks it skipped
+async fn timed<F>(name: &'static str, check: F) -> PreflightCheck
+where
+    F: Future<Output = Result<Option<String>, String>>,
+{
+    let started = Instant::now();
+    let (status, detail) = match tokio::time::timeout(CHECK_TIMEOUT, check).await {
+        Ok(Ok(Some(detail))) => (CheckStatus::Pass, detail),
+        Ok(Ok(None)) => (CheckStatus::Skipped, "not configured".to_string()),
+        Ok(Err(e)) => (CheckStatus::Fail, e),
+        Err(_) => (
+            CheckStatus::Fail,
+            format!("timed out after {}s", CHECK_TIMEOUT.as_secs()),
+        ),
+    };
+    PreflightCheck {
+        name,
+        status,
+        detail,
+        duration_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+/// Check RPC health, WebSocket connectivity, database writability and Telegram
+pub async fn run(
+    config: &Config,
+    solana: &SolanaService,
+    database: &DatabaseService,
+    alerts: &AlertService,
+) -> PreflightReport {
+    let rpc_url = redact_url(&config.rpc_url);
+    let ws_url = redact_url(&config.ws_url);
+
+    let rpc = timed("rpc", async {
+        solana
+            .client()
+            .get_health()
+            .await
+            .map(|()| Some(format!("{} healthy", rpc_url)))
+            .map_err(|e| scrub_query_strings(&format!("getHealth failed: {}", e)))
+    });
+
+    let ws = timed("websocket", async {
+        // Poll mode never opens the WebSocket
+        if config.subscription_mode == SubscriptionMode::Poll {
+            return Ok(None);
+        }
+        let (mut stream, _) = tokio_tungstenite::connect_async(config.ws_url.as_str())
+            .await
+            .map_err(|e| scrub_query_strings(&format!("connect failed: {}", e)))?;
+        let _ = stream.close(None).await;
+        Ok(Some(format!("{} connected", ws_url)))
+    });
+
+    let db = timed("database", async {
+        database
+            .check_writable()
+            .map(|()| Some("writable".to_string()))
+            .map_err(|e| format!("not writable: {}", e))
+    });
+
+    let telegram = timed("telegram", async {
+        match alerts.check_telegram().await {
+            Some(Ok(bot)) => Ok(Some(format!("bot {}", bot))),
+            Some(Err(e)) => Err(format!("getMe failed: {}", e)),
+            None => Ok(None),
+        }
+    });
+
+    let (rpc, ws, db, telegram) = tokio::join!(rpc, ws, db, telegram);
+    let checks = vec![rpc, ws, db, telegram];
+
+    PreflightReport {
+        passed: checks.iter().all(|c| c.status != CheckStatus::Fail),
+        checks,
+        ran_at: Utc::now().to_rfc3339(),
+    }
+}
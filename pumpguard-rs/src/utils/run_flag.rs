@@ -0,0 +1,68 @@
+//! Run Flag - Running state of a module's background loops across stop/start cycles
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Shared running state of a module. Each `start` opens a new run, and loops spawned
+/// for a run exit once it is over, even when the module was started again before they
+/// next woke up (a leader that lost and regained the lease, say).
+#[derive(Debug, Clone, Default)]
+pub struct RunFlag {
+    running: Arc<AtomicBool>,
+    generation: Arc<AtomicU64>,
+}
+
+impl RunFlag {
+    /// Open a new run; `None` when the module is already running
+    pub fn start(&self) -> Option<Run> {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return None;
+        }
+        Some(Run {
+            flag: self.clone(),
+            generation: self.generation.fetch_add(1, Ordering::SeqCst) + 1,
+        })
+    }
+
+    /// End the current run
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+}
+
+/// One run of a module, held by the loops spawned for it
+#[derive(Debug, Clone)]
+pub struct Run {
+    flag: RunFlag,
+    generation: u64,
+}
+
+impl Run {
+    /// Whether this run is still the module's current one
+    pub fn is_current(&self) -> bool {
+        self.flag.is_running() && self.flag.generation.load(Ordering::SeqCst) == self.generation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restart_ends_the_previous_run() {
+        let flag = RunFlag::default();
+        let first = flag.start().unwrap();
+        assert!(flag.start().is_none());
+        assert!(first.is_current());
+
+        flag.stop();
+        assert!(!first.is_current());
+        let second = flag.start().unwrap();
+        assert!(!first.is_current());
+        assert!(second.is_current());
+    }
+}
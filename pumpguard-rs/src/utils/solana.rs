@@ -2,20 +2,83 @@
 
 use anyhow::Result;
 use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
     nonblocking::rpc_client::RpcClient,
     rpc_config::RpcTransactionConfig,
+    rpc_client::GetConfirmedSignaturesForAddress2Config,
+    rpc_custom_error::JSON_RPC_SERVER_ERROR_UNSUPPORTED_TRANSACTION_VERSION,
+    rpc_request::RpcError,
 };
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     pubkey::Pubkey,
     signature::Signature,
 };
-use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta,
+    EncodedTransaction, UiMessage, UiTransactionEncoding,
+};
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU32, AtomicU64, Ordering};
 use std::{str::FromStr, sync::Arc};
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, watch};
 use tracing::{info, error, warn};
 
-use crate::config::Config;
+use crate::config::{redact_url, Config};
+use crate::utils::error::{PumpError, PumpResult};
+
+/// JSON-RPC "invalid params", which some providers return for an encoding they don't serve
+const JSON_RPC_INVALID_PARAMS: i64 = -32602;
+
+/// Encoding requested from `getTransaction`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxEncoding {
+    JsonParsed,
+    Json,
+    Base64,
+}
+
+impl TxEncoding {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "json_parsed" | "jsonparsed" => Some(Self::JsonParsed),
+            "json" => Some(Self::Json),
+            "base64" => Some(Self::Base64),
+            _ => None,
+        }
+    }
+
+    fn ui_encoding(self) -> UiTransactionEncoding {
+        match self {
+            Self::JsonParsed => UiTransactionEncoding::JsonParsed,
+            Self::Json => UiTransactionEncoding::Json,
+            Self::Base64 => UiTransactionEncoding::Base64,
+        }
+    }
+}
+
+/// How log events reach the modules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionMode {
+    /// `logsSubscribe` over the WebSocket connections
+    Ws,
+    /// `getSignaturesForAddress` on an interval, with logs fetched per signature
+    Poll,
+}
+
+impl SubscriptionMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "ws" | "websocket" => Some(Self::Ws),
+            "poll" | "polling" => Some(Self::Poll),
+            _ => None,
+        }
+    }
+}
 
 /// Log event from Solana WebSocket subscription
 #[derive(Debug, Clone)]
@@ -24,35 +87,734 @@ pub struct LogEvent {
     pub logs: Vec<String>,
 }
 
+/// Log event from a custom-watch program subscription, tagged with that program
+#[derive(Debug, Clone)]
+pub struct ProgramLogEvent {
+    pub program: String,
+    pub signature: String,
+    pub logs: Vec<String>,
+}
+
+/// SPL Token program, owner of every pump.fun token account
+const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// Associated Token Account program, used to derive the bonding curve's token account
+const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+
+/// Metaplex Token Metadata program, which keeps each mint's name and symbol in a PDA
+const TOKEN_METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
+/// Offset of the name in a metadata account: key (1), update authority (32), mint (32)
+const METADATA_NAME_OFFSET: usize = 65;
+
+/// Size of an SPL token account; the mint is at offset 0 and the amount at 64
+const TOKEN_ACCOUNT_SIZE: u64 = 165;
+
+/// pump.fun bonding curve virtual reserves at launch (SOL, whole tokens)
+const CURVE_INITIAL_VIRTUAL_SOL: f64 = 30.0;
+const CURVE_INITIAL_VIRTUAL_TOKENS: f64 = 1_073_000_000.0;
+
+/// Every pump.fun token mints a fixed supply of one billion
+pub const PUMP_TOKEN_SUPPLY: f64 = 1_000_000_000.0;
+
+/// Decimals of every pump.fun mint
+pub const PUMP_TOKEN_DECIMALS: u8 = 6;
+
+/// Offset of the `complete` flag in a bonding curve account: discriminator, then five u64 fields
+const CURVE_COMPLETE_OFFSET: usize = 48;
+
+/// Offset of the `creator` pubkey, right after `complete`; curves created before the
+/// field was added are shorter or leave it zeroed
+const CURVE_CREATOR_OFFSET: usize = 49;
+
+/// Smoothing factor for the per-endpoint latency EMA
+const LATENCY_EMA_ALPHA: f64 = 0.3;
+
+/// Recent log signatures remembered for dedup across subscription connections
+const SIGNATURE_DEDUP_CAPACITY: usize = 20_000;
+
+/// Transactions fetched at once for their logs while polling
+const POLL_FETCH_CONCURRENCY: usize = 8;
+
+/// RPC endpoint with rolling latency and health tracking
+pub struct RpcEndpoint {
+    pub url: String,
+    pub client: Arc<RpcClient>,
+    latency_ms: RwLock<Option<f64>>,
+    consecutive_failures: AtomicU32,
+}
+
+/// A bonding curve balance read for liquidity checks, with whether the account at the
+/// derived address really is a pump.fun curve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CurveBalance {
+    Verified(f64),
+    /// No account at the derived address, which reads as a zero balance
+    Missing,
+    /// The account exists but is owned by another program
+    WrongOwner(Pubkey),
+}
+
+/// A token account among a mint's largest holders
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenHolder {
+    pub address: String,
+    pub amount: f64,
+    /// Share of the fixed pump.fun supply
+    pub percent: f64,
+}
+
+/// Endpoint health snapshot for metrics/API
+#[derive(Debug, Clone, Serialize)]
+pub struct RpcEndpointStats {
+    pub endpoint: String,
+    pub latency_ms: Option<f64>,
+    pub consecutive_failures: u32,
+    pub healthy: bool,
+}
+
+/// Health of one log subscription connection
+struct WsConnection {
+    url: String,
+    connected: AtomicBool,
+    messages: AtomicU64,
+    reconnects: AtomicU64,
+    last_message_ms: AtomicI64,
+}
+
+/// Log subscription connection snapshot for metrics/API
+#[derive(Debug, Clone, Serialize)]
+pub struct WsConnectionStats {
+    pub connection: usize,
+    pub endpoint: String,
+    pub connected: bool,
+    pub messages: u64,
+    pub reconnects: u64,
+    pub last_message_age_secs: Option<f64>,
+}
+
+/// Signatures already forwarded to subscribers. Redundant connections deliver every
+/// log twice, and so does one connection for a tx mentioning several subscribed programs.
+struct SignatureDedup {
+    seen: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl SignatureDedup {
+    fn new() -> Self {
+        Self {
+            seen: HashSet::with_capacity(SIGNATURE_DEDUP_CAPACITY),
+            order: VecDeque::with_capacity(SIGNATURE_DEDUP_CAPACITY),
+        }
+    }
+
+    /// Record a signature; false when it was already seen
+    fn insert(&mut self, signature: &str) -> bool {
+        if !self.seen.insert(signature.to_string()) {
+            return false;
+        }
+        self.order.push_back(signature.to_string());
+        if self.order.len() > SIGNATURE_DEDUP_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+/// Matched events a module lost before analysis: the transaction never came back
+/// from RPC (`fetch_none`) or came back in a shape the parser couldn't use (`parse_none`)
+#[derive(Debug, Clone, Default)]
+pub struct TxLossStats {
+    fetch_none: Arc<AtomicU64>,
+    parse_none: Arc<AtomicU64>,
+}
+
+impl TxLossStats {
+    pub fn record_fetch_none(&self) {
+        self.fetch_none.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn record_parse_none(&self) {
+        self.parse_none.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn fetch_none(&self) -> u64 {
+        self.fetch_none.load(Ordering::SeqCst)
+    }
+
+    pub fn parse_none(&self) -> u64 {
+        self.parse_none.load(Ordering::SeqCst)
+    }
+}
+
+impl RpcEndpoint {
+    fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Arc::new(RpcClient::new_with_commitment(
+                url.to_string(),
+                CommitmentConfig::confirmed(),
+            )),
+            latency_ms: RwLock::new(None),
+            consecutive_failures: AtomicU32::new(0),
+        }
+    }
+
+    /// URL without query string, so API keys don't end up in metric labels
+    pub fn label(&self) -> String {
+        self.url.split('?').next().unwrap_or(&self.url).to_string()
+    }
+
+    fn record_success(&self, latency_ms: f64) {
+        let mut ema = self.latency_ms.write();
+        *ema = Some(match *ema {
+            Some(prev) => prev + LATENCY_EMA_ALPHA * (latency_ms - prev),
+            None => latency_ms,
+        });
+        self.consecutive_failures.store(0, Ordering::SeqCst);
+    }
+
+    fn record_failure(&self) -> u32 {
+        self.consecutive_failures.fetch_add(1, Ordering::SeqCst) + 1
+    }
+}
+
 /// Solana service for RPC interactions
 pub struct SolanaService {
-    pub client: Arc<RpcClient>,
+    endpoints: Arc<Vec<RpcEndpoint>>,
     pub pump_program_id: Pubkey,
     config: Config,
     log_sender: broadcast::Sender<LogEvent>,
+    last_log_ms: Arc<AtomicI64>,
+    ws_parse_errors: Arc<AtomicU64>,
+    curve_verification_failures: Arc<AtomicU64>,
+    encoding_fallbacks: Arc<AtomicU64>,
+    programs: watch::Sender<Vec<String>>,
+    ws_connections: Vec<Arc<WsConnection>>,
+    dedup: Arc<Mutex<SignatureDedup>>,
+    duplicate_logs: Arc<AtomicU64>,
+    /// Programs subscribed for the custom watcher only; their logs skip the pump modules
+    custom_programs: watch::Sender<Vec<String>>,
+    custom_log_sender: broadcast::Sender<ProgramLogEvent>,
+    custom_dedup: Arc<Mutex<SignatureDedup>>,
+    /// Set once the signature poller runs instead of the WebSocket subscriptions
+    polling: Arc<AtomicBool>,
+}
+
+/// Live `logsSubscribe` subscriptions on one WebSocket connection
+#[derive(Default)]
+struct LogSubscriptions {
+    next_request_id: u64,
+    /// Subscribe requests awaiting confirmation, by request id
+    pending: HashMap<u64, String>,
+    /// Confirmed subscription ids, by program
+    active: HashMap<String, u64>,
+}
+
+impl LogSubscriptions {
+    /// Program a notification's subscription id belongs to
+    fn program_for(&self, subscription_id: u64) -> Option<&str> {
+        self.active
+            .iter()
+            .find(|(_, id)| **id == subscription_id)
+            .map(|(program, _)| program.as_str())
+    }
+
+    fn next_id(&mut self) -> u64 {
+        self.next_request_id += 1;
+        self.next_request_id
+    }
+
+    fn subscribe_message(&mut self, program: &str) -> String {
+        let id = self.next_id();
+        self.pending.insert(id, program.to_string());
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "logsSubscribe",
+            "params": [
+                {"mentions": [program]},
+                {"commitment": "confirmed"}
+            ]
+        })
+        .to_string()
+    }
+
+    fn unsubscribe_message(&mut self, subscription_id: u64) -> String {
+        let id = self.next_id();
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "logsUnsubscribe",
+            "params": [subscription_id]
+        })
+        .to_string()
+    }
+
+    /// Messages needed to move from the current subscriptions to `desired`
+    fn reconcile(&mut self, desired: &[String]) -> Vec<String> {
+        let removed: Vec<(String, u64)> = self
+            .active
+            .iter()
+            .filter(|(program, _)| !desired.contains(program))
+            .map(|(program, id)| (program.clone(), *id))
+            .collect();
+
+        let mut messages = Vec::new();
+        for (program, subscription_id) in removed {
+            self.active.remove(&program);
+            messages.push(self.unsubscribe_message(subscription_id));
+        }
+        for program in desired {
+            let requested = self.pending.values().any(|p| p == program);
+            if !self.active.contains_key(program) && !requested {
+                messages.push(self.subscribe_message(program));
+            }
+        }
+        messages
+    }
+}
+
+/// Feeds the log channels from `getSignaturesForAddress` instead of `logsSubscribe`, for
+/// providers whose WebSocket is missing or unreliable. Each new signature costs a
+/// `getTransaction` for its logs.
+#[derive(Clone)]
+struct LogPoller {
+    endpoints: Arc<Vec<RpcEndpoint>>,
+    max_failures: u32,
+    interval: std::time::Duration,
+    signature_limit: usize,
+    programs: watch::Receiver<Vec<String>>,
+    custom_programs: watch::Receiver<Vec<String>>,
+    sender: broadcast::Sender<LogEvent>,
+    custom_sender: broadcast::Sender<ProgramLogEvent>,
+    dedup: Arc<Mutex<SignatureDedup>>,
+    custom_dedup: Arc<Mutex<SignatureDedup>>,
+    duplicate_logs: Arc<AtomicU64>,
+    last_log_ms: Arc<AtomicI64>,
+    active: Arc<AtomicBool>,
+}
+
+impl LogPoller {
+    /// Spawn the poll loop unless it is already running; once started it runs until restart
+    fn start(&self) {
+        if self.active.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        info!(
+            target: "SOLANA",
+            "Polling program signatures every {}ms",
+            self.interval.as_millis()
+        );
+
+        let poller = self.clone();
+        tokio::spawn(async move {
+            // Newest signature seen per program; each poll only asks for what came after it
+            let mut cursors: HashMap<String, Signature> = HashMap::new();
+            let mut interval = tokio::time::interval(poller.interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                interval.tick().await;
+                let monitored = poller.programs.borrow().clone();
+                let custom = poller.custom_programs.borrow().clone();
+                let programs = SolanaService::subscribed_programs(&monitored, &custom);
+                cursors.retain(|program, _| programs.contains(program));
+
+                for program in programs {
+                    let until = cursors.get(&program).copied();
+                    let to_modules = monitored.contains(&program);
+                    let to_custom = custom.contains(&program);
+                    match poller.poll_program(&program, until, to_modules, to_custom).await {
+                        Ok(Some(cursor)) => {
+                            cursors.insert(program, cursor);
+                        }
+                        Ok(None) => {}
+                        Err(e) => warn!(
+                            target: "SOLANA",
+                            "Failed to poll signatures for {}: {}",
+                            program,
+                            e
+                        ),
+                    }
+                }
+            }
+        });
+    }
+
+    /// Forward the program's successful transactions newer than `until`, oldest first, and
+    /// return the new cursor. The first poll of a program only sets the cursor; there is
+    /// no backfill.
+    async fn poll_program(
+        &self,
+        program: &str,
+        until: Option<Signature>,
+        to_modules: bool,
+        to_custom: bool,
+    ) -> PumpResult<Option<Signature>> {
+        use futures_util::StreamExt;
+
+        let address = SolanaService::parse_pubkey(program)?;
+        let client = SolanaService::best_client(&self.endpoints, self.max_failures);
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before: None,
+            until,
+            limit: Some(self.signature_limit),
+            commitment: Some(CommitmentConfig::confirmed()),
+        };
+        let statuses = client.get_signatures_for_address_with_config(&address, config).await?;
+
+        let newest = statuses
+            .first()
+            .and_then(|status| Signature::from_str(&status.signature).ok())
+            .or(until);
+        if until.is_none() {
+            return Ok(newest);
+        }
+        if statuses.len() >= self.signature_limit {
+            warn!(
+                target: "SOLANA",
+                "Poll for {} hit the {} signature limit; older signatures were skipped",
+                program,
+                self.signature_limit
+            );
+        }
+
+        // Claim signatures before fetching so one another source already delivered
+        // doesn't cost a getTransaction
+        let pending: Vec<(String, bool, bool)> = statuses
+            .iter()
+            .rev()
+            .filter(|status| status.err.is_none())
+            .filter_map(|status| {
+                let signature = &status.signature;
+                let to_modules = to_modules && {
+                    let fresh = self.dedup.lock().insert(signature);
+                    if !fresh {
+                        self.duplicate_logs.fetch_add(1, Ordering::SeqCst);
+                    }
+                    fresh
+                };
+                let to_custom = to_custom
+                    && self.custom_dedup.lock().insert(&format!("{}:{}", program, signature));
+                (to_modules || to_custom).then(|| (signature.clone(), to_modules, to_custom))
+            })
+            .collect();
+
+        let total = pending.len();
+        let client = &client;
+        let fetched: Vec<_> = futures_util::stream::iter(pending)
+            .map(|(signature, to_modules, to_custom)| async move {
+                let logs = Self::fetch_logs(client, &signature).await;
+                (signature, to_modules, to_custom, logs)
+            })
+            .buffered(POLL_FETCH_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut failed = 0;
+        for (signature, to_modules, to_custom, logs) in fetched {
+            let Some(logs) = logs else {
+                failed += 1;
+                continue;
+            };
+            self.last_log_ms.store(chrono::Utc::now().timestamp_millis(), Ordering::SeqCst);
+            if to_custom {
+                let _ = self.custom_sender.send(ProgramLogEvent {
+                    program: program.to_string(),
+                    signature: signature.clone(),
+                    logs: logs.clone(),
+                });
+            }
+            if to_modules {
+                let _ = self.sender.send(LogEvent { signature, logs });
+            }
+        }
+        if failed > 0 {
+            warn!(
+                target: "SOLANA",
+                "Failed to fetch logs for {} of {} polled signature(s) of {}",
+                failed,
+                total,
+                program
+            );
+        }
+        Ok(newest)
+    }
+
+    /// Log messages of a confirmed transaction; they are the same in every encoding, so
+    /// the smallest one is requested
+    async fn fetch_logs(client: &RpcClient, signature: &str) -> Option<Vec<String>> {
+        let signature = Signature::from_str(signature).ok()?;
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        };
+        let tx = client.get_transaction_with_config(&signature, config).await.ok()?;
+        match tx.transaction.meta?.log_messages {
+            OptionSerializer::Some(logs) => Some(logs),
+            _ => None,
+        }
+    }
 }
 
 impl SolanaService {
     /// Create a new Solana service
     pub fn new(config: Config) -> Self {
-        let client = Arc::new(RpcClient::new_with_commitment(
-            config.rpc_url.clone(),
-            CommitmentConfig::confirmed(),
-        ));
+        let endpoints = Arc::new(
+            config
+                .rpc_urls
+                .iter()
+                .map(|url| RpcEndpoint::new(url))
+                .collect::<Vec<_>>(),
+        );
 
         let pump_program_id = Pubkey::from_str(&config.pump_program_id)
             .expect("Invalid pump program ID");
 
+        let ws_connections = (0..config.ws_redundancy.max(1))
+            .map(|index| {
+                Arc::new(WsConnection {
+                    url: config.ws_urls[index % config.ws_urls.len()].clone(),
+                    connected: AtomicBool::new(false),
+                    messages: AtomicU64::new(0),
+                    reconnects: AtomicU64::new(0),
+                    last_message_ms: AtomicI64::new(0),
+                })
+            })
+            .collect();
+
         let (log_sender, _) = broadcast::channel(10000);
+        let (programs, _) = watch::channel(vec![pump_program_id.to_string()]);
+        let (custom_programs, _) = watch::channel(Vec::new());
+        let (custom_log_sender, _) = broadcast::channel(1000);
 
-        info!(target: "SOLANA", "Connected to Solana RPC (monitor-only mode)");
+        info!(
+            target: "SOLANA",
+            "Connected to Solana RPC (monitor-only mode) - {} endpoint(s)",
+            endpoints.len()
+        );
 
         Self {
-            client,
+            endpoints,
             pump_program_id,
             config,
             log_sender,
+            last_log_ms: Arc::new(AtomicI64::new(0)),
+            ws_parse_errors: Arc::new(AtomicU64::new(0)),
+            curve_verification_failures: Arc::new(AtomicU64::new(0)),
+            encoding_fallbacks: Arc::new(AtomicU64::new(0)),
+            programs,
+            ws_connections,
+            dedup: Arc::new(Mutex::new(SignatureDedup::new())),
+            duplicate_logs: Arc::new(AtomicU64::new(0)),
+            custom_programs,
+            custom_log_sender,
+            custom_dedup: Arc::new(Mutex::new(SignatureDedup::new())),
+            polling: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Programs whose logs are subscribed to
+    pub fn monitored_programs(&self) -> Vec<String> {
+        self.programs.borrow().clone()
+    }
+
+    /// Replace the monitored program set; the live WebSocket re-subscribes in place
+    pub fn set_monitored_programs(&self, programs: Vec<String>) -> PumpResult<Vec<String>> {
+        let mut validated = Vec::with_capacity(programs.len());
+        for program in programs {
+            let program = Self::parse_pubkey(&program)?.to_string();
+            if !validated.contains(&program) {
+                validated.push(program);
+            }
+        }
+        if validated.is_empty() {
+            return Err(PumpError::InvalidInput(
+                "At least one program ID is required".to_string(),
+            ));
+        }
+
+        info!(target: "SOLANA", "Monitored programs updated: {:?}", validated);
+        self.programs.send_replace(validated.clone());
+        Ok(validated)
+    }
+
+    /// Programs subscribed for the custom watcher
+    pub fn custom_programs(&self) -> Vec<String> {
+        self.custom_programs.borrow().clone()
+    }
+
+    /// Replace the custom watcher's program set; unlike the monitored set it may be empty
+    pub fn set_custom_programs(&self, programs: Vec<String>) -> PumpResult<Vec<String>> {
+        let mut validated = Vec::with_capacity(programs.len());
+        for program in programs {
+            let program = Self::parse_pubkey(&program)?.to_string();
+            if !validated.contains(&program) {
+                validated.push(program);
+            }
         }
+
+        if *self.custom_programs.borrow() != validated {
+            info!(target: "SOLANA", "Custom watch programs updated: {:?}", validated);
+            self.custom_programs.send_replace(validated.clone());
+        }
+        Ok(validated)
+    }
+
+    /// Get a receiver for logs of the custom watch programs
+    pub fn subscribe_custom_logs(&self) -> broadcast::Receiver<ProgramLogEvent> {
+        self.custom_log_sender.subscribe()
+    }
+
+    /// Every program a connection subscribes to: the monitored set plus custom watch programs
+    fn subscribed_programs(monitored: &[String], custom: &[String]) -> Vec<String> {
+        let mut programs = monitored.to_vec();
+        for program in custom {
+            if !programs.contains(program) {
+                programs.push(program.clone());
+            }
+        }
+        programs
+    }
+
+    /// Seconds since the last log event was forwarded (None if none received yet)
+    pub fn last_log_age_secs(&self) -> Option<f64> {
+        let last = self.last_log_ms.load(Ordering::SeqCst);
+        if last == 0 {
+            return None;
+        }
+        Some((chrono::Utc::now().timestamp_millis() - last).max(0) as f64 / 1000.0)
+    }
+
+    /// Client for the fastest healthy endpoint (falls back to the least-failing one)
+    pub fn client(&self) -> Arc<RpcClient> {
+        Self::best_client(&self.endpoints, self.config.rpc_max_failures)
+    }
+
+    fn best_client(endpoints: &[RpcEndpoint], max_failures: u32) -> Arc<RpcClient> {
+        let healthy = endpoints
+            .iter()
+            .filter(|e| e.consecutive_failures.load(Ordering::SeqCst) < max_failures)
+            .min_by(|a, b| {
+                let a = a.latency_ms.read().unwrap_or(f64::MAX);
+                let b = b.latency_ms.read().unwrap_or(f64::MAX);
+                a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+            });
+
+        let endpoint = healthy.or_else(|| {
+            endpoints
+                .iter()
+                .min_by_key(|e| e.consecutive_failures.load(Ordering::SeqCst))
+        });
+
+        Arc::clone(&endpoint.expect("at least one RPC endpoint configured").client)
+    }
+
+    /// Current latency/health for every configured endpoint
+    pub fn endpoint_stats(&self) -> Vec<RpcEndpointStats> {
+        let max_failures = self.config.rpc_max_failures;
+        self.endpoints
+            .iter()
+            .map(|e| {
+                let failures = e.consecutive_failures.load(Ordering::SeqCst);
+                RpcEndpointStats {
+                    endpoint: e.label(),
+                    latency_ms: *e.latency_ms.read(),
+                    consecutive_failures: failures,
+                    healthy: failures < max_failures,
+                }
+            })
+            .collect()
+    }
+
+    /// Periodically measure `getSlot` latency on every endpoint. Demoted endpoints
+    /// keep being probed so they are promoted again once they recover.
+    pub fn start_endpoint_probes(&self) {
+        let endpoints = Arc::clone(&self.endpoints);
+        let interval_secs = self.config.rpc_probe_interval_secs.max(1);
+        let max_failures = self.config.rpc_max_failures;
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(interval_secs));
+            loop {
+                interval.tick().await;
+
+                for endpoint in endpoints.iter() {
+                    let started = std::time::Instant::now();
+                    match endpoint.client.get_slot().await {
+                        Ok(_) => {
+                            let was_demoted =
+                                endpoint.consecutive_failures.load(Ordering::SeqCst) >= max_failures;
+                            endpoint.record_success(started.elapsed().as_secs_f64() * 1000.0);
+                            if was_demoted {
+                                info!(target: "SOLANA", "RPC endpoint recovered: {}", endpoint.label());
+                            }
+                        }
+                        Err(e) => {
+                            if endpoint.record_failure() == max_failures {
+                                warn!(
+                                    target: "SOLANA",
+                                    "RPC endpoint demoted after {} failures: {} ({})",
+                                    max_failures,
+                                    endpoint.label(),
+                                    e
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// WebSocket messages dropped as malformed, non-UTF-8 or oversized
+    pub fn ws_parse_errors(&self) -> u64 {
+        self.ws_parse_errors.load(Ordering::SeqCst)
+    }
+
+    /// Bonding curve reads whose account was missing or not owned by the pump program
+    pub fn curve_verification_failures(&self) -> u64 {
+        self.curve_verification_failures.load(Ordering::SeqCst)
+    }
+
+    /// Transactions re-fetched in the fallback encoding after the RPC rejected the preferred one
+    pub fn encoding_fallbacks(&self) -> u64 {
+        self.encoding_fallbacks.load(Ordering::SeqCst)
+    }
+
+    /// Duplicate log events dropped by signature dedup
+    pub fn duplicate_logs(&self) -> u64 {
+        self.duplicate_logs.load(Ordering::SeqCst)
+    }
+
+    /// Current health of every log subscription connection
+    pub fn ws_connection_stats(&self) -> Vec<WsConnectionStats> {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        self.ws_connections
+            .iter()
+            .enumerate()
+            .map(|(index, connection)| {
+                let last = connection.last_message_ms.load(Ordering::SeqCst);
+                WsConnectionStats {
+                    connection: index,
+                    endpoint: redact_url(&connection.url),
+                    connected: connection.connected.load(Ordering::SeqCst),
+                    messages: connection.messages.load(Ordering::SeqCst),
+                    reconnects: connection.reconnects.load(Ordering::SeqCst),
+                    last_message_age_secs: (last > 0).then(|| (now_ms - last).max(0) as f64 / 1000.0),
+                }
+            })
+            .collect()
+    }
+
+    /// Log events queued in the broadcast channel and the number of subscribers
+    pub fn log_channel_stats(&self) -> (usize, usize) {
+        (self.log_sender.len(), self.log_sender.receiver_count())
     }
 
     /// Get a receiver for log events
@@ -60,112 +822,296 @@ impl SolanaService {
         self.log_sender.subscribe()
     }
 
-    /// Start the WebSocket log subscription
+    /// Where log events currently come from; `Poll` also once the WebSocket fell back
+    pub fn subscription_mode(&self) -> SubscriptionMode {
+        if self.polling.load(Ordering::SeqCst) {
+            SubscriptionMode::Poll
+        } else {
+            SubscriptionMode::Ws
+        }
+    }
+
+    fn log_poller(&self) -> LogPoller {
+        LogPoller {
+            endpoints: Arc::clone(&self.endpoints),
+            max_failures: self.config.rpc_max_failures,
+            interval: std::time::Duration::from_millis(self.config.poll_interval_ms.max(100)),
+            signature_limit: self.config.poll_signature_limit.clamp(1, 1000),
+            programs: self.programs.subscribe(),
+            custom_programs: self.custom_programs.subscribe(),
+            sender: self.log_sender.clone(),
+            custom_sender: self.custom_log_sender.clone(),
+            dedup: Arc::clone(&self.dedup),
+            custom_dedup: Arc::clone(&self.custom_dedup),
+            duplicate_logs: Arc::clone(&self.duplicate_logs),
+            last_log_ms: Arc::clone(&self.last_log_ms),
+            active: Arc::clone(&self.polling),
+        }
+    }
+
+    /// Start the log feed: one WebSocket subscription per configured connection, or the
+    /// signature poller in poll mode
     pub async fn start_log_subscription(&self) -> Result<()> {
+        if self.config.subscription_mode == SubscriptionMode::Poll {
+            self.log_poller().start();
+            return Ok(());
+        }
+        if self.ws_connections.len() > 1 {
+            info!(target: "SOLANA", "Starting {} redundant log subscriptions", self.ws_connections.len());
+        }
+        for (index, connection) in self.ws_connections.iter().enumerate() {
+            self.spawn_log_connection(index, Arc::clone(connection));
+        }
+        Ok(())
+    }
+
+    /// Keep one log subscription connected, reconnecting with backoff
+    fn spawn_log_connection(&self, index: usize, connection: Arc<WsConnection>) {
         use futures_util::{SinkExt, StreamExt};
-        use tokio_tungstenite::{connect_async, tungstenite::Message};
-        use std::sync::atomic::{AtomicU64, Ordering};
+        use tokio_tungstenite::connect_async_with_config;
+        use tokio_tungstenite::tungstenite::{protocol::WebSocketConfig, Error as WsError, Message};
 
-        let ws_url = self.config.ws_url.clone();
-        let program_id = self.pump_program_id.to_string();
+        let ws_url = connection.url.clone();
+        let mut programs_rx = self.programs.subscribe();
+        let mut custom_rx = self.custom_programs.subscribe();
         let sender = self.log_sender.clone();
+        let custom_sender = self.custom_log_sender.clone();
+        let custom_dedup = Arc::clone(&self.custom_dedup);
+        let last_log_ms = Arc::clone(&self.last_log_ms);
+        let ws_parse_errors = Arc::clone(&self.ws_parse_errors);
+        let dedup = Arc::clone(&self.dedup);
+        let duplicate_logs = Arc::clone(&self.duplicate_logs);
+        let poller = self.log_poller();
+        let connections = self.ws_connections.clone();
+        let fallback_after = self.config.ws_poll_fallback_failures;
+
+        // Cap message size so a misbehaving RPC can't make us buffer unbounded payloads
+        let max_message_bytes = self.config.ws_max_message_bytes;
+        let ws_config = WebSocketConfig {
+            max_message_size: Some(max_message_bytes),
+            max_frame_size: Some(max_message_bytes),
+            ..Default::default()
+        };
+
+        let initial_delay = self.config.ws_reconnect_initial_secs.max(0.1);
+        let multiplier = self.config.ws_reconnect_multiplier.max(1.0);
+        let max_delay = self.config.ws_reconnect_max_secs.max(initial_delay);
+        let stable_after = std::time::Duration::from_secs(self.config.ws_reconnect_stable_secs);
 
         // Spawn WebSocket connection handler
         tokio::spawn(async move {
-            let mut reconnect_delay = 5;
-            let message_count = Arc::new(AtomicU64::new(0));
+            let mut reconnect_delay = initial_delay;
+            let mut connect_failures = 0;
 
             loop {
-                match connect_async(&ws_url).await {
+                if poller.active.load(Ordering::SeqCst) {
+                    info!(target: "SOLANA", "WebSocket {} stopped, logs are polled instead", index);
+                    return;
+                }
+
+                match connect_async_with_config(&ws_url, Some(ws_config), false).await {
                     Ok((ws_stream, _)) => {
-                        info!(target: "SOLANA", "WebSocket connected to {}", ws_url);
-                        reconnect_delay = 5; // Reset delay on successful connection
+                        info!(target: "SOLANA", "WebSocket {} connected to {}", index, redact_url(&ws_url));
+                        connection.connected.store(true, Ordering::SeqCst);
+                        connect_failures = 0;
+                        let connected_at = std::time::Instant::now();
 
                         let (mut write, mut read) = ws_stream.split();
 
-                        // Subscribe to program logs
-                        let subscribe_msg = serde_json::json!({
-                            "jsonrpc": "2.0",
-                            "id": 1,
-                            "method": "logsSubscribe",
-                            "params": [
-                                {"mentions": [program_id]},
-                                {"commitment": "confirmed"}
-                            ]
-                        });
-
-                        if let Err(e) = write.send(Message::Text(subscribe_msg.to_string())).await {
-                            error!(target: "SOLANA", "Failed to send subscribe message: {}", e);
+                        // Subscribe to program logs (one subscription per program)
+                        let mut subscriptions = LogSubscriptions::default();
+                        let desired = Self::subscribed_programs(
+                            &programs_rx.borrow_and_update(),
+                            &custom_rx.borrow_and_update(),
+                        );
+                        let mut subscribe_failed = false;
+                        for msg in subscriptions.reconcile(&desired) {
+                            if let Err(e) = write.send(Message::Text(msg)).await {
+                                error!(target: "SOLANA", "Failed to send subscribe message: {}", e);
+                                subscribe_failed = true;
+                                break;
+                            }
+                        }
+                        if subscribe_failed {
+                            connection.connected.store(false, Ordering::SeqCst);
+                            connection.reconnects.fetch_add(1, Ordering::SeqCst);
                             continue;
                         }
 
-                        info!(target: "SOLANA", "Subscribed to pump.fun program logs");
+                        info!(target: "SOLANA", "Subscribed to logs for {} program(s)", desired.len());
 
                         // Keepalive ping task
-                        let msg_count = Arc::clone(&message_count);
+                        let ping_connection = Arc::clone(&connection);
                         let ping_task = tokio::spawn(async move {
                             let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(30));
                             loop {
                                 interval.tick().await;
-                                let count = msg_count.load(Ordering::SeqCst);
-                                info!(target: "SOLANA", "WebSocket keepalive - {} messages received", count);
+                                let count = ping_connection.messages.load(Ordering::SeqCst);
+                                info!(target: "SOLANA", "WebSocket {} keepalive - {} messages received", index, count);
                             }
                         });
 
                         // Message handling loop
                         let mut last_message_time = std::time::Instant::now();
                         
+                        let mut programs_open = true;
+                        let mut custom_open = true;
+
                         loop {
                             // Use timeout to detect stale connections
-                            let msg = tokio::time::timeout(
-                                tokio::time::Duration::from_secs(120), // 2 minute timeout
-                                read.next()
-                            ).await;
+                            let msg = tokio::select! {
+                                msg = tokio::time::timeout(
+                                    tokio::time::Duration::from_secs(120), // 2 minute timeout
+                                    read.next()
+                                ) => msg,
+                                changed = programs_rx.changed(), if programs_open => {
+                                    if changed.is_err() {
+                                        programs_open = false;
+                                        continue;
+                                    }
+                                    // Logs for changed programs can be missed until the
+                                    // new subscription is confirmed; there is no backfill
+                                    let desired = Self::subscribed_programs(
+                                        &programs_rx.borrow_and_update(),
+                                        &custom_rx.borrow(),
+                                    );
+                                    for msg in subscriptions.reconcile(&desired) {
+                                        if let Err(e) = write.send(Message::Text(msg)).await {
+                                            error!(target: "SOLANA", "Failed to update subscriptions: {}", e);
+                                        }
+                                    }
+                                    continue;
+                                }
+                                changed = custom_rx.changed(), if custom_open => {
+                                    if changed.is_err() {
+                                        custom_open = false;
+                                        continue;
+                                    }
+                                    let desired = Self::subscribed_programs(
+                                        &programs_rx.borrow(),
+                                        &custom_rx.borrow_and_update(),
+                                    );
+                                    for msg in subscriptions.reconcile(&desired) {
+                                        if let Err(e) = write.send(Message::Text(msg)).await {
+                                            error!(target: "SOLANA", "Failed to update subscriptions: {}", e);
+                                        }
+                                    }
+                                    continue;
+                                }
+                            };
 
                             match msg {
-                                Ok(Some(Ok(Message::Text(text)))) => {
+                                Ok(Some(Ok(frame @ (Message::Text(_) | Message::Binary(_))))) => {
                                     last_message_time = std::time::Instant::now();
-                                    
-                                    if let Ok(json) = serde_json::from_str::<serde_json::Value>(&text) {
-                                        // Check for subscription confirmation
-                                        if json.get("result").is_some() {
-                                            info!(target: "SOLANA", "Subscription confirmed");
+
+                                    // Binary frames are accepted when they carry UTF-8 JSON
+                                    let text = match frame.into_text() {
+                                        Ok(text) => text,
+                                        Err(_) => {
+                                            ws_parse_errors.fetch_add(1, Ordering::SeqCst);
+                                            warn!(target: "SOLANA", "Dropping non-UTF-8 binary WebSocket frame");
                                             continue;
                                         }
-                                        
-                                        // Check for errors
-                                        if let Some(error) = json.get("error") {
-                                            error!(target: "SOLANA", "RPC error: {:?}", error);
+                                    };
+
+                                    let json = match serde_json::from_str::<serde_json::Value>(&text) {
+                                        Ok(json) => json,
+                                        Err(e) => {
+                                            ws_parse_errors.fetch_add(1, Ordering::SeqCst);
+                                            let preview: String = text.chars().take(120).collect();
+                                            warn!(target: "SOLANA", "Malformed WebSocket message ({}): {}", e, preview);
                                             continue;
                                         }
+                                    };
+
+                                    // Check for subscribe/unsubscribe confirmations
+                                    if let Some(result) = json.get("result") {
+                                        let request_id = json.get("id").and_then(|id| id.as_u64());
+                                        let program = request_id.and_then(|id| subscriptions.pending.remove(&id));
+                                        if let (Some(program), Some(subscription_id)) = (program, result.as_u64()) {
+                                            info!(target: "SOLANA", "Subscription confirmed for {}", program);
+                                            if programs_rx.borrow().contains(&program)
+                                                || custom_rx.borrow().contains(&program)
+                                            {
+                                                subscriptions.active.insert(program, subscription_id);
+                                            } else {
+                                                // Program was removed while the subscribe was in flight
+                                                let msg = subscriptions.unsubscribe_message(subscription_id);
+                                                let _ = write.send(Message::Text(msg)).await;
+                                            }
+                                        }
+                                        continue;
+                                    }
+                                    
+                                    // Check for errors
+                                    if let Some(error) = json.get("error") {
+                                        error!(target: "SOLANA", "RPC error: {:?}", error);
+                                        continue;
+                                    }
+
+                                    let program = json
+                                        .get("params")
+                                        .and_then(|p| p.get("subscription"))
+                                        .and_then(|id| id.as_u64())
+                                        .and_then(|id| subscriptions.program_for(id))
+                                        .map(String::from);
+
+                                    if let Some(result) = json.get("params").and_then(|p| p.get("result")) {
+                                        if let Some(value) = result.get("value") {
+                                            let signature = value
+                                                .get("signature")
+                                                .and_then(|s| s.as_str())
+                                                .unwrap_or("")
+                                                .to_string();
 
-                                        if let Some(result) = json.get("params").and_then(|p| p.get("result")) {
-                                            if let Some(value) = result.get("value") {
-                                                let signature = value
-                                                    .get("signature")
-                                                    .and_then(|s| s.as_str())
-                                                    .unwrap_or("")
-                                                    .to_string();
-
-                                                let logs: Vec<String> = value
-                                                    .get("logs")
-                                                    .and_then(|l| l.as_array())
-                                                    .map(|arr| {
-                                                        arr.iter()
-                                                            .filter_map(|v| v.as_str().map(String::from))
-                                                            .collect()
-                                                    })
-                                                    .unwrap_or_default();
-
-                                                if !signature.is_empty() {
-                                                    message_count.fetch_add(1, Ordering::SeqCst);
+                                            let logs: Vec<String> = value
+                                                .get("logs")
+                                                .and_then(|l| l.as_array())
+                                                .map(|arr| {
+                                                    arr.iter()
+                                                        .filter_map(|v| v.as_str().map(String::from))
+                                                        .collect()
+                                                })
+                                                .unwrap_or_default();
+
+                                            if !signature.is_empty() {
+                                                let now_ms = chrono::Utc::now().timestamp_millis();
+                                                connection.messages.fetch_add(1, Ordering::SeqCst);
+                                                connection.last_message_ms.store(now_ms, Ordering::SeqCst);
+                                                last_log_ms.store(now_ms, Ordering::SeqCst);
+
+                                                // Custom watch programs stay out of the pump modules'
+                                                // feed unless they are monitored programs too
+                                                if let Some(program) = program
+                                                    .as_ref()
+                                                    .filter(|p| custom_rx.borrow().contains(p))
+                                                {
+                                                    let key = format!("{}:{}", program, signature);
+                                                    if custom_dedup.lock().insert(&key) {
+                                                        let _ = custom_sender.send(ProgramLogEvent {
+                                                            program: program.clone(),
+                                                            signature: signature.clone(),
+                                                            logs: logs.clone(),
+                                                        });
+                                                    }
+                                                }
+                                                let monitored = program
+                                                    .as_ref()
+                                                    .is_none_or(|p| programs_rx.borrow().contains(p));
+                                                if !monitored {
+                                                    continue;
+                                                }
+
+                                                if dedup.lock().insert(&signature) {
                                                     let _ = sender.send(LogEvent { signature, logs });
+                                                } else {
+                                                    duplicate_logs.fetch_add(1, Ordering::SeqCst);
                                                 }
                                             }
                                         }
                                     }
                                 }
-                                Ok(Some(Ok(Message::Ping(data)))) => {
+                                Ok(Some(Ok(Message::Ping(_)))) => {
                                     last_message_time = std::time::Instant::now();
                                     // Note: pong is sent via write half, but we're in read half
                                     // Most WS implementations auto-respond to pings
@@ -177,6 +1123,16 @@ impl SolanaService {
                                     warn!(target: "SOLANA", "WebSocket closed by server: {:?}", frame);
                                     break;
                                 }
+                                Ok(Some(Err(WsError::Capacity(e)))) => {
+                                    // The stream can't resume past an oversized frame
+                                    ws_parse_errors.fetch_add(1, Ordering::SeqCst);
+                                    warn!(
+                                        target: "SOLANA",
+                                        "Dropped oversized WebSocket message ({}; limit {} bytes), reconnecting",
+                                        e, max_message_bytes
+                                    );
+                                    break;
+                                }
                                 Ok(Some(Err(e))) => {
                                     error!(target: "SOLANA", "WebSocket error: {}", e);
                                     break;
@@ -185,74 +1141,320 @@ impl SolanaService {
                                     warn!(target: "SOLANA", "WebSocket stream ended");
                                     break;
                                 }
-                                Err(_) => {
-                                    // Timeout - check if connection is stale
-                                    if last_message_time.elapsed() > tokio::time::Duration::from_secs(120) {
-                                        warn!(target: "SOLANA", "WebSocket connection stale (no messages for 2 min), reconnecting...");
-                                        break;
-                                    }
+                                Err(_) if last_message_time.elapsed() > tokio::time::Duration::from_secs(120) => {
+                                    // Timeout - connection is stale
+                                    warn!(target: "SOLANA", "WebSocket connection stale (no messages for 2 min), reconnecting...");
+                                    break;
                                 }
                                 _ => {}
                             }
                         }
 
                         ping_task.abort();
+                        connection.connected.store(false, Ordering::SeqCst);
+
+                        // Only reset backoff once the connection proved stable, so a
+                        // flapping endpoint doesn't drop us back to the floor every time
+                        if connected_at.elapsed() >= stable_after {
+                            reconnect_delay = initial_delay;
+                        }
                     }
                     Err(e) => {
-                        error!(target: "SOLANA", "Failed to connect WebSocket: {}", e);
+                        error!(target: "SOLANA", "Failed to connect WebSocket {}: {}", index, e);
+                        connect_failures += 1;
+
+                        // Only when no other connection is up, so one bad endpoint among
+                        // redundant ones doesn't switch everything to polling
+                        let all_down = connections
+                            .iter()
+                            .all(|other| !other.connected.load(Ordering::SeqCst));
+                        if fallback_after > 0 && connect_failures >= fallback_after && all_down {
+                            warn!(
+                                target: "SOLANA",
+                                "WebSocket {} failed to connect {} times, falling back to polling",
+                                index,
+                                connect_failures
+                            );
+                            poller.start();
+                            return;
+                        }
                     }
                 }
 
-                // Wait before reconnecting with exponential backoff (max 60s)
-                info!(target: "SOLANA", "Reconnecting WebSocket in {} seconds...", reconnect_delay);
-                tokio::time::sleep(tokio::time::Duration::from_secs(reconnect_delay)).await;
-                reconnect_delay = (reconnect_delay * 2).min(60);
+                // Wait before reconnecting with jittered exponential backoff
+                connection.reconnects.fetch_add(1, Ordering::SeqCst);
+                let delay = Self::jittered_delay(reconnect_delay);
+                info!(target: "SOLANA", "Reconnecting WebSocket {} in {:.1} seconds...", index, delay);
+                tokio::time::sleep(tokio::time::Duration::from_secs_f64(delay)).await;
+                reconnect_delay = (reconnect_delay * multiplier).min(max_delay);
             }
         });
+    }
 
-        Ok(())
+    /// Apply +/-20% jitter so instances sharing an RPC don't reconnect in lockstep
+    fn jittered_delay(delay_secs: f64) -> f64 {
+        use rand::Rng;
+        delay_secs * rand::thread_rng().gen_range(0.8..=1.2)
+    }
+
+    fn parse_pubkey(value: &str) -> PumpResult<Pubkey> {
+        Pubkey::from_str(value.trim())
+            .map_err(|e| PumpError::InvalidInput(format!("{}: {}", value, e)))
     }
 
     /// Get account balance in SOL
-    pub async fn get_balance(&self, pubkey: &str) -> Result<f64> {
-        let pubkey = Pubkey::from_str(pubkey)?;
-        let balance = self.client.get_balance(&pubkey).await?;
+    pub async fn get_balance(&self, pubkey: &str) -> PumpResult<f64> {
+        let pubkey = Self::parse_pubkey(pubkey)?;
+        let balance = self.client().get_balance(&pubkey).await?;
         Ok(balance as f64 / 1_000_000_000.0)
     }
 
-    /// Get a parsed transaction by signature with retry logic
-    pub async fn get_transaction(&self, signature: &str) -> Result<Option<EncodedConfirmedTransactionWithStatusMeta>> {
-        let sig = Signature::from_str(signature)?;
+    /// Balance of the mint's bonding curve in SOL, checking the derived account exists and
+    /// belongs to the pump program. A derivation that no longer matches (e.g. after a program
+    /// upgrade) would otherwise read as a drained curve.
+    pub async fn get_curve_balance(&self, mint: &Pubkey) -> PumpResult<CurveBalance> {
+        let bonding_curve = self.derive_bonding_curve(mint);
+        let client = self.client();
+        let account = client
+            .get_account_with_commitment(&bonding_curve, client.commitment())
+            .await?
+            .value;
+
+        let balance = match account {
+            Some(account) if account.owner == self.pump_program_id => {
+                return Ok(CurveBalance::Verified(account.lamports as f64 / 1_000_000_000.0));
+            }
+            Some(account) => CurveBalance::WrongOwner(account.owner),
+            None => CurveBalance::Missing,
+        };
+        self.curve_verification_failures.fetch_add(1, Ordering::SeqCst);
+        Ok(balance)
+    }
+
+    /// Whether the token's bonding curve has completed, i.e. the token graduated to an AMM pool
+    pub async fn is_curve_complete(&self, mint: &str) -> PumpResult<bool> {
+        let mint = Self::parse_pubkey(mint)?;
+        let bonding_curve = self.derive_bonding_curve(&mint);
+        let data = self.client().get_account_data(&bonding_curve).await?;
+        Ok(data.get(CURVE_COMPLETE_OFFSET).is_some_and(|flag| *flag != 0))
+    }
+
+    /// Creator recorded on the token's bonding curve, `None` when the curve predates the field
+    pub async fn get_curve_creator(&self, mint: &str) -> PumpResult<Option<String>> {
+        let mint = Self::parse_pubkey(mint)?;
+        let bonding_curve = self.derive_bonding_curve(&mint);
+        let data = self.client().get_account_data(&bonding_curve).await?;
+        let creator = data
+            .get(CURVE_CREATOR_OFFSET..CURVE_CREATOR_OFFSET + 32)
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .filter(|bytes| bytes.iter().any(|b| *b != 0))
+            .map(|bytes| Pubkey::new_from_array(bytes).to_string());
+        Ok(creator)
+    }
+
+    /// Name and symbol from the mint's Metaplex metadata account; `None` when it has none
+    pub async fn get_token_metadata(&self, mint: &str) -> PumpResult<Option<(String, String)>> {
+        let mint = Self::parse_pubkey(mint)?;
+        let program = Pubkey::from_str(TOKEN_METADATA_PROGRAM_ID).expect("valid token metadata program ID");
+        let seeds = &[b"metadata", program.as_ref(), mint.as_ref()];
+        let (metadata, _) = Pubkey::find_program_address(seeds, &program);
+
+        let account = self
+            .client()
+            .get_account_with_commitment(&metadata, CommitmentConfig::confirmed())
+            .await?
+            .value;
+        let Some(account) = account else {
+            return Ok(None);
+        };
+
+        let mut offset = METADATA_NAME_OFFSET;
+        let name = Self::read_borsh_string(&account.data, &mut offset);
+        let symbol = Self::read_borsh_string(&account.data, &mut offset);
+        Ok(name.zip(symbol))
+    }
+
+    /// Read a length-prefixed string; Metaplex pads names with NULs to a fixed width
+    fn read_borsh_string(data: &[u8], offset: &mut usize) -> Option<String> {
+        let len_bytes = data.get(*offset..*offset + 4)?;
+        let len = u32::from_le_bytes(len_bytes.try_into().ok()?) as usize;
+        let bytes = data.get(*offset + 4..*offset + 4 + len)?;
+        *offset += 4 + len;
+        Some(String::from_utf8_lossy(bytes).trim_end_matches('\0').trim().to_string())
+    }
+
+    /// Count token accounts holding a non-zero balance of `mint`. Uses
+    /// getProgramAccounts, which some public RPCs disable or rate limit heavily.
+    pub async fn get_holder_count(&self, mint: &str) -> PumpResult<u64> {
+        use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
+        use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+        use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+
+        let mint = Self::parse_pubkey(mint)?;
+        let token_program = Pubkey::from_str(TOKEN_PROGRAM_ID).expect("valid token program ID");
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![
+                RpcFilterType::DataSize(TOKEN_ACCOUNT_SIZE),
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &mint.to_bytes())),
+            ]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                // Only fetch the 8-byte amount
+                data_slice: Some(UiDataSliceConfig { offset: 64, length: 8 }),
+                commitment: Some(CommitmentConfig::confirmed()),
+                min_context_slot: None,
+            },
+            with_context: None,
+        };
+
+        let accounts = self
+            .client()
+            .get_program_accounts_with_config(&token_program, config)
+            .await?;
+
+        let holders = accounts
+            .iter()
+            .filter(|(_, account)| {
+                <[u8; 8]>::try_from(account.data.as_slice())
+                    .map(|amount| u64::from_le_bytes(amount) > 0)
+                    .unwrap_or(false)
+            })
+            .count();
+        Ok(holders as u64)
+    }
+
+    /// Total supply of `mint` in UI units (decimals applied), from getTokenSupply
+    pub async fn get_token_supply(&self, mint: &str) -> PumpResult<f64> {
+        let mint_pubkey = Self::parse_pubkey(mint)?;
+        let supply = self.client().get_token_supply(&mint_pubkey).await?;
+        supply
+            .ui_amount
+            .or_else(|| supply.ui_amount_string.parse().ok())
+            .ok_or_else(|| PumpError::InvalidInput(format!("Unreadable supply for {}", mint)))
+    }
+
+    /// Largest token accounts for `mint`, biggest first, excluding the bonding curve's own
+    /// account (which holds the unsold supply). Each entry is a token account, not its owner.
+    pub async fn get_top_holders(&self, mint: &str, limit: usize) -> PumpResult<Vec<TokenHolder>> {
+        let mint = Self::parse_pubkey(mint)?;
+        let curve_account = self.derive_curve_token_account(&mint).to_string();
+        let accounts = self.client().get_token_largest_accounts(&mint).await?;
+
+        let mut holders: Vec<TokenHolder> = accounts
+            .into_iter()
+            .filter(|account| account.address != curve_account)
+            .filter_map(|account| {
+                let amount = account.amount.ui_amount?;
+                (amount > 0.0).then(|| TokenHolder {
+                    address: account.address,
+                    amount,
+                    percent: amount / PUMP_TOKEN_SUPPLY * 100.0,
+                })
+            })
+            .collect();
+        holders.sort_by(|a, b| b.amount.total_cmp(&a.amount));
+        holders.truncate(limit);
+        Ok(holders)
+    }
+
+    /// Get a transaction by signature in `RPC_TX_ENCODING`, retrying once in
+    /// `RPC_TX_FALLBACK_ENCODING` when the node can't serve the preferred encoding
+    pub async fn get_transaction(&self, signature: &str) -> PumpResult<Option<EncodedConfirmedTransactionWithStatusMeta>> {
+        let sig = Signature::from_str(signature)
+            .map_err(|e| PumpError::InvalidInput(format!("{}: {}", signature, e)))?;
+        let preferred = self.config.rpc_tx_encoding;
+
+        let error = match self.fetch_transaction(&sig, preferred).await? {
+            Ok(tx) => return Ok(Some(tx)),
+            Err(e) => e,
+        };
+        if !Self::is_encoding_incompatibility(&error) {
+            warn!(target: "SOLANA", "Failed to get transaction {}: {}", signature, error);
+            return Ok(None);
+        }
+
+        let fallback = match self.config.rpc_tx_fallback_encoding.filter(|f| *f != preferred) {
+            Some(fallback) => fallback,
+            None => {
+                warn!(
+                    target: "SOLANA",
+                    "RPC can't return transaction {} as {:?} and no fallback encoding is set: {}",
+                    signature,
+                    preferred,
+                    error
+                );
+                return Ok(None);
+            }
+        };
+        self.encoding_fallbacks.fetch_add(1, Ordering::SeqCst);
+        warn!(
+            target: "SOLANA",
+            "RPC can't return transaction {} as {:?}, retrying as {:?}: {}",
+            signature,
+            preferred,
+            fallback,
+            error
+        );
+        match self.fetch_transaction(&sig, fallback).await? {
+            Ok(tx) => Ok(Some(tx)),
+            Err(e) => {
+                warn!(
+                    target: "SOLANA",
+                    "Failed to get transaction {} as {:?}: {}",
+                    signature,
+                    fallback,
+                    e
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// One `getTransaction` call with exponential backoff while rate limited. The outer error
+    /// means the retries ran out; the inner one is any other RPC failure.
+    async fn fetch_transaction(
+        &self,
+        sig: &Signature,
+        encoding: TxEncoding,
+    ) -> PumpResult<Result<EncodedConfirmedTransactionWithStatusMeta, ClientError>> {
         let config = RpcTransactionConfig {
-            encoding: Some(UiTransactionEncoding::JsonParsed),
+            encoding: Some(encoding.ui_encoding()),
             commitment: Some(CommitmentConfig::confirmed()),
             max_supported_transaction_version: Some(0),
         };
 
-        // Retry with exponential backoff for rate limiting
         let mut attempts = 0;
         let max_attempts = 3;
         let mut delay_ms = 500;
 
         loop {
-            match self.client.get_transaction_with_config(&sig, config.clone()).await {
-                Ok(tx) => return Ok(Some(tx)),
-                Err(e) => {
-                    let error_str = e.to_string();
-                    
-                    // Check if rate limited (429)
-                    if error_str.contains("429") && attempts < max_attempts {
-                        attempts += 1;
-                        warn!(target: "SOLANA", "Rate limited, retrying in {}ms (attempt {}/{})", delay_ms, attempts, max_attempts);
-                        tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
-                        delay_ms *= 2; // Exponential backoff
-                        continue;
+            match self.client().get_transaction_with_config(sig, config).await {
+                Ok(tx) => return Ok(Ok(tx)),
+                Err(e) if e.to_string().contains("429") => {
+                    if attempts >= max_attempts {
+                        return Err(PumpError::RateLimited);
                     }
-                    
-                    warn!(target: "SOLANA", "Failed to get transaction {}: {}", signature, e);
-                    return Ok(None);
+                    attempts += 1;
+                    warn!(target: "SOLANA", "Rate limited, retrying in {}ms (attempt {}/{})", delay_ms, attempts, max_attempts);
+                    tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
+                    delay_ms *= 2; // Exponential backoff
                 }
+                Err(e) => return Ok(Err(e)),
+            }
+        }
+    }
+
+    /// The node rejected the requested encoding or transaction version, or answered in a
+    /// shape the client couldn't deserialize
+    fn is_encoding_incompatibility(error: &ClientError) -> bool {
+        match error.kind() {
+            ClientErrorKind::SerdeJson(_) | ClientErrorKind::RpcError(RpcError::ParseError(_)) => true,
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code, message, .. }) => {
+                *code == JSON_RPC_SERVER_ERROR_UNSUPPORTED_TRANSACTION_VERSION
+                    || *code == JSON_RPC_INVALID_PARAMS
+                    || message.to_ascii_lowercase().contains("encoding")
             }
+            _ => false,
         }
     }
 
@@ -270,5 +1472,246 @@ impl SolanaService {
         let (pda, _) = Pubkey::find_program_address(seeds, &self.pump_program_id);
         pda
     }
+
+    /// Associated token account of the bonding curve PDA for `mint`
+    fn derive_curve_token_account(&self, mint: &Pubkey) -> Pubkey {
+        let curve = self.derive_bonding_curve(mint);
+        let token_program = Pubkey::from_str(TOKEN_PROGRAM_ID).expect("valid token program ID");
+        let ata_program =
+            Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID).expect("valid associated token program ID");
+        let seeds = &[curve.as_ref(), token_program.as_ref(), mint.as_ref()];
+        let (ata, _) = Pubkey::find_program_address(seeds, &ata_program);
+        ata
+    }
+
+    /// Static account keys of a fetched transaction in any encoding, fee payer first.
+    /// Lookup-table addresses are in `meta.loaded_addresses`, not here.
+    pub fn account_keys(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Vec<String> {
+        match &tx.transaction.transaction {
+            EncodedTransaction::Json(ui_tx) => match &ui_tx.message {
+                UiMessage::Parsed(msg) => msg.account_keys.iter().map(|k| k.pubkey.clone()).collect(),
+                UiMessage::Raw(msg) => msg.account_keys.clone(),
+            },
+            encoded => encoded
+                .decode()
+                .map(|decoded| {
+                    decoded.message.static_account_keys().iter().map(|k| k.to_string()).collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// First signature of a fetched transaction in any encoding, or empty if undecodable
+    pub fn signature_of(tx: &EncodedConfirmedTransactionWithStatusMeta) -> String {
+        match &tx.transaction.transaction {
+            EncodedTransaction::Json(ui_tx) => ui_tx.signatures.first().cloned().unwrap_or_default(),
+            encoded => encoded
+                .decode()
+                .and_then(|decoded| decoded.signatures.first().map(|s| s.to_string()))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Whether a fetched transaction failed on chain; its balance deltas must not be counted
+    pub fn transaction_failed(tx: &EncodedConfirmedTransactionWithStatusMeta) -> bool {
+        tx.transaction.meta.as_ref().is_some_and(|meta| meta.err.is_some())
+    }
+
+    /// Implied market cap in SOL from the SOL held by a bonding curve.
+    /// The curve is constant-product over virtual reserves, so price = virtual_sol^2 / k.
+    pub fn implied_market_cap_sol(curve_balance_sol: f64) -> f64 {
+        let k = CURVE_INITIAL_VIRTUAL_SOL * CURVE_INITIAL_VIRTUAL_TOKENS;
+        let virtual_sol = CURVE_INITIAL_VIRTUAL_SOL + curve_balance_sol.max(0.0);
+        virtual_sol * virtual_sol / k * PUMP_TOKEN_SUPPLY
+    }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::SinkExt;
+    use solana_sdk::transaction::Transaction;
+    use tokio_tungstenite::tungstenite::Message;
+
+    /// Log subscription against a local WebSocket server that sends `frames` and stays open
+    async fn subscribe_to(frames: Vec<Message>) -> (SolanaService, broadcast::Receiver<LogEvent>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            for frame in frames {
+                if ws.send(frame).await.is_err() {
+                    break;
+                }
+            }
+            std::future::pending::<()>().await;
+        });
+
+        let mut config = Config::for_tests();
+        config.ws_url = ws_url.clone();
+        config.ws_urls = vec![ws_url];
+        config.ws_redundancy = 1;
+        config.subscription_mode = SubscriptionMode::Ws;
+        config.ws_max_message_bytes = 1024;
+        let solana = SolanaService::new(config);
+        let logs = solana.subscribe_logs();
+        solana.start_log_subscription().await.unwrap();
+        (solana, logs)
+    }
+
+    #[tokio::test]
+    async fn malformed_and_non_utf8_frames_are_counted_and_skipped() {
+        let notification = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "logsNotification",
+            "params": {
+                "result": { "value": { "signature": "sig1", "logs": ["Program log: ok"] } },
+            },
+        })
+        .to_string();
+        let (solana, mut logs) = subscribe_to(vec![
+            Message::Text("{\"jsonrpc\": \"2.0\", \"params\"".to_string()),
+            Message::Binary(vec![0xff, 0xfe, 0x00]),
+            // UTF-8 JSON in a binary frame is read like a text frame
+            Message::Binary(notification.into_bytes()),
+        ])
+        .await;
+
+        let event = tokio::time::timeout(std::time::Duration::from_secs(5), logs.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.signature, "sig1");
+        assert_eq!(event.logs, vec!["Program log: ok".to_string()]);
+        assert_eq!(solana.ws_parse_errors(), 2);
+    }
+
+    #[tokio::test]
+    async fn oversized_messages_are_dropped() {
+        let (solana, _logs) = subscribe_to(vec![Message::Text("x".repeat(4096))]).await;
+
+        let dropped = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while solana.ws_parse_errors() == 0 {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        });
+        assert!(dropped.await.is_ok());
+    }
+
+    /// A one-instruction transaction with a known fee payer and signature
+    fn sample_transaction() -> (Transaction, Pubkey, Signature) {
+        use solana_sdk::instruction::{AccountMeta, Instruction};
+
+        let payer = Pubkey::new_unique();
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[1, 2, 3],
+            vec![AccountMeta::new(Pubkey::new_unique(), false)],
+        );
+        let mut tx = Transaction::new_with_payer(&[instruction], Some(&payer));
+        let signature = Signature::from([7; 64]);
+        tx.signatures = vec![signature];
+        (tx, payer, signature)
+    }
+
+    fn fetched(
+        tx: &Transaction,
+        encoding: UiTransactionEncoding,
+    ) -> EncodedConfirmedTransactionWithStatusMeta {
+        use solana_transaction_status::{Encodable, EncodedTransactionWithStatusMeta};
+
+        EncodedConfirmedTransactionWithStatusMeta {
+            slot: 1,
+            transaction: EncodedTransactionWithStatusMeta {
+                transaction: tx.encode(encoding),
+                meta: None,
+                version: None,
+            },
+            block_time: None,
+        }
+    }
+
+    #[test]
+    fn account_keys_and_signature_are_read_from_every_encoding() {
+        let (tx, payer, signature) = sample_transaction();
+        for encoding in [
+            UiTransactionEncoding::JsonParsed,
+            UiTransactionEncoding::Json,
+            UiTransactionEncoding::Base64,
+        ] {
+            let fetched = fetched(&tx, encoding);
+            let keys = SolanaService::account_keys(&fetched);
+            assert_eq!(keys.len(), 3, "{:?}", encoding);
+            assert_eq!(keys[0], payer.to_string(), "{:?}", encoding);
+            assert_eq!(SolanaService::signature_of(&fetched), signature.to_string());
+        }
+    }
+
+    /// RPC node that rejects `jsonParsed` as invalid params and serves any other encoding
+    async fn json_parsed_rejecting_rpc(tx: Transaction) -> String {
+        use axum::{routing::post, Json, Router};
+        use serde_json::{json, Value};
+
+        let app = Router::new().route(
+            "/",
+            post(move |Json(request): Json<Value>| async move {
+                let encoding = request["params"][1]["encoding"].as_str().unwrap_or("json");
+                let result = match (request["method"].as_str(), encoding) {
+                    (Some("getVersion"), _) => json!({ "solana-core": "1.18.0" }),
+                    (Some("getTransaction"), "jsonParsed") => {
+                        let error = json!({ "code": -32602, "message": "Invalid params" });
+                        let id = &request["id"];
+                        return Json(json!({ "jsonrpc": "2.0", "id": id, "error": error }));
+                    }
+                    (Some("getTransaction"), "base64") => {
+                        serde_json::to_value(fetched(&tx, UiTransactionEncoding::Base64)).unwrap()
+                    }
+                    (Some("getTransaction"), _) => {
+                        serde_json::to_value(fetched(&tx, UiTransactionEncoding::Json)).unwrap()
+                    }
+                    _ => Value::Null,
+                };
+                Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        url
+    }
+
+    #[tokio::test]
+    async fn rejected_encoding_is_retried_in_the_fallback() {
+        let (tx, payer, signature) = sample_transaction();
+        let rpc_url = json_parsed_rejecting_rpc(tx).await;
+        let solana = |preferred: TxEncoding, fallback: Option<TxEncoding>| {
+            let mut config = Config::for_tests();
+            config.rpc_url = rpc_url.clone();
+            config.rpc_urls = vec![rpc_url.clone()];
+            config.rpc_tx_encoding = preferred;
+            config.rpc_tx_fallback_encoding = fallback;
+            SolanaService::new(config)
+        };
+        let signature = signature.to_string();
+
+        for fallback in [TxEncoding::Base64, TxEncoding::Json] {
+            let fallback_solana = solana(TxEncoding::JsonParsed, Some(fallback));
+            let fetched = fallback_solana.get_transaction(&signature).await.unwrap().unwrap();
+            assert_eq!(SolanaService::account_keys(&fetched)[0], payer.to_string());
+            assert_eq!(SolanaService::signature_of(&fetched), signature);
+            assert_eq!(fallback_solana.encoding_fallbacks(), 1);
+        }
+
+        // Without a fallback the transaction is dropped
+        let no_fallback = solana(TxEncoding::JsonParsed, None);
+        assert!(no_fallback.get_transaction(&signature).await.unwrap().is_none());
+        assert_eq!(no_fallback.encoding_fallbacks(), 0);
+
+        // A preferred encoding the node serves needs no retry
+        let raw = solana(TxEncoding::Json, Some(TxEncoding::Base64));
+        assert!(raw.get_transaction(&signature).await.unwrap().is_some());
+        assert_eq!(raw.encoding_fallbacks(), 0);
+    }
+}
@@ -0,0 +1,227 @@
+//! Transaction Kind - Typed classification of transactions from their program logs
+//!
+//! All log matching lives here so a program upgrade that renames an instruction
+//! only needs updating in one place.
+
+use serde::{Deserialize, Serialize};
+
+/// SPL Token-2022 program; its transfers are reported separately from classic SPL ones
+const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// What a transaction (or one of its instructions) did
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxKind {
+    /// pump.fun token launch
+    Create,
+    /// Bonding curve or mint initialization, usually part of a launch
+    Initialize,
+    Buy,
+    Sell,
+    /// LP withdrawal, liquidity removal or curve migration
+    RemoveLiquidity,
+    CreatePool,
+    AddLiquidity,
+    /// Program parameter update, e.g. fee or curve settings
+    SetParams,
+    Token2022Transfer,
+    Unknown,
+}
+
+impl TxKind {
+    /// Kinds ordered by how much they matter when a transaction has several
+    const PRIORITY: [TxKind; 9] = [
+        TxKind::Create,
+        TxKind::Initialize,
+        TxKind::RemoveLiquidity,
+        TxKind::CreatePool,
+        TxKind::AddLiquidity,
+        TxKind::SetParams,
+        TxKind::Sell,
+        TxKind::Buy,
+        TxKind::Token2022Transfer,
+    ];
+
+    /// Classify one log line; `program` is the program currently executing, if known
+    pub fn from_log(line: &str, program: Option<&str>) -> Option<TxKind> {
+        // Some programs log plain snake_case markers rather than an instruction name
+        if line.contains("withdraw") || line.contains("remove_liquidity") || line.contains("migrate") {
+            return Some(TxKind::RemoveLiquidity);
+        }
+        if line.contains("create_pool") {
+            return Some(TxKind::CreatePool);
+        }
+
+        let name = line.strip_prefix("Program log: Instruction: ")?.trim();
+        let kind = match name {
+            "Create" => TxKind::Create,
+            "Initialize" | "InitializeMint" | "InitializeMint2" => TxKind::Initialize,
+            "Buy" => TxKind::Buy,
+            "Sell" => TxKind::Sell,
+            "Withdraw" | "Migrate" | "RemoveLiquidity" => TxKind::RemoveLiquidity,
+            "CreatePool" => TxKind::CreatePool,
+            "Deposit" | "AddLiquidity" => TxKind::AddLiquidity,
+            "SetParams" => TxKind::SetParams,
+            "Transfer" | "TransferChecked" if program == Some(TOKEN_2022_PROGRAM_ID) => {
+                TxKind::Token2022Transfer
+            }
+            _ => return None,
+        };
+        Some(kind)
+    }
+}
+
+/// Which instruction marks a launch for the token monitor; transactions carrying only
+/// the other one are used to fill in liquidity for an already-detected token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchInstruction {
+    Create,
+    Initialize,
+}
+
+impl LaunchInstruction {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "create" => Some(Self::Create),
+            "initialize" => Some(Self::Initialize),
+            _ => None,
+        }
+    }
+
+    /// The kind that is detected as a launch
+    pub fn launch_kind(self) -> TxKind {
+        match self {
+            Self::Create => TxKind::Create,
+            Self::Initialize => TxKind::Initialize,
+        }
+    }
+
+    /// The kind that only enriches a launch
+    pub fn enrich_kind(self) -> TxKind {
+        match self {
+            Self::Create => TxKind::Initialize,
+            Self::Initialize => TxKind::Create,
+        }
+    }
+}
+
+/// One recognized log line and the program that was executing when it was logged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClassifiedLog<'a> {
+    pub line: &'a str,
+    pub program: Option<&'a str>,
+    pub kind: TxKind,
+}
+
+/// Every recognized log line in order, attributing each to the innermost invoked program
+pub fn classify_logs(logs: &[String]) -> Vec<ClassifiedLog<'_>> {
+    let mut invoke_stack: Vec<&str> = Vec::new();
+    let mut classified = Vec::new();
+
+    for log in logs {
+        if let Some(rest) = log.strip_prefix("Program ") {
+            let mut parts = rest.split_whitespace();
+            match (parts.next(), parts.next()) {
+                (Some(program), Some("invoke")) => {
+                    invoke_stack.push(program);
+                    continue;
+                }
+                (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                    invoke_stack.pop();
+                    continue;
+                }
+                _ => {}
+            }
+        }
+
+        let program = invoke_stack.last().copied();
+        if let Some(kind) = TxKind::from_log(log, program) {
+            classified.push(ClassifiedLog { line: log, program, kind });
+        }
+    }
+    classified
+}
+
+/// Every recognized instruction kind in a log set, in first-seen order without repeats
+pub fn instruction_kinds(logs: &[String]) -> Vec<TxKind> {
+    let mut kinds = Vec::new();
+    for log in classify_logs(logs) {
+        if !kinds.contains(&log.kind) {
+            kinds.push(log.kind);
+        }
+    }
+    kinds
+}
+
+/// The most significant kind in a log set, e.g. `Create` for a launch with a dev buy
+// The modules filter on the full kind set from `instruction_kinds`; this is the one-kind summary
+#[allow(dead_code)]
+pub fn classify_transaction(logs: &[String]) -> TxKind {
+    let kinds = instruction_kinds(logs);
+    TxKind::PRIORITY
+        .into_iter()
+        .find(|kind| kinds.contains(kind))
+        .unwrap_or(TxKind::Unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUMP: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+
+    fn logs(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn nested_invokes_attribute_logs_to_the_inner_program() {
+        let logs = logs(&[
+            &format!("Program {} invoke [1]", PUMP),
+            "Program log: Instruction: Buy",
+            &format!("Program {} invoke [2]", TOKEN_2022_PROGRAM_ID),
+            "Program log: Instruction: TransferChecked",
+            &format!("Program {} success", TOKEN_2022_PROGRAM_ID),
+            // Back in the pump program, so this is not a Token-2022 transfer
+            "Program log: Instruction: Transfer",
+            &format!("Program {} success", PUMP),
+        ]);
+
+        let classified = classify_logs(&logs);
+        assert_eq!(classified.len(), 2);
+        assert_eq!(classified[1].program, Some(TOKEN_2022_PROGRAM_ID));
+        assert_eq!(instruction_kinds(&logs), vec![TxKind::Buy, TxKind::Token2022Transfer]);
+        assert_eq!(classify_transaction(&logs), TxKind::Buy);
+    }
+
+    #[test]
+    fn failed_instructions_pop_the_invoke_stack() {
+        let logs = logs(&[
+            &format!("Program {} invoke [1]", PUMP),
+            &format!("Program {} invoke [2]", TOKEN_2022_PROGRAM_ID),
+            &format!("Program {} failed: insufficient funds", TOKEN_2022_PROGRAM_ID),
+            "Program log: Instruction: Transfer",
+            "Program log: Instruction: Sell",
+            &format!("Program {} failed: custom program error: 0x1771", PUMP),
+        ]);
+
+        let classified = classify_logs(&logs);
+        assert_eq!(classified.len(), 1);
+        assert_eq!(classified[0].program, Some(PUMP));
+        assert_eq!(classify_transaction(&logs), TxKind::Sell);
+    }
+
+    #[test]
+    fn unknown_programs_and_instructions_are_unclassified() {
+        let logs = logs(&[
+            "Program Unknown1111111111111111111111111111111 invoke [1]",
+            "Program log: Instruction: Transfer",
+            "Program log: Instruction: Swap",
+            "Program Unknown1111111111111111111111111111111 success",
+        ]);
+
+        assert!(classify_logs(&logs).is_empty());
+        assert_eq!(classify_transaction(&logs), TxKind::Unknown);
+    }
+}
@@ -0,0 +1,134 @@
+//! Work Queue - Bounded queue drained by a fixed pool of worker tasks
+//!
+//! Log receive loops push matched signatures here instead of analyzing them
+//! inline, so a slow RPC call doesn't stall the loop and lag the broadcast channel.
+//! Telegram alert sends go through one too, which caps how many are in flight.
+
+use std::collections::hash_map::DefaultHasher;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex, MutexGuard};
+use tracing::warn;
+
+/// Queue counters shared between a module and its current queue
+#[derive(Debug, Clone, Default)]
+pub struct QueueStats {
+    depth: Arc<AtomicU64>,
+    dropped: Arc<AtomicU64>,
+    active: Arc<AtomicU64>,
+}
+
+impl QueueStats {
+    /// Items waiting for a worker
+    pub fn depth(&self) -> u64 {
+        self.depth.load(Ordering::SeqCst)
+    }
+
+    /// Items waiting or being handled, so zero means the pool has gone idle
+    pub fn pending(&self) -> u64 {
+        self.depth() + self.active.load(Ordering::SeqCst)
+    }
+
+    /// Items discarded because the queue was full
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::SeqCst)
+    }
+}
+
+/// Sending half of a bounded worker pool; workers exit once this is dropped
+pub struct WorkQueue<T> {
+    name: &'static str,
+    sender: mpsc::Sender<T>,
+    stats: QueueStats,
+}
+
+impl<T: Send + 'static> WorkQueue<T> {
+    /// Spawn `workers` tasks that run `handler` for each queued item
+    pub fn spawn<F, Fut>(
+        name: &'static str,
+        capacity: usize,
+        workers: usize,
+        stats: QueueStats,
+        handler: F,
+    ) -> Self
+    where
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel(capacity.max(1));
+        let receiver = Arc::new(Mutex::new(receiver));
+        let handler = Arc::new(handler);
+
+        for _ in 0..workers.max(1) {
+            let receiver = Arc::clone(&receiver);
+            let handler = Arc::clone(&handler);
+            let depth = Arc::clone(&stats.depth);
+            let active = Arc::clone(&stats.active);
+
+            tokio::spawn(async move {
+                loop {
+                    // Only the idle worker waiting on the lock is woken for the next item
+                    let item = receiver.lock().await.recv().await;
+                    let Some(item) = item else {
+                        break;
+                    };
+                    // Counted as active before it stops counting as queued, so the pool
+                    // never looks idle in between
+                    active.fetch_add(1, Ordering::SeqCst);
+                    depth.fetch_sub(1, Ordering::SeqCst);
+                    handler(item).await;
+                    active.fetch_sub(1, Ordering::SeqCst);
+                }
+            });
+        }
+
+        Self { name, sender, stats }
+    }
+
+    /// Queue an item without waiting; it is dropped (and counted) when the queue is full
+    pub fn push(&self, item: T) -> bool {
+        self.stats.depth.fetch_add(1, Ordering::SeqCst);
+        match self.sender.try_send(item) {
+            Ok(()) => true,
+            Err(e) => {
+                self.stats.depth.fetch_sub(1, Ordering::SeqCst);
+                if let mpsc::error::TrySendError::Full(_) = e {
+                    let dropped = self.stats.dropped.fetch_add(1, Ordering::SeqCst);
+                    if dropped.is_multiple_of(100) {
+                        warn!(
+                            target: "WORK_QUEUE",
+                            "{} queue full, dropped {} items so far",
+                            self.name,
+                            dropped + 1
+                        );
+                    }
+                }
+                false
+            }
+        }
+    }
+}
+
+/// Fixed set of async locks picked by key hash. Workers hold the lock for a key (a mint)
+/// across a whole analysis, so two jobs for the same key never interleave, while
+/// unrelated keys only wait on each other when they happen to share a shard.
+pub struct KeyedLocks {
+    shards: Vec<Mutex<()>>,
+}
+
+impl KeyedLocks {
+    pub fn new(shards: usize) -> Self {
+        Self {
+            shards: (0..shards.max(1)).map(|_| Mutex::new(())).collect(),
+        }
+    }
+
+    /// Wait for the lock covering `key`
+    pub async fn lock(&self, key: &str) -> MutexGuard<'_, ()> {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        self.shards[hasher.finish() as usize % self.shards.len()].lock().await
+    }
+}